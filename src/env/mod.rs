@@ -6,9 +6,14 @@
 
 mod procedures;
 
+pub(crate) use procedures::call_procedure;
+
 use crate::error::Error;
 use crate::macros;
+use crate::types::char_set;
 use crate::types::ports::Port;
+use crate::types::random::RandomSource;
+use crate::types::symbol::Symbol;
 use crate::types::{Expr, Parameter, Procedure};
 
 use std::cell::RefCell;
@@ -29,7 +34,7 @@ pub type EnvRef = Rc<RefCell<Env>>;
 
 #[derive(Debug, Clone)]
 pub struct Env {
-    pub data: HashMap<String, Expr>,
+    pub data: HashMap<Symbol, Expr>,
     params: HashMap<String, Expr>,
     pub outer: Option<EnvRef>,
 }
@@ -59,13 +64,84 @@ impl Env {
             env.insert_proc("-", procedures::sub);
             env.insert_proc("*", procedures::mult);
             env.insert_proc("/", procedures::div);
+            env.insert_proc("=", procedures::num_eq);
+            env.insert_proc("<", procedures::num_lt);
+            env.insert_proc(">", procedures::num_gt);
+            env.insert_proc("<=", procedures::num_le);
+            env.insert_proc(">=", procedures::num_ge);
             env.insert_proc("modulo", procedures::modulo);
             env.insert_proc("expt", procedures::exponent);
             env.insert_proc("abs", procedures::abs);
             env.insert_proc("ceiling", procedures::ceil);
             env.insert_proc("floor", procedures::floor);
+            env.insert_proc("round", procedures::round);
+            env.insert_proc("truncate", procedures::truncate);
+            env.insert_proc("numerator", procedures::numerator);
+            env.insert_proc("denominator", procedures::denominator);
+            env.insert_proc("rationalize", procedures::rationalize);
+            env.insert_proc("exact", procedures::exact);
+            env.insert_proc("inexact", procedures::inexact);
+            env.insert_proc("exp", procedures::exp);
+            env.insert_proc("log", procedures::log);
+            env.insert_proc("sin", procedures::sin);
+            env.insert_proc("cos", procedures::cos);
+            env.insert_proc("tan", procedures::tan);
+            env.insert_proc("asin", procedures::asin);
+            env.insert_proc("acos", procedures::acos);
+            env.insert_proc("atan", procedures::atan);
+            env.insert_proc("sqrt", procedures::sqrt);
+            env.insert_proc("make-rectangular", procedures::make_rectangular);
+            env.insert_proc("make-polar", procedures::make_polar);
+            env.insert_proc("real-part", procedures::real_part);
+            env.insert_proc("imag-part", procedures::imag_part);
+            env.insert_proc("magnitude", procedures::magnitude);
+            env.insert_proc("angle", procedures::angle);
             env.insert_proc("min", procedures::min);
             env.insert_proc("max", procedures::max);
+            // Bitwise
+            env.insert_proc("bitwise-and", procedures::bitwise_and);
+            env.insert_proc("bitwise-ior", procedures::bitwise_ior);
+            env.insert_proc("bitwise-xor", procedures::bitwise_xor);
+            env.insert_proc("bitwise-not", procedures::bitwise_not);
+            env.insert_proc("arithmetic-shift", procedures::arithmetic_shift);
+            env.insert_proc("bit-count", procedures::bit_count);
+            env.insert_proc("integer-length", procedures::integer_length);
+            env.insert_proc("bit-set?", procedures::is_bit_set);
+            env.insert_proc("copy-bit", procedures::copy_bit);
+            env.insert_proc("bit-field", procedures::bit_field);
+            // Random
+            env.insert_proc("random-integer", procedures::random_integer);
+            env.insert_proc("random-real", procedures::random_real);
+            env.insert_proc("make-random-source", procedures::make_random_source);
+            env.insert_proc("random-source?", procedures::is_random_source);
+            env.insert_proc(
+                "random-source-state-ref",
+                procedures::random_source_state_ref,
+            );
+            env.insert_proc(
+                "random-source-state-set!",
+                procedures::random_source_state_set,
+            );
+            env.insert_proc(
+                "random-source-randomize!",
+                procedures::random_source_randomize,
+            );
+            env.insert_proc(
+                "random-source-pseudo-randomize!",
+                procedures::random_source_pseudo_randomize,
+            );
+            env.insert_proc(
+                "random-source-make-integers",
+                procedures::random_source_make_integers,
+            );
+            env.insert_proc(
+                "random-source-make-reals",
+                procedures::random_source_make_reals,
+            );
+            env.insert_expr(
+                "default-random-source",
+                Expr::RandomSource(RandomSource::new()),
+            );
             // Strings
             env.insert_proc("string", procedures::new_string);
             env.insert_proc("string-append", procedures::str_append);
@@ -73,6 +149,110 @@ impl Env {
             env.insert_proc("string-upcase", procedures::string_to_upcase);
             env.insert_proc("string-downcase", procedures::string_to_downcase);
             env.insert_proc("string-reverse", procedures::string_reverse);
+            env.insert_proc("string-foldcase", procedures::string_foldcase);
+            env.insert_proc("make-string", procedures::make_string);
+            env.insert_proc("string-ref", procedures::string_ref);
+            env.insert_proc("string-set!", procedures::string_set);
+            env.insert_proc("substring", procedures::substring);
+            env.insert_proc("string-copy", procedures::string_copy);
+            env.insert_proc("string-copy!", procedures::string_copy_to);
+            env.insert_proc("string-fill!", procedures::string_fill);
+            env.insert_proc("string=?", procedures::string_eq);
+            env.insert_proc("string<?", procedures::string_lt);
+            env.insert_proc("string>?", procedures::string_gt);
+            env.insert_proc("string<=?", procedures::string_le);
+            env.insert_proc("string>=?", procedures::string_ge);
+            env.insert_proc("string-ci=?", procedures::string_ci_eq);
+            env.insert_proc("string-ci<?", procedures::string_ci_lt);
+            env.insert_proc("string-ci>?", procedures::string_ci_gt);
+            env.insert_proc("string-ci<=?", procedures::string_ci_le);
+            env.insert_proc("string-ci>=?", procedures::string_ci_ge);
+            // String library
+            env.insert_proc("string-index", procedures::string_index);
+            env.insert_proc("string-index-right", procedures::string_index_right);
+            env.insert_proc("string-skip", procedures::string_skip);
+            env.insert_proc("string-skip-right", procedures::string_skip_right);
+            env.insert_proc("string-contains", procedures::string_contains);
+            env.insert_proc("string-contains-right", procedures::string_contains_right);
+            env.insert_proc("string-search-forward", procedures::string_search_forward);
+            env.insert_proc("string-search-backward", procedures::string_search_backward);
+            env.insert_proc("string-search-all", procedures::string_search_all);
+            env.insert_proc("string-prefix?", procedures::string_prefix);
+            env.insert_proc("string-suffix?", procedures::string_suffix);
+            env.insert_proc("string-prefix-ci?", procedures::string_prefix_ci);
+            env.insert_proc("string-suffix-ci?", procedures::string_suffix_ci);
+            env.insert_proc("string-split", procedures::string_split);
+            env.insert_proc("string-join", procedures::string_join);
+            env.insert_proc("string-trim", procedures::string_trim);
+            env.insert_proc("string-trim-left", procedures::string_trim);
+            env.insert_proc("string-trim-right", procedures::string_trim_right);
+            env.insert_proc("string-trim-both", procedures::string_trim_both);
+            env.insert_proc("string-pad", procedures::string_pad);
+            env.insert_proc("string-pad-left", procedures::string_pad);
+            env.insert_proc("string-pad-right", procedures::string_pad_right);
+            env.insert_proc("string-replace", procedures::string_replace);
+            env.insert_proc("string-count", procedures::string_count);
+            env.insert_proc("string-tokenize", procedures::string_tokenize);
+            // Chars
+            env.insert_proc("char=?", procedures::char_eq);
+            env.insert_proc("char<?", procedures::char_lt);
+            env.insert_proc("char>?", procedures::char_gt);
+            env.insert_proc("char<=?", procedures::char_le);
+            env.insert_proc("char>=?", procedures::char_ge);
+            env.insert_proc("char-ci=?", procedures::char_ci_eq);
+            env.insert_proc("char-ci<?", procedures::char_ci_lt);
+            env.insert_proc("char-ci>?", procedures::char_ci_gt);
+            env.insert_proc("char-ci<=?", procedures::char_ci_le);
+            env.insert_proc("char-ci>=?", procedures::char_ci_ge);
+            env.insert_proc("char->integer", procedures::char_to_integer);
+            env.insert_proc("integer->char", procedures::integer_to_char);
+            env.insert_proc("char-upcase", procedures::char_upcase);
+            env.insert_proc("char-downcase", procedures::char_downcase);
+            env.insert_proc("char-foldcase", procedures::char_foldcase);
+            env.insert_proc("digit-value", procedures::digit_value);
+            // Char sets
+            env.insert_proc("char-set?", procedures::is_char_set);
+            env.insert_proc("char-set", procedures::new_char_set);
+            env.insert_proc("char-set-contains?", procedures::char_set_contains);
+            env.insert_proc("char-set-union", procedures::char_set_union);
+            env.insert_proc("char-set-union!", procedures::char_set_union);
+            env.insert_proc("char-set-intersection", procedures::char_set_intersection);
+            env.insert_proc("char-set-intersection!", procedures::char_set_intersection);
+            env.insert_proc("char-set-difference", procedures::char_set_difference);
+            env.insert_proc("char-set-difference!", procedures::char_set_difference);
+            env.insert_proc("char-set-xor", procedures::char_set_xor);
+            env.insert_proc("char-set-xor!", procedures::char_set_xor);
+            env.insert_proc("char-set-complement", procedures::char_set_complement);
+            env.insert_proc("char-set-complement!", procedures::char_set_complement);
+            env.insert_proc("char-set-adjoin", procedures::char_set_adjoin);
+            env.insert_proc("char-set-adjoin!", procedures::char_set_adjoin);
+            env.insert_proc("char-set-delete", procedures::char_set_delete);
+            env.insert_proc("char-set-delete!", procedures::char_set_delete);
+            env.insert_proc("char-set-copy", procedures::to_char_set);
+            env.insert_proc("list->char-set", procedures::list_to_char_set);
+            env.insert_proc("list->char-set!", procedures::list_to_char_set);
+            env.insert_proc("string->char-set", procedures::string_to_char_set);
+            env.insert_proc("string->char-set!", procedures::string_to_char_set);
+            env.insert_proc("ucs-range->char-set", procedures::ucs_range_to_char_set);
+            env.insert_proc("ucs-range->char-set!", procedures::ucs_range_to_char_set);
+            env.insert_proc("->char-set", procedures::to_char_set);
+            env.insert_proc("char-set->list", procedures::char_set_to_list);
+            env.insert_proc("char-set->string", procedures::char_set_to_string);
+            env.insert_proc("char-set-size", procedures::char_set_size);
+            env.insert_proc("char-set-count", procedures::char_set_count);
+            env.insert_proc("char-set=", procedures::char_set_eq);
+            env.insert_proc("char-set<=", procedures::char_set_le);
+            env.insert_proc("char-set-fold", procedures::char_set_fold);
+            env.insert_proc("char-set-for-each", procedures::char_set_for_each);
+            env.insert_proc("char-set-map", procedures::char_set_map);
+            env.insert_proc("char-set-filter", procedures::char_set_filter);
+            env.insert_proc("char-set-filter!", procedures::char_set_filter);
+            env.insert_proc("char-set-any", procedures::char_set_any);
+            env.insert_proc("char-set-every", procedures::char_set_every);
+            env.insert_proc("char-set-cursor", procedures::char_set_cursor);
+            env.insert_proc("char-set-ref", procedures::char_set_ref);
+            env.insert_proc("char-set-cursor-next", procedures::char_set_cursor_next);
+            env.insert_proc("end-of-char-set?", procedures::is_end_of_char_set);
             // Booleans
             env.insert_proc("not", procedures::not);
             env.insert_proc("and", procedures::and);
@@ -115,6 +295,163 @@ impl Env {
             env.insert_proc("set-car!", macros::set_car);
             env.insert_proc("set-cdr!", macros::set_cdr);
             env.insert_proc("reverse", procedures::list_reverse);
+            env.insert_proc("memq", procedures::memq);
+            env.insert_proc("memv", procedures::memv);
+            env.insert_proc("member", procedures::member);
+            env.insert_proc("assq", procedures::assq);
+            env.insert_proc("assv", procedures::assv);
+            env.insert_proc("assoc", procedures::assoc);
+            env.insert_proc("list-tail", procedures::list_tail);
+            env.insert_proc("list-ref", procedures::list_ref);
+            env.insert_proc("list-set!", procedures::list_set);
+            env.insert_proc("list-copy", procedures::list_copy);
+            env.insert_proc("make-list", procedures::make_list);
+            // Lists (SRFI 1)
+            env.insert_proc("xcons", procedures::xcons);
+            env.insert_proc("cons*", procedures::cons_star);
+            env.insert_proc("list-tabulate", procedures::list_tabulate);
+            env.insert_proc("circular-list", procedures::circular_list);
+            env.insert_proc("iota", procedures::iota);
+            env.insert_proc("proper-list?", procedures::is_proper_list);
+            env.insert_proc("circular-list?", procedures::is_circular_list);
+            env.insert_proc("dotted-list?", procedures::is_dotted_list);
+            env.insert_proc("null-list?", procedures::is_null_list);
+            env.insert_proc("not-pair?", procedures::is_not_pair);
+            env.insert_proc("list=", procedures::list_eq);
+            env.insert_proc("first", procedures::first);
+            env.insert_proc("second", procedures::second);
+            env.insert_proc("third", procedures::third);
+            env.insert_proc("fourth", procedures::fourth);
+            env.insert_proc("fifth", procedures::fifth);
+            env.insert_proc("sixth", procedures::sixth);
+            env.insert_proc("seventh", procedures::seventh);
+            env.insert_proc("eighth", procedures::eighth);
+            env.insert_proc("ninth", procedures::ninth);
+            env.insert_proc("tenth", procedures::tenth);
+            env.insert_proc("car+cdr", procedures::car_cdr);
+            env.insert_proc("take", procedures::take);
+            env.insert_proc("take!", procedures::take);
+            env.insert_proc("drop", procedures::drop);
+            env.insert_proc("take-right", procedures::take_right);
+            env.insert_proc("drop-right", procedures::drop_right);
+            env.insert_proc("drop-right!", procedures::drop_right);
+            env.insert_proc("split-at", procedures::split_at);
+            env.insert_proc("split-at!", procedures::split_at);
+            env.insert_proc("last", procedures::last);
+            env.insert_proc("last-pair", procedures::last_pair);
+            env.insert_proc("length+", procedures::length_plus);
+            env.insert_proc("concatenate", procedures::concatenate);
+            env.insert_proc("concatenate!", procedures::concatenate);
+            env.insert_proc("append!", procedures::list_append);
+            env.insert_proc("reverse!", procedures::list_reverse);
+            env.insert_proc("append-reverse", procedures::append_reverse);
+            env.insert_proc("append-reverse!", procedures::append_reverse);
+            env.insert_proc("zip", procedures::zip);
+            env.insert_proc("unzip1", procedures::unzip1);
+            env.insert_proc("unzip2", procedures::unzip2);
+            env.insert_proc("unzip3", procedures::unzip3);
+            env.insert_proc("count", procedures::count);
+            env.insert_proc("fold", procedures::fold);
+            env.insert_proc("fold-right", procedures::fold_right);
+            env.insert_proc("pair-fold", procedures::pair_fold);
+            env.insert_proc("pair-fold-right", procedures::pair_fold_right);
+            env.insert_proc("reduce", procedures::reduce);
+            env.insert_proc("reduce-right", procedures::reduce_right);
+            env.insert_proc("unfold", procedures::unfold);
+            env.insert_proc("unfold-right", procedures::unfold_right);
+            env.insert_proc("append-map", procedures::append_map);
+            env.insert_proc("append-map!", procedures::append_map);
+            env.insert_proc("map!", procedures::map);
+            env.insert_proc("map-in-order", procedures::map);
+            env.insert_proc("filter-map", procedures::filter_map);
+            env.insert_proc("pair-for-each", procedures::pair_for_each);
+            env.insert_proc("filter", procedures::filter);
+            env.insert_proc("filter!", procedures::filter);
+            env.insert_proc("remove", procedures::remove);
+            env.insert_proc("remove!", procedures::remove);
+            env.insert_proc("partition", procedures::partition);
+            env.insert_proc("partition!", procedures::partition);
+            env.insert_proc("find", procedures::find);
+            env.insert_proc("find-tail", procedures::find_tail);
+            env.insert_proc("any", procedures::any);
+            env.insert_proc("every", procedures::every);
+            env.insert_proc("list-index", procedures::list_index);
+            env.insert_proc("take-while", procedures::take_while);
+            env.insert_proc("take-while!", procedures::take_while);
+            env.insert_proc("drop-while", procedures::drop_while);
+            env.insert_proc("span", procedures::span);
+            env.insert_proc("span!", procedures::span);
+            env.insert_proc("break", procedures::break_list);
+            env.insert_proc("break!", procedures::break_list);
+            env.insert_proc("delete", procedures::delete);
+            env.insert_proc("delete!", procedures::delete);
+            env.insert_proc("delete-duplicates", procedures::delete_duplicates);
+            env.insert_proc("delete-duplicates!", procedures::delete_duplicates);
+            env.insert_proc("alist-cons", procedures::alist_cons);
+            env.insert_proc("alist-copy", procedures::alist_copy);
+            env.insert_proc("alist-delete", procedures::alist_delete);
+            env.insert_proc("alist-delete!", procedures::alist_delete);
+            env.insert_proc("lset<=", procedures::lset_le);
+            env.insert_proc("lset=", procedures::lset_eq);
+            env.insert_proc("lset-adjoin", procedures::lset_adjoin);
+            env.insert_proc("lset-union", procedures::lset_union);
+            env.insert_proc("lset-union!", procedures::lset_union);
+            env.insert_proc("lset-intersection", procedures::lset_intersection);
+            env.insert_proc("lset-intersection!", procedures::lset_intersection);
+            env.insert_proc("lset-difference", procedures::lset_difference);
+            env.insert_proc("lset-difference!", procedures::lset_difference);
+            env.insert_proc("lset-diff+intersection", procedures::lset_diff_intersection);
+            env.insert_proc(
+                "lset-diff+intersection!",
+                procedures::lset_diff_intersection,
+            );
+            env.insert_proc("lset-xor", procedures::lset_xor);
+            env.insert_proc("lset-xor!", procedures::lset_xor);
+            // Sorting
+            env.insert_proc("list-sort", procedures::list_sort);
+            env.insert_proc("list-sorted?", procedures::is_list_sorted);
+            env.insert_proc("list-merge", procedures::list_merge);
+            env.insert_proc("vector-sort", procedures::vector_sort);
+            env.insert_proc("vector-sort!", procedures::vector_sort_in_place);
+            env.insert_proc("vector-sorted?", procedures::is_vector_sorted);
+            env.insert_proc("vector-merge", procedures::vector_merge);
+            env.insert_proc(
+                "vector-delete-neighbor-dups",
+                procedures::vector_delete_neighbor_dups,
+            );
+            // Hash tables
+            env.insert_proc("make-hash-table", procedures::make_hash_table);
+            env.insert_proc("hash-table?", procedures::is_hash_table);
+            env.insert_proc("hash-table-ref", procedures::hash_table_ref);
+            env.insert_proc("hash-table-ref/default", procedures::hash_table_ref_default);
+            env.insert_proc("hash-table-set!", procedures::hash_table_set);
+            env.insert_proc("hash-table-delete!", procedures::hash_table_delete);
+            env.insert_proc("hash-table-contains?", procedures::hash_table_contains);
+            env.insert_proc("hash-table-exists?", procedures::hash_table_contains);
+            env.insert_proc("hash-table-size", procedures::hash_table_size);
+            env.insert_proc("hash-table-update!", procedures::hash_table_update);
+            env.insert_proc(
+                "hash-table-update!/default",
+                procedures::hash_table_update_default,
+            );
+            env.insert_proc("hash-table-walk", procedures::hash_table_walk);
+            env.insert_proc("hash-table-fold", procedures::hash_table_fold);
+            env.insert_proc("hash-table-keys", procedures::hash_table_keys);
+            env.insert_proc("hash-table-values", procedures::hash_table_values);
+            env.insert_proc("hash-table->alist", procedures::hash_table_to_alist);
+            env.insert_proc("alist->hash-table", procedures::alist_to_hash_table);
+            env.insert_proc("hash-table-copy", procedures::hash_table_copy);
+            env.insert_proc("hash-table-clear!", procedures::hash_table_clear);
+            env.insert_proc("equal-hash", procedures::equal_hash);
+            env.insert_proc("eqv-hash", procedures::eqv_hash);
+            env.insert_proc("string-hash", procedures::string_hash);
+            env.insert_proc("hash", procedures::equal_hash);
+
+            // Lazy
+            env.insert_proc("force", procedures::force);
+            env.insert_proc("make-promise", procedures::make_promise);
+            env.insert_proc("promise?", procedures::is_promise);
+
             // Vectors
             env.insert_proc("vector", procedures::new_vector);
             env.insert_proc("make-vector", procedures::make_vector);
@@ -125,6 +462,14 @@ impl Env {
             env.insert_proc("vector-copy!", procedures::vector_copy_from);
             env.insert_proc("vector-fill!", procedures::vector_fill);
             env.insert_proc("vector-append", procedures::vector_append);
+            // Control
+            env.insert_proc("apply", procedures::apply);
+            env.insert_proc("map", procedures::map);
+            env.insert_proc("for-each", procedures::for_each);
+            env.insert_proc("string-map", procedures::string_map);
+            env.insert_proc("string-for-each", procedures::string_for_each);
+            env.insert_proc("vector-map", procedures::vector_map);
+            env.insert_proc("vector-for-each", procedures::vector_for_each);
             // Bytevectors
             env.insert_proc("bytevector", procedures::new_bytevector);
             env.insert_proc("make-bytevector", procedures::make_bytevector);
@@ -134,8 +479,70 @@ impl Env {
             env.insert_proc("bytevector-copy", procedures::bytevector_copy);
             env.insert_proc("bytevector-append", procedures::bytevector_append);
             env.insert_proc("bytevector-copy!", procedures::bytevector_copy_from);
+            env.insert_proc("bytevector-s8-ref", procedures::bytevector_s8_ref);
+            env.insert_proc("bytevector-s8-set!", procedures::bytevector_s8_set);
+            env.insert_proc("bytevector-u16-ref", procedures::bytevector_u16_ref);
+            env.insert_proc("bytevector-s16-ref", procedures::bytevector_s16_ref);
+            env.insert_proc("bytevector-u32-ref", procedures::bytevector_u32_ref);
+            env.insert_proc("bytevector-s32-ref", procedures::bytevector_s32_ref);
+            env.insert_proc("bytevector-u64-ref", procedures::bytevector_u64_ref);
+            env.insert_proc("bytevector-s64-ref", procedures::bytevector_s64_ref);
+            env.insert_proc("bytevector-u16-set!", procedures::bytevector_u16_set);
+            env.insert_proc("bytevector-s16-set!", procedures::bytevector_s16_set);
+            env.insert_proc("bytevector-u32-set!", procedures::bytevector_u32_set);
+            env.insert_proc("bytevector-s32-set!", procedures::bytevector_s32_set);
+            env.insert_proc("bytevector-u64-set!", procedures::bytevector_u64_set);
+            env.insert_proc("bytevector-s64-set!", procedures::bytevector_s64_set);
+            env.insert_proc("bytevector-uint-ref", procedures::bytevector_uint_ref);
+            env.insert_proc("bytevector-sint-ref", procedures::bytevector_sint_ref);
+            env.insert_proc("bytevector-uint-set!", procedures::bytevector_uint_set);
+            env.insert_proc("bytevector-sint-set!", procedures::bytevector_sint_set);
+            env.insert_proc(
+                "bytevector-ieee-single-ref",
+                procedures::bytevector_ieee_single_ref,
+            );
+            env.insert_proc(
+                "bytevector-ieee-double-ref",
+                procedures::bytevector_ieee_double_ref,
+            );
+            env.insert_proc(
+                "bytevector-ieee-single-set!",
+                procedures::bytevector_ieee_single_set,
+            );
+            env.insert_proc(
+                "bytevector-ieee-double-set!",
+                procedures::bytevector_ieee_double_set,
+            );
+            env.insert_proc("native-endianness", procedures::native_endianness);
             // Ports & Files
             env.insert_proc("open-input-file", procedures::open_input_file);
+            env.insert_proc("transcoded-port", procedures::transcoded_port);
+            env.insert_proc(
+                "make-custom-textual-input-port",
+                procedures::make_custom_textual_input_port,
+            );
+            env.insert_proc(
+                "make-custom-textual-output-port",
+                procedures::make_custom_textual_output_port,
+            );
+            env.insert_proc(
+                "make-custom-binary-input-port",
+                procedures::make_custom_binary_input_port,
+            );
+            env.insert_proc(
+                "make-custom-binary-output-port",
+                procedures::make_custom_binary_output_port,
+            );
+            env.insert_proc(
+                "port-has-port-position?",
+                procedures::port_has_port_position,
+            );
+            env.insert_proc(
+                "port-has-set-port-position!?",
+                procedures::port_has_set_port_position,
+            );
+            env.insert_proc("port-position", procedures::port_position);
+            env.insert_proc("set-port-position!", procedures::set_port_position);
             env.insert_proc("open-output-file", procedures::open_output_file);
             env.insert_proc("open-input-string", procedures::open_input_string);
             env.insert_proc("open-output-string", procedures::open_output_string);
@@ -149,6 +556,8 @@ impl Env {
             env.insert_proc("open-output-bytevector", procedures::open_output_bytevector);
             env.insert_proc("get-output-bytevector", procedures::get_output_bytevector);
             env.insert_proc("close-port", procedures::close_port);
+            env.insert_proc("close-input-port", procedures::close_input_port);
+            env.insert_proc("close-output-port", procedures::close_output_port);
             env.insert_proc("read-char", procedures::read_char);
             env.insert_proc("peek-char", procedures::peek_char);
             env.insert_proc("read-string", procedures::read_string);
@@ -177,6 +586,15 @@ impl Env {
             env.insert_proc("symbol->string", procedures::symbol_to_string);
             env.insert_proc("string->number", procedures::string_to_num);
             env.insert_proc("string->symbol", procedures::string_to_symbol);
+            env.insert_proc(
+                "string->uninterned-symbol",
+                procedures::string_to_uninterned_symbol,
+            );
+            env.insert_proc(
+                "generate-uninterned-symbol",
+                procedures::generate_uninterned_symbol,
+            );
+            env.insert_proc("gensym", procedures::generate_uninterned_symbol);
             env.insert_proc("string->list", procedures::string_to_list);
             env.insert_proc("string->vector", procedures::string_to_vector);
             env.insert_proc("string->utf8", procedures::string_to_utf8);
@@ -196,7 +614,11 @@ impl Env {
             env.insert_proc("exact?", procedures::is_exact);
             env.insert_proc("inexact?", procedures::is_inexact);
             env.insert_proc("exact-integer?", procedures::is_exact_integer);
+            env.insert_proc("finite?", procedures::is_finite);
+            env.insert_proc("infinite?", procedures::is_infinite);
+            env.insert_proc("nan?", procedures::is_nan);
             env.insert_proc("symbol?", procedures::is_symbol);
+            env.insert_proc("symbol-interned?", procedures::is_symbol_interned);
             env.insert_proc("string?", procedures::is_string);
             env.insert_proc("char?", procedures::is_char);
             env.insert_proc("char-alphabetic?", procedures::is_char_alphabetic);
@@ -210,6 +632,7 @@ impl Env {
             env.insert_proc("vector?", procedures::is_vector);
             env.insert_proc("procedure?", procedures::is_procedure);
             env.insert_proc("bytevector?", procedures::is_bytevector);
+            env.insert_proc("port?", procedures::is_port);
             env.insert_proc("input-port?", procedures::is_input_port);
             env.insert_proc("output-port?", procedures::is_output_port);
             env.insert_proc("textual-port?", procedures::is_textual_port);
@@ -220,7 +643,7 @@ impl Env {
             env.insert_proc("output-port-open?", procedures::is_output_port_open);
             env.insert_proc("eof-object?", procedures::is_eof_object);
             env.insert_proc("eqv?", procedures::are_eqv);
-            env.insert_proc("eq?", procedures::are_eqv);
+            env.insert_proc("eq?", procedures::are_eq);
             env.insert_proc("equal?", procedures::are_equal);
             env.insert_proc("parameter?", procedures::is_parameter);
             env.insert_proc("null?", procedures::is_null);
@@ -250,40 +673,42 @@ impl Env {
     /// Find value in environment.
     ///
     /// Checks self before recursively checking outer environment.
-    pub fn find_value(&self, symbol: &str) -> Option<Expr> {
-        if let Some(val) = self.data.get(symbol) {
+    pub fn find_value(&self, symbol: impl Into<Symbol>) -> Option<Expr> {
+        let symbol = symbol.into();
+        if let Some(val) = self.data.get(&symbol) {
             Some(val.clone())
         } else if let Some(outer) = &self.outer {
             outer.borrow().find_value(symbol)
         } else {
-            None
+            // Predefined char sets are costly to build, so they are bound on first use.
+            char_set::named(&symbol.name()).map(Expr::CharSet)
         }
     }
 
     /// Check if 2 symbols point to the same environment value.
-    pub fn same_value(&self, symbol_a: &str, symbol_b: &str) -> bool {
+    pub fn same_value(&self, symbol_a: Symbol, symbol_b: Symbol) -> bool {
         symbol_a == symbol_b
             && self.find_value(symbol_a).is_some()
             && self.find_value(symbol_b).is_some()
     }
 
-    /// Insert a new `Procedure` into `HashMap<String, Expr>`.
+    /// Insert a new `Procedure` into `HashMap<Symbol, Expr>`.
     /// Used to reduce boilerplate in `env::standard_env()`.
     fn insert_proc(&mut self, name: &str, function: Procedure) {
         self.data
-            .insert(name.to_string(), Expr::Procedure(function));
+            .insert(Symbol::intern(name), Expr::Procedure(function));
     }
 
     /// Insert a new `Expr` into `&self`.
-    pub fn insert_expr(&mut self, name: &str, value: Expr) {
-        self.data.insert(name.to_string(), value);
+    pub fn insert_expr(&mut self, name: impl Into<Symbol>, value: Expr) {
+        self.data.insert(name.into(), value);
     }
 
     /// Initialize parameter and set in environment. Created to clean up boilerplate in `env::standard_env()`.
     fn new_param(&mut self, name: &str, value: &Expr) {
         let id = next_parameter_id();
         let env_placeholder = Expr::Parameter(Parameter::new(id, None));
-        self.data.insert(name.to_string(), env_placeholder);
+        self.data.insert(Symbol::intern(name), env_placeholder);
         self.set_param(&id.to_string(), value);
     }
 
@@ -291,7 +716,7 @@ impl Env {
     /// then uses its ID to retrieve the value from `self.params`.
     /// Checks outer env if name not found in current env.
     pub fn find_param(&self, param_name: &str) -> Option<Expr> {
-        if let Some(Expr::Parameter(param)) = self.data.get(param_name) {
+        if let Some(Expr::Parameter(param)) = self.data.get(&Symbol::intern(param_name)) {
             let id = param.id.to_string();
             if let Some(val) = self.params.get(&id) {
                 return Some(val.clone());
@@ -325,12 +750,17 @@ impl Env {
     /// Initialize default ports:
     /// - current-input-port
     /// - current-output-port
+    /// - current-error-port
     fn init_default_ports(&mut self) {
         self.new_param("current-input-port", &Expr::Port(Port::text_input_stdin()));
         self.new_param(
             "current-output-port",
             &Expr::Port(Port::text_output_stdout()),
         );
+        self.new_param(
+            "current-error-port",
+            &Expr::Port(Port::text_output_stderr()),
+        );
     }
 }
 
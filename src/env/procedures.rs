@@ -2,17 +2,32 @@
 // Author: Sebastian Ibanez
 // Created: 2025-11-11
 
-use num_traits::ToPrimitive;
+use num_bigint::{BigInt, Sign};
+use num_traits::{ToPrimitive, Zero};
 
-use crate::env::{EnvRef, next_parameter_id};
+use crate::env::{Env, EnvRef, next_parameter_id};
 use crate::error::Error;
 use crate::macros::apply_lambda;
+use crate::types::char_set::{self, CharSet};
+use crate::types::chars;
+use crate::types::hash_table::{self, Equivalence, HashTable};
+use crate::types::number::IntVariant;
 use crate::types::number::IntVariant::Small;
-use crate::types::ports::{BinaryOutputPort, Port};
-use crate::types::{ByteVector, Expr, Number, Pair, PairIter, Parameter, Result, Vector};
+use crate::types::ports::{
+    BinaryOutputPort, CustomItem, CustomPort, Port, borrow_port, borrow_port_mut,
+};
+use crate::types::promise::{Promise, PromiseState};
+use crate::types::random::RandomSource;
+use crate::types::symbol::Symbol;
+use crate::types::transcoder::{Codec, EolStyle, ErrorMode, Transcoder};
+use crate::types::{
+    ByteVector, Closure, Expr, Number, Pair, Parameter, Procedure, Result, SchemeString, Vector,
+};
 use crate::{io, parser};
+use std::cmp::Ordering;
 use std::fs;
 use std::ops::{Add, Deref, Div, Mul, Sub};
+use std::rc::Rc;
 
 // I/O
 
@@ -50,7 +65,7 @@ pub fn println(args: &[Expr], _: EnvRef) -> Result {
 /// Return an error with an optional string message.
 pub fn error(args: &[Expr], _: EnvRef) -> Result {
     match args {
-        [Expr::String(s)] => Err(Error::new(s)),
+        [Expr::String(s)] => Err(Error::Message(s.to_string())),
         [] => Err(Error::new("")),
         _ => Err(Error::new(
             "invalid error argument: expected optional string",
@@ -62,14 +77,24 @@ pub fn error(args: &[Expr], _: EnvRef) -> Result {
 pub fn pretty_print(args: &[Expr], _: EnvRef) -> Result {
     match args.first() {
         Some(Expr::Closure(c)) => {
-            let c_args = c.parameters.join(" ");
+            let c_args = c
+                .parameters
+                .iter()
+                .map(|p| p.to_string())
+                .collect::<Vec<String>>()
+                .join(" ");
+            let c_args = match c.rest {
+                Some(rest) if c.parameters.is_empty() => rest.to_string(),
+                Some(rest) => format!("({} . {})", c_args, rest),
+                None => format!("({})", c_args),
+            };
             let c_body = c
                 .body
                 .iter()
                 .map(|e| e.to_string())
                 .collect::<Vec<String>>()
                 .join(" ");
-            println!("(lambda ({}) {})", c_args, c_body);
+            println!("(lambda {} {})", c_args, c_body);
             return Ok(Expr::Void());
         }
         Some(_) => {
@@ -102,72 +127,120 @@ pub fn exit(args: &[Expr], _: EnvRef) -> Result {
 
 /// Add all arguments together.
 pub fn add(args: &[Expr], _: EnvRef) -> Result {
-    let numbers = parser::parse_number_list(args)?;
-    let initial_sum = Number::from_i64(0);
-    let sum = numbers
-        .into_iter()
-        .try_fold(initial_sum, |current_sum, num| current_sum.add(num))?;
-    Ok(Expr::Number(sum))
+    fold_numbers(args, Number::from_i64(0), i64::checked_add, Number::add)
 }
 
 /// Subtract all arguments together.
 pub fn sub(args: &[Expr], _: EnvRef) -> Result {
-    let numbers = parser::parse_number_list(args)?;
-    if numbers.is_empty() {
-        return Ok(Expr::Number(Number::from_i64(0)));
-    }
-
-    let mut iter = numbers.clone().into_iter();
-    let first_num = iter.next().unwrap();
-
-    if iter.next().is_none() {
-        let zero = Number::from_i64(0);
-        let result = zero.sub(first_num)?;
-        Ok(Expr::Number(result))
-    } else {
-        let mut iter = numbers.into_iter();
-        let initial_diff = iter.next().unwrap();
-        let final_diff = iter.try_fold(initial_diff, |current_diff, num| current_diff.sub(num))?;
-        Ok(Expr::Number(final_diff))
+    match args {
+        [] => Ok(Expr::Number(Number::from_i64(0))),
+        [_] => fold_numbers(args, Number::from_i64(0), i64::checked_sub, Number::sub),
+        [Expr::Number(first), rest @ ..] => {
+            fold_numbers(rest, first.clone(), i64::checked_sub, Number::sub)
+        }
+        _ => Err(Error::new("expected a number")),
     }
 }
 
 /// Multiply all arguments together.
 pub fn mult(args: &[Expr], _: EnvRef) -> Result {
-    let numbers = parser::parse_number_list(args)?;
-    if numbers.is_empty() {
-        return Err(Error::new("expected at least one number"));
-    }
-    let initial_value: Number = Number::from_i64(1);
-    let product = numbers
-        .into_iter()
-        .try_fold(initial_value, |current_product, num| {
-            current_product.mul(num)
-        })?;
-    Ok(Expr::Number(product))
+    fold_numbers(args, Number::from_i64(1), i64::checked_mul, Number::mul)
 }
 
 /// Divide all arguments together.
 pub fn div(args: &[Expr], _: EnvRef) -> Result {
-    let numbers = parser::parse_number_list(args)?;
-    if numbers.is_empty() {
+    match args {
+        [] => Err(Error::new("expected at least one number")),
+        [_] => fold_numbers(args, Number::from_i64(1), checked_exact_div, Number::div),
+        [Expr::Number(first), rest @ ..] => {
+            fold_numbers(rest, first.clone(), checked_exact_div, Number::div)
+        }
+        _ => Err(Error::new("expected a number")),
+    }
+}
+
+/// Fold numeric arguments into `init` with an arithmetic operation.
+///
+/// Pairs of fixnums are handled by `fixnum_op` without allocating. If it returns `None`
+/// (overflow, or a result that isn't a fixnum), or either operand is another kind of
+/// number, `op` is used instead and promotes the result as needed.
+fn fold_numbers(
+    args: &[Expr],
+    init: Number,
+    fixnum_op: fn(i64, i64) -> Option<i64>,
+    op: fn(Number, Number) -> std::result::Result<Number, Error>,
+) -> Result {
+    let mut acc = init;
+    for arg in args {
+        let Expr::Number(n) = arg else {
+            return Err(Error::new("expected a number"));
+        };
+        if let (Number::Int(Small(a)), Number::Int(Small(b))) = (&acc, n)
+            && let Some(result) = fixnum_op(*a, *b)
+        {
+            acc = Number::Int(Small(result));
+            continue;
+        }
+        acc = op(acc, n.clone())?;
+    }
+    Ok(Expr::Number(acc))
+}
+
+/// Divide two fixnums if the quotient is an exact fixnum.
+fn checked_exact_div(a: i64, b: i64) -> Option<i64> {
+    match a.checked_rem(b) {
+        Some(0) => a.checked_div(b),
+        _ => None,
+    }
+}
+
+/// Return true if all arguments are numerically equal.
+pub fn num_eq(args: &[Expr], _: EnvRef) -> Result {
+    compare_numbers(args, false, |o| o == Ordering::Equal)
+}
+
+/// Return true if arguments are monotonically increasing.
+pub fn num_lt(args: &[Expr], _: EnvRef) -> Result {
+    compare_numbers(args, true, |o| o == Ordering::Less)
+}
+
+/// Return true if arguments are monotonically decreasing.
+pub fn num_gt(args: &[Expr], _: EnvRef) -> Result {
+    compare_numbers(args, true, |o| o == Ordering::Greater)
+}
+
+/// Return true if arguments are monotonically non-decreasing.
+pub fn num_le(args: &[Expr], _: EnvRef) -> Result {
+    compare_numbers(args, true, |o| o != Ordering::Greater)
+}
+
+/// Return true if arguments are monotonically non-increasing.
+pub fn num_ge(args: &[Expr], _: EnvRef) -> Result {
+    compare_numbers(args, true, |o| o != Ordering::Less)
+}
+
+/// Check that each pair of adjacent numeric arguments satisfies `test`.
+/// Unordered pairs, such as comparisons with NaN, never satisfy `test`.
+fn compare_numbers(args: &[Expr], real_only: bool, test: fn(Ordering) -> bool) -> Result {
+    if args.is_empty() {
         return Err(Error::new("expected at least one number"));
     }
-    let mut length_check_iter = numbers.clone().into_iter();
-    length_check_iter.next();
-    if length_check_iter.next().is_none() {
-        let one = Number::from_i64(1);
-        let first_num = numbers.into_iter().next().unwrap();
-        let result = one.div(first_num).map_err(Error::from)?;
-        Ok(Expr::Number(result))
-    } else {
-        let mut iter = numbers.into_iter();
-        let first_num = iter.next().unwrap();
-        let result = iter.try_fold(first_num, |current_quotient, num| {
-            current_quotient.div(num).map_err(Error::from)
-        })?;
-        Ok(Expr::Number(result))
+
+    let mut result = true;
+    let mut prev: Option<&Number> = None;
+    for arg in args {
+        let Expr::Number(n) = arg else {
+            return Err(Error::new("expected a number"));
+        };
+        if real_only && !n.is_real() {
+            return Err(Error::new("expected real numbers"));
+        }
+        if let Some(prev) = prev {
+            result = result && prev.partial_cmp(n).is_some_and(test);
+        }
+        prev = Some(n);
     }
+    Ok(Expr::Boolean(result))
 }
 
 /// Apply exponent to number.
@@ -214,15 +287,7 @@ pub fn abs(args: &[Expr], _: EnvRef) -> Result {
 /// Round number up to the nearest integer.
 pub fn ceil(args: &[Expr], _: EnvRef) -> Result {
     match args {
-        [Expr::Number(Number::Complex(_))] => Err(Error::new("unable to round complex number")),
-        [Expr::Number(n)] => {
-            if let Some(result) = n.to_f64() {
-                return Ok(Expr::Number(Number::from_f64(result.ceil())));
-            }
-            Err(Error::Message(
-                "unable to convert number to float".to_string(),
-            ))
-        }
+        [Expr::Number(n)] => Ok(Expr::Number(n.ceiling()?)),
         _ => Err(Error::new("expected real number")),
     }
 }
@@ -230,19 +295,191 @@ pub fn ceil(args: &[Expr], _: EnvRef) -> Result {
 /// Round number down to the nearest integer.
 pub fn floor(args: &[Expr], _: EnvRef) -> Result {
     match args {
-        [Expr::Number(Number::Complex(_))] => Err(Error::new("unable to round complex number")),
-        [Expr::Number(n)] => {
-            if let Some(result) = n.to_f64() {
-                return Ok(Expr::Number(Number::from_f64(result.floor())));
-            }
-            Err(Error::Message(
-                "unable to convert number to float".to_string(),
-            ))
-        }
+        [Expr::Number(n)] => Ok(Expr::Number(n.floor()?)),
+        _ => Err(Error::new("expected real number")),
+    }
+}
+
+/// Round number to the nearest integer, rounding to even when halfway between integers.
+pub fn round(args: &[Expr], _: EnvRef) -> Result {
+    match args {
+        [Expr::Number(n)] => Ok(Expr::Number(n.round()?)),
+        _ => Err(Error::new("expected real number")),
+    }
+}
+
+/// Round number towards zero.
+pub fn truncate(args: &[Expr], _: EnvRef) -> Result {
+    match args {
+        [Expr::Number(n)] => Ok(Expr::Number(n.truncate()?)),
         _ => Err(Error::new("expected real number")),
     }
 }
 
+/// Convert number to an exact number.
+pub fn exact(args: &[Expr], _: EnvRef) -> Result {
+    match args {
+        [Expr::Number(n)] => Ok(Expr::Number(n.to_exact()?)),
+        _ => Err(Error::new("expected number")),
+    }
+}
+
+/// Convert number to an inexact number.
+pub fn inexact(args: &[Expr], _: EnvRef) -> Result {
+    match args {
+        [Expr::Number(n)] => Ok(Expr::Number(n.to_inexact()?)),
+        _ => Err(Error::new("expected number")),
+    }
+}
+
+/// Return numerator of a rational number in lowest terms.
+pub fn numerator(args: &[Expr], _: EnvRef) -> Result {
+    match args {
+        [Expr::Number(n)] => Ok(Expr::Number(n.numerator()?)),
+        _ => Err(Error::new("expected rational number")),
+    }
+}
+
+/// Return denominator of a rational number in lowest terms.
+pub fn denominator(args: &[Expr], _: EnvRef) -> Result {
+    match args {
+        [Expr::Number(n)] => Ok(Expr::Number(n.denominator()?)),
+        _ => Err(Error::new("expected rational number")),
+    }
+}
+
+/// Return the simplest rational number within a tolerance of a number.
+pub fn rationalize(args: &[Expr], _: EnvRef) -> Result {
+    match args {
+        [Expr::Number(x), Expr::Number(y)] => Ok(Expr::Number(x.rationalize(y)?)),
+        _ => Err(Error::new("expected 2 real numbers")),
+    }
+}
+
+/// Return `e` raised to the power of a number.
+pub fn exp(args: &[Expr], _: EnvRef) -> Result {
+    match args {
+        [Expr::Number(n)] => Ok(Expr::Number(n.exp()?)),
+        _ => Err(Error::new("expected number")),
+    }
+}
+
+/// Return the natural logarithm of a number, or the logarithm in an optional base.
+pub fn log(args: &[Expr], _: EnvRef) -> Result {
+    match args {
+        [Expr::Number(n)] => Ok(Expr::Number(n.ln()?)),
+        [Expr::Number(n), Expr::Number(base)] => Ok(Expr::Number(n.log(base)?)),
+        _ => Err(Error::new("expected 1 or 2 numbers")),
+    }
+}
+
+/// Return the sine of a number.
+pub fn sin(args: &[Expr], _: EnvRef) -> Result {
+    match args {
+        [Expr::Number(n)] => Ok(Expr::Number(n.sin()?)),
+        _ => Err(Error::new("expected number")),
+    }
+}
+
+/// Return the cosine of a number.
+pub fn cos(args: &[Expr], _: EnvRef) -> Result {
+    match args {
+        [Expr::Number(n)] => Ok(Expr::Number(n.cos()?)),
+        _ => Err(Error::new("expected number")),
+    }
+}
+
+/// Return the tangent of a number.
+pub fn tan(args: &[Expr], _: EnvRef) -> Result {
+    match args {
+        [Expr::Number(n)] => Ok(Expr::Number(n.tan()?)),
+        _ => Err(Error::new("expected number")),
+    }
+}
+
+/// Return the arcsine of a number.
+pub fn asin(args: &[Expr], _: EnvRef) -> Result {
+    match args {
+        [Expr::Number(n)] => Ok(Expr::Number(n.asin()?)),
+        _ => Err(Error::new("expected number")),
+    }
+}
+
+/// Return the arccosine of a number.
+pub fn acos(args: &[Expr], _: EnvRef) -> Result {
+    match args {
+        [Expr::Number(n)] => Ok(Expr::Number(n.acos()?)),
+        _ => Err(Error::new("expected number")),
+    }
+}
+
+/// Return the arctangent of a number, or the angle of the point `(x, y)` when given `y` and `x`.
+pub fn atan(args: &[Expr], _: EnvRef) -> Result {
+    match args {
+        [Expr::Number(n)] => Ok(Expr::Number(n.atan()?)),
+        [Expr::Number(y), Expr::Number(x)] => Ok(Expr::Number(y.atan2(x)?)),
+        _ => Err(Error::new("expected 1 or 2 numbers")),
+    }
+}
+
+/// Return the principal square root of a number.
+pub fn sqrt(args: &[Expr], _: EnvRef) -> Result {
+    match args {
+        [Expr::Number(n)] => Ok(Expr::Number(n.sqrt()?)),
+        _ => Err(Error::new("expected number")),
+    }
+}
+
+/// Create a complex number from real and imaginary parts.
+pub fn make_rectangular(args: &[Expr], _: EnvRef) -> Result {
+    match args {
+        [Expr::Number(re), Expr::Number(im)] => Ok(Expr::Number(Number::make_rectangular(re, im)?)),
+        _ => Err(Error::new("expected 2 real numbers")),
+    }
+}
+
+/// Create a complex number from a magnitude and an angle.
+pub fn make_polar(args: &[Expr], _: EnvRef) -> Result {
+    match args {
+        [Expr::Number(magnitude), Expr::Number(angle)] => {
+            Ok(Expr::Number(Number::make_polar(magnitude, angle)?))
+        }
+        _ => Err(Error::new("expected 2 real numbers")),
+    }
+}
+
+/// Return the real part of a number.
+pub fn real_part(args: &[Expr], _: EnvRef) -> Result {
+    match args {
+        [Expr::Number(n)] => Ok(Expr::Number(n.real_part())),
+        _ => Err(Error::new("expected number")),
+    }
+}
+
+/// Return the imaginary part of a number.
+pub fn imag_part(args: &[Expr], _: EnvRef) -> Result {
+    match args {
+        [Expr::Number(n)] => Ok(Expr::Number(n.imag_part())),
+        _ => Err(Error::new("expected number")),
+    }
+}
+
+/// Return the magnitude of a number.
+pub fn magnitude(args: &[Expr], _: EnvRef) -> Result {
+    match args {
+        [Expr::Number(n)] => Ok(Expr::Number(n.magnitude()?)),
+        _ => Err(Error::new("expected number")),
+    }
+}
+
+/// Return the angle of a number in the complex plane.
+pub fn angle(args: &[Expr], _: EnvRef) -> Result {
+    match args {
+        [Expr::Number(n)] => Ok(Expr::Number(n.angle()?)),
+        _ => Err(Error::new("expected number")),
+    }
+}
+
 /// Return smallest real number from arguments.
 pub fn min(args: &[Expr], _: EnvRef) -> Result {
     if args.is_empty() {
@@ -254,7 +491,7 @@ pub fn min(args: &[Expr], _: EnvRef) -> Result {
     for arg in args {
         match arg {
             Expr::Number(current) => match current {
-                Number::Complex(_) => {
+                Number::Complex(_) | Number::ExactComplex(_) => {
                     return Err(Error::new("expected real numbers"));
                 }
                 _ => match min {
@@ -286,7 +523,7 @@ pub fn max(args: &[Expr], _: EnvRef) -> Result {
     for arg in args {
         match arg {
             Expr::Number(current) => match current {
-                Number::Complex(_) => {
+                Number::Complex(_) | Number::ExactComplex(_) => {
                     return Err(Error::new("expected real numbers"));
                 }
                 _ => match min {
@@ -307,894 +544,5058 @@ pub fn max(args: &[Expr], _: EnvRef) -> Result {
     Ok(Expr::Number(min.unwrap()))
 }
 
-// Strings
+// Bitwise
 
-/// Appends two strings together.
-pub fn str_append(args: &[Expr], _: EnvRef) -> Result {
+/// Return the bitwise AND of all arguments.
+pub fn bitwise_and(args: &[Expr], _: EnvRef) -> Result {
+    fold_integers(args, IntVariant::Small(-1), IntVariant::bit_and)
+}
+
+/// Return the bitwise inclusive OR of all arguments.
+pub fn bitwise_ior(args: &[Expr], _: EnvRef) -> Result {
+    fold_integers(args, IntVariant::Small(0), IntVariant::bit_or)
+}
+
+/// Return the bitwise exclusive OR of all arguments.
+pub fn bitwise_xor(args: &[Expr], _: EnvRef) -> Result {
+    fold_integers(args, IntVariant::Small(0), IntVariant::bit_xor)
+}
+
+/// Return the bitwise NOT of an integer.
+pub fn bitwise_not(args: &[Expr], _: EnvRef) -> Result {
     match args {
-        [Expr::String(a), Expr::String(b)] => {
-            let c = a.clone() + b;
-            Ok(Expr::String(c))
-        }
-        _ => Err(Error::Message(format!("expected 2 strings"))),
+        [Expr::Number(Number::Int(i))] => Ok(Expr::Number(Number::Int(i.bit_not()))),
+        _ => Err(Error::new("expected exact integer")),
     }
 }
 
-/// Returns the size of a string as an `Expr::Number` (more specifically an `IntVariant::Small`).
-pub fn str_length(args: &[Expr], _: EnvRef) -> Result {
+/// Shift an integer left by a number of bits, or right if the count is negative.
+pub fn arithmetic_shift(args: &[Expr], _: EnvRef) -> Result {
     match args {
-        [Expr::String(s)] => Ok(Expr::Number(Number::from_usize(s.len()))),
-        _ => Err(Error::new("expected string")),
+        [
+            Expr::Number(Number::Int(i)),
+            Expr::Number(Number::Int(count)),
+        ] => {
+            let count = match count.to_i64() {
+                Some(count) => count,
+                // Every right shift past the bit length gives 0 or -1.
+                None if count.is_negative() => i64::MIN,
+                None => return Err(Error::new("shift count is too large")),
+            };
+            Ok(Expr::Number(Number::Int(i.shift(count)?)))
+        }
+        _ => Err(Error::new("expected 2 exact integers")),
     }
 }
 
-/// Create either a new empty string or a string from a char.
-pub fn new_string(args: &[Expr], _: EnvRef) -> Result {
+/// Return the number of 1 bits in a non-negative integer, or 0 bits in a negative integer.
+pub fn bit_count(args: &[Expr], _: EnvRef) -> Result {
     match args {
-        [] => Ok(Expr::String(String::new())),
-        [Expr::Char(c)] => Ok(Expr::String(String::from(*c))),
-        _ => Err(Error::new("expected character")),
+        [Expr::Number(Number::Int(i))] => Ok(Expr::Number(Number::from_u64(i.bit_count()))),
+        _ => Err(Error::new("expected exact integer")),
     }
 }
 
-/// Convert string to upper case.
-pub fn string_to_upcase(args: &[Expr], _: EnvRef) -> Result {
+/// Return the number of bits needed to represent an integer.
+pub fn integer_length(args: &[Expr], _: EnvRef) -> Result {
     match args {
-        [Expr::String(s)] => {
-            let upcase = s
-                .chars()
-                .map(|c| c.to_ascii_uppercase())
-                .collect::<String>();
-            return Ok(Expr::String(upcase));
-        }
-        _ => Err(Error::new("expected string")),
+        [Expr::Number(Number::Int(i))] => Ok(Expr::Number(Number::from_u64(i.integer_length()))),
+        _ => Err(Error::new("expected exact integer")),
     }
 }
 
-/// Convert string to lowercase.
-pub fn string_to_downcase(args: &[Expr], _: EnvRef) -> Result {
+/// Return true if the bit at an index of an integer is set.
+pub fn is_bit_set(args: &[Expr], _: EnvRef) -> Result {
     match args {
-        [Expr::String(s)] => {
-            let upcase = s
-                .chars()
-                .map(|c| c.to_ascii_lowercase())
-                .collect::<String>();
-            return Ok(Expr::String(upcase));
+        [Expr::Number(index), Expr::Number(Number::Int(i))] => {
+            Ok(Expr::Boolean(i.bit_is_set(bit_index(index)?)))
         }
-        _ => Err(Error::new("expected string")),
+        _ => Err(Error::new("expected bit index and exact integer")),
     }
 }
 
-/// Return reverse version of string.
-pub fn string_reverse(args: &[Expr], _: EnvRef) -> Result {
+/// Return a copy of an integer with the bit at an index set or cleared.
+pub fn copy_bit(args: &[Expr], _: EnvRef) -> Result {
     match args {
-        [Expr::String(s)] => {
-            let reverse = s.chars().rev().collect();
-            Ok(Expr::String(reverse))
-        }
-        _ => Err(Error::new("expected a string")),
+        [
+            Expr::Number(index),
+            Expr::Number(Number::Int(i)),
+            Expr::Boolean(value),
+        ] => Ok(Expr::Number(Number::Int(
+            i.with_bit(bit_index(index)?, *value)?,
+        ))),
+        _ => Err(Error::new("expected bit index, exact integer and boolean")),
     }
 }
 
-// Boolean
-
-/// Returns the opposite value of a `bool`.
-pub fn not(args: &[Expr], _: EnvRef) -> Result {
+/// Return the bits of an integer from a start index (inclusive) to an end index (exclusive).
+pub fn bit_field(args: &[Expr], _: EnvRef) -> Result {
     match args {
-        [Expr::Boolean(false)] => Ok(Expr::Boolean(true)),
-        _ => Ok(Expr::Boolean(false)),
+        [
+            Expr::Number(Number::Int(i)),
+            Expr::Number(start),
+            Expr::Number(end),
+        ] => {
+            let field = i.bit_field(bit_index(start)?, bit_index(end)?)?;
+            Ok(Expr::Number(Number::Int(field)))
+        }
+        _ => Err(Error::new("expected exact integer, start and end")),
     }
 }
 
-/// Returns `true` if any arguments are `false`.
-pub fn and(args: &[Expr], _: EnvRef) -> Result {
-    let contains_false = args.iter().all(|arg| !matches!(arg, Expr::Boolean(false)));
-    Ok(Expr::Boolean(contains_false))
+/// Fold exact integer arguments into `init` with a bitwise operation.
+fn fold_integers(
+    args: &[Expr],
+    init: IntVariant,
+    op: fn(&IntVariant, &IntVariant) -> IntVariant,
+) -> Result {
+    let mut acc = init;
+    for arg in args {
+        match arg {
+            Expr::Number(Number::Int(i)) => acc = op(&acc, i),
+            _ => return Err(Error::new("expected exact integers")),
+        }
+    }
+    Ok(Expr::Number(Number::Int(acc)))
 }
 
-/// Returns `true` if any argument is not `#f`.
-pub fn or(args: &[Expr], _: EnvRef) -> Result {
-    let contains_true = args.iter().any(|arg| !matches!(arg, Expr::Boolean(false)));
-    Ok(Expr::Boolean(contains_true))
+/// Convert a number to a bit index.
+fn bit_index(n: &Number) -> std::result::Result<u64, Error> {
+    match n {
+        Number::Int(i) if !i.is_negative() => {
+            i.to_u64().ok_or(Error::new("bit index is too large"))
+        }
+        _ => Err(Error::new("expected non-negative exact integer bit index")),
+    }
 }
 
-// Pairs & Lists
+// Random
 
-/// Construct a new pair from 2 expressions.
-pub fn cons_proc(args: &[Expr], _: EnvRef) -> Result {
-    use crate::types::Pair;
+/// Return a random exact integer in `[0, n)` from the default random source.
+pub fn random_integer(args: &[Expr], env: EnvRef) -> Result {
     match args {
-        [a, b] => Ok(Expr::Pair(Pair::cons((a.clone(), b.clone())))),
-        _ => Err(Error::new("expected 2 arguments")),
+        [n] => random_source_integer(&[default_random_source(&env)?, n.clone()], env),
+        _ => Err(Error::new("expected positive exact integer")),
     }
 }
 
-/// Make a new list.
-pub fn new_list(args: &[Expr], _: EnvRef) -> Result {
-    Ok(Pair::list(args))
+/// Return a random real in `(0, 1)` from the default random source.
+pub fn random_real(args: &[Expr], env: EnvRef) -> Result {
+    match args {
+        [] => random_source_real(&[default_random_source(&env)?], env),
+        _ => Err(Error::new("expected 0 arguments")),
+    }
 }
 
-/// Append 2 lists together.
-pub fn list_append(args: &[Expr], _: EnvRef) -> Result {
+/// Create a new random source in the default initial state.
+pub fn make_random_source(args: &[Expr], _: EnvRef) -> Result {
     match args {
-        [Expr::Pair(list_a), Expr::Pair(list_b)] if list_a.is_list() && list_b.is_list() => {
-            let result = list_a.clone().append(Expr::Pair(list_b.clone()))?;
-            Ok(result)
-        }
-        _ => Err(Error::new("expected 2 lists")),
+        [] => Ok(Expr::RandomSource(RandomSource::new())),
+        _ => Err(Error::new("expected 0 arguments")),
     }
 }
 
-/// Get length of list.
-pub fn list_length(args: &[Expr], _: EnvRef) -> Result {
+/// Return the state of a random source as an external representation.
+pub fn random_source_state_ref(args: &[Expr], _: EnvRef) -> Result {
     match args {
-        [Expr::Pair(p)] => Ok(Expr::Number(Number::from_usize(p.len()))),
-        _ => Err(Error::new("expected list")),
+        [Expr::RandomSource(source)] => {
+            let mut state = vec![Expr::Symbol(Symbol::intern(RANDOM_STATE_TAG))];
+            state.extend(
+                source
+                    .state()
+                    .iter()
+                    .map(|word| Expr::Number(Number::from_u64(*word))),
+            );
+            Ok(Pair::list(&state))
+        }
+        _ => Err(Error::new("expected random source")),
     }
 }
 
-/// Return first element from `Pair`.
-pub fn car(args: &[Expr], _: EnvRef) -> Result {
+/// Restore the state of a random source from `random-source-state-ref`.
+pub fn random_source_state_set(args: &[Expr], _: EnvRef) -> Result {
     match args {
-        [Expr::Pair(pair)] => Ok(pair.car()),
-        _ => Err(Error::new("expected pair")),
+        [Expr::RandomSource(source), Expr::Pair(state)] => {
+            let invalid = || Error::new("invalid random source state");
+            let elements: Vec<Expr> = state.iter().collect();
+            let [Expr::Symbol(tag), words @ ..] = elements.as_slice() else {
+                return Err(invalid());
+            };
+            if *tag != Symbol::intern(RANDOM_STATE_TAG) || words.len() != 4 {
+                return Err(invalid());
+            }
+            let mut new_state = [0u64; 4];
+            for (word, expr) in new_state.iter_mut().zip(words) {
+                *word = match expr {
+                    Expr::Number(Number::Int(i)) => i.to_u64().ok_or_else(invalid)?,
+                    _ => return Err(invalid()),
+                };
+            }
+            source.set_state(new_state)?;
+            Ok(Expr::Void())
+        }
+        _ => Err(Error::new("expected random source and state")),
     }
 }
 
-/// Return second element from `Pair`.
-pub fn cdr(args: &[Expr], _: EnvRef) -> Result {
+/// Reseed a random source from the system clock.
+pub fn random_source_randomize(args: &[Expr], _: EnvRef) -> Result {
     match args {
-        [Expr::Pair(pair)] => Ok(pair.cdr()),
-        _ => Err(Error::new("expected pair")),
+        [Expr::RandomSource(source)] => {
+            source.randomize();
+            Ok(Expr::Void())
+        }
+        _ => Err(Error::new("expected random source")),
     }
 }
 
-pub fn caar(args: &[Expr], _: EnvRef) -> Result {
+/// Reseed a random source deterministically from two non-negative exact integers.
+pub fn random_source_pseudo_randomize(args: &[Expr], _: EnvRef) -> Result {
     match args {
-        [Expr::Pair(p)] => p.caar().map_err(|_| Error::new("caar: not enough pairs")),
-        _ => Err(Error::new("caar: expected pair")),
+        [
+            Expr::RandomSource(source),
+            Expr::Number(Number::Int(i)),
+            Expr::Number(Number::Int(j)),
+        ] => {
+            source.pseudo_randomize(&i.to_bigint(), &j.to_bigint())?;
+            Ok(Expr::Void())
+        }
+        _ => Err(Error::new("expected random source and 2 exact integers")),
     }
 }
 
-pub fn cadr(args: &[Expr], _: EnvRef) -> Result {
+/// Return a procedure that generates random integers in `[0, n)` from a random source.
+pub fn random_source_make_integers(args: &[Expr], env: EnvRef) -> Result {
     match args {
-        [Expr::Pair(p)] => p.cadr().map_err(|_| Error::new("cadr: not enough pairs")),
-        _ => Err(Error::new("cadr: expected pair")),
+        [Expr::RandomSource(source)] => Ok(random_source_closure(
+            source,
+            random_source_integer,
+            &["n"],
+            env,
+        )),
+        _ => Err(Error::new("expected random source")),
     }
 }
 
-pub fn cdar(args: &[Expr], _: EnvRef) -> Result {
+/// Return a procedure that generates random reals in `(0, 1)` from a random source,
+/// optionally checking they are at least as fine-grained as `unit`.
+pub fn random_source_make_reals(args: &[Expr], env: EnvRef) -> Result {
     match args {
-        [Expr::Pair(p)] => p.cdar().map_err(|_| Error::new("cdar: not enough pairs")),
-        _ => Err(Error::new("cdar: expected pair")),
+        [Expr::RandomSource(source)] => {
+            Ok(random_source_closure(source, random_source_real, &[], env))
+        }
+        [Expr::RandomSource(source), Expr::Number(unit)] => {
+            let unit = unit.to_f64().unwrap_or(f64::NAN);
+            if !(unit > 0.0 && unit < 1.0) {
+                return Err(Error::new("expected unit in the open interval (0, 1)"));
+            }
+            if unit < RANDOM_REAL_UNIT {
+                return Err(Error::new("unit is finer than the available precision"));
+            }
+            Ok(random_source_closure(source, random_source_real, &[], env))
+        }
+        _ => Err(Error::new("expected random source and optional real unit")),
     }
 }
 
-pub fn cddr(args: &[Expr], _: EnvRef) -> Result {
+/// Returns true if arg is a random source.
+pub fn is_random_source(args: &[Expr], _: EnvRef) -> Result {
     match args {
-        [Expr::Pair(p)] => p.cddr().map_err(|_| Error::new("cddr: not enough pairs")),
-        _ => Err(Error::new("cddr: expected pair")),
+        [Expr::RandomSource(_)] => Ok(Expr::Boolean(true)),
+        [_] => Ok(Expr::Boolean(false)),
+        _ => Err(Error::Message(format!(
+            "expected 1 argument, got {}",
+            args.len()
+        ))),
     }
 }
 
-pub fn caaar(args: &[Expr], _: EnvRef) -> Result {
+/// Spacing between the reals generated by `RandomSource::real`.
+const RANDOM_REAL_UNIT: f64 = 1.0 / (1u64 << 53) as f64;
+
+/// Tag at the start of the external representation of a random source state.
+const RANDOM_STATE_TAG: &str = "xoshiro256**";
+
+/// Return a random exact integer in `[0, n)` from a random source.
+fn random_source_integer(args: &[Expr], _: EnvRef) -> Result {
     match args {
-        [Expr::Pair(p)] => p.caaar().map_err(|_| Error::new("caaar: not enough pairs")),
-        _ => Err(Error::new("caaar: expected pair")),
+        [Expr::RandomSource(source), Expr::Number(Number::Int(n))] => {
+            let value = source.integer(&n.to_bigint())?;
+            Ok(Expr::Number(Number::from_bigint(value)))
+        }
+        _ => Err(Error::new("expected positive exact integer")),
     }
 }
 
-pub fn caadr(args: &[Expr], _: EnvRef) -> Result {
+/// Return a random real in `(0, 1)` from a random source.
+fn random_source_real(args: &[Expr], _: EnvRef) -> Result {
     match args {
-        [Expr::Pair(p)] => p.caadr().map_err(|_| Error::new("caadr: not enough pairs")),
-        _ => Err(Error::new("caadr: expected pair")),
+        [Expr::RandomSource(source)] => Ok(Expr::Number(Number::from_f64(source.real()))),
+        _ => Err(Error::new("expected random source")),
     }
 }
 
-pub fn cadar(args: &[Expr], _: EnvRef) -> Result {
-    match args {
-        [Expr::Pair(p)] => p.cadar().map_err(|_| Error::new("cadar: not enough pairs")),
-        _ => Err(Error::new("cadar: expected pair")),
+/// Find the default random source in the environment.
+fn default_random_source(env: &EnvRef) -> std::result::Result<Expr, Error> {
+    match env.borrow().find_value("default-random-source") {
+        Some(source @ Expr::RandomSource(_)) => Ok(source),
+        _ => Err(Error::new("default-random-source is not a random source")),
     }
 }
 
-pub fn caddr(args: &[Expr], _: EnvRef) -> Result {
-    match args {
-        [Expr::Pair(p)] => p.caddr().map_err(|_| Error::new("caddr: not enough pairs")),
-        _ => Err(Error::new("caddr: expected pair")),
-    }
+/// Create a closure that calls `generator` with a random source followed by `parameters`.
+///
+/// Builtins are plain function pointers and cannot capture the source, so the
+/// closure body is the call `(generator source parameters...)` evaluated in a
+/// local environment that binds `generator` and `source`. The body cannot see
+/// the caller's bindings, so those names never clash with user code.
+fn random_source_closure(
+    source: &RandomSource,
+    generator: Procedure,
+    parameters: &[&str],
+    env: EnvRef,
+) -> Expr {
+    let closure_env = Env::local_env(env);
+    {
+        let mut closure_env = closure_env.borrow_mut();
+        closure_env.insert_expr("generator", Expr::Procedure(generator));
+        closure_env.insert_expr("source", Expr::RandomSource(source.clone()));
+    }
+
+    let mut call = vec![
+        Expr::Symbol(Symbol::intern("generator")),
+        Expr::Symbol(Symbol::intern("source")),
+    ];
+    call.extend(parameters.iter().map(|p| Expr::Symbol(Symbol::intern(p))));
+    let parameters = parameters.iter().map(|p| Symbol::intern(p)).collect();
+    let closure = Closure::init(closure_env, parameters, None, vec![Pair::list(&call)]);
+    Expr::Closure(Rc::new(closure))
 }
 
-pub fn cdaar(args: &[Expr], _: EnvRef) -> Result {
-    match args {
-        [Expr::Pair(p)] => p.cdaar().map_err(|_| Error::new("cdaar: not enough pairs")),
-        _ => Err(Error::new("cdaar: expected pair")),
-    }
+// Strings
+
+/// Create a new string from chars.
+pub fn new_string(args: &[Expr], _: EnvRef) -> Result {
+    let chars = args
+        .iter()
+        .map(|arg| match arg {
+            Expr::Char(c) => Ok(*c),
+            _ => Err(Error::new("expected character")),
+        })
+        .collect::<std::result::Result<Vec<char>, Error>>()?;
+    Ok(Expr::String(SchemeString::from_chars(chars)))
+}
+
+/// Create a new string of length `k`, optionally filled with a char.
+pub fn make_string(args: &[Expr], _: EnvRef) -> Result {
+    let (k, fill) = match args {
+        [Expr::Number(k)] => (k, ' '),
+        [Expr::Number(k), Expr::Char(c)] => (k, *c),
+        _ => return Err(Error::new("expected length and optional character")),
+    };
+    let k = k
+        .to_usize()
+        .ok_or_else(|| Error::new("expected non-negative exact integer"))?;
+    Ok(Expr::String(SchemeString::from_chars(vec![fill; k])))
 }
 
-pub fn cdadr(args: &[Expr], _: EnvRef) -> Result {
-    match args {
-        [Expr::Pair(p)] => p.cdadr().map_err(|_| Error::new("cdadr: not enough pairs")),
-        _ => Err(Error::new("cdadr: expected pair")),
+/// Appends strings together.
+pub fn str_append(args: &[Expr], _: EnvRef) -> Result {
+    let mut chars = Vec::new();
+    for arg in args {
+        match arg {
+            Expr::String(s) => chars.extend(s.chars()),
+            _ => return Err(Error::new("expected strings")),
+        }
     }
+    Ok(Expr::String(SchemeString::from_chars(chars)))
 }
 
-pub fn cddar(args: &[Expr], _: EnvRef) -> Result {
+/// Returns the size of a string as an `Expr::Number` (more specifically an `IntVariant::Small`).
+pub fn str_length(args: &[Expr], _: EnvRef) -> Result {
     match args {
-        [Expr::Pair(p)] => p.cddar().map_err(|_| Error::new("cddar: not enough pairs")),
-        _ => Err(Error::new("cddar: expected pair")),
+        [Expr::String(s)] => Ok(Expr::Number(Number::from_usize(s.len()))),
+        _ => Err(Error::new("expected string")),
     }
 }
 
-pub fn cdddr(args: &[Expr], _: EnvRef) -> Result {
+/// Return the char at index `k` of a string.
+pub fn string_ref(args: &[Expr], _: EnvRef) -> Result {
     match args {
-        [Expr::Pair(p)] => p.cdddr().map_err(|_| Error::new("cdddr: not enough pairs")),
-        _ => Err(Error::new("cdddr: expected pair")),
+        [Expr::String(s), Expr::Number(k)] => k
+            .to_usize()
+            .and_then(|k| s.get(k))
+            .map(Expr::Char)
+            .ok_or_else(|| Error::new("index out of range")),
+        _ => Err(Error::new("expected string and index")),
     }
 }
 
-pub fn caaaar(args: &[Expr], _: EnvRef) -> Result {
+/// Set the char at index `k` of a mutable string.
+pub fn string_set(args: &[Expr], _: EnvRef) -> Result {
     match args {
-        [Expr::Pair(p)] => p
-            .caaaar()
-            .map_err(|_| Error::new("caaaar: not enough pairs")),
-        _ => Err(Error::new("caaaar: expected pair")),
+        [Expr::String(s), Expr::Number(k), Expr::Char(c)] => {
+            let k = k
+                .to_usize()
+                .ok_or_else(|| Error::new("index out of range"))?;
+            s.set(k, *c)?;
+            Ok(Expr::Void())
+        }
+        _ => Err(Error::new("expected string, index and character")),
     }
 }
 
-pub fn caaadr(args: &[Expr], _: EnvRef) -> Result {
+/// Return a new string with the chars from `start` to `end`.
+pub fn substring(args: &[Expr], _: EnvRef) -> Result {
     match args {
-        [Expr::Pair(p)] => p
-            .caaadr()
-            .map_err(|_| Error::new("caaadr: not enough pairs")),
-        _ => Err(Error::new("caaadr: expected pair")),
+        [Expr::String(s), Expr::Number(_), Expr::Number(_)] => {
+            Ok(Expr::String(string_range(s, &args[1..])?))
+        }
+        _ => Err(Error::new("expected string, start and end")),
     }
 }
 
-pub fn caadar(args: &[Expr], _: EnvRef) -> Result {
+/// Return a newly allocated copy of a string, with optional start and end indexes.
+pub fn string_copy(args: &[Expr], _: EnvRef) -> Result {
     match args {
-        [Expr::Pair(p)] => p
-            .caadar()
-            .map_err(|_| Error::new("caadar: not enough pairs")),
-        _ => Err(Error::new("caadar: expected pair")),
+        [Expr::String(s), range @ ..] => Ok(Expr::String(string_range(s, range)?)),
+        _ => Err(Error::new("expected string")),
     }
 }
 
-pub fn caaddr(args: &[Expr], _: EnvRef) -> Result {
+/// Copy the chars of `from` into `to`, starting at index `at`.
+pub fn string_copy_to(args: &[Expr], _: EnvRef) -> Result {
     match args {
-        [Expr::Pair(p)] => p
-            .caaddr()
-            .map_err(|_| Error::new("caaddr: not enough pairs")),
-        _ => Err(Error::new("caaddr: expected pair")),
+        [
+            Expr::String(to),
+            Expr::Number(at),
+            Expr::String(from),
+            range @ ..,
+        ] => {
+            let at = at
+                .to_usize()
+                .ok_or_else(|| Error::new("index out of range"))?;
+            let (start, end) = string_bounds(from, range)?;
+            to.copy_into(at, from, start, end)?;
+            Ok(Expr::Void())
+        }
+        _ => Err(Error::new("expected string, index and string")),
     }
 }
 
-pub fn cadaar(args: &[Expr], _: EnvRef) -> Result {
+/// Fill a mutable string with a char, with optional start and end indexes.
+pub fn string_fill(args: &[Expr], _: EnvRef) -> Result {
     match args {
-        [Expr::Pair(p)] => p
-            .cadaar()
-            .map_err(|_| Error::new("cadaar: not enough pairs")),
-        _ => Err(Error::new("cadaar: expected pair")),
+        [Expr::String(s), Expr::Char(c), range @ ..] => {
+            let (start, end) = string_bounds(s, range)?;
+            s.fill(*c, start, end)?;
+            Ok(Expr::Void())
+        }
+        _ => Err(Error::new("expected string and character")),
     }
 }
 
-pub fn cadadr(args: &[Expr], _: EnvRef) -> Result {
+/// Convert string to upper case.
+pub fn string_to_upcase(args: &[Expr], _: EnvRef) -> Result {
     match args {
-        [Expr::Pair(p)] => p
-            .cadadr()
-            .map_err(|_| Error::new("cadadr: not enough pairs")),
-        _ => Err(Error::new("cadadr: expected pair")),
+        [Expr::String(s)] => Ok(Expr::String(SchemeString::new(
+            &s.to_string().to_uppercase(),
+        ))),
+        _ => Err(Error::new("expected string")),
     }
 }
 
-pub fn caddar(args: &[Expr], _: EnvRef) -> Result {
+/// Convert string to lowercase.
+pub fn string_to_downcase(args: &[Expr], _: EnvRef) -> Result {
     match args {
-        [Expr::Pair(p)] => p
-            .caddar()
-            .map_err(|_| Error::new("caddar: not enough pairs")),
-        _ => Err(Error::new("caddar: expected pair")),
+        [Expr::String(s)] => Ok(Expr::String(SchemeString::new(
+            &s.to_string().to_lowercase(),
+        ))),
+        _ => Err(Error::new("expected string")),
     }
 }
 
-pub fn cadddr(args: &[Expr], _: EnvRef) -> Result {
+/// Convert string to its case-folded form.
+pub fn string_foldcase(args: &[Expr], _: EnvRef) -> Result {
     match args {
-        [Expr::Pair(p)] => p
-            .cadddr()
-            .map_err(|_| Error::new("cadddr: not enough pairs")),
-        _ => Err(Error::new("cadddr: expected pair")),
+        [Expr::String(s)] => Ok(Expr::String(SchemeString::new(&fold_case(s)))),
+        _ => Err(Error::new("expected string")),
     }
 }
 
-pub fn cdaaar(args: &[Expr], _: EnvRef) -> Result {
+/// Return reverse version of string.
+pub fn string_reverse(args: &[Expr], _: EnvRef) -> Result {
     match args {
-        [Expr::Pair(p)] => p
-            .cdaaar()
-            .map_err(|_| Error::new("cdaaar: not enough pairs")),
-        _ => Err(Error::new("cdaaar: expected pair")),
+        [Expr::String(s)] => {
+            let reverse = s.chars().into_iter().rev().collect();
+            Ok(Expr::String(SchemeString::from_chars(reverse)))
+        }
+        _ => Err(Error::new("expected a string")),
     }
 }
 
-pub fn cdaadr(args: &[Expr], _: EnvRef) -> Result {
-    match args {
-        [Expr::Pair(p)] => p
-            .cdaadr()
-            .map_err(|_| Error::new("cdaadr: not enough pairs")),
-        _ => Err(Error::new("cdaadr: expected pair")),
-    }
+/// Return true if all strings are equal.
+pub fn string_eq(args: &[Expr], _: EnvRef) -> Result {
+    compare_strings(args, false, Ordering::is_eq)
 }
 
-pub fn cdadar(args: &[Expr], _: EnvRef) -> Result {
-    match args {
-        [Expr::Pair(p)] => p
-            .cdadar()
-            .map_err(|_| Error::new("cdadar: not enough pairs")),
-        _ => Err(Error::new("cdadar: expected pair")),
-    }
+/// Return true if strings are monotonically increasing.
+pub fn string_lt(args: &[Expr], _: EnvRef) -> Result {
+    compare_strings(args, false, Ordering::is_lt)
 }
 
-pub fn cdaddr(args: &[Expr], _: EnvRef) -> Result {
-    match args {
-        [Expr::Pair(p)] => p
-            .cdaddr()
-            .map_err(|_| Error::new("cdaddr: not enough pairs")),
-        _ => Err(Error::new("cdaddr: expected pair")),
-    }
+/// Return true if strings are monotonically decreasing.
+pub fn string_gt(args: &[Expr], _: EnvRef) -> Result {
+    compare_strings(args, false, Ordering::is_gt)
 }
 
-pub fn cddaar(args: &[Expr], _: EnvRef) -> Result {
-    match args {
-        [Expr::Pair(p)] => p
-            .cddaar()
-            .map_err(|_| Error::new("cddaar: not enough pairs")),
-        _ => Err(Error::new("cddaar: expected pair")),
-    }
+/// Return true if strings are monotonically non-decreasing.
+pub fn string_le(args: &[Expr], _: EnvRef) -> Result {
+    compare_strings(args, false, Ordering::is_le)
 }
 
-pub fn cddadr(args: &[Expr], _: EnvRef) -> Result {
-    match args {
-        [Expr::Pair(p)] => p
-            .cddadr()
-            .map_err(|_| Error::new("cddadr: not enough pairs")),
-        _ => Err(Error::new("cddadr: expected pair")),
-    }
+/// Return true if strings are monotonically non-increasing.
+pub fn string_ge(args: &[Expr], _: EnvRef) -> Result {
+    compare_strings(args, false, Ordering::is_ge)
 }
 
-pub fn cdddar(args: &[Expr], _: EnvRef) -> Result {
-    match args {
-        [Expr::Pair(p)] => p
-            .cdddar()
-            .map_err(|_| Error::new("cdddar: not enough pairs")),
-        _ => Err(Error::new("cdddar: expected pair")),
-    }
+/// Return true if all strings are equal, ignoring case.
+pub fn string_ci_eq(args: &[Expr], _: EnvRef) -> Result {
+    compare_strings(args, true, Ordering::is_eq)
 }
 
-pub fn cddddr(args: &[Expr], _: EnvRef) -> Result {
-    match args {
-        [Expr::Pair(p)] => p
-            .cddddr()
-            .map_err(|_| Error::new("cddddr: not enough pairs")),
-        _ => Err(Error::new("cddddr: expected pair")),
-    }
+/// Return true if strings are monotonically increasing, ignoring case.
+pub fn string_ci_lt(args: &[Expr], _: EnvRef) -> Result {
+    compare_strings(args, true, Ordering::is_lt)
 }
 
-/// Reverse list.
-pub fn list_reverse(args: &[Expr], _: EnvRef) -> Result {
-    match args {
-        [Expr::Pair(pair)] => {
-            let items: Vec<Expr> = PairIter::new(pair).map(|e| e.clone()).collect();
-            let reversed: Vec<Expr> = items.into_iter().rev().collect::<Vec<_>>();
-            Ok(Pair::list(&reversed))
-        }
-        _ => Err(Error::new("expected list")),
-    }
+/// Return true if strings are monotonically decreasing, ignoring case.
+pub fn string_ci_gt(args: &[Expr], _: EnvRef) -> Result {
+    compare_strings(args, true, Ordering::is_gt)
 }
 
-// Vectors
+/// Return true if strings are monotonically non-decreasing, ignoring case.
+pub fn string_ci_le(args: &[Expr], _: EnvRef) -> Result {
+    compare_strings(args, true, Ordering::is_le)
+}
 
-/// Create a new vector containing the given arguments.
-pub fn new_vector(args: &[Expr], _: EnvRef) -> Result {
-    Ok(Expr::Vector(Vector::from(args)))
+/// Return true if strings are monotonically non-increasing, ignoring case.
+pub fn string_ci_ge(args: &[Expr], _: EnvRef) -> Result {
+    compare_strings(args, true, Ordering::is_ge)
 }
 
-/// Create a new vector with an optional pre-allocated size.
-pub fn make_vector(args: &[Expr], _: EnvRef) -> Result {
-    match args {
-        [Expr::Number(n)] => match n.to_usize() {
-            Some(size) => {
-                let vector = Vector::new();
-                vector.alloc_size(size, None);
-                Ok(Expr::Vector(vector))
-            }
-            _ => Err(Error::Message(
-                "invalid size, expected int or float".to_string(),
-            )),
-        },
-        _ => Ok(Expr::Vector(Vector::new())),
-    }
+/// Return the index of the first char in a string that matches a char, char set or predicate.
+pub fn string_index(args: &[Expr], env: EnvRef) -> Result {
+    find_char(args, &env, true, false)
 }
 
-/// Return contents of vector at specified index.
-pub fn vector_ref(args: &[Expr], _: EnvRef) -> Result {
+/// Return the index of the last char in a string that matches a char, char set or predicate.
+pub fn string_index_right(args: &[Expr], env: EnvRef) -> Result {
+    find_char(args, &env, true, true)
+}
+
+/// Return the index of the first char in a string that does not match a char test.
+pub fn string_skip(args: &[Expr], env: EnvRef) -> Result {
+    find_char(args, &env, false, false)
+}
+
+/// Return the index of the last char in a string that does not match a char test.
+pub fn string_skip_right(args: &[Expr], env: EnvRef) -> Result {
+    find_char(args, &env, false, true)
+}
+
+/// Return the index of the first occurrence of the second string in the first, or false.
+pub fn string_contains(args: &[Expr], _: EnvRef) -> Result {
+    let (haystack, needle, offset) = search_ranges(args)?;
+    Ok(index_or_false(
+        find_chars(&haystack, &needle).map(|i| i + offset),
+    ))
+}
+
+/// Return the index of the last occurrence of the second string in the first, or false.
+pub fn string_contains_right(args: &[Expr], _: EnvRef) -> Result {
+    let (haystack, needle, offset) = search_ranges(args)?;
+    Ok(index_or_false(
+        rfind_chars(&haystack, &needle).map(|i| i + offset),
+    ))
+}
+
+/// Return the index where a pattern first occurs in a string at or after an optional start.
+pub fn string_search_forward(args: &[Expr], _: EnvRef) -> Result {
     match args {
-        [Expr::Vector(v), Expr::Number(n)] => match n.to_usize() {
-            Some(size) => match v.get(size) {
-                Some(e) => Ok(e.clone()),
-                _ => Err(Error::new("invalid index")),
-            },
-            _ => Err(Error::Message(
-                "invalid length, expected int or float".to_string(),
-            )),
-        },
-        _ => Ok(Expr::Vector(Vector::new())),
+        [Expr::String(pattern), Expr::String(s), start @ ..] if start.len() <= 1 => {
+            let (start, end) = string_bounds(s, start)?;
+            let found = find_chars(&s.chars()[start..end], &pattern.chars());
+            Ok(index_or_false(found.map(|i| i + start)))
+        }
+        _ => Err(Error::new("expected pattern, string and optional start")),
     }
 }
 
-/// Set contents of vector at specified index.
-pub fn vector_set(args: &[Expr], _: EnvRef) -> Result {
+/// Return the index after the end of the last occurrence of a pattern in a string
+/// that ends at or before an optional end.
+pub fn string_search_backward(args: &[Expr], _: EnvRef) -> Result {
     match args {
-        [Expr::Vector(v), Expr::Number(n), expr] => match n.to_usize() {
-            Some(index) => {
-                v.set(index, expr.clone())?;
-                Ok(Expr::Void())
-            }
-            _ => Err(Error::new("invalid index")),
-        },
-        _ => Err(Error::Message(
-            "expected vector, index, and new value".to_string(),
-        )),
+        [Expr::String(pattern), Expr::String(s), end @ ..] if end.len() <= 1 => {
+            let end = match end {
+                [Expr::Number(end)] => end
+                    .to_usize()
+                    .filter(|end| *end <= s.len())
+                    .ok_or(Error::new("index out of range"))?,
+                [] => s.len(),
+                _ => return Err(Error::new("expected end index")),
+            };
+            let pattern = pattern.chars();
+            let found = rfind_chars(&s.chars()[..end], &pattern);
+            Ok(index_or_false(found.map(|i| i + pattern.len())))
+        }
+        _ => Err(Error::new("expected pattern, string and optional end")),
     }
 }
 
-/// Return length of vector.
-pub fn vector_len(args: &[Expr], _: EnvRef) -> Result {
+/// Return a list of every index where a pattern occurs in a string.
+pub fn string_search_all(args: &[Expr], _: EnvRef) -> Result {
     match args {
-        [Expr::Vector(v)] => Ok(Expr::Number(Number::from_usize(v.elements.borrow().len()))),
-        _ => Err(Error::new("expected vector")),
+        [Expr::String(pattern), Expr::String(s), range @ ..] => {
+            let (start, end) = string_bounds(s, range)?;
+            let (chars, pattern) = (s.chars(), pattern.chars());
+            let indexes: Vec<Expr> = (start..end)
+                .filter(|i| chars[*i..end].starts_with(&pattern))
+                .map(|i| Expr::Number(Number::from_usize(i)))
+                .collect();
+            Ok(Pair::list(&indexes))
+        }
+        _ => Err(Error::new("expected pattern, string and optional range")),
     }
 }
 
-/// Return a newly allocated copy of `&self`. Accepts optional start and end indexes.
-pub fn vector_copy(args: &[Expr], _: EnvRef) -> Result {
-    match args {
-        [Expr::Vector(v)] => Ok(Expr::Vector(v.clone())),
+/// Return true if the first string is a prefix of the second.
+pub fn string_prefix(args: &[Expr], _: EnvRef) -> Result {
+    let (prefix, s) = affix_ranges(args, false)?;
+    Ok(Expr::Boolean(s.starts_with(&prefix)))
+}
+
+/// Return true if the first string is a suffix of the second.
+pub fn string_suffix(args: &[Expr], _: EnvRef) -> Result {
+    let (suffix, s) = affix_ranges(args, false)?;
+    Ok(Expr::Boolean(s.ends_with(&suffix)))
+}
+
+/// Return true if the first string is a prefix of the second, ignoring case.
+pub fn string_prefix_ci(args: &[Expr], _: EnvRef) -> Result {
+    let (prefix, s) = affix_ranges(args, true)?;
+    Ok(Expr::Boolean(s.starts_with(&prefix)))
+}
+
+/// Return true if the first string is a suffix of the second, ignoring case.
+pub fn string_suffix_ci(args: &[Expr], _: EnvRef) -> Result {
+    let (suffix, s) = affix_ranges(args, true)?;
+    Ok(Expr::Boolean(s.ends_with(&suffix)))
+}
+
+/// Split a string into a list of fields at a delimiter string, char or char set.
+/// Takes an optional grammar, limit on the number of splits, start and end.
+pub fn string_split(args: &[Expr], _: EnvRef) -> Result {
+    let [Expr::String(s), delimiter, options @ ..] = args else {
+        return Err(Error::new(
+            "expected string, delimiter and optional grammar, limit and range",
+        ));
+    };
+    let (grammar, options) = match options {
+        [Expr::Symbol(grammar), rest @ ..] => (Some(*grammar), rest),
+        _ => (None, options),
+    };
+    let (limit, range) = match options {
+        [Expr::Boolean(false), range @ ..] => (None, range),
+        [Expr::Number(limit), range @ ..] => (
+            Some(
+                limit
+                    .to_usize()
+                    .ok_or(Error::new("expected non-negative limit"))?,
+            ),
+            range,
+        ),
+        _ => (None, options),
+    };
+    let grammar = join_grammar(grammar)?;
+    let chars = string_range(s, range)?.chars();
+
+    if chars.is_empty() {
+        return match grammar {
+            Grammar::StrictInfix => Err(Error::new("cannot split empty string with strict-infix")),
+            _ => Ok(Expr::Null),
+        };
+    }
+
+    let delimiter = Delimiter::from_expr(delimiter)?;
+    let mut fields: Vec<&[char]> = Vec::new();
+    let mut field_start = 0;
+    while limit.is_none_or(|limit| fields.len() < limit) {
+        let Some((at, len)) = delimiter.find(&chars, field_start) else {
+            break;
+        };
+        fields.push(&chars[field_start..at]);
+        field_start = at + len;
+    }
+    fields.push(&chars[field_start..]);
+
+    if grammar == Grammar::Prefix && fields.first().is_some_and(|f| f.is_empty()) {
+        fields.remove(0);
+    }
+    if grammar == Grammar::Suffix && fields.last().is_some_and(|f| f.is_empty()) {
+        fields.pop();
+    }
+    let fields: Vec<Expr> = fields
+        .into_iter()
+        .map(|field| Expr::String(SchemeString::from_chars(field.to_vec())))
+        .collect();
+    Ok(Pair::list(&fields))
+}
+
+/// Join a list of strings with an optional delimiter, which defaults to a space,
+/// and an optional grammar.
+pub fn string_join(args: &[Expr], _: EnvRef) -> Result {
+    let (list, delimiter, grammar) = match args {
+        [list] => (list, " ".to_string(), None),
+        [list, Expr::String(delimiter)] => (list, delimiter.to_string(), None),
+        [list, Expr::String(delimiter), Expr::Symbol(grammar)] => {
+            (list, delimiter.to_string(), Some(*grammar))
+        }
+        _ => {
+            return Err(Error::new(
+                "expected list, optional delimiter and optional grammar",
+            ));
+        }
+    };
+    let strings = list_elements(list)?
+        .iter()
+        .map(|e| match e {
+            Expr::String(s) => Ok(s.to_string()),
+            _ => Err(Error::new("expected list of strings")),
+        })
+        .collect::<std::result::Result<Vec<String>, Error>>()?;
+
+    let joined = match join_grammar(grammar)? {
+        Grammar::StrictInfix if strings.is_empty() => {
+            return Err(Error::new("cannot join empty list with strict-infix"));
+        }
+        Grammar::Infix | Grammar::StrictInfix => strings.join(&delimiter),
+        Grammar::Prefix => strings.iter().map(|s| format!("{delimiter}{s}")).collect(),
+        Grammar::Suffix => strings.iter().map(|s| format!("{s}{delimiter}")).collect(),
+    };
+    Ok(Expr::String(SchemeString::new(&joined)))
+}
+
+/// Remove leading chars that match an optional char test, which defaults to whitespace.
+pub fn string_trim(args: &[Expr], env: EnvRef) -> Result {
+    trim_string(args, &env, true, false)
+}
+
+/// Remove trailing chars that match an optional char test, which defaults to whitespace.
+pub fn string_trim_right(args: &[Expr], env: EnvRef) -> Result {
+    trim_string(args, &env, false, true)
+}
+
+/// Remove leading and trailing chars that match an optional char test, which defaults to whitespace.
+pub fn string_trim_both(args: &[Expr], env: EnvRef) -> Result {
+    trim_string(args, &env, true, true)
+}
+
+/// Pad a string on the left to a length, or keep its rightmost chars if it is longer.
+pub fn string_pad(args: &[Expr], _: EnvRef) -> Result {
+    let (chars, len, fill) = pad_args(args)?;
+    let padded = if chars.len() >= len {
+        chars[chars.len() - len..].to_vec()
+    } else {
+        let mut padded = vec![fill; len - chars.len()];
+        padded.extend(chars);
+        padded
+    };
+    Ok(Expr::String(SchemeString::from_chars(padded)))
+}
+
+/// Pad a string on the right to a length, or keep its leftmost chars if it is longer.
+pub fn string_pad_right(args: &[Expr], _: EnvRef) -> Result {
+    let (mut chars, len, fill) = pad_args(args)?;
+    chars.resize(len, fill);
+    Ok(Expr::String(SchemeString::from_chars(chars)))
+}
+
+/// Replace the chars between start and end of the first string with the second string.
+pub fn string_replace(args: &[Expr], _: EnvRef) -> Result {
+    match args {
+        [
+            Expr::String(s1),
+            Expr::String(s2),
+            start1,
+            end1,
+            range2 @ ..,
+        ] => {
+            let (start, end) = string_bounds(s1, &[start1.clone(), end1.clone()])?;
+            let chars = s1.chars();
+            let mut replaced = chars[..start].to_vec();
+            replaced.extend(string_range(s2, range2)?.chars());
+            replaced.extend(&chars[end..]);
+            Ok(Expr::String(SchemeString::from_chars(replaced)))
+        }
+        _ => Err(Error::new(
+            "expected 2 strings, start, end and optional range of the second string",
+        )),
+    }
+}
+
+/// Return the number of chars in a string that match a char, char set or predicate.
+pub fn string_count(args: &[Expr], env: EnvRef) -> Result {
+    match args {
+        [Expr::String(s), test, range @ ..] => {
+            let test = char_test(test)?;
+            let mut count = 0;
+            for c in string_range(s, range)?.chars() {
+                if test.matches(c, &env)? {
+                    count += 1;
+                }
+            }
+            Ok(Expr::Number(Number::from_usize(count)))
+        }
+        _ => Err(Error::new("expected string, char test and optional range")),
+    }
+}
+
+/// Return a list of the maximal runs of chars in a string that are in a char set,
+/// which defaults to `char-set:graphic`.
+pub fn string_tokenize(args: &[Expr], _: EnvRef) -> Result {
+    let (s, token_set, range) = match args {
+        [Expr::String(s)] => (s, None, &args[1..]),
+        [Expr::String(s), Expr::CharSet(set), range @ ..] => (s, Some(set.clone()), range),
+        _ => {
+            return Err(Error::new(
+                "expected string, optional char set and optional range",
+            ));
+        }
+    };
+    let token_set = match token_set {
+        Some(set) => set,
+        None => {
+            char_set::named("char-set:graphic").ok_or(Error::new("missing char-set:graphic"))?
+        }
+    };
+    let tokens: Vec<Expr> = string_range(s, range)?
+        .chars()
+        .split(|c| !token_set.contains(*c))
+        .filter(|token| !token.is_empty())
+        .map(|token| Expr::String(SchemeString::from_chars(token.to_vec())))
+        .collect();
+    Ok(Pair::list(&tokens))
+}
+
+/// A char test given as a char, char set or predicate procedure.
+enum CharTest {
+    Char(char),
+    Set(CharSet),
+    Predicate(Expr),
+}
+
+impl CharTest {
+    /// Return true if `c` passes the test.
+    fn matches(&self, c: char, env: &EnvRef) -> std::result::Result<bool, Error> {
+        match self {
+            CharTest::Char(expected) => Ok(c == *expected),
+            CharTest::Set(set) => Ok(set.contains(c)),
+            CharTest::Predicate(pred) => Ok(is_true(&call_procedure(
+                pred,
+                vec![Expr::Char(c)],
+                env.clone(),
+            )?)),
+        }
+    }
+}
+
+/// Convert a char, char set or procedure argument into a `CharTest`.
+fn char_test(expr: &Expr) -> std::result::Result<CharTest, Error> {
+    match expr {
+        Expr::Char(c) => Ok(CharTest::Char(*c)),
+        Expr::CharSet(set) => Ok(CharTest::Set(set.clone())),
+        Expr::Procedure(_) | Expr::Closure(_) => Ok(CharTest::Predicate(expr.clone())),
+        _ => Err(Error::new("expected char, char set or predicate")),
+    }
+}
+
+/// Search the range of a string for the first or last char whose test result is `want`.
+fn find_char(args: &[Expr], env: &EnvRef, want: bool, from_right: bool) -> Result {
+    let [Expr::String(s), test, range @ ..] = args else {
+        return Err(Error::new("expected string, char test and optional range"));
+    };
+    let test = char_test(test)?;
+    let (start, end) = string_bounds(s, range)?;
+    let chars = s.chars();
+    let indexes: Box<dyn Iterator<Item = usize>> = match from_right {
+        true => Box::new((start..end).rev()),
+        false => Box::new(start..end),
+    };
+    for i in indexes {
+        if test.matches(chars[i], env)? == want {
+            return Ok(Expr::Number(Number::from_usize(i)));
+        }
+    }
+    Ok(Expr::Boolean(false))
+}
+
+/// Trim chars matching an optional char test from the left and/or right of a string range.
+fn trim_string(args: &[Expr], env: &EnvRef, left: bool, right: bool) -> Result {
+    let (s, test, range) = match args {
+        [Expr::String(s)] => (
+            s,
+            CharTest::Predicate(Expr::Procedure(is_char_whitespace)),
+            &args[1..],
+        ),
+        [Expr::String(s), test, range @ ..] => (s, char_test(test)?, range),
+        _ => {
+            return Err(Error::new(
+                "expected string, optional char test and optional range",
+            ));
+        }
+    };
+    let chars = string_range(s, range)?.chars();
+    let mut start = 0;
+    let mut end = chars.len();
+    while left && start < end && test.matches(chars[start], env)? {
+        start += 1;
+    }
+    while right && end > start && test.matches(chars[end - 1], env)? {
+        end -= 1;
+    }
+    Ok(Expr::String(SchemeString::from_chars(
+        chars[start..end].to_vec(),
+    )))
+}
+
+/// Return the chars of a string range, the padded length and the fill char.
+fn pad_args(args: &[Expr]) -> std::result::Result<(Vec<char>, usize, char), Error> {
+    let (s, len, fill, range) = match args {
+        [Expr::String(s), Expr::Number(len)] => (s, len, ' ', &args[2..]),
+        [
+            Expr::String(s),
+            Expr::Number(len),
+            Expr::Char(fill),
+            range @ ..,
+        ] => (s, len, *fill, range),
+        _ => {
+            return Err(Error::new(
+                "expected string, length, optional char and optional range",
+            ));
+        }
+    };
+    let len = len
+        .to_usize()
+        .ok_or(Error::new("expected non-negative exact integer length"))?;
+    Ok((string_range(s, range)?.chars(), len, fill))
+}
+
+/// Return the chars of two string ranges for a search and the start of the first range.
+/// Arguments are two strings followed by optional start and end indexes for each.
+fn search_ranges(args: &[Expr]) -> std::result::Result<(Vec<char>, Vec<char>, usize), Error> {
+    match args {
+        [Expr::String(s1), Expr::String(s2), ranges @ ..] if ranges.len() <= 4 => {
+            let (range1, range2) = ranges.split_at(ranges.len().min(2));
+            let (start, _) = string_bounds(s1, range1)?;
+            Ok((
+                string_range(s1, range1)?.chars(),
+                string_range(s2, range2)?.chars(),
+                start,
+            ))
+        }
+        _ => Err(Error::new("expected 2 strings and optional ranges")),
+    }
+}
+
+/// Return the chars of two string ranges for a prefix or suffix test, optionally case folded.
+fn affix_ranges(args: &[Expr], fold: bool) -> std::result::Result<(Vec<char>, Vec<char>), Error> {
+    let (affix, s, _) = search_ranges(args)?;
+    match fold {
+        true => Ok((
+            affix.into_iter().map(chars::foldcase).collect(),
+            s.into_iter().map(chars::foldcase).collect(),
+        )),
+        false => Ok((affix, s)),
+    }
+}
+
+/// Return the index of the first occurrence of `needle` in `haystack`.
+fn find_chars(haystack: &[char], needle: &[char]) -> Option<usize> {
+    (0..=haystack.len().checked_sub(needle.len())?).find(|i| haystack[*i..].starts_with(needle))
+}
+
+/// Return the index of the last occurrence of `needle` in `haystack`.
+fn rfind_chars(haystack: &[char], needle: &[char]) -> Option<usize> {
+    (0..=haystack.len().checked_sub(needle.len())?)
+        .rev()
+        .find(|i| haystack[*i..].starts_with(needle))
+}
+
+/// Convert an optional index to an exact integer or false.
+fn index_or_false(index: Option<usize>) -> Expr {
+    match index {
+        Some(i) => Expr::Number(Number::from_usize(i)),
+        None => Expr::Boolean(false),
+    }
+}
+
+/// Grammar of `string-split` and `string-join`, controlling delimiters at the ends.
+#[derive(PartialEq)]
+enum Grammar {
+    Infix,
+    StrictInfix,
+    Prefix,
+    Suffix,
+}
+
+/// Convert an optional grammar symbol into a `Grammar`, defaulting to infix.
+fn join_grammar(grammar: Option<Symbol>) -> std::result::Result<Grammar, Error> {
+    let Some(grammar) = grammar else {
+        return Ok(Grammar::Infix);
+    };
+    match &*grammar.name() {
+        "infix" => Ok(Grammar::Infix),
+        "strict-infix" => Ok(Grammar::StrictInfix),
+        "prefix" => Ok(Grammar::Prefix),
+        "suffix" => Ok(Grammar::Suffix),
+        _ => Err(Error::Message(format!("invalid grammar: {}", grammar))),
+    }
+}
+
+/// Field delimiter for `string-split`.
+enum Delimiter {
+    Chars(Vec<char>),
+    Test(CharSet),
+}
+
+impl Delimiter {
+    /// Convert a non-empty string, char or char set into a `Delimiter`.
+    fn from_expr(expr: &Expr) -> std::result::Result<Delimiter, Error> {
+        match expr {
+            Expr::String(s) if !s.is_empty() => Ok(Delimiter::Chars(s.chars())),
+            Expr::Char(c) => Ok(Delimiter::Test(CharSet::from_chars([*c]))),
+            Expr::CharSet(set) => Ok(Delimiter::Test(set.clone())),
+            _ => Err(Error::new(
+                "expected non-empty string, char or char set delimiter",
+            )),
+        }
+    }
+
+    /// Return the index and length of the first delimiter in `chars` at or after `from`.
+    fn find(&self, chars: &[char], from: usize) -> Option<(usize, usize)> {
+        match self {
+            Delimiter::Chars(delimiter) => {
+                find_chars(&chars[from..], delimiter).map(|i| (i + from, delimiter.len()))
+            }
+            Delimiter::Test(set) => chars[from..]
+                .iter()
+                .position(|c| set.contains(*c))
+                .map(|i| (i + from, 1)),
+        }
+    }
+}
+
+/// Compare each adjacent pair of strings with `test`, optionally ignoring case.
+fn compare_strings(args: &[Expr], fold: bool, test: fn(Ordering) -> bool) -> Result {
+    let strings = args
+        .iter()
+        .map(|arg| match arg {
+            Expr::String(s) if fold => Ok(fold_case(s)),
+            Expr::String(s) => Ok(s.to_string()),
+            _ => Err(Error::new("expected strings")),
+        })
+        .collect::<std::result::Result<Vec<String>, Error>>()?;
+    if strings.is_empty() {
+        return Err(Error::new("expected at least 1 string"));
+    }
+    let result = strings.windows(2).all(|pair| test(pair[0].cmp(&pair[1])));
+    Ok(Expr::Boolean(result))
+}
+
+/// Return the full case-folded form of a string.
+fn fold_case(s: &SchemeString) -> String {
+    s.chars()
+        .into_iter()
+        .flat_map(chars::full_foldcase)
+        .collect()
+}
+
+/// Return the `(start, end)` indexes given by optional start and end arguments.
+fn string_bounds(s: &SchemeString, range: &[Expr]) -> std::result::Result<(usize, usize), Error> {
+    let index = |n: &Number| {
+        n.to_usize()
+            .ok_or_else(|| Error::new("invalid index, expected non-negative integer"))
+    };
+    let (start, end) = match range {
+        [] => (0, s.len()),
+        [Expr::Number(start)] => (index(start)?, s.len()),
+        [Expr::Number(start), Expr::Number(end)] => (index(start)?, index(end)?),
+        _ => return Err(Error::new("expected optional start and end indexes")),
+    };
+    if start > end || end > s.len() {
+        return Err(Error::new("index out of range"));
+    }
+    Ok((start, end))
+}
+
+/// Return a new string with the chars given by optional start and end arguments.
+fn string_range(s: &SchemeString, range: &[Expr]) -> std::result::Result<SchemeString, Error> {
+    let (start, end) = string_bounds(s, range)?;
+    s.substring(start, end)
+        .ok_or_else(|| Error::new("index out of range"))
+}
+
+// Chars
+
+/// Return true if all chars are equal.
+pub fn char_eq(args: &[Expr], _: EnvRef) -> Result {
+    compare_chars(args, false, Ordering::is_eq)
+}
+
+/// Return true if chars are monotonically increasing.
+pub fn char_lt(args: &[Expr], _: EnvRef) -> Result {
+    compare_chars(args, false, Ordering::is_lt)
+}
+
+/// Return true if chars are monotonically decreasing.
+pub fn char_gt(args: &[Expr], _: EnvRef) -> Result {
+    compare_chars(args, false, Ordering::is_gt)
+}
+
+/// Return true if chars are monotonically non-decreasing.
+pub fn char_le(args: &[Expr], _: EnvRef) -> Result {
+    compare_chars(args, false, Ordering::is_le)
+}
+
+/// Return true if chars are monotonically non-increasing.
+pub fn char_ge(args: &[Expr], _: EnvRef) -> Result {
+    compare_chars(args, false, Ordering::is_ge)
+}
+
+/// Return true if all chars are equal, ignoring case.
+pub fn char_ci_eq(args: &[Expr], _: EnvRef) -> Result {
+    compare_chars(args, true, Ordering::is_eq)
+}
+
+/// Return true if chars are monotonically increasing, ignoring case.
+pub fn char_ci_lt(args: &[Expr], _: EnvRef) -> Result {
+    compare_chars(args, true, Ordering::is_lt)
+}
+
+/// Return true if chars are monotonically decreasing, ignoring case.
+pub fn char_ci_gt(args: &[Expr], _: EnvRef) -> Result {
+    compare_chars(args, true, Ordering::is_gt)
+}
+
+/// Return true if chars are monotonically non-decreasing, ignoring case.
+pub fn char_ci_le(args: &[Expr], _: EnvRef) -> Result {
+    compare_chars(args, true, Ordering::is_le)
+}
+
+/// Return true if chars are monotonically non-increasing, ignoring case.
+pub fn char_ci_ge(args: &[Expr], _: EnvRef) -> Result {
+    compare_chars(args, true, Ordering::is_ge)
+}
+
+/// Return the Unicode scalar value of a char.
+pub fn char_to_integer(args: &[Expr], _: EnvRef) -> Result {
+    match args {
+        [Expr::Char(c)] => Ok(Expr::Number(Number::from_i64(*c as i64))),
+        _ => Err(Error::new("expected character")),
+    }
+}
+
+/// Return the char with the given Unicode scalar value.
+pub fn integer_to_char(args: &[Expr], _: EnvRef) -> Result {
+    match args {
+        [Expr::Number(Number::Int(n))] => n
+            .to_u32()
+            .and_then(char::from_u32)
+            .map(Expr::Char)
+            .ok_or_else(|| Error::new("not a Unicode scalar value")),
+        _ => Err(Error::new("expected exact integer")),
+    }
+}
+
+/// Return the uppercase form of a char.
+pub fn char_upcase(args: &[Expr], _: EnvRef) -> Result {
+    match args {
+        [Expr::Char(c)] => Ok(Expr::Char(chars::upcase(*c))),
+        _ => Err(Error::new("expected character")),
+    }
+}
+
+/// Return the lowercase form of a char.
+pub fn char_downcase(args: &[Expr], _: EnvRef) -> Result {
+    match args {
+        [Expr::Char(c)] => Ok(Expr::Char(chars::downcase(*c))),
+        _ => Err(Error::new("expected character")),
+    }
+}
+
+/// Return the case-folded form of a char.
+pub fn char_foldcase(args: &[Expr], _: EnvRef) -> Result {
+    match args {
+        [Expr::Char(c)] => Ok(Expr::Char(chars::foldcase(*c))),
+        _ => Err(Error::new("expected character")),
+    }
+}
+
+/// Return the value of a decimal digit char, or false if it is not a digit.
+pub fn digit_value(args: &[Expr], _: EnvRef) -> Result {
+    match args {
+        [Expr::Char(c)] => match chars::digit_value(*c) {
+            Some(d) => Ok(Expr::Number(Number::from_i64(d as i64))),
+            None => Ok(Expr::Boolean(false)),
+        },
+        _ => Err(Error::new("expected character")),
+    }
+}
+
+/// Compare each adjacent pair of chars with `test`, optionally ignoring case.
+fn compare_chars(args: &[Expr], fold: bool, test: fn(Ordering) -> bool) -> Result {
+    let chars = args
+        .iter()
+        .map(|arg| match arg {
+            Expr::Char(c) if fold => Ok(chars::foldcase(*c)),
+            Expr::Char(c) => Ok(*c),
+            _ => Err(Error::new("expected characters")),
+        })
+        .collect::<std::result::Result<Vec<char>, Error>>()?;
+    if chars.is_empty() {
+        return Err(Error::new("expected at least 1 character"));
+    }
+    let result = chars.windows(2).all(|pair| test(pair[0].cmp(&pair[1])));
+    Ok(Expr::Boolean(result))
+}
+
+// Char sets
+
+/// Returns true if arg is a char set.
+pub fn is_char_set(args: &[Expr], _: EnvRef) -> Result {
+    match args {
+        [Expr::CharSet(_)] => Ok(Expr::Boolean(true)),
+        [_] => Ok(Expr::Boolean(false)),
+        _ => Err(Error::Message(format!(
+            "expected 1 argument, got {}",
+            args.len()
+        ))),
+    }
+}
+
+/// Create a char set from chars.
+pub fn new_char_set(args: &[Expr], _: EnvRef) -> Result {
+    Ok(Expr::CharSet(CharSet::from_chars(char_args(args)?)))
+}
+
+/// Returns true if a char set contains a char.
+pub fn char_set_contains(args: &[Expr], _: EnvRef) -> Result {
+    match args {
+        [Expr::CharSet(set), Expr::Char(c)] => Ok(Expr::Boolean(set.contains(*c))),
+        _ => Err(Error::new("expected char set and character")),
+    }
+}
+
+/// Return the chars in any of the char sets.
+pub fn char_set_union(args: &[Expr], _: EnvRef) -> Result {
+    let sets = char_set_args(args)?;
+    let union = sets
+        .iter()
+        .fold(CharSet::empty(), |acc, set| acc.union(set));
+    Ok(Expr::CharSet(union))
+}
+
+/// Return the chars in every char set.
+pub fn char_set_intersection(args: &[Expr], _: EnvRef) -> Result {
+    let sets = char_set_args(args)?;
+    let intersection = sets
+        .iter()
+        .fold(CharSet::full(), |acc, set| acc.intersection(set));
+    Ok(Expr::CharSet(intersection))
+}
+
+/// Return the chars in the first char set that are in none of the others.
+pub fn char_set_difference(args: &[Expr], _: EnvRef) -> Result {
+    match char_set_args(args)?.split_first() {
+        Some((first, rest)) => Ok(Expr::CharSet(
+            rest.iter()
+                .fold(first.clone(), |acc, set| acc.difference(set)),
+        )),
+        None => Err(Error::new("expected at least 1 char set")),
+    }
+}
+
+/// Return the chars in an odd number of the char sets.
+pub fn char_set_xor(args: &[Expr], _: EnvRef) -> Result {
+    let sets = char_set_args(args)?;
+    let xor = sets.iter().fold(CharSet::empty(), |acc, set| acc.xor(set));
+    Ok(Expr::CharSet(xor))
+}
+
+/// Return the chars that are not in a char set.
+pub fn char_set_complement(args: &[Expr], _: EnvRef) -> Result {
+    match args {
+        [Expr::CharSet(set)] => Ok(Expr::CharSet(set.complement())),
+        _ => Err(Error::new("expected char set")),
+    }
+}
+
+/// Return a char set with the given chars added.
+pub fn char_set_adjoin(args: &[Expr], _: EnvRef) -> Result {
+    match args {
+        [Expr::CharSet(set), chars @ ..] => Ok(Expr::CharSet(
+            set.union(&CharSet::from_chars(char_args(chars)?)),
+        )),
+        _ => Err(Error::new("expected char set and characters")),
+    }
+}
+
+/// Return a char set with the given chars removed.
+pub fn char_set_delete(args: &[Expr], _: EnvRef) -> Result {
+    match args {
+        [Expr::CharSet(set), chars @ ..] => Ok(Expr::CharSet(
+            set.difference(&CharSet::from_chars(char_args(chars)?)),
+        )),
+        _ => Err(Error::new("expected char set and characters")),
+    }
+}
+
+/// Create a char set from a list of chars, added to an optional base char set.
+pub fn list_to_char_set(args: &[Expr], _: EnvRef) -> Result {
+    match args {
+        [list, base @ ..] if base.len() <= 1 => {
+            let chars = char_args(&list_elements(list)?)?;
+            let set = CharSet::from_chars(chars).union(&base_char_set(base)?);
+            Ok(Expr::CharSet(set))
+        }
+        _ => Err(Error::new("expected list and optional char set")),
+    }
+}
+
+/// Create a char set from the chars of a string, added to an optional base char set.
+pub fn string_to_char_set(args: &[Expr], _: EnvRef) -> Result {
+    match args {
+        [Expr::String(s), base @ ..] if base.len() <= 1 => {
+            let set = CharSet::from_chars(s.chars()).union(&base_char_set(base)?);
+            Ok(Expr::CharSet(set))
+        }
+        _ => Err(Error::new("expected string and optional char set")),
+    }
+}
+
+/// Create a char set from the code points in `[lower, upper)`, added to an optional base char set.
+pub fn ucs_range_to_char_set(args: &[Expr], _: EnvRef) -> Result {
+    match args {
+        [
+            Expr::Number(Number::Int(lower)),
+            Expr::Number(Number::Int(upper)),
+            rest @ ..,
+        ] if rest.len() <= 2 => {
+            let lower = lower.to_u32().ok_or(Error::new("invalid code point"))?;
+            let upper = upper.to_u32().ok_or(Error::new("invalid code point"))?;
+            // With a true error flag, code points that are not chars are an error
+            // rather than being left out.
+            let (surrogate_lo, surrogate_hi) = char_set::SURROGATES;
+            if rest.first().is_some_and(is_true)
+                && lower < upper
+                && (upper > char_set::MAX_CODE_POINT + 1
+                    || (lower <= surrogate_hi && upper > surrogate_lo))
+            {
+                return Err(Error::new("range contains code points that are not chars"));
+            }
+            let upper = upper.min(char_set::MAX_CODE_POINT + 1);
+            let range = match lower < upper {
+                true => CharSet::from_ranges(vec![(lower, upper - 1)]),
+                false => CharSet::empty(),
+            };
+            let set = range.union(&base_char_set(rest.get(1..).unwrap_or_default())?);
+            Ok(Expr::CharSet(set))
+        }
+        _ => Err(Error::new(
+            "expected 2 exact integers, optional error flag and optional char set",
+        )),
+    }
+}
+
+/// Convert a string, char or char set to a char set.
+pub fn to_char_set(args: &[Expr], _: EnvRef) -> Result {
+    match args {
+        [set @ Expr::CharSet(_)] => Ok(set.clone()),
+        [Expr::Char(c)] => Ok(Expr::CharSet(CharSet::from_chars([*c]))),
+        [Expr::String(s)] => Ok(Expr::CharSet(CharSet::from_chars(s.chars()))),
+        _ => Err(Error::new("expected string, character or char set")),
+    }
+}
+
+/// Return a list of the chars in a char set.
+pub fn char_set_to_list(args: &[Expr], _: EnvRef) -> Result {
+    match args {
+        [Expr::CharSet(set)] => {
+            let chars: Vec<Expr> = set.chars().map(Expr::Char).collect();
+            Ok(Pair::list(&chars))
+        }
+        _ => Err(Error::new("expected char set")),
+    }
+}
+
+/// Return a string of the chars in a char set.
+pub fn char_set_to_string(args: &[Expr], _: EnvRef) -> Result {
+    match args {
+        [Expr::CharSet(set)] => Ok(Expr::String(SchemeString::from_chars(
+            set.chars().collect(),
+        ))),
+        _ => Err(Error::new("expected char set")),
+    }
+}
+
+/// Return the number of chars in a char set.
+pub fn char_set_size(args: &[Expr], _: EnvRef) -> Result {
+    match args {
+        [Expr::CharSet(set)] => Ok(Expr::Number(Number::from_usize(set.len()))),
+        _ => Err(Error::new("expected char set")),
+    }
+}
+
+/// Return the number of chars in a char set that satisfy a predicate.
+pub fn char_set_count(args: &[Expr], env: EnvRef) -> Result {
+    match args {
+        [pred, Expr::CharSet(set)] => {
+            let mut count = 0;
+            for c in set.chars() {
+                if is_true(&call_procedure(pred, vec![Expr::Char(c)], env.clone())?) {
+                    count += 1;
+                }
+            }
+            Ok(Expr::Number(Number::from_usize(count)))
+        }
+        _ => Err(Error::new("expected predicate and char set")),
+    }
+}
+
+/// Returns true if all char sets have the same chars.
+pub fn char_set_eq(args: &[Expr], _: EnvRef) -> Result {
+    let sets = char_set_args(args)?;
+    Ok(Expr::Boolean(
+        sets.windows(2).all(|pair| pair[0] == pair[1]),
+    ))
+}
+
+/// Returns true if each char set is a subset of the next.
+pub fn char_set_le(args: &[Expr], _: EnvRef) -> Result {
+    let sets = char_set_args(args)?;
+    Ok(Expr::Boolean(
+        sets.windows(2).all(|pair| pair[0].is_subset(&pair[1])),
+    ))
+}
+
+/// Fold a procedure over the chars of a char set.
+pub fn char_set_fold(args: &[Expr], env: EnvRef) -> Result {
+    match args {
+        [kons, knil, Expr::CharSet(set)] => {
+            let mut acc = knil.clone();
+            for c in set.chars() {
+                acc = call_procedure(kons, vec![Expr::Char(c), acc], env.clone())?;
+            }
+            Ok(acc)
+        }
+        _ => Err(Error::new("expected procedure, initial value and char set")),
+    }
+}
+
+/// Call a procedure with each char of a char set.
+pub fn char_set_for_each(args: &[Expr], env: EnvRef) -> Result {
+    match args {
+        [procedure, Expr::CharSet(set)] => {
+            for c in set.chars() {
+                call_procedure(procedure, vec![Expr::Char(c)], env.clone())?;
+            }
+            Ok(Expr::Void())
+        }
+        _ => Err(Error::new("expected procedure and char set")),
+    }
+}
+
+/// Return the char set of the results of calling a procedure on each char of a char set.
+pub fn char_set_map(args: &[Expr], env: EnvRef) -> Result {
+    match args {
+        [procedure, Expr::CharSet(set)] => {
+            let mut chars = Vec::new();
+            for c in set.chars() {
+                match call_procedure(procedure, vec![Expr::Char(c)], env.clone())? {
+                    Expr::Char(mapped) => chars.push(mapped),
+                    _ => return Err(Error::new("expected procedure to return a character")),
+                }
+            }
+            Ok(Expr::CharSet(CharSet::from_chars(chars)))
+        }
+        _ => Err(Error::new("expected procedure and char set")),
+    }
+}
+
+/// Return the chars of a char set that satisfy a predicate, added to an optional base char set.
+pub fn char_set_filter(args: &[Expr], env: EnvRef) -> Result {
+    match args {
+        [pred, Expr::CharSet(set), base @ ..] if base.len() <= 1 => {
+            let mut chars = Vec::new();
+            for c in set.chars() {
+                if is_true(&call_procedure(pred, vec![Expr::Char(c)], env.clone())?) {
+                    chars.push(c);
+                }
+            }
+            Ok(Expr::CharSet(
+                CharSet::from_chars(chars).union(&base_char_set(base)?),
+            ))
+        }
+        _ => Err(Error::new(
+            "expected predicate, char set and optional char set",
+        )),
+    }
+}
+
+/// Return the first true result of a predicate on the chars of a char set, or false.
+pub fn char_set_any(args: &[Expr], env: EnvRef) -> Result {
+    match args {
+        [pred, Expr::CharSet(set)] => {
+            for c in set.chars() {
+                let result = call_procedure(pred, vec![Expr::Char(c)], env.clone())?;
+                if is_true(&result) {
+                    return Ok(result);
+                }
+            }
+            Ok(Expr::Boolean(false))
+        }
+        _ => Err(Error::new("expected predicate and char set")),
+    }
+}
+
+/// Return the last result of a predicate if it is true for every char of a char set.
+pub fn char_set_every(args: &[Expr], env: EnvRef) -> Result {
+    match args {
+        [pred, Expr::CharSet(set)] => {
+            let mut result = Expr::Boolean(true);
+            for c in set.chars() {
+                result = call_procedure(pred, vec![Expr::Char(c)], env.clone())?;
+                if !is_true(&result) {
+                    return Ok(result);
+                }
+            }
+            Ok(result)
+        }
+        _ => Err(Error::new("expected predicate and char set")),
+    }
+}
+
+/// Return a cursor to the first char of a char set.
+pub fn char_set_cursor(args: &[Expr], _: EnvRef) -> Result {
+    match args {
+        [Expr::CharSet(set)] => Ok(char_set_cursor_at(set, 0)),
+        _ => Err(Error::new("expected char set")),
+    }
+}
+
+/// Return the char at a char set cursor.
+pub fn char_set_ref(args: &[Expr], _: EnvRef) -> Result {
+    match args {
+        [Expr::CharSet(set), Expr::Number(Number::Int(cursor))] => cursor
+            .to_u32()
+            .and_then(char::from_u32)
+            .filter(|c| set.contains(*c))
+            .map(Expr::Char)
+            .ok_or(Error::new("invalid char set cursor")),
+        _ => Err(Error::new("expected char set and cursor")),
+    }
+}
+
+/// Return a cursor to the char after a cursor in a char set.
+pub fn char_set_cursor_next(args: &[Expr], _: EnvRef) -> Result {
+    match args {
+        [Expr::CharSet(set), Expr::Number(Number::Int(cursor))] => {
+            let cursor = cursor
+                .to_u32()
+                .ok_or(Error::new("invalid char set cursor"))?;
+            Ok(char_set_cursor_at(set, cursor + 1))
+        }
+        _ => Err(Error::new("expected char set and cursor")),
+    }
+}
+
+/// Returns true if a cursor is past the last char of its char set.
+pub fn is_end_of_char_set(args: &[Expr], _: EnvRef) -> Result {
+    match args {
+        [Expr::Number(Number::Int(cursor))] => Ok(Expr::Boolean(
+            cursor.to_u32().is_none_or(|c| c >= CHAR_SET_END),
+        )),
+        _ => Err(Error::new("expected char set cursor")),
+    }
+}
+
+/// Cursor value past the last char of every char set.
+const CHAR_SET_END: u32 = 0x110000;
+
+/// Return a cursor to the first char of `set` at or after code point `start`.
+fn char_set_cursor_at(set: &CharSet, start: u32) -> Expr {
+    let cursor = set
+        .ranges()
+        .iter()
+        .find(|(_, hi)| *hi >= start)
+        .map_or(CHAR_SET_END, |(lo, _)| start.max(*lo));
+    Expr::Number(Number::from_u64(cursor as u64))
+}
+
+/// Collect char set arguments.
+fn char_set_args(args: &[Expr]) -> std::result::Result<Vec<CharSet>, Error> {
+    args.iter()
+        .map(|arg| match arg {
+            Expr::CharSet(set) => Ok(set.clone()),
+            _ => Err(Error::new("expected char sets")),
+        })
+        .collect()
+}
+
+/// Collect char arguments.
+fn char_args(args: &[Expr]) -> std::result::Result<Vec<char>, Error> {
+    args.iter()
+        .map(|arg| match arg {
+            Expr::Char(c) => Ok(*c),
+            _ => Err(Error::new("expected characters")),
+        })
+        .collect()
+}
+
+/// Return the optional base char set argument, or the empty set.
+fn base_char_set(base: &[Expr]) -> std::result::Result<CharSet, Error> {
+    match base {
+        [] => Ok(CharSet::empty()),
+        [Expr::CharSet(set)] => Ok(set.clone()),
+        _ => Err(Error::new("expected char set")),
+    }
+}
+
+// Boolean
+
+/// Returns the opposite value of a `bool`.
+pub fn not(args: &[Expr], _: EnvRef) -> Result {
+    match args {
+        [Expr::Boolean(false)] => Ok(Expr::Boolean(true)),
+        _ => Ok(Expr::Boolean(false)),
+    }
+}
+
+/// Returns `true` if any arguments are `false`.
+pub fn and(args: &[Expr], _: EnvRef) -> Result {
+    let contains_false = args.iter().all(|arg| !matches!(arg, Expr::Boolean(false)));
+    Ok(Expr::Boolean(contains_false))
+}
+
+/// Returns `true` if any argument is not `#f`.
+pub fn or(args: &[Expr], _: EnvRef) -> Result {
+    let contains_true = args.iter().any(|arg| !matches!(arg, Expr::Boolean(false)));
+    Ok(Expr::Boolean(contains_true))
+}
+
+// Pairs & Lists
+
+/// Construct a new pair from 2 expressions.
+pub fn cons_proc(args: &[Expr], _: EnvRef) -> Result {
+    use crate::types::Pair;
+    match args {
+        [a, b] => Ok(Expr::Pair(Pair::cons((a.clone(), b.clone())))),
+        _ => Err(Error::new("expected 2 arguments")),
+    }
+}
+
+/// Make a new list.
+pub fn new_list(args: &[Expr], _: EnvRef) -> Result {
+    Ok(Pair::list(args))
+}
+
+/// Append lists together. The last argument may be any value.
+pub fn list_append(args: &[Expr], _: EnvRef) -> Result {
+    append_lists(args)
+}
+
+/// Get length of list.
+pub fn list_length(args: &[Expr], _: EnvRef) -> Result {
+    match args {
+        [Expr::Pair(p)] => Ok(Expr::Number(Number::from_usize(p.len()))),
+        _ => Err(Error::new("expected list")),
+    }
+}
+
+/// Return first element from `Pair`.
+pub fn car(args: &[Expr], _: EnvRef) -> Result {
+    match args {
+        [Expr::Pair(pair)] => Ok(pair.car()),
+        _ => Err(Error::new("expected pair")),
+    }
+}
+
+/// Return second element from `Pair`.
+pub fn cdr(args: &[Expr], _: EnvRef) -> Result {
+    match args {
+        [Expr::Pair(pair)] => Ok(pair.cdr()),
+        _ => Err(Error::new("expected pair")),
+    }
+}
+
+pub fn caar(args: &[Expr], _: EnvRef) -> Result {
+    match args {
+        [Expr::Pair(p)] => p.caar().map_err(|_| Error::new("caar: not enough pairs")),
+        _ => Err(Error::new("caar: expected pair")),
+    }
+}
+
+pub fn cadr(args: &[Expr], _: EnvRef) -> Result {
+    match args {
+        [Expr::Pair(p)] => p.cadr().map_err(|_| Error::new("cadr: not enough pairs")),
+        _ => Err(Error::new("cadr: expected pair")),
+    }
+}
+
+pub fn cdar(args: &[Expr], _: EnvRef) -> Result {
+    match args {
+        [Expr::Pair(p)] => p.cdar().map_err(|_| Error::new("cdar: not enough pairs")),
+        _ => Err(Error::new("cdar: expected pair")),
+    }
+}
+
+pub fn cddr(args: &[Expr], _: EnvRef) -> Result {
+    match args {
+        [Expr::Pair(p)] => p.cddr().map_err(|_| Error::new("cddr: not enough pairs")),
+        _ => Err(Error::new("cddr: expected pair")),
+    }
+}
+
+pub fn caaar(args: &[Expr], _: EnvRef) -> Result {
+    match args {
+        [Expr::Pair(p)] => p.caaar().map_err(|_| Error::new("caaar: not enough pairs")),
+        _ => Err(Error::new("caaar: expected pair")),
+    }
+}
+
+pub fn caadr(args: &[Expr], _: EnvRef) -> Result {
+    match args {
+        [Expr::Pair(p)] => p.caadr().map_err(|_| Error::new("caadr: not enough pairs")),
+        _ => Err(Error::new("caadr: expected pair")),
+    }
+}
+
+pub fn cadar(args: &[Expr], _: EnvRef) -> Result {
+    match args {
+        [Expr::Pair(p)] => p.cadar().map_err(|_| Error::new("cadar: not enough pairs")),
+        _ => Err(Error::new("cadar: expected pair")),
+    }
+}
+
+pub fn caddr(args: &[Expr], _: EnvRef) -> Result {
+    match args {
+        [Expr::Pair(p)] => p.caddr().map_err(|_| Error::new("caddr: not enough pairs")),
+        _ => Err(Error::new("caddr: expected pair")),
+    }
+}
+
+pub fn cdaar(args: &[Expr], _: EnvRef) -> Result {
+    match args {
+        [Expr::Pair(p)] => p.cdaar().map_err(|_| Error::new("cdaar: not enough pairs")),
+        _ => Err(Error::new("cdaar: expected pair")),
+    }
+}
+
+pub fn cdadr(args: &[Expr], _: EnvRef) -> Result {
+    match args {
+        [Expr::Pair(p)] => p.cdadr().map_err(|_| Error::new("cdadr: not enough pairs")),
+        _ => Err(Error::new("cdadr: expected pair")),
+    }
+}
+
+pub fn cddar(args: &[Expr], _: EnvRef) -> Result {
+    match args {
+        [Expr::Pair(p)] => p.cddar().map_err(|_| Error::new("cddar: not enough pairs")),
+        _ => Err(Error::new("cddar: expected pair")),
+    }
+}
+
+pub fn cdddr(args: &[Expr], _: EnvRef) -> Result {
+    match args {
+        [Expr::Pair(p)] => p.cdddr().map_err(|_| Error::new("cdddr: not enough pairs")),
+        _ => Err(Error::new("cdddr: expected pair")),
+    }
+}
+
+pub fn caaaar(args: &[Expr], _: EnvRef) -> Result {
+    match args {
+        [Expr::Pair(p)] => p
+            .caaaar()
+            .map_err(|_| Error::new("caaaar: not enough pairs")),
+        _ => Err(Error::new("caaaar: expected pair")),
+    }
+}
+
+pub fn caaadr(args: &[Expr], _: EnvRef) -> Result {
+    match args {
+        [Expr::Pair(p)] => p
+            .caaadr()
+            .map_err(|_| Error::new("caaadr: not enough pairs")),
+        _ => Err(Error::new("caaadr: expected pair")),
+    }
+}
+
+pub fn caadar(args: &[Expr], _: EnvRef) -> Result {
+    match args {
+        [Expr::Pair(p)] => p
+            .caadar()
+            .map_err(|_| Error::new("caadar: not enough pairs")),
+        _ => Err(Error::new("caadar: expected pair")),
+    }
+}
+
+pub fn caaddr(args: &[Expr], _: EnvRef) -> Result {
+    match args {
+        [Expr::Pair(p)] => p
+            .caaddr()
+            .map_err(|_| Error::new("caaddr: not enough pairs")),
+        _ => Err(Error::new("caaddr: expected pair")),
+    }
+}
+
+pub fn cadaar(args: &[Expr], _: EnvRef) -> Result {
+    match args {
+        [Expr::Pair(p)] => p
+            .cadaar()
+            .map_err(|_| Error::new("cadaar: not enough pairs")),
+        _ => Err(Error::new("cadaar: expected pair")),
+    }
+}
+
+pub fn cadadr(args: &[Expr], _: EnvRef) -> Result {
+    match args {
+        [Expr::Pair(p)] => p
+            .cadadr()
+            .map_err(|_| Error::new("cadadr: not enough pairs")),
+        _ => Err(Error::new("cadadr: expected pair")),
+    }
+}
+
+pub fn caddar(args: &[Expr], _: EnvRef) -> Result {
+    match args {
+        [Expr::Pair(p)] => p
+            .caddar()
+            .map_err(|_| Error::new("caddar: not enough pairs")),
+        _ => Err(Error::new("caddar: expected pair")),
+    }
+}
+
+pub fn cadddr(args: &[Expr], _: EnvRef) -> Result {
+    match args {
+        [Expr::Pair(p)] => p
+            .cadddr()
+            .map_err(|_| Error::new("cadddr: not enough pairs")),
+        _ => Err(Error::new("cadddr: expected pair")),
+    }
+}
+
+pub fn cdaaar(args: &[Expr], _: EnvRef) -> Result {
+    match args {
+        [Expr::Pair(p)] => p
+            .cdaaar()
+            .map_err(|_| Error::new("cdaaar: not enough pairs")),
+        _ => Err(Error::new("cdaaar: expected pair")),
+    }
+}
+
+pub fn cdaadr(args: &[Expr], _: EnvRef) -> Result {
+    match args {
+        [Expr::Pair(p)] => p
+            .cdaadr()
+            .map_err(|_| Error::new("cdaadr: not enough pairs")),
+        _ => Err(Error::new("cdaadr: expected pair")),
+    }
+}
+
+pub fn cdadar(args: &[Expr], _: EnvRef) -> Result {
+    match args {
+        [Expr::Pair(p)] => p
+            .cdadar()
+            .map_err(|_| Error::new("cdadar: not enough pairs")),
+        _ => Err(Error::new("cdadar: expected pair")),
+    }
+}
+
+pub fn cdaddr(args: &[Expr], _: EnvRef) -> Result {
+    match args {
+        [Expr::Pair(p)] => p
+            .cdaddr()
+            .map_err(|_| Error::new("cdaddr: not enough pairs")),
+        _ => Err(Error::new("cdaddr: expected pair")),
+    }
+}
+
+pub fn cddaar(args: &[Expr], _: EnvRef) -> Result {
+    match args {
+        [Expr::Pair(p)] => p
+            .cddaar()
+            .map_err(|_| Error::new("cddaar: not enough pairs")),
+        _ => Err(Error::new("cddaar: expected pair")),
+    }
+}
+
+pub fn cddadr(args: &[Expr], _: EnvRef) -> Result {
+    match args {
+        [Expr::Pair(p)] => p
+            .cddadr()
+            .map_err(|_| Error::new("cddadr: not enough pairs")),
+        _ => Err(Error::new("cddadr: expected pair")),
+    }
+}
+
+pub fn cdddar(args: &[Expr], _: EnvRef) -> Result {
+    match args {
+        [Expr::Pair(p)] => p
+            .cdddar()
+            .map_err(|_| Error::new("cdddar: not enough pairs")),
+        _ => Err(Error::new("cdddar: expected pair")),
+    }
+}
+
+pub fn cddddr(args: &[Expr], _: EnvRef) -> Result {
+    match args {
+        [Expr::Pair(p)] => p
+            .cddddr()
+            .map_err(|_| Error::new("cddddr: not enough pairs")),
+        _ => Err(Error::new("cddddr: expected pair")),
+    }
+}
+
+/// Reverse list.
+pub fn list_reverse(args: &[Expr], _: EnvRef) -> Result {
+    match args {
+        [list] => {
+            let reversed: Vec<Expr> = list_elements(list)?.into_iter().rev().collect();
+            Ok(Pair::list(&reversed))
+        }
+        _ => Err(Error::new("expected list")),
+    }
+}
+
+/// Return the first sublist of `list` whose car is `eq?` to `x`, or false.
+pub fn memq(args: &[Expr], _: EnvRef) -> Result {
+    match args {
+        [x, list] => find_member(list, |e| Ok(x.identical(e))),
+        _ => Err(Error::new("expected object and list")),
+    }
+}
+
+/// Return the first sublist of `list` whose car is `eqv?` to `x`, or false.
+pub fn memv(args: &[Expr], _: EnvRef) -> Result {
+    match args {
+        [x, list] => find_member(list, |e| x.eqv(e)),
+        _ => Err(Error::new("expected object and list")),
+    }
+}
+
+/// Return the first sublist of `list` whose car is `equal?` to `x`, or false.
+/// Uses `compare` instead of `equal?` if given.
+pub fn member(args: &[Expr], env: EnvRef) -> Result {
+    match args {
+        [x, list] => find_member(list, |e| x.equal(e)),
+        [x, list, compare] => find_member(list, |e| {
+            let result = call_procedure(compare, vec![x.clone(), e.clone()], env.clone())?;
+            Ok(is_true(&result))
+        }),
+        _ => Err(Error::new("expected object, list and optional predicate")),
+    }
+}
+
+/// Return the first pair in `alist` whose car is `eq?` to `x`, or false.
+pub fn assq(args: &[Expr], _: EnvRef) -> Result {
+    match args {
+        [x, alist] => find_association(alist, |e| Ok(x.identical(e))),
+        _ => Err(Error::new("expected object and association list")),
+    }
+}
+
+/// Return the first pair in `alist` whose car is `eqv?` to `x`, or false.
+pub fn assv(args: &[Expr], _: EnvRef) -> Result {
+    match args {
+        [x, alist] => find_association(alist, |e| x.eqv(e)),
+        _ => Err(Error::new("expected object and association list")),
+    }
+}
+
+/// Return the first pair in `alist` whose car is `equal?` to `x`, or false.
+/// Uses `compare` instead of `equal?` if given.
+pub fn assoc(args: &[Expr], env: EnvRef) -> Result {
+    match args {
+        [x, alist] => find_association(alist, |e| x.equal(e)),
+        [x, alist, compare] => find_association(alist, |e| {
+            let result = call_procedure(compare, vec![x.clone(), e.clone()], env.clone())?;
+            Ok(is_true(&result))
+        }),
+        _ => Err(Error::new(
+            "expected object, association list and optional predicate",
+        )),
+    }
+}
+
+/// Return the sublist of `list` obtained by omitting the first `k` elements.
+pub fn list_tail(args: &[Expr], _: EnvRef) -> Result {
+    match args {
+        [list, Expr::Number(k)] => {
+            let k = k
+                .to_usize()
+                .ok_or_else(|| Error::new("invalid index, expected non-negative integer"))?;
+            nth_tail(list, k)
+        }
+        _ => Err(Error::new("expected list and index")),
+    }
+}
+
+/// Return the `k`th element of `list`.
+pub fn list_ref(args: &[Expr], _: EnvRef) -> Result {
+    match args {
+        [list, Expr::Number(k)] => {
+            let k = k
+                .to_usize()
+                .ok_or_else(|| Error::new("invalid index, expected non-negative integer"))?;
+            match nth_tail(list, k)? {
+                Expr::Pair(p) => Ok(p.car()),
+                _ => Err(Error::new("index out of range")),
+            }
+        }
+        _ => Err(Error::new("expected list and index")),
+    }
+}
+
+/// Set the `k`th element of `list` to `obj`.
+pub fn list_set(args: &[Expr], _: EnvRef) -> Result {
+    match args {
+        [list, Expr::Number(k), obj] => {
+            let k = k
+                .to_usize()
+                .ok_or_else(|| Error::new("invalid index, expected non-negative integer"))?;
+            match nth_tail(list, k)? {
+                Expr::Pair(p) => {
+                    p.set_car(obj.clone());
+                    Ok(Expr::Void())
+                }
+                _ => Err(Error::new("index out of range")),
+            }
+        }
+        _ => Err(Error::new("expected list, index and object")),
+    }
+}
+
+/// Return a newly allocated copy of a list, preserving an improper tail.
+pub fn list_copy(args: &[Expr], _: EnvRef) -> Result {
+    match args {
+        [obj] => {
+            let (elements, tail) = finite_elements(obj)?;
+            Ok(list_with_tail(elements, tail))
+        }
+        _ => Err(Error::new("expected 1 argument")),
+    }
+}
+
+/// Create a new list of length `k`, optionally filled with `fill`.
+/// The unspecified default fill is `#f`, so the list prints readably.
+pub fn make_list(args: &[Expr], _: EnvRef) -> Result {
+    let (k, fill) = match args {
+        [Expr::Number(k)] => (k, Expr::Boolean(false)),
+        [Expr::Number(k), fill] => (k, fill.clone()),
+        _ => return Err(Error::new("expected length and optional fill")),
+    };
+    let k = k
+        .to_usize()
+        .ok_or_else(|| Error::new("expected non-negative exact integer"))?;
+    Ok(Pair::list(&vec![fill; k]))
+}
+
+/// Return the first sublist of `list` whose car satisfies `matches`, or false.
+fn find_member(
+    list: &Expr,
+    mut matches: impl FnMut(&Expr) -> std::result::Result<bool, Error>,
+) -> Result {
+    let mut current = list.clone();
+    while let Expr::Pair(p) = current {
+        if matches(&p.car())? {
+            return Ok(Expr::Pair(p));
+        }
+        current = p.cdr();
+    }
+    match current {
+        Expr::Null => Ok(Expr::Boolean(false)),
+        _ => Err(Error::new("expected list")),
+    }
+}
+
+/// Return the first pair in `alist` whose car satisfies `matches`, or false.
+fn find_association(
+    alist: &Expr,
+    mut matches: impl FnMut(&Expr) -> std::result::Result<bool, Error>,
+) -> Result {
+    let mut current = alist.clone();
+    while let Expr::Pair(p) = current {
+        match p.car() {
+            Expr::Pair(entry) => {
+                if matches(&entry.car())? {
+                    return Ok(Expr::Pair(entry));
+                }
+            }
+            _ => return Err(Error::new("expected association list of pairs")),
+        }
+        current = p.cdr();
+    }
+    match current {
+        Expr::Null => Ok(Expr::Boolean(false)),
+        _ => Err(Error::new("expected association list")),
+    }
+}
+
+/// Return the result of taking the cdr of `list` `k` times.
+fn nth_tail(list: &Expr, k: usize) -> Result {
+    let mut current = list.clone();
+    for _ in 0..k {
+        current = match current {
+            Expr::Pair(p) => p.cdr(),
+            _ => return Err(Error::new("index out of range")),
+        };
+    }
+    Ok(current)
+}
+
+// Vectors
+
+/// Create a new vector containing the given arguments.
+pub fn new_vector(args: &[Expr], _: EnvRef) -> Result {
+    Ok(Expr::Vector(Vector::from(args)))
+}
+
+/// Create a new vector with an optional pre-allocated size.
+pub fn make_vector(args: &[Expr], _: EnvRef) -> Result {
+    match args {
+        [Expr::Number(n)] => match n.to_usize() {
+            Some(size) => {
+                let vector = Vector::new();
+                vector.alloc_size(size, None);
+                Ok(Expr::Vector(vector))
+            }
+            _ => Err(Error::Message(
+                "invalid size, expected non-negative exact integer".to_string(),
+            )),
+        },
+        _ => Ok(Expr::Vector(Vector::new())),
+    }
+}
+
+/// Return contents of vector at specified index.
+pub fn vector_ref(args: &[Expr], _: EnvRef) -> Result {
+    match args {
+        [Expr::Vector(v), Expr::Number(n)] => match n.to_usize() {
+            Some(size) => match v.get(size) {
+                Some(e) => Ok(e.clone()),
+                _ => Err(Error::new("invalid index")),
+            },
+            _ => Err(Error::Message(
+                "invalid length, expected non-negative exact integer".to_string(),
+            )),
+        },
+        _ => Ok(Expr::Vector(Vector::new())),
+    }
+}
+
+/// Set contents of vector at specified index.
+pub fn vector_set(args: &[Expr], _: EnvRef) -> Result {
+    match args {
+        [Expr::Vector(v), Expr::Number(n), expr] => match n.to_usize() {
+            Some(index) => {
+                v.set(index, expr.clone())?;
+                Ok(Expr::Void())
+            }
+            _ => Err(Error::new("invalid index")),
+        },
+        _ => Err(Error::Message(
+            "expected vector, index, and new value".to_string(),
+        )),
+    }
+}
+
+/// Return length of vector.
+pub fn vector_len(args: &[Expr], _: EnvRef) -> Result {
+    match args {
+        [Expr::Vector(v)] => Ok(Expr::Number(Number::from_usize(v.elements.borrow().len()))),
+        _ => Err(Error::new("expected vector")),
+    }
+}
+
+/// Return a newly allocated copy of `&self`. Accepts optional start and end indexes.
+pub fn vector_copy(args: &[Expr], _: EnvRef) -> Result {
+    match args {
+        [Expr::Vector(v)] => Ok(Expr::Vector(v.clone())),
         [Expr::Vector(vec), Expr::Number(start)] => {
             if *start == Number::from_usize(vec.len()) {
                 return Ok(Expr::Null);
             }
-            let start = match start.to_usize() {
-                Some(s) => s,
-                None => {
-                    return Err(Error::Message(
-                        "invalid index, expected int or float".to_string(),
-                    ));
+            let start = match start.to_usize() {
+                Some(s) => s,
+                None => {
+                    return Err(Error::Message(
+                        "invalid index, expected non-negative exact integer".to_string(),
+                    ));
+                }
+            };
+            match vec.sub_vector(start, vec.len()) {
+                Some(v) => Ok(Expr::Vector(v.clone())),
+                None => Err(Error::new("out of range")),
+            }
+        }
+        [Expr::Vector(vec), Expr::Number(start), Expr::Number(end)] => {
+            let v_len = Number::from_usize(vec.len());
+            if *start == v_len && *end == v_len {
+                return Ok(Expr::Null);
+            }
+            let start = match start.to_usize() {
+                Some(s) => s,
+                None => {
+                    return Err(Error::Message(
+                        "invalid index, expected non-negative exact integer".to_string(),
+                    ));
+                }
+            };
+            let end = match end.to_usize() {
+                Some(s) => s,
+                None => {
+                    return Err(Error::Message(
+                        "invalid index, expected non-negative exact integer".to_string(),
+                    ));
+                }
+            };
+            match vec.sub_vector(start, end) {
+                Some(v) => Ok(Expr::Vector(v.clone())),
+                None => Err(Error::new("out of range")),
+            }
+        }
+        _ => Err(Error::new("expected vector")),
+    }
+}
+
+/// Copy elements from one `ByteVector` into another using range indexes.
+pub fn vector_copy_from(args: &[Expr], _: EnvRef) -> Result {
+    match args {
+        [Expr::Vector(dest), Expr::Number(at), Expr::Vector(from)] => {
+            let at = at
+                .to_usize()
+                .ok_or_else(|| Error::new("invalid index, expected non-negative exact integer"))?;
+
+            if at > dest.len() {
+                return Err(Error::new("out of range"));
+            }
+
+            for (i, expr) in from.elements.borrow().iter().enumerate() {
+                dest.set(i + at, expr.clone())?;
+            }
+
+            Ok(Expr::Void())
+        }
+        [
+            Expr::Vector(dest),
+            Expr::Number(at),
+            Expr::Vector(from),
+            Expr::Number(start),
+        ] => {
+            let at = at
+                .to_usize()
+                .ok_or_else(|| Error::new("invalid index, expected non-negative exact integer"))?;
+
+            let start = start
+                .to_usize()
+                .ok_or_else(|| Error::new("invalid index, expected non-negative exact integer"))?;
+
+            if at > dest.len() || (dest.len() - at) < (from.len() - start) {
+                return Err(Error::new("out of range"));
+            }
+
+            let from_ref = from.elements.borrow();
+            for (i, expr) in from_ref.as_slice()[start..].iter().enumerate() {
+                dest.set(i + at, expr.clone())?;
+            }
+
+            Ok(Expr::Void())
+        }
+        [
+            Expr::Vector(dest),
+            Expr::Number(at),
+            Expr::Vector(from),
+            Expr::Number(start),
+            Expr::Number(end),
+        ] => {
+            let at = at
+                .to_usize()
+                .ok_or_else(|| Error::new("invalid index, expected non-negative exact integer"))?;
+
+            let start = start
+                .to_usize()
+                .ok_or_else(|| Error::new("invalid index, expected non-negative exact integer"))?;
+
+            let end = end
+                .to_usize()
+                .ok_or_else(|| Error::new("invalid index, expected non-negative exact integer"))?;
+
+            if at > dest.len()
+                || end > from.len()
+                || start > from.len()
+                || (dest.len() - at) < (end - start)
+            {
+                return Err(Error::new("out of range"));
+            }
+
+            let from_ref = from.elements.borrow();
+            for (i, expr) in from_ref.as_slice()[start..end].iter().enumerate() {
+                dest.set(i + at, expr.clone())?;
+            }
+
+            Ok(Expr::Void())
+        }
+        _ => Err(Error::new("expected a vector, index, and vector")),
+    }
+}
+
+/// Fill a vector with the given argument.
+pub fn vector_fill(args: &[Expr], _: EnvRef) -> Result {
+    match args {
+        [Expr::Vector(vec), new_value] => {
+            vec.fill(new_value, 0, vec.len())?;
+            Ok(Expr::Void())
+        }
+        [Expr::Vector(vec), new_value, Expr::Number(start)] => {
+            if *start == Number::from_usize(vec.len()) {
+                return Ok(Expr::Null);
+            }
+            let start = match start.to_usize() {
+                Some(s) => s,
+                None => {
+                    return Err(Error::Message(
+                        "invalid index, expected non-negative exact integer".to_string(),
+                    ));
+                }
+            };
+            vec.fill(new_value, start, vec.len())?;
+            Ok(Expr::Void())
+        }
+        [
+            Expr::Vector(vec),
+            new_value,
+            Expr::Number(start),
+            Expr::Number(end),
+        ] => {
+            let v_len = Number::from_usize(vec.len());
+            if *start == v_len && *end == v_len {
+                return Ok(Expr::Null);
+            }
+            let start = match start.to_usize() {
+                Some(s) => s,
+                None => {
+                    return Err(Error::Message(
+                        "invalid index, expected non-negative exact integer".to_string(),
+                    ));
+                }
+            };
+            let end = match end.to_usize() {
+                Some(s) => s,
+                None => {
+                    return Err(Error::Message(
+                        "invalid index, expected non-negative exact integer".to_string(),
+                    ));
+                }
+            };
+            vec.fill(new_value, start, end)?;
+            Ok(Expr::Void())
+        }
+        _ => Err(Error::new("expected vector and new value")),
+    }
+}
+
+/// Append two `Vector` and return resulting `Vector`.
+pub fn vector_append(args: &[Expr], _: EnvRef) -> Result {
+    match args {
+        [Expr::Vector(a), Expr::Vector(b)] => {
+            let new_vec = a.deep_copy();
+            new_vec.append(b.deep_copy());
+            Ok(Expr::Vector(new_vec))
+        }
+        _ => Err(Error::new("expected 2 vectors")),
+    }
+}
+
+/// Bytevectors
+
+/// Return a newly allocated `ByteVector` filled with all `u8` arguments.
+pub fn new_bytevector(args: &[Expr], _: EnvRef) -> Result {
+    let vector = ByteVector::new(args.len());
+    for (i, arg) in args.iter().enumerate() {
+        match arg {
+            Expr::Number(n) if n.is_byte() => {
+                let byte = n.to_u8().expect("value should have been converted to byte");
+                vector.set(i, byte).expect("index should be in bounds");
+            }
+            _ => return Err(Error::new("expected byte")),
+        }
+    }
+    Ok(Expr::ByteVector(vector))
+}
+
+/// Return a newly allocated `ByteVector` of a given size and an optional value. Defaults to 0 if no value is provided.
+pub fn make_bytevector(args: &[Expr], _: EnvRef) -> Result {
+    match args {
+        [Expr::Number(size), Expr::Number(default)] if size.is_usize() && default.is_byte() => {
+            let byte = default
+                .to_u8()
+                .expect("value should have converted to byte");
+            let size = size
+                .to_usize()
+                .expect("value should have converted to usize");
+            let vec = vec![byte.clone(); size];
+            Ok(Expr::ByteVector(ByteVector::from(vec.as_slice())))
+        }
+        _ => Err(Error::new("expected size and optional byte value")),
+    }
+}
+
+/// Return the length of a `ByteVector`.
+pub fn bytevector_length(args: &[Expr], _: EnvRef) -> Result {
+    match args {
+        [Expr::ByteVector(bv)] => Ok(Expr::Number(Number::from_usize(bv.len()))),
+        _ => Err(Error::new("expected bytevector")),
+    }
+}
+
+/// Return byte at given index.
+pub fn bytevector_ref(args: &[Expr], _: EnvRef) -> Result {
+    match args {
+        [Expr::ByteVector(bv), Expr::Number(n_index)] if n_index.is_usize() => {
+            let index = n_index
+                .to_usize()
+                .expect("value should have been converted to usize");
+            match bv.get(index) {
+                Some(byte) => Ok(Expr::Number(Number::from_u8(byte))),
+                None => Err(Error::new("index out of range")),
+            }
+        }
+        _ => Err(Error::new("expected bytevector")),
+    }
+}
+
+/// Set byte at index to new value.
+pub fn bytevector_set(args: &[Expr], _: EnvRef) -> Result {
+    match args {
+        [
+            Expr::ByteVector(bv),
+            Expr::Number(n_index),
+            Expr::Number(n_byte),
+        ] if n_index.is_usize() && n_byte.is_byte() => {
+            let index = n_index
+                .to_usize()
+                .expect("value should have been converted to usize");
+            let byte = n_byte
+                .to_u8()
+                .expect("value should have been converted to byte");
+            if index < bv.len() {
+                bv.set(index, byte)?;
+                return Ok(Expr::Void());
+            }
+            Err(Error::new("index out of range"))
+        }
+        _ => Err(Error::new("expected bytevector")),
+    }
+}
+
+/// Return a newly allocated copy of a `ByteVector`
+pub fn bytevector_copy(args: &[Expr], _: EnvRef) -> Result {
+    match args {
+        [Expr::ByteVector(bv)] => Ok(Expr::ByteVector(bv.clone())),
+        [Expr::ByteVector(vec), Expr::Number(start)] => {
+            let start = start
+                .to_usize()
+                .ok_or_else(|| Error::new("invalid index, expected non-negative exact integer"))?;
+
+            if start == vec.len() {
+                return Ok(Expr::ByteVector(ByteVector::new(0)));
+            }
+
+            match vec.sub_bytevector(start, vec.len()) {
+                Some(v) => Ok(Expr::ByteVector(v.clone())),
+                None => Err(Error::new("out of range")),
+            }
+        }
+        [
+            Expr::ByteVector(vec),
+            Expr::Number(start),
+            Expr::Number(end),
+        ] => {
+            let v_len = Number::from_usize(vec.len());
+            if *start == v_len && *end == v_len {
+                return Ok(Expr::Null);
+            }
+            let start = start
+                .to_usize()
+                .ok_or_else(|| Error::new("invalid index, expected non-negative exact integer"))?;
+            let end = end
+                .to_usize()
+                .ok_or_else(|| Error::new("invalid index, expected non-negative exact integer"))?;
+            match vec.sub_bytevector(start, end) {
+                Some(v) => Ok(Expr::ByteVector(v.clone())),
+                None => Err(Error::new("out of range")),
+            }
+        }
+        _ => Err(Error::new("expected vector")),
+    }
+}
+
+/// Copy elements from one `ByteVector` into another using range indexes.
+pub fn bytevector_copy_from(args: &[Expr], _: EnvRef) -> Result {
+    match args {
+        [
+            Expr::ByteVector(dest),
+            Expr::Number(at),
+            Expr::ByteVector(from),
+        ] => {
+            let at = at
+                .to_usize()
+                .ok_or_else(|| Error::new("invalid index, expected non-negative exact integer"))?;
+
+            if at > from.len() {
+                return Err(Error::new("out of range"));
+            }
+
+            for (i, byte) in from.to_slice().iter().enumerate() {
+                dest.set(i + at, *byte)?;
+            }
+
+            Ok(Expr::Void())
+        }
+        [
+            Expr::ByteVector(dest),
+            Expr::Number(at),
+            Expr::ByteVector(from),
+            Expr::Number(start),
+        ] => {
+            let at = at
+                .to_usize()
+                .ok_or_else(|| Error::new("invalid index, expected non-negative exact integer"))?;
+
+            let start = start
+                .to_usize()
+                .ok_or_else(|| Error::new("invalid index, expected non-negative exact integer"))?;
+
+            if at > dest.len() || (dest.len() - at) < (from.len() - start) {
+                return Err(Error::new("out of range"));
+            }
+
+            let from_slice = from.to_slice();
+            for (i, byte) in from_slice[start..].iter().enumerate() {
+                dest.set(i + at, *byte)?;
+            }
+
+            Ok(Expr::Void())
+        }
+        [
+            Expr::ByteVector(dest),
+            Expr::Number(at),
+            Expr::ByteVector(from),
+            Expr::Number(start),
+            Expr::Number(end),
+        ] => {
+            let at = at
+                .to_usize()
+                .ok_or_else(|| Error::new("invalid index, expected non-negative exact integer"))?;
+
+            let start = start
+                .to_usize()
+                .ok_or_else(|| Error::new("invalid index, expected non-negative exact integer"))?;
+
+            let end = end
+                .to_usize()
+                .ok_or_else(|| Error::new("invalid index, expected non-negative exact integer"))?;
+
+            if at > dest.len()
+                || end > from.len()
+                || start > from.len()
+                || (dest.len() - at) < (end - start)
+            {
+                return Err(Error::new("out of range"));
+            }
+
+            let from_slice = from.to_slice();
+            for (i, byte) in from_slice[start..end].iter().enumerate() {
+                dest.set(i + at, *byte)?;
+            }
+
+            Ok(Expr::Void())
+        }
+        _ => Err(Error::new("expected a bytevector, index, and bytevector")),
+    }
+}
+
+/// Return a newly allocated `ByteVector` created from concatenating 2 `ByteVector`.
+pub fn bytevector_append(args: &[Expr], _: EnvRef) -> Result {
+    match args {
+        [Expr::ByteVector(a), Expr::ByteVector(b)] => {
+            let new_slice = [a.to_slice(), b.to_slice()];
+            let bytevector = ByteVector::from(new_slice.concat().as_slice());
+            Ok(Expr::ByteVector(bytevector))
+        }
+        _ => Err(Error::new("expected 2 bytevectors")),
+    }
+}
+
+/// Return the signed byte at an index.
+pub fn bytevector_s8_ref(args: &[Expr], _: EnvRef) -> Result {
+    match args {
+        [bv, index] => integer_ref(bv, index, Endianness::Big, 1, true),
+        _ => Err(Error::new("expected bytevector and index")),
+    }
+}
+
+/// Set the byte at an index to a signed byte.
+pub fn bytevector_s8_set(args: &[Expr], _: EnvRef) -> Result {
+    match args {
+        [bv, index, n] => integer_set(bv, index, n, Endianness::Big, 1, true),
+        _ => Err(Error::new("expected bytevector, index and signed byte")),
+    }
+}
+
+/// Return the unsigned 16-bit integer at an index with the given endianness.
+pub fn bytevector_u16_ref(args: &[Expr], _: EnvRef) -> Result {
+    fixed_integer_ref(args, 2, false)
+}
+
+/// Return the signed 16-bit integer at an index with the given endianness.
+pub fn bytevector_s16_ref(args: &[Expr], _: EnvRef) -> Result {
+    fixed_integer_ref(args, 2, true)
+}
+
+/// Return the unsigned 32-bit integer at an index with the given endianness.
+pub fn bytevector_u32_ref(args: &[Expr], _: EnvRef) -> Result {
+    fixed_integer_ref(args, 4, false)
+}
+
+/// Return the signed 32-bit integer at an index with the given endianness.
+pub fn bytevector_s32_ref(args: &[Expr], _: EnvRef) -> Result {
+    fixed_integer_ref(args, 4, true)
+}
+
+/// Return the unsigned 64-bit integer at an index with the given endianness.
+pub fn bytevector_u64_ref(args: &[Expr], _: EnvRef) -> Result {
+    fixed_integer_ref(args, 8, false)
+}
+
+/// Return the signed 64-bit integer at an index with the given endianness.
+pub fn bytevector_s64_ref(args: &[Expr], _: EnvRef) -> Result {
+    fixed_integer_ref(args, 8, true)
+}
+
+/// Store an unsigned 16-bit integer at an index with the given endianness.
+pub fn bytevector_u16_set(args: &[Expr], _: EnvRef) -> Result {
+    fixed_integer_set(args, 2, false)
+}
+
+/// Store a signed 16-bit integer at an index with the given endianness.
+pub fn bytevector_s16_set(args: &[Expr], _: EnvRef) -> Result {
+    fixed_integer_set(args, 2, true)
+}
+
+/// Store an unsigned 32-bit integer at an index with the given endianness.
+pub fn bytevector_u32_set(args: &[Expr], _: EnvRef) -> Result {
+    fixed_integer_set(args, 4, false)
+}
+
+/// Store a signed 32-bit integer at an index with the given endianness.
+pub fn bytevector_s32_set(args: &[Expr], _: EnvRef) -> Result {
+    fixed_integer_set(args, 4, true)
+}
+
+/// Store an unsigned 64-bit integer at an index with the given endianness.
+pub fn bytevector_u64_set(args: &[Expr], _: EnvRef) -> Result {
+    fixed_integer_set(args, 8, false)
+}
+
+/// Store a signed 64-bit integer at an index with the given endianness.
+pub fn bytevector_s64_set(args: &[Expr], _: EnvRef) -> Result {
+    fixed_integer_set(args, 8, true)
+}
+
+/// Return the unsigned integer of any size at an index with the given endianness.
+pub fn bytevector_uint_ref(args: &[Expr], _: EnvRef) -> Result {
+    sized_integer_ref(args, false)
+}
+
+/// Return the signed integer of any size at an index with the given endianness.
+pub fn bytevector_sint_ref(args: &[Expr], _: EnvRef) -> Result {
+    sized_integer_ref(args, true)
+}
+
+/// Store an unsigned integer of any size at an index with the given endianness.
+pub fn bytevector_uint_set(args: &[Expr], _: EnvRef) -> Result {
+    sized_integer_set(args, false)
+}
+
+/// Store a signed integer of any size at an index with the given endianness.
+pub fn bytevector_sint_set(args: &[Expr], _: EnvRef) -> Result {
+    sized_integer_set(args, true)
+}
+
+/// Return the IEEE-754 single precision float at an index with the given endianness.
+pub fn bytevector_ieee_single_ref(args: &[Expr], _: EnvRef) -> Result {
+    match args {
+        [bv, index, endianness] => {
+            let bytes = element_bytes(bv, index, to_endianness(endianness)?, 4)?;
+            let bytes = bytes.try_into().expect("element should be 4 bytes");
+            Ok(Expr::Number(Number::from_f64(
+                f32::from_be_bytes(bytes) as f64
+            )))
+        }
+        _ => Err(Error::new("expected bytevector, index and endianness")),
+    }
+}
+
+/// Return the IEEE-754 double precision float at an index with the given endianness.
+pub fn bytevector_ieee_double_ref(args: &[Expr], _: EnvRef) -> Result {
+    match args {
+        [bv, index, endianness] => {
+            let bytes = element_bytes(bv, index, to_endianness(endianness)?, 8)?;
+            let bytes = bytes.try_into().expect("element should be 8 bytes");
+            Ok(Expr::Number(Number::from_f64(f64::from_be_bytes(bytes))))
+        }
+        _ => Err(Error::new("expected bytevector, index and endianness")),
+    }
+}
+
+/// Store a real number as an IEEE-754 single precision float at an index with the given endianness.
+pub fn bytevector_ieee_single_set(args: &[Expr], _: EnvRef) -> Result {
+    match args {
+        [bv, index, Expr::Number(n), endianness] => {
+            let x = n.to_f64().ok_or(Error::new("expected real number"))?;
+            let bytes = (x as f32).to_be_bytes();
+            set_element_bytes(bv, index, to_endianness(endianness)?, bytes.to_vec())
+        }
+        _ => Err(Error::new(
+            "expected bytevector, index, real number and endianness",
+        )),
+    }
+}
+
+/// Store a real number as an IEEE-754 double precision float at an index with the given endianness.
+pub fn bytevector_ieee_double_set(args: &[Expr], _: EnvRef) -> Result {
+    match args {
+        [bv, index, Expr::Number(n), endianness] => {
+            let x = n.to_f64().ok_or(Error::new("expected real number"))?;
+            let bytes = x.to_be_bytes();
+            set_element_bytes(bv, index, to_endianness(endianness)?, bytes.to_vec())
+        }
+        _ => Err(Error::new(
+            "expected bytevector, index, real number and endianness",
+        )),
+    }
+}
+
+/// Return the endianness of the host as a symbol.
+pub fn native_endianness(args: &[Expr], _: EnvRef) -> Result {
+    match args {
+        [] if cfg!(target_endian = "big") => Ok(Expr::Symbol(Symbol::BIG)),
+        [] => Ok(Expr::Symbol(Symbol::LITTLE)),
+        _ => Err(Error::new("expected no arguments")),
+    }
+}
+
+/// Byte order of a multi-byte bytevector element.
+#[derive(Clone, Copy, PartialEq)]
+enum Endianness {
+    Big,
+    Little,
+}
+
+/// Convert a `big` or `little` symbol into an `Endianness`.
+fn to_endianness(expr: &Expr) -> std::result::Result<Endianness, Error> {
+    match expr {
+        Expr::Symbol(Symbol::BIG) => Ok(Endianness::Big),
+        Expr::Symbol(Symbol::LITTLE) => Ok(Endianness::Little),
+        _ => Err(Error::new("expected endianness big or little")),
+    }
+}
+
+/// Read a fixed-size integer from `[bytevector index endianness]` arguments.
+fn fixed_integer_ref(args: &[Expr], size: usize, signed: bool) -> Result {
+    match args {
+        [bv, index, endianness] => integer_ref(bv, index, to_endianness(endianness)?, size, signed),
+        _ => Err(Error::new("expected bytevector, index and endianness")),
+    }
+}
+
+/// Write a fixed-size integer from `[bytevector index n endianness]` arguments.
+fn fixed_integer_set(args: &[Expr], size: usize, signed: bool) -> Result {
+    match args {
+        [bv, index, n, endianness] => {
+            integer_set(bv, index, n, to_endianness(endianness)?, size, signed)
+        }
+        _ => Err(Error::new(
+            "expected bytevector, index, integer and endianness",
+        )),
+    }
+}
+
+/// Read an integer from `[bytevector index endianness size]` arguments.
+fn sized_integer_ref(args: &[Expr], signed: bool) -> Result {
+    match args {
+        [bv, index, endianness, Expr::Number(size)] => {
+            let size = element_size(size)?;
+            integer_ref(bv, index, to_endianness(endianness)?, size, signed)
+        }
+        _ => Err(Error::new(
+            "expected bytevector, index, endianness and size",
+        )),
+    }
+}
+
+/// Write an integer from `[bytevector index n endianness size]` arguments.
+fn sized_integer_set(args: &[Expr], signed: bool) -> Result {
+    match args {
+        [bv, index, n, endianness, Expr::Number(size)] => {
+            let size = element_size(size)?;
+            integer_set(bv, index, n, to_endianness(endianness)?, size, signed)
+        }
+        _ => Err(Error::new(
+            "expected bytevector, index, integer, endianness and size",
+        )),
+    }
+}
+
+/// Convert a positive element size argument to `usize`.
+fn element_size(size: &Number) -> std::result::Result<usize, Error> {
+    match size {
+        Number::Int(Small(size)) if *size > 0 => Ok(*size as usize),
+        _ => Err(Error::new("expected positive element size")),
+    }
+}
+
+/// Return the integer stored in `size` bytes at `index`.
+fn integer_ref(
+    bv: &Expr,
+    index: &Expr,
+    endianness: Endianness,
+    size: usize,
+    signed: bool,
+) -> Result {
+    let bytes = element_bytes(bv, index, endianness, size)?;
+    let value = match signed {
+        true => BigInt::from_signed_bytes_be(&bytes),
+        false => BigInt::from_bytes_be(Sign::Plus, &bytes),
+    };
+    Ok(Expr::Number(Number::from_bigint(value)))
+}
+
+/// Store an exact integer in `size` bytes at `index`, checking that it fits.
+fn integer_set(
+    bv: &Expr,
+    index: &Expr,
+    n: &Expr,
+    endianness: Endianness,
+    size: usize,
+    signed: bool,
+) -> Result {
+    let Expr::Number(Number::Int(n)) = n else {
+        return Err(Error::new("expected exact integer"));
+    };
+    let (bytevector, start) = element_location(bv, index)?;
+    if start
+        .checked_add(size)
+        .is_none_or(|end| end > bytevector.len())
+    {
+        return Err(Error::new("index out of range"));
+    }
+    let n = n.to_bigint();
+    let bits = size * 8;
+    let (min, max) = match signed {
+        true => (
+            -(BigInt::from(1) << (bits - 1)),
+            BigInt::from(1) << (bits - 1),
+        ),
+        false => (BigInt::from(0), BigInt::from(1) << bits),
+    };
+    if n < min || n >= max {
+        return Err(Error::Message(format!(
+            "integer {} does not fit in {} bytes",
+            n, size
+        )));
+    }
+    let (fill, mut bytes) = match signed {
+        true => (
+            if n < BigInt::from(0) { 0xFF } else { 0 },
+            n.to_signed_bytes_be(),
+        ),
+        false => (0, n.to_bytes_be().1),
+    };
+    // Minimal encodings can be shorter than the element, or one sign byte longer.
+    if bytes.len() > size {
+        bytes.drain(..bytes.len() - size);
+    }
+    let mut element = vec![fill; size - bytes.len()];
+    element.extend(bytes);
+    set_element_bytes(bv, index, endianness, element)
+}
+
+/// Return the `size` bytes at `index` in big-endian order.
+fn element_bytes(
+    bv: &Expr,
+    index: &Expr,
+    endianness: Endianness,
+    size: usize,
+) -> std::result::Result<Vec<u8>, Error> {
+    let (bv, index) = element_location(bv, index)?;
+    let mut bytes = bv
+        .get_bytes(index, size)
+        .ok_or(Error::new("index out of range"))?;
+    if endianness == Endianness::Little {
+        bytes.reverse();
+    }
+    Ok(bytes)
+}
+
+/// Store big-endian `bytes` at `index` in the given endianness.
+fn set_element_bytes(
+    bv: &Expr,
+    index: &Expr,
+    endianness: Endianness,
+    mut bytes: Vec<u8>,
+) -> Result {
+    let (bv, index) = element_location(bv, index)?;
+    if endianness == Endianness::Little {
+        bytes.reverse();
+    }
+    bv.set_bytes(index, &bytes)?;
+    Ok(Expr::Void())
+}
+
+/// Return the bytevector and index arguments of an element accessor.
+fn element_location<'a>(
+    bv: &'a Expr,
+    index: &Expr,
+) -> std::result::Result<(&'a ByteVector, usize), Error> {
+    match (bv, index) {
+        (Expr::ByteVector(bv), Expr::Number(index)) if index.is_usize() => Ok((
+            bv,
+            index
+                .to_usize()
+                .expect("value should have been converted to usize"),
+        )),
+        _ => Err(Error::new("expected bytevector and index")),
+    }
+}
+
+// Control
+
+/// Call `procedure` with the given arguments, spreading the final list argument.
+pub fn apply(args: &[Expr], env: EnvRef) -> Result {
+    match args {
+        [procedure, rest @ .., list] => {
+            let mut arguments = rest.to_vec();
+            arguments.extend(list_elements(list)?);
+            call_procedure(procedure, arguments, env)
+        }
+        _ => Err(Error::new("expected procedure and argument list")),
+    }
+}
+
+/// Apply `procedure` element-wise to lists and return a list of the results.
+pub fn map(args: &[Expr], env: EnvRef) -> Result {
+    match args {
+        [procedure, lists @ ..] if !lists.is_empty() => {
+            let results = map_rows(procedure, list_rows(lists)?, env)?;
+            Ok(Pair::list(&results))
+        }
+        _ => Err(Error::new("expected procedure and at least 1 list")),
+    }
+}
+
+/// Apply `procedure` element-wise to lists for its side effects.
+pub fn for_each(args: &[Expr], env: EnvRef) -> Result {
+    match args {
+        [procedure, lists @ ..] if !lists.is_empty() => {
+            map_rows(procedure, list_rows(lists)?, env)?;
+            Ok(Expr::Void())
+        }
+        _ => Err(Error::new("expected procedure and at least 1 list")),
+    }
+}
+
+/// Apply `procedure` element-wise to strings and return a string of the results.
+pub fn string_map(args: &[Expr], env: EnvRef) -> Result {
+    match args {
+        [procedure, strings @ ..] if !strings.is_empty() => {
+            let sequences = strings
+                .iter()
+                .map(string_elements)
+                .collect::<std::result::Result<Vec<_>, Error>>()?;
+            let chars = map_rows(procedure, transpose(&sequences), env)?
+                .into_iter()
+                .map(|result| match result {
+                    Expr::Char(c) => Ok(c),
+                    _ => Err(Error::new("expected procedure to return a character")),
+                })
+                .collect::<std::result::Result<Vec<char>, Error>>()?;
+            Ok(Expr::String(SchemeString::from_chars(chars)))
+        }
+        _ => Err(Error::new("expected procedure and at least 1 string")),
+    }
+}
+
+/// Apply `procedure` element-wise to strings for its side effects.
+pub fn string_for_each(args: &[Expr], env: EnvRef) -> Result {
+    match args {
+        [procedure, strings @ ..] if !strings.is_empty() => {
+            let sequences = strings
+                .iter()
+                .map(string_elements)
+                .collect::<std::result::Result<Vec<_>, Error>>()?;
+            map_rows(procedure, transpose(&sequences), env)?;
+            Ok(Expr::Void())
+        }
+        _ => Err(Error::new("expected procedure and at least 1 string")),
+    }
+}
+
+/// Apply `procedure` element-wise to vectors and return a vector of the results.
+pub fn vector_map(args: &[Expr], env: EnvRef) -> Result {
+    match args {
+        [procedure, vectors @ ..] if !vectors.is_empty() => {
+            let sequences = vectors
+                .iter()
+                .map(vector_elements)
+                .collect::<std::result::Result<Vec<_>, Error>>()?;
+            let results = map_rows(procedure, transpose(&sequences), env)?;
+            Ok(Expr::Vector(Vector::from(&results)))
+        }
+        _ => Err(Error::new("expected procedure and at least 1 vector")),
+    }
+}
+
+/// Apply `procedure` element-wise to vectors for its side effects.
+pub fn vector_for_each(args: &[Expr], env: EnvRef) -> Result {
+    match args {
+        [procedure, vectors @ ..] if !vectors.is_empty() => {
+            let sequences = vectors
+                .iter()
+                .map(vector_elements)
+                .collect::<std::result::Result<Vec<_>, Error>>()?;
+            map_rows(procedure, transpose(&sequences), env)?;
+            Ok(Expr::Void())
+        }
+        _ => Err(Error::new("expected procedure and at least 1 vector")),
+    }
+}
+
+/// Call `procedure` with each row of arguments.
+fn map_rows(
+    procedure: &Expr,
+    rows: Vec<Vec<Expr>>,
+    env: EnvRef,
+) -> std::result::Result<Vec<Expr>, Error> {
+    rows.into_iter()
+        .map(|row| call_procedure(procedure, row, env.clone()))
+        .collect()
+}
+
+/// Return rows of the `i`th element of each sequence, stopping at the shortest.
+fn transpose(sequences: &[Vec<Expr>]) -> Vec<Vec<Expr>> {
+    let len = sequences.iter().map(Vec::len).min().unwrap_or(0);
+    (0..len)
+        .map(|i| sequences.iter().map(|seq| seq[i].clone()).collect())
+        .collect()
+}
+
+/// Return the elements of a proper list.
+fn list_elements(list: &Expr) -> std::result::Result<Vec<Expr>, Error> {
+    match finite_elements(list)? {
+        (elements, Expr::Null) => Ok(elements),
+        _ => Err(Error::new("expected list")),
+    }
+}
+
+/// Return the chars of a string as expressions.
+fn string_elements(s: &Expr) -> std::result::Result<Vec<Expr>, Error> {
+    match s {
+        Expr::String(s) => Ok(s.chars().into_iter().map(Expr::Char).collect()),
+        _ => Err(Error::new("expected string")),
+    }
+}
+
+/// Return the elements of a vector.
+fn vector_elements(v: &Expr) -> std::result::Result<Vec<Expr>, Error> {
+    match v {
+        Expr::Vector(v) => Ok(v.iter().collect()),
+        _ => Err(Error::new("expected vector")),
+    }
+}
+
+// Lists (SRFI 1)
+
+/// Return a pair with `a` as the cdr and `b` as the car.
+pub fn xcons(args: &[Expr], _: EnvRef) -> Result {
+    match args {
+        [a, b] => Ok(Expr::Pair(Pair::cons((b.clone(), a.clone())))),
+        _ => Err(Error::new("expected 2 arguments")),
+    }
+}
+
+/// Make a new list whose last argument is the tail of the list.
+pub fn cons_star(args: &[Expr], _: EnvRef) -> Result {
+    match args {
+        [elements @ .., tail] => Ok(list_with_tail(elements.to_vec(), tail.clone())),
+        _ => Err(Error::new("expected at least 1 argument")),
+    }
+}
+
+/// Make a list of `n` elements, where element `i` is `(init-proc i)`.
+pub fn list_tabulate(args: &[Expr], env: EnvRef) -> Result {
+    match args {
+        [Expr::Number(n), init] => {
+            let n = n
+                .to_usize()
+                .ok_or_else(|| Error::new("expected non-negative exact integer"))?;
+            let elements = (0..n)
+                .map(|i| {
+                    call_procedure(init, vec![Expr::Number(Number::from_usize(i))], env.clone())
+                })
+                .collect::<std::result::Result<Vec<Expr>, Error>>()?;
+            Ok(Pair::list(&elements))
+        }
+        _ => Err(Error::new("expected length and procedure")),
+    }
+}
+
+/// Make a circular list of the arguments.
+pub fn circular_list(args: &[Expr], _: EnvRef) -> Result {
+    let Expr::Pair(head) = Pair::list(args) else {
+        return Err(Error::new("expected at least 1 argument"));
+    };
+    let mut last = head.clone();
+    while let Expr::Pair(next) = last.cdr() {
+        last = next;
+    }
+    last.set_cdr(Expr::Pair(head.clone()));
+    Ok(Expr::Pair(head))
+}
+
+/// Make a list of `count` numbers, starting at `start` and incremented by `step`.
+pub fn iota(args: &[Expr], _: EnvRef) -> Result {
+    let (count, start, step) = match args {
+        [Expr::Number(count)] => (count, Number::from_i64(0), Number::from_i64(1)),
+        [Expr::Number(count), Expr::Number(start)] => (count, start.clone(), Number::from_i64(1)),
+        [Expr::Number(count), Expr::Number(start), Expr::Number(step)] => {
+            (count, start.clone(), step.clone())
+        }
+        _ => return Err(Error::new("expected count and optional start and step")),
+    };
+    let count = count
+        .to_usize()
+        .ok_or_else(|| Error::new("expected non-negative exact integer"))?;
+    let elements = (0..count)
+        .map(|i| (Number::from_usize(i) * step.clone()).and_then(|n| start.clone() + n))
+        .map(|n| n.map(Expr::Number))
+        .collect::<std::result::Result<Vec<Expr>, Error>>()?;
+    Ok(Pair::list(&elements))
+}
+
+/// Return true if `x` is a proper list.
+pub fn is_proper_list(args: &[Expr], _: EnvRef) -> Result {
+    match args {
+        [x] => Ok(Expr::Boolean(matches!(list_shape(x), ListShape::Proper(_)))),
+        _ => Err(Error::new("expected 1 argument")),
+    }
+}
+
+/// Return true if `x` is a circular list.
+pub fn is_circular_list(args: &[Expr], _: EnvRef) -> Result {
+    match args {
+        [x] => Ok(Expr::Boolean(matches!(list_shape(x), ListShape::Circular))),
+        _ => Err(Error::new("expected 1 argument")),
+    }
+}
+
+/// Return true if `x` is a finite list terminated by a non-null value.
+pub fn is_dotted_list(args: &[Expr], _: EnvRef) -> Result {
+    match args {
+        [x] => Ok(Expr::Boolean(matches!(list_shape(x), ListShape::Dotted))),
+        _ => Err(Error::new("expected 1 argument")),
+    }
+}
+
+/// Return true if the proper or circular list `list` is empty.
+pub fn is_null_list(args: &[Expr], _: EnvRef) -> Result {
+    match args {
+        [Expr::Null] => Ok(Expr::Boolean(true)),
+        [Expr::Pair(_)] => Ok(Expr::Boolean(false)),
+        _ => Err(Error::new("expected list")),
+    }
+}
+
+/// Return true if `x` is not a pair.
+pub fn is_not_pair(args: &[Expr], _: EnvRef) -> Result {
+    match args {
+        [x] => Ok(Expr::Boolean(!matches!(x, Expr::Pair(_)))),
+        _ => Err(Error::new("expected 1 argument")),
+    }
+}
+
+/// Return true if all lists have the same length and elements equal by `elt=`.
+pub fn list_eq(args: &[Expr], env: EnvRef) -> Result {
+    match args {
+        [elt_eq, lists @ ..] => {
+            let lists = lists
+                .iter()
+                .map(list_elements)
+                .collect::<std::result::Result<Vec<_>, Error>>()?;
+            for pair in lists.windows(2) {
+                if pair[0].len() != pair[1].len() {
+                    return Ok(Expr::Boolean(false));
+                }
+                for (a, b) in pair[0].iter().zip(&pair[1]) {
+                    let result = call_procedure(elt_eq, vec![a.clone(), b.clone()], env.clone())?;
+                    if !is_true(&result) {
+                        return Ok(Expr::Boolean(false));
+                    }
+                }
+            }
+            Ok(Expr::Boolean(true))
+        }
+        _ => Err(Error::new("expected equality procedure")),
+    }
+}
+
+/// Return the first element of a list.
+pub fn first(args: &[Expr], _: EnvRef) -> Result {
+    nth_element(args, 0)
+}
+
+/// Return the second element of a list.
+pub fn second(args: &[Expr], _: EnvRef) -> Result {
+    nth_element(args, 1)
+}
+
+/// Return the third element of a list.
+pub fn third(args: &[Expr], _: EnvRef) -> Result {
+    nth_element(args, 2)
+}
+
+/// Return the fourth element of a list.
+pub fn fourth(args: &[Expr], _: EnvRef) -> Result {
+    nth_element(args, 3)
+}
+
+/// Return the fifth element of a list.
+pub fn fifth(args: &[Expr], _: EnvRef) -> Result {
+    nth_element(args, 4)
+}
+
+/// Return the sixth element of a list.
+pub fn sixth(args: &[Expr], _: EnvRef) -> Result {
+    nth_element(args, 5)
+}
+
+/// Return the seventh element of a list.
+pub fn seventh(args: &[Expr], _: EnvRef) -> Result {
+    nth_element(args, 6)
+}
+
+/// Return the eighth element of a list.
+pub fn eighth(args: &[Expr], _: EnvRef) -> Result {
+    nth_element(args, 7)
+}
+
+/// Return the ninth element of a list.
+pub fn ninth(args: &[Expr], _: EnvRef) -> Result {
+    nth_element(args, 8)
+}
+
+/// Return the tenth element of a list.
+pub fn tenth(args: &[Expr], _: EnvRef) -> Result {
+    nth_element(args, 9)
+}
+
+/// Return the car and cdr of a pair as a list of 2 values.
+pub fn car_cdr(args: &[Expr], _: EnvRef) -> Result {
+    match args {
+        [Expr::Pair(p)] => Ok(Pair::list(&[p.car(), p.cdr()])),
+        _ => Err(Error::new("expected pair")),
+    }
+}
+
+/// Return a new list of the first `i` elements of `x`.
+pub fn take(args: &[Expr], _: EnvRef) -> Result {
+    match args {
+        [x, Expr::Number(i)] => Ok(Pair::list(&take_elements(x, list_index_arg(i)?)?)),
+        _ => Err(Error::new("expected list and index")),
+    }
+}
+
+/// Return the tail of `x` after the first `i` elements.
+pub fn drop(args: &[Expr], _: EnvRef) -> Result {
+    match args {
+        [x, Expr::Number(i)] => nth_tail(x, list_index_arg(i)?),
+        _ => Err(Error::new("expected list and index")),
+    }
+}
+
+/// Return the tail of `flist` holding its last `i` elements.
+pub fn take_right(args: &[Expr], _: EnvRef) -> Result {
+    match args {
+        [flist, Expr::Number(i)] => {
+            let i = list_index_arg(i)?;
+            let len = finite_elements(flist)?.0.len();
+            let skip = len
+                .checked_sub(i)
+                .ok_or_else(|| Error::new("index out of range"))?;
+            nth_tail(flist, skip)
+        }
+        _ => Err(Error::new("expected list and index")),
+    }
+}
+
+/// Return a new list of all but the last `i` elements of `flist`.
+pub fn drop_right(args: &[Expr], _: EnvRef) -> Result {
+    match args {
+        [flist, Expr::Number(i)] => {
+            let i = list_index_arg(i)?;
+            let elements = finite_elements(flist)?.0;
+            let keep = elements
+                .len()
+                .checked_sub(i)
+                .ok_or_else(|| Error::new("index out of range"))?;
+            Ok(Pair::list(&elements[..keep]))
+        }
+        _ => Err(Error::new("expected list and index")),
+    }
+}
+
+/// Split `x` at index `i`, returning the prefix and suffix as a list of 2 values.
+pub fn split_at(args: &[Expr], _: EnvRef) -> Result {
+    match args {
+        [x, Expr::Number(i)] => {
+            let i = list_index_arg(i)?;
+            let prefix = Pair::list(&take_elements(x, i)?);
+            Ok(Pair::list(&[prefix, nth_tail(x, i)?]))
+        }
+        _ => Err(Error::new("expected list and index")),
+    }
+}
+
+/// Return the last element of a non-empty finite list.
+pub fn last(args: &[Expr], _: EnvRef) -> Result {
+    match args {
+        [pair @ Expr::Pair(_)] => Ok(last_pair_of(pair)?.car()),
+        _ => Err(Error::new("expected pair")),
+    }
+}
+
+/// Return the last pair of a non-empty finite list.
+pub fn last_pair(args: &[Expr], _: EnvRef) -> Result {
+    match args {
+        [pair @ Expr::Pair(_)] => Ok(Expr::Pair(last_pair_of(pair)?)),
+        _ => Err(Error::new("expected pair")),
+    }
+}
+
+/// Return the length of a proper list, or false if the list is circular.
+pub fn length_plus(args: &[Expr], _: EnvRef) -> Result {
+    match args {
+        [x] => match list_shape(x) {
+            ListShape::Proper(len) => Ok(Expr::Number(Number::from_usize(len))),
+            ListShape::Circular => Ok(Expr::Boolean(false)),
+            ListShape::Dotted => Err(Error::new("expected proper or circular list")),
+        },
+        _ => Err(Error::new("expected 1 argument")),
+    }
+}
+
+/// Append the lists in a list of lists.
+pub fn concatenate(args: &[Expr], _: EnvRef) -> Result {
+    match args {
+        [lists] => append_lists(&list_elements(lists)?),
+        _ => Err(Error::new("expected list of lists")),
+    }
+}
+
+/// Append the reverse of `rev-head` to `tail`.
+pub fn append_reverse(args: &[Expr], _: EnvRef) -> Result {
+    match args {
+        [rev_head, tail] => {
+            let elements = list_elements(rev_head)?.into_iter().rev().collect();
+            Ok(list_with_tail(elements, tail.clone()))
+        }
+        _ => Err(Error::new("expected list and tail")),
+    }
+}
+
+/// Make a list of lists, where list `i` holds element `i` of each argument list.
+pub fn zip(args: &[Expr], _: EnvRef) -> Result {
+    match args {
+        [] => Err(Error::new("expected at least 1 list")),
+        lists => {
+            let rows: Vec<Expr> = list_rows(lists)?
+                .iter()
+                .map(|row| Pair::list(row))
+                .collect();
+            Ok(Pair::list(&rows))
+        }
+    }
+}
+
+/// Return the first element of each list in a list.
+pub fn unzip1(args: &[Expr], _: EnvRef) -> Result {
+    match args {
+        [list] => Ok(unzip_columns(list, 1)?.remove(0)),
+        _ => Err(Error::new("expected list of lists")),
+    }
+}
+
+/// Return the first and second elements of each list in a list as a list of 2 lists.
+pub fn unzip2(args: &[Expr], _: EnvRef) -> Result {
+    match args {
+        [list] => Ok(Pair::list(&unzip_columns(list, 2)?)),
+        _ => Err(Error::new("expected list of lists")),
+    }
+}
+
+/// Return the first 3 elements of each list in a list as a list of 3 lists.
+pub fn unzip3(args: &[Expr], _: EnvRef) -> Result {
+    match args {
+        [list] => Ok(Pair::list(&unzip_columns(list, 3)?)),
+        _ => Err(Error::new("expected list of lists")),
+    }
+}
+
+/// Count the rows of elements that satisfy `pred`.
+pub fn count(args: &[Expr], env: EnvRef) -> Result {
+    match args {
+        [pred, lists @ ..] if !lists.is_empty() => {
+            let mut count = 0;
+            for row in list_rows(lists)? {
+                if is_true(&call_procedure(pred, row, env.clone())?) {
+                    count += 1;
+                }
+            }
+            Ok(Expr::Number(Number::from_usize(count)))
+        }
+        _ => Err(Error::new("expected predicate and at least 1 list")),
+    }
+}
+
+/// Fold `kons` over the elements of the lists from left to right.
+pub fn fold(args: &[Expr], env: EnvRef) -> Result {
+    match args {
+        [kons, knil, lists @ ..] if !lists.is_empty() => {
+            let mut acc = knil.clone();
+            for mut row in list_rows(lists)? {
+                row.push(acc);
+                acc = call_procedure(kons, row, env.clone())?;
+            }
+            Ok(acc)
+        }
+        _ => Err(Error::new(
+            "expected procedure, initial value and at least 1 list",
+        )),
+    }
+}
+
+/// Fold `kons` over the elements of the lists from right to left.
+pub fn fold_right(args: &[Expr], env: EnvRef) -> Result {
+    match args {
+        [kons, knil, lists @ ..] if !lists.is_empty() => {
+            let mut acc = knil.clone();
+            for mut row in list_rows(lists)?.into_iter().rev() {
+                row.push(acc);
+                acc = call_procedure(kons, row, env.clone())?;
+            }
+            Ok(acc)
+        }
+        _ => Err(Error::new(
+            "expected procedure, initial value and at least 1 list",
+        )),
+    }
+}
+
+/// Fold `kons` over the successive sublists of the lists from left to right.
+pub fn pair_fold(args: &[Expr], env: EnvRef) -> Result {
+    match args {
+        [kons, knil, lists @ ..] if !lists.is_empty() => {
+            let mut acc = knil.clone();
+            for pairs in finite_lockstep(lists)? {
+                let mut row: Vec<Expr> = pairs.into_iter().map(Expr::Pair).collect();
+                row.push(acc);
+                acc = call_procedure(kons, row, env.clone())?;
+            }
+            Ok(acc)
+        }
+        _ => Err(Error::new(
+            "expected procedure, initial value and at least 1 list",
+        )),
+    }
+}
+
+/// Fold `kons` over the successive sublists of the lists from right to left.
+pub fn pair_fold_right(args: &[Expr], env: EnvRef) -> Result {
+    match args {
+        [kons, knil, lists @ ..] if !lists.is_empty() => {
+            let mut acc = knil.clone();
+            let rows: Vec<Vec<Pair>> = finite_lockstep(lists)?.collect();
+            for pairs in rows.into_iter().rev() {
+                let mut row: Vec<Expr> = pairs.into_iter().map(Expr::Pair).collect();
+                row.push(acc);
+                acc = call_procedure(kons, row, env.clone())?;
+            }
+            Ok(acc)
+        }
+        _ => Err(Error::new(
+            "expected procedure, initial value and at least 1 list",
+        )),
+    }
+}
+
+/// Reduce a list with `f`, returning `ridentity` if the list is empty.
+pub fn reduce(args: &[Expr], env: EnvRef) -> Result {
+    match args {
+        [f, ridentity, list] => {
+            let mut elements = list_elements(list)?.into_iter();
+            let Some(mut acc) = elements.next() else {
+                return Ok(ridentity.clone());
+            };
+            for e in elements {
+                acc = call_procedure(f, vec![e, acc], env.clone())?;
+            }
+            Ok(acc)
+        }
+        _ => Err(Error::new("expected procedure, identity and list")),
+    }
+}
+
+/// Reduce a list from the right with `f`, returning `ridentity` if the list is empty.
+pub fn reduce_right(args: &[Expr], env: EnvRef) -> Result {
+    match args {
+        [f, ridentity, list] => {
+            let mut elements = list_elements(list)?.into_iter().rev();
+            let Some(mut acc) = elements.next() else {
+                return Ok(ridentity.clone());
+            };
+            for e in elements {
+                acc = call_procedure(f, vec![e, acc], env.clone())?;
+            }
+            Ok(acc)
+        }
+        _ => Err(Error::new("expected procedure, identity and list")),
+    }
+}
+
+/// Build a list from a seed: `(f seed)` is added and `(g seed)` is the next seed until `(p seed)`.
+pub fn unfold(args: &[Expr], env: EnvRef) -> Result {
+    match args {
+        [p, f, g, seed, tail_gen @ ..] if tail_gen.len() <= 1 => {
+            let mut seed = seed.clone();
+            let mut elements = Vec::new();
+            while !is_true(&call_procedure(p, vec![seed.clone()], env.clone())?) {
+                elements.push(call_procedure(f, vec![seed.clone()], env.clone())?);
+                seed = call_procedure(g, vec![seed], env.clone())?;
+            }
+            let tail = match tail_gen {
+                [tail_gen] => call_procedure(tail_gen, vec![seed], env)?,
+                _ => Expr::Null,
+            };
+            Ok(list_with_tail(elements, tail))
+        }
+        _ => Err(Error::new(
+            "expected stop predicate, mapper, successor, seed and optional tail generator",
+        )),
+    }
+}
+
+/// Build a list from a seed from right to left, optionally onto `tail`.
+pub fn unfold_right(args: &[Expr], env: EnvRef) -> Result {
+    match args {
+        [p, f, g, seed, tail @ ..] if tail.len() <= 1 => {
+            let mut seed = seed.clone();
+            let mut acc = tail.first().cloned().unwrap_or(Expr::Null);
+            while !is_true(&call_procedure(p, vec![seed.clone()], env.clone())?) {
+                let element = call_procedure(f, vec![seed.clone()], env.clone())?;
+                acc = Expr::Pair(Pair::cons((element, acc)));
+                seed = call_procedure(g, vec![seed], env.clone())?;
+            }
+            Ok(acc)
+        }
+        _ => Err(Error::new(
+            "expected stop predicate, mapper, successor, seed and optional tail",
+        )),
+    }
+}
+
+/// Map `f` over the lists and append the resulting lists.
+pub fn append_map(args: &[Expr], env: EnvRef) -> Result {
+    match args {
+        [f, lists @ ..] if !lists.is_empty() => append_lists(&map_rows(f, list_rows(lists)?, env)?),
+        _ => Err(Error::new("expected procedure and at least 1 list")),
+    }
+}
+
+/// Map `f` over the lists, keeping only the true results.
+pub fn filter_map(args: &[Expr], env: EnvRef) -> Result {
+    match args {
+        [f, lists @ ..] if !lists.is_empty() => {
+            let results: Vec<Expr> = map_rows(f, list_rows(lists)?, env)?
+                .into_iter()
+                .filter(is_true)
+                .collect();
+            Ok(Pair::list(&results))
+        }
+        _ => Err(Error::new("expected procedure and at least 1 list")),
+    }
+}
+
+/// Apply `f` to the successive sublists of the lists for its side effects.
+pub fn pair_for_each(args: &[Expr], env: EnvRef) -> Result {
+    match args {
+        [f, lists @ ..] if !lists.is_empty() => {
+            for pairs in finite_lockstep(lists)? {
+                let row = pairs.into_iter().map(Expr::Pair).collect();
+                call_procedure(f, row, env.clone())?;
+            }
+            Ok(Expr::Void())
+        }
+        _ => Err(Error::new("expected procedure and at least 1 list")),
+    }
+}
+
+/// Return a list of the elements that satisfy `pred`.
+pub fn filter(args: &[Expr], env: EnvRef) -> Result {
+    match args {
+        [pred, list] => Ok(Pair::list(&partition_elements(pred, list, env)?.0)),
+        _ => Err(Error::new("expected predicate and list")),
+    }
+}
+
+/// Return a list of the elements that do not satisfy `pred`.
+pub fn remove(args: &[Expr], env: EnvRef) -> Result {
+    match args {
+        [pred, list] => Ok(Pair::list(&partition_elements(pred, list, env)?.1)),
+        _ => Err(Error::new("expected predicate and list")),
+    }
+}
+
+/// Split a list by `pred`, returning the matching and other elements as a list of 2 values.
+pub fn partition(args: &[Expr], env: EnvRef) -> Result {
+    match args {
+        [pred, list] => {
+            let (matching, other) = partition_elements(pred, list, env)?;
+            Ok(Pair::list(&[Pair::list(&matching), Pair::list(&other)]))
+        }
+        _ => Err(Error::new("expected predicate and list")),
+    }
+}
+
+/// Return the first element that satisfies `pred`, or false.
+pub fn find(args: &[Expr], env: EnvRef) -> Result {
+    match args {
+        [pred, clist] => match find_tail_of(pred, clist, env)? {
+            Expr::Pair(p) => Ok(p.car()),
+            other => Ok(other),
+        },
+        _ => Err(Error::new("expected predicate and list")),
+    }
+}
+
+/// Return the first pair whose car satisfies `pred`, or false.
+pub fn find_tail(args: &[Expr], env: EnvRef) -> Result {
+    match args {
+        [pred, clist] => find_tail_of(pred, clist, env),
+        _ => Err(Error::new("expected predicate and list")),
+    }
+}
+
+/// Return the first true result of `pred` applied to each row of elements, or false.
+pub fn any(args: &[Expr], env: EnvRef) -> Result {
+    match args {
+        [pred, lists @ ..] if !lists.is_empty() => {
+            for pairs in Lockstep::new(lists) {
+                let result = call_procedure(pred, row_cars(&pairs), env.clone())?;
+                if is_true(&result) {
+                    return Ok(result);
+                }
+            }
+            Ok(Expr::Boolean(false))
+        }
+        _ => Err(Error::new("expected predicate and at least 1 list")),
+    }
+}
+
+/// Return the last result of `pred` if it is true for every row of elements, or false.
+pub fn every(args: &[Expr], env: EnvRef) -> Result {
+    match args {
+        [pred, lists @ ..] if !lists.is_empty() => {
+            let mut result = Expr::Boolean(true);
+            for pairs in Lockstep::new(lists) {
+                result = call_procedure(pred, row_cars(&pairs), env.clone())?;
+                if !is_true(&result) {
+                    return Ok(result);
+                }
+            }
+            Ok(result)
+        }
+        _ => Err(Error::new("expected predicate and at least 1 list")),
+    }
+}
+
+/// Return the index of the first row of elements that satisfies `pred`, or false.
+pub fn list_index(args: &[Expr], env: EnvRef) -> Result {
+    match args {
+        [pred, lists @ ..] if !lists.is_empty() => {
+            for (i, pairs) in Lockstep::new(lists).enumerate() {
+                if is_true(&call_procedure(pred, row_cars(&pairs), env.clone())?) {
+                    return Ok(Expr::Number(Number::from_usize(i)));
                 }
-            };
-            match vec.sub_vector(start, vec.len()) {
-                Some(v) => Ok(Expr::Vector(v.clone())),
-                None => Err(Error::new("out of range")),
             }
+            Ok(Expr::Boolean(false))
         }
-        [Expr::Vector(vec), Expr::Number(start), Expr::Number(end)] => {
-            let v_len = Number::from_usize(vec.len());
-            if *start == v_len && *end == v_len {
-                return Ok(Expr::Null);
+        _ => Err(Error::new("expected predicate and at least 1 list")),
+    }
+}
+
+/// Return the longest prefix of elements that satisfy `pred`.
+pub fn take_while(args: &[Expr], env: EnvRef) -> Result {
+    match args {
+        [pred, clist] => Ok(Pair::list(&span_of(pred, clist, env)?.0)),
+        _ => Err(Error::new("expected predicate and list")),
+    }
+}
+
+/// Return the tail after the longest prefix of elements that satisfy `pred`.
+pub fn drop_while(args: &[Expr], env: EnvRef) -> Result {
+    match args {
+        [pred, clist] => Ok(span_of(pred, clist, env)?.1),
+        _ => Err(Error::new("expected predicate and list")),
+    }
+}
+
+/// Split a list at the first element that fails `pred`, returning a list of 2 values.
+pub fn span(args: &[Expr], env: EnvRef) -> Result {
+    match args {
+        [pred, clist] => {
+            let (prefix, rest) = span_of(pred, clist, env)?;
+            Ok(Pair::list(&[Pair::list(&prefix), rest]))
+        }
+        _ => Err(Error::new("expected predicate and list")),
+    }
+}
+
+/// Split a list at the first element that satisfies `pred`, returning a list of 2 values.
+pub fn break_list(args: &[Expr], env: EnvRef) -> Result {
+    match args {
+        [pred, clist] => {
+            let mut prefix = Vec::new();
+            let mut current = clist.clone();
+            while let Expr::Pair(p) = &current {
+                if is_true(&call_procedure(pred, vec![p.car()], env.clone())?) {
+                    break;
+                }
+                prefix.push(p.car());
+                current = p.cdr();
             }
-            let start = match start.to_usize() {
-                Some(s) => s,
-                None => {
-                    return Err(Error::Message(
-                        "invalid index, expected int or float".to_string(),
-                    ));
+            Ok(Pair::list(&[Pair::list(&prefix), current]))
+        }
+        _ => Err(Error::new("expected predicate and list")),
+    }
+}
+
+/// Return a list without the elements equal to `x`, using `=` or `equal?`.
+pub fn delete(args: &[Expr], env: EnvRef) -> Result {
+    match args {
+        [x, list, compare @ ..] if compare.len() <= 1 => {
+            let compare = compare.first();
+            let mut kept = Vec::new();
+            for e in list_elements(list)? {
+                if !elements_equal(compare, x, &e, &env)? {
+                    kept.push(e);
                 }
-            };
-            let end = match end.to_usize() {
-                Some(s) => s,
-                None => {
-                    return Err(Error::Message(
-                        "invalid index, expected int or float".to_string(),
-                    ));
+            }
+            Ok(Pair::list(&kept))
+        }
+        _ => Err(Error::new(
+            "expected object, list and optional equality procedure",
+        )),
+    }
+}
+
+/// Return a list without duplicate elements, using `=` or `equal?`.
+pub fn delete_duplicates(args: &[Expr], env: EnvRef) -> Result {
+    match args {
+        [list, compare @ ..] if compare.len() <= 1 => {
+            let compare = compare.first();
+            let mut kept: Vec<Expr> = Vec::new();
+            for e in list_elements(list)? {
+                if !contains(compare, &kept, &e, &env)? {
+                    kept.push(e);
                 }
-            };
-            match vec.sub_vector(start, end) {
-                Some(v) => Ok(Expr::Vector(v.clone())),
-                None => Err(Error::new("out of range")),
             }
+            Ok(Pair::list(&kept))
         }
-        _ => Err(Error::new("expected vector")),
+        _ => Err(Error::new("expected list and optional equality procedure")),
     }
 }
 
-/// Copy elements from one `ByteVector` into another using range indexes.
-pub fn vector_copy_from(args: &[Expr], _: EnvRef) -> Result {
+/// Add a `(key . datum)` entry to the front of an association list.
+pub fn alist_cons(args: &[Expr], _: EnvRef) -> Result {
     match args {
-        [Expr::Vector(dest), Expr::Number(at), Expr::Vector(from)] => {
-            let at = at
-                .to_usize()
-                .ok_or_else(|| Error::new("invalid index, expected int or float"))?;
+        [key, datum, alist] => {
+            let entry = Expr::Pair(Pair::cons((key.clone(), datum.clone())));
+            Ok(Expr::Pair(Pair::cons((entry, alist.clone()))))
+        }
+        _ => Err(Error::new("expected key, datum and association list")),
+    }
+}
 
-            if at > dest.len() {
-                return Err(Error::new("out of range"));
+/// Return a copy of an association list with newly allocated entries.
+pub fn alist_copy(args: &[Expr], _: EnvRef) -> Result {
+    match args {
+        [alist] => {
+            let entries = list_elements(alist)?
+                .into_iter()
+                .map(|entry| match entry {
+                    Expr::Pair(p) => Ok(Expr::Pair(Pair::cons((p.car(), p.cdr())))),
+                    _ => Err(Error::new("expected association list of pairs")),
+                })
+                .collect::<std::result::Result<Vec<Expr>, Error>>()?;
+            Ok(Pair::list(&entries))
+        }
+        _ => Err(Error::new("expected association list")),
+    }
+}
+
+/// Return an association list without the entries whose key equals `key`.
+pub fn alist_delete(args: &[Expr], env: EnvRef) -> Result {
+    match args {
+        [key, alist, compare @ ..] if compare.len() <= 1 => {
+            let compare = compare.first();
+            let mut kept = Vec::new();
+            for entry in list_elements(alist)? {
+                let Expr::Pair(p) = &entry else {
+                    return Err(Error::new("expected association list of pairs"));
+                };
+                if !elements_equal(compare, key, &p.car(), &env)? {
+                    kept.push(entry);
+                }
             }
+            Ok(Pair::list(&kept))
+        }
+        _ => Err(Error::new(
+            "expected key, association list and optional equality procedure",
+        )),
+    }
+}
 
-            for (i, expr) in from.elements.borrow().iter().enumerate() {
-                dest.set(i + at, expr.clone())?;
+/// Return true if each list is a subset of the next.
+pub fn lset_le(args: &[Expr], env: EnvRef) -> Result {
+    match args {
+        [compare, lists @ ..] => {
+            let sets = list_sets(lists)?;
+            for pair in sets.windows(2) {
+                if !is_subset(compare, &pair[0], &pair[1], &env)? {
+                    return Ok(Expr::Boolean(false));
+                }
             }
+            Ok(Expr::Boolean(true))
+        }
+        _ => Err(Error::new("expected equality procedure")),
+    }
+}
 
-            Ok(Expr::Void())
+/// Return true if every list has the same elements.
+pub fn lset_eq(args: &[Expr], env: EnvRef) -> Result {
+    match args {
+        [compare, lists @ ..] => {
+            let sets = list_sets(lists)?;
+            for pair in sets.windows(2) {
+                if !is_subset(compare, &pair[0], &pair[1], &env)?
+                    || !is_subset(compare, &pair[1], &pair[0], &env)?
+                {
+                    return Ok(Expr::Boolean(false));
+                }
+            }
+            Ok(Expr::Boolean(true))
         }
-        [
-            Expr::Vector(dest),
-            Expr::Number(at),
-            Expr::Vector(from),
-            Expr::Number(start),
-        ] => {
-            let at = at
-                .to_usize()
-                .ok_or_else(|| Error::new("invalid index, expected int or float"))?;
+        _ => Err(Error::new("expected equality procedure")),
+    }
+}
 
-            let start = start
-                .to_usize()
-                .ok_or_else(|| Error::new("invalid index, expected int or float"))?;
+/// Add the elements not already in `list` to its front.
+pub fn lset_adjoin(args: &[Expr], env: EnvRef) -> Result {
+    match args {
+        [compare, list, elements @ ..] => {
+            let mut set = list_elements(list)?;
+            let mut result = list.clone();
+            for e in elements {
+                if !contains(Some(compare), &set, e, &env)? {
+                    set.push(e.clone());
+                    result = Expr::Pair(Pair::cons((e.clone(), result)));
+                }
+            }
+            Ok(result)
+        }
+        _ => Err(Error::new("expected equality procedure and list")),
+    }
+}
 
-            if at > dest.len() || (dest.len() - at) < (from.len() - start) {
-                return Err(Error::new("out of range"));
+/// Return the union of the lists.
+pub fn lset_union(args: &[Expr], env: EnvRef) -> Result {
+    match args {
+        [compare, lists @ ..] => {
+            let mut set: Vec<Expr> = Vec::new();
+            let mut result = Expr::Null;
+            for list in lists {
+                if matches!(result, Expr::Null) {
+                    set = list_elements(list)?;
+                    result = list.clone();
+                    continue;
+                }
+                for e in list_elements(list)? {
+                    if !contains(Some(compare), &set, &e, &env)? {
+                        set.push(e.clone());
+                        result = Expr::Pair(Pair::cons((e, result)));
+                    }
+                }
             }
+            Ok(result)
+        }
+        _ => Err(Error::new("expected equality procedure")),
+    }
+}
 
-            let from_ref = from.elements.borrow();
-            for (i, expr) in from_ref.as_slice()[start..].iter().enumerate() {
-                dest.set(i + at, expr.clone())?;
+/// Return the elements of the first list that are in every other list.
+pub fn lset_intersection(args: &[Expr], env: EnvRef) -> Result {
+    match args {
+        [compare, list, others @ ..] => {
+            let others = list_sets(others)?;
+            let mut kept = Vec::new();
+            for e in list_elements(list)? {
+                let mut in_all = true;
+                for other in &others {
+                    if !contains(Some(compare), other, &e, &env)? {
+                        in_all = false;
+                        break;
+                    }
+                }
+                if in_all {
+                    kept.push(e);
+                }
             }
+            Ok(Pair::list(&kept))
+        }
+        _ => Err(Error::new(
+            "expected equality procedure and at least 1 list",
+        )),
+    }
+}
 
-            Ok(Expr::Void())
+/// Return the elements of the first list that are in no other list.
+pub fn lset_difference(args: &[Expr], env: EnvRef) -> Result {
+    match args {
+        [compare, list, others @ ..] => {
+            let others = list_sets(others)?;
+            let mut kept = Vec::new();
+            for e in list_elements(list)? {
+                let mut in_any = false;
+                for other in &others {
+                    if contains(Some(compare), other, &e, &env)? {
+                        in_any = true;
+                        break;
+                    }
+                }
+                if !in_any {
+                    kept.push(e);
+                }
+            }
+            Ok(Pair::list(&kept))
         }
-        [
-            Expr::Vector(dest),
-            Expr::Number(at),
-            Expr::Vector(from),
-            Expr::Number(start),
-            Expr::Number(end),
-        ] => {
-            let at = at
-                .to_usize()
-                .ok_or_else(|| Error::new("invalid index, expected int or float"))?;
+        _ => Err(Error::new(
+            "expected equality procedure and at least 1 list",
+        )),
+    }
+}
+
+/// Return the difference and intersection of the first list with the other lists
+/// as a list of 2 lists.
+pub fn lset_diff_intersection(args: &[Expr], env: EnvRef) -> Result {
+    match args {
+        [compare, list, others @ ..] => {
+            let others = list_sets(others)?;
+            let (mut difference, mut intersection) = (Vec::new(), Vec::new());
+            for e in list_elements(list)? {
+                let mut in_any = false;
+                for other in &others {
+                    if contains(Some(compare), other, &e, &env)? {
+                        in_any = true;
+                        break;
+                    }
+                }
+                match in_any {
+                    true => intersection.push(e),
+                    false => difference.push(e),
+                }
+            }
+            Ok(Pair::list(&[
+                Pair::list(&difference),
+                Pair::list(&intersection),
+            ]))
+        }
+        _ => Err(Error::new(
+            "expected equality procedure and at least 1 list",
+        )),
+    }
+}
+
+/// Return the elements that are in an odd number of the lists.
+pub fn lset_xor(args: &[Expr], env: EnvRef) -> Result {
+    match args {
+        [compare, lists @ ..] => {
+            let mut result: Vec<Expr> = Vec::new();
+            for set in list_sets(lists)? {
+                let mut next = Vec::new();
+                for e in &result {
+                    if !contains(Some(compare), &set, e, &env)? {
+                        next.push(e.clone());
+                    }
+                }
+                for e in &set {
+                    if !contains(Some(compare), &result, e, &env)? {
+                        next.push(e.clone());
+                    }
+                }
+                result = next;
+            }
+            Ok(Pair::list(&result))
+        }
+        _ => Err(Error::new("expected equality procedure")),
+    }
+}
+
+/// Shape of a chain of pairs.
+enum ListShape {
+    /// Terminated by `()`, with the number of elements.
+    Proper(usize),
+    /// Terminated by a non-null value.
+    Dotted,
+    Circular,
+}
+
+/// Return the shape of `list`, detecting cycles with Floyd's algorithm.
+fn list_shape(list: &Expr) -> ListShape {
+    let mut len = 0;
+    let mut fast = list.clone();
+    let mut slow = list.clone();
+    loop {
+        for _ in 0..2 {
+            match &fast {
+                Expr::Pair(p) => fast = p.cdr(),
+                Expr::Null => return ListShape::Proper(len),
+                _ => return ListShape::Dotted,
+            }
+            len += 1;
+        }
+        if let Expr::Pair(p) = &slow {
+            slow = p.cdr();
+        }
+        if let (Expr::Pair(a), Expr::Pair(b)) = (&fast, &slow)
+            && a.raw_ptr() == b.raw_ptr()
+        {
+            return ListShape::Circular;
+        }
+    }
+}
+
+/// Return the elements and terminating tail of a finite list.
+fn finite_elements(list: &Expr) -> std::result::Result<(Vec<Expr>, Expr), Error> {
+    if let ListShape::Circular = list_shape(list) {
+        return Err(Error::new("expected finite list"));
+    }
+    let mut elements = Vec::new();
+    let mut current = list.clone();
+    while let Expr::Pair(p) = current {
+        elements.push(p.car());
+        current = p.cdr();
+    }
+    Ok((elements, current))
+}
+
+/// Make a list of `elements` whose final cdr is `tail`.
+fn list_with_tail(elements: Vec<Expr>, tail: Expr) -> Expr {
+    elements
+        .into_iter()
+        .rev()
+        .fold(tail, |tail, e| Expr::Pair(Pair::cons((e, tail))))
+}
+
+/// Append lists, where the last argument may be any value.
+fn append_lists(lists: &[Expr]) -> Result {
+    match lists {
+        [] => Ok(Expr::Null),
+        [heads @ .., tail] => heads.iter().rev().try_fold(tail.clone(), |tail, list| {
+            Ok(list_with_tail(list_elements(list)?, tail))
+        }),
+    }
+}
+
+/// Iterates over the pairs of several lists in step, stopping when any list ends.
+struct Lockstep {
+    cursors: Vec<Expr>,
+}
+
+impl Lockstep {
+    fn new(lists: &[Expr]) -> Lockstep {
+        Lockstep {
+            cursors: lists.to_vec(),
+        }
+    }
+}
+
+impl Iterator for Lockstep {
+    type Item = Vec<Pair>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let pairs = self
+            .cursors
+            .iter()
+            .map(|cursor| match cursor {
+                Expr::Pair(p) => Some(p.clone()),
+                _ => None,
+            })
+            .collect::<Option<Vec<Pair>>>()?;
+        // Advance before yielding, so callers may mutate the pairs.
+        for (cursor, pair) in self.cursors.iter_mut().zip(&pairs) {
+            *cursor = pair.cdr();
+        }
+        Some(pairs)
+    }
+}
+
+/// Iterate over lists in step, where at least one list must be finite.
+fn finite_lockstep(lists: &[Expr]) -> std::result::Result<Lockstep, Error> {
+    if lists
+        .iter()
+        .all(|list| matches!(list_shape(list), ListShape::Circular))
+    {
+        return Err(Error::new("expected at least 1 finite list"));
+    }
+    Ok(Lockstep::new(lists))
+}
+
+/// Return rows of the `i`th element of each list, stopping at the shortest list.
+fn list_rows(lists: &[Expr]) -> std::result::Result<Vec<Vec<Expr>>, Error> {
+    Ok(finite_lockstep(lists)?
+        .map(|pairs| row_cars(&pairs))
+        .collect())
+}
+
+/// Return `n` lists, where list `i` holds element `i` of each list in `list`.
+fn unzip_columns(list: &Expr, n: usize) -> std::result::Result<Vec<Expr>, Error> {
+    let mut columns = vec![Vec::new(); n];
+    for element in list_elements(list)? {
+        for (column, e) in columns.iter_mut().zip(take_elements(&element, n)?) {
+            column.push(e);
+        }
+    }
+    Ok(columns.iter().map(|column| Pair::list(column)).collect())
+}
+
+/// Return the cars of a row of pairs.
+fn row_cars(pairs: &[Pair]) -> Vec<Expr> {
+    pairs.iter().map(Pair::car).collect()
+}
+
+/// Return element `k` of a list argument.
+fn nth_element(args: &[Expr], k: usize) -> Result {
+    match args {
+        [list] => match nth_tail(list, k)? {
+            Expr::Pair(p) => Ok(p.car()),
+            _ => Err(Error::new("index out of range")),
+        },
+        _ => Err(Error::new("expected list")),
+    }
+}
+
+/// Convert a list index argument to `usize`.
+fn list_index_arg(i: &Number) -> std::result::Result<usize, Error> {
+    i.to_usize()
+        .ok_or_else(|| Error::new("invalid index, expected non-negative integer"))
+}
+
+/// Return the first `i` elements of `list`.
+fn take_elements(list: &Expr, i: usize) -> std::result::Result<Vec<Expr>, Error> {
+    let mut elements = Vec::new();
+    let mut current = list.clone();
+    for _ in 0..i {
+        match current {
+            Expr::Pair(p) => {
+                elements.push(p.car());
+                current = p.cdr();
+            }
+            _ => return Err(Error::new("index out of range")),
+        }
+    }
+    Ok(elements)
+}
+
+/// Return the last pair of a finite list.
+fn last_pair_of(list: &Expr) -> std::result::Result<Pair, Error> {
+    if let ListShape::Circular = list_shape(list) {
+        return Err(Error::new("expected finite list"));
+    }
+    let Expr::Pair(mut last) = list.clone() else {
+        return Err(Error::new("expected pair"));
+    };
+    while let Expr::Pair(next) = last.cdr() {
+        last = next;
+    }
+    Ok(last)
+}
+
+/// Split the elements of a list into those that satisfy `pred` and those that do not.
+fn partition_elements(
+    pred: &Expr,
+    list: &Expr,
+    env: EnvRef,
+) -> std::result::Result<(Vec<Expr>, Vec<Expr>), Error> {
+    let mut matching = Vec::new();
+    let mut other = Vec::new();
+    for e in list_elements(list)? {
+        if is_true(&call_procedure(pred, vec![e.clone()], env.clone())?) {
+            matching.push(e);
+        } else {
+            other.push(e);
+        }
+    }
+    Ok((matching, other))
+}
+
+/// Return the first pair whose car satisfies `pred`, or false.
+fn find_tail_of(pred: &Expr, clist: &Expr, env: EnvRef) -> Result {
+    let mut current = clist.clone();
+    while let Expr::Pair(p) = current {
+        if is_true(&call_procedure(pred, vec![p.car()], env.clone())?) {
+            return Ok(Expr::Pair(p));
+        }
+        current = p.cdr();
+    }
+    Ok(Expr::Boolean(false))
+}
+
+/// Return the longest prefix of elements that satisfy `pred`, and the remaining tail.
+fn span_of(
+    pred: &Expr,
+    clist: &Expr,
+    env: EnvRef,
+) -> std::result::Result<(Vec<Expr>, Expr), Error> {
+    let mut prefix = Vec::new();
+    let mut current = clist.clone();
+    while let Expr::Pair(p) = &current {
+        if !is_true(&call_procedure(pred, vec![p.car()], env.clone())?) {
+            break;
+        }
+        prefix.push(p.car());
+        current = p.cdr();
+    }
+    Ok((prefix, current))
+}
+
+/// Return true if `a` and `b` are equal by `compare`, or by `equal?` if no procedure is given.
+fn elements_equal(
+    compare: Option<&Expr>,
+    a: &Expr,
+    b: &Expr,
+    env: &EnvRef,
+) -> std::result::Result<bool, Error> {
+    match compare {
+        Some(compare) => Ok(is_true(&call_procedure(
+            compare,
+            vec![a.clone(), b.clone()],
+            env.clone(),
+        )?)),
+        None => a.equal(b),
+    }
+}
+
+/// Return true if some element of `set` is equal to `x`.
+fn contains(
+    compare: Option<&Expr>,
+    set: &[Expr],
+    x: &Expr,
+    env: &EnvRef,
+) -> std::result::Result<bool, Error> {
+    for e in set {
+        if elements_equal(compare, e, x, env)? {
+            return Ok(true);
+        }
+    }
+    Ok(false)
+}
+
+/// Return the elements of each list.
+fn list_sets(lists: &[Expr]) -> std::result::Result<Vec<Vec<Expr>>, Error> {
+    lists.iter().map(list_elements).collect()
+}
+
+/// Return true if every element of `a` is in `b`.
+fn is_subset(
+    compare: &Expr,
+    a: &[Expr],
+    b: &[Expr],
+    env: &EnvRef,
+) -> std::result::Result<bool, Error> {
+    for x in a {
+        let mut found = false;
+        for y in b {
+            if elements_equal(Some(compare), x, y, env)? {
+                found = true;
+                break;
+            }
+        }
+        if !found {
+            return Ok(false);
+        }
+    }
+    Ok(true)
+}
 
-            let start = start
-                .to_usize()
-                .ok_or_else(|| Error::new("invalid index, expected int or float"))?;
+// Sorting (SRFI 132)
 
-            let end = end
-                .to_usize()
-                .ok_or_else(|| Error::new("invalid index, expected int or float"))?;
+/// Return a new list with the elements of a list sorted stably by a less-than predicate.
+pub fn list_sort(args: &[Expr], env: EnvRef) -> Result {
+    match args {
+        [less, list] => Ok(Pair::list(&sort_elements(
+            list_elements(list)?,
+            less,
+            &env,
+        )?)),
+        _ => Err(Error::new("expected predicate and list")),
+    }
+}
 
-            if at > dest.len()
-                || end > from.len()
-                || start > from.len()
-                || (dest.len() - at) < (end - start)
-            {
-                return Err(Error::new("out of range"));
-            }
+/// Return true if the elements of a list are in order by a less-than predicate.
+pub fn is_list_sorted(args: &[Expr], env: EnvRef) -> Result {
+    match args {
+        [less, list] => Ok(Expr::Boolean(is_sorted(&list_elements(list)?, less, &env)?)),
+        _ => Err(Error::new("expected predicate and list")),
+    }
+}
 
-            let from_ref = from.elements.borrow();
-            for (i, expr) in from_ref.as_slice()[start..end].iter().enumerate() {
-                dest.set(i + at, expr.clone())?;
-            }
+/// Merge two sorted lists into a new sorted list, keeping elements of the first list
+/// before equal elements of the second.
+pub fn list_merge(args: &[Expr], env: EnvRef) -> Result {
+    match args {
+        [less, list1, list2] => Ok(Pair::list(&merge_elements(
+            list_elements(list1)?,
+            list_elements(list2)?,
+            less,
+            &env,
+        )?)),
+        _ => Err(Error::new("expected predicate and 2 lists")),
+    }
+}
 
-            Ok(Expr::Void())
+/// Return a new vector with the elements of a vector sorted stably by a less-than predicate.
+/// Accepts optional start and end indexes.
+pub fn vector_sort(args: &[Expr], env: EnvRef) -> Result {
+    match args {
+        [less, Expr::Vector(v), range @ ..] => {
+            let elements = vector_range(v, range)?;
+            Ok(Expr::Vector(Vector::from(&sort_elements(
+                elements, less, &env,
+            )?)))
         }
-        _ => Err(Error::new("expected a vector, index, and vector")),
+        _ => Err(Error::new("expected predicate, vector and optional range")),
     }
 }
 
-/// Fill a vector with the given argument.
-pub fn vector_fill(args: &[Expr], _: EnvRef) -> Result {
+/// Sort the elements of a vector in place by a less-than predicate.
+/// Accepts optional start and end indexes.
+pub fn vector_sort_in_place(args: &[Expr], env: EnvRef) -> Result {
     match args {
-        [Expr::Vector(vec), new_value] => {
-            vec.fill(new_value, 0, vec.len())?;
+        [Expr::Vector(v), less, range @ ..] => {
+            let (start, end) = vector_bounds(v, range)?;
+            let elements = vector_range(v, range)?;
+            let sorted = sort_elements(elements, less, &env)?;
+            v.elements.borrow_mut().splice(start..end, sorted);
             Ok(Expr::Void())
         }
-        [Expr::Vector(vec), new_value, Expr::Number(start)] => {
-            if *start == Number::from_usize(vec.len()) {
-                return Ok(Expr::Null);
-            }
-            let start = match start.to_usize() {
-                Some(s) => s,
-                None => {
-                    return Err(Error::Message(
-                        "invalid index, expected int or float".to_string(),
-                    ));
+        _ => Err(Error::new("expected vector, predicate and optional range")),
+    }
+}
+
+/// Return true if the elements of a vector are in order by a less-than predicate.
+/// Accepts optional start and end indexes.
+pub fn is_vector_sorted(args: &[Expr], env: EnvRef) -> Result {
+    match args {
+        [less, Expr::Vector(v), range @ ..] => Ok(Expr::Boolean(is_sorted(
+            &vector_range(v, range)?,
+            less,
+            &env,
+        )?)),
+        _ => Err(Error::new("expected predicate, vector and optional range")),
+    }
+}
+
+/// Merge two sorted vectors into a new sorted vector.
+/// Accepts optional start and end indexes for each vector.
+pub fn vector_merge(args: &[Expr], env: EnvRef) -> Result {
+    match args {
+        [less, Expr::Vector(v1), Expr::Vector(v2), ranges @ ..] if ranges.len() <= 4 => {
+            let (range1, range2) = ranges.split_at(ranges.len().min(2));
+            let merged = merge_elements(
+                vector_range(v1, range1)?,
+                vector_range(v2, range2)?,
+                less,
+                &env,
+            )?;
+            Ok(Expr::Vector(Vector::from(&merged)))
+        }
+        _ => Err(Error::new(
+            "expected predicate, 2 vectors and optional ranges",
+        )),
+    }
+}
+
+/// Return a new vector with runs of adjacent equal elements reduced to their first element.
+/// Accepts optional start and end indexes.
+pub fn vector_delete_neighbor_dups(args: &[Expr], env: EnvRef) -> Result {
+    match args {
+        [equal, Expr::Vector(v), range @ ..] => {
+            let mut result: Vec<Expr> = Vec::new();
+            for e in vector_range(v, range)? {
+                if let Some(last) = result.last()
+                    && is_true(&call_procedure(
+                        equal,
+                        vec![last.clone(), e.clone()],
+                        env.clone(),
+                    )?)
+                {
+                    continue;
                 }
-            };
-            vec.fill(new_value, start, vec.len())?;
-            Ok(Expr::Void())
+                result.push(e);
+            }
+            Ok(Expr::Vector(Vector::from(&result)))
         }
-        [
-            Expr::Vector(vec),
-            new_value,
-            Expr::Number(start),
-            Expr::Number(end),
-        ] => {
-            let v_len = Number::from_usize(vec.len());
-            if *start == v_len && *end == v_len {
-                return Ok(Expr::Null);
+        _ => Err(Error::new("expected predicate, vector and optional range")),
+    }
+}
+
+/// Call a less-than predicate on two elements.
+fn is_less(less: &Expr, a: &Expr, b: &Expr, env: &EnvRef) -> std::result::Result<bool, Error> {
+    Ok(is_true(&call_procedure(
+        less,
+        vec![a.clone(), b.clone()],
+        env.clone(),
+    )?))
+}
+
+/// Sort elements stably with a bottom-up merge sort, stopping at the first predicate error.
+fn sort_elements(
+    elements: Vec<Expr>,
+    less: &Expr,
+    env: &EnvRef,
+) -> std::result::Result<Vec<Expr>, Error> {
+    let mut runs: Vec<Vec<Expr>> = elements.into_iter().map(|e| vec![e]).collect();
+    while runs.len() > 1 {
+        let mut merged = Vec::with_capacity(runs.len().div_ceil(2));
+        let mut runs_iter = runs.into_iter();
+        while let Some(left) = runs_iter.next() {
+            match runs_iter.next() {
+                Some(right) => merged.push(merge_elements(left, right, less, env)?),
+                None => merged.push(left),
             }
-            let start = match start.to_usize() {
-                Some(s) => s,
-                None => {
-                    return Err(Error::Message(
-                        "invalid index, expected int or float".to_string(),
-                    ));
-                }
-            };
-            let end = match end.to_usize() {
-                Some(s) => s,
-                None => {
-                    return Err(Error::Message(
-                        "invalid index, expected int or float".to_string(),
-                    ));
-                }
-            };
-            vec.fill(new_value, start, end)?;
-            Ok(Expr::Void())
         }
-        _ => Err(Error::new("expected vector and new value")),
+        runs = merged;
+    }
+    Ok(runs.pop().unwrap_or_default())
+}
+
+/// Merge two sorted runs, taking from `left` unless the next element of `right` is less.
+fn merge_elements(
+    left: Vec<Expr>,
+    right: Vec<Expr>,
+    less: &Expr,
+    env: &EnvRef,
+) -> std::result::Result<Vec<Expr>, Error> {
+    let mut merged = Vec::with_capacity(left.len() + right.len());
+    let mut left = left.into_iter().peekable();
+    let mut right = right.into_iter().peekable();
+    while let (Some(l), Some(r)) = (left.peek(), right.peek()) {
+        let next = match is_less(less, r, l, env)? {
+            true => right.next(),
+            false => left.next(),
+        };
+        merged.extend(next);
     }
+    merged.extend(left);
+    merged.extend(right);
+    Ok(merged)
 }
 
-/// Append two `Vector` and return resulting `Vector`.
-pub fn vector_append(args: &[Expr], _: EnvRef) -> Result {
-    match args {
-        [Expr::Vector(a), Expr::Vector(b)] => {
-            let new_vec = a.deep_copy();
-            new_vec.append(b.deep_copy());
-            Ok(Expr::Vector(new_vec))
+/// Return true if no element is less than the element before it.
+fn is_sorted(elements: &[Expr], less: &Expr, env: &EnvRef) -> std::result::Result<bool, Error> {
+    for pair in elements.windows(2) {
+        if is_less(less, &pair[1], &pair[0], env)? {
+            return Ok(false);
         }
-        _ => Err(Error::new("expected 2 vectors")),
     }
+    Ok(true)
 }
 
-/// Bytevectors
+/// Return the `(start, end)` indexes of a vector given by optional start and end arguments.
+fn vector_bounds(v: &Vector, range: &[Expr]) -> std::result::Result<(usize, usize), Error> {
+    let index = |n: &Number| {
+        n.to_usize()
+            .ok_or_else(|| Error::new("invalid index, expected non-negative integer"))
+    };
+    let (start, end) = match range {
+        [] => (0, v.len()),
+        [Expr::Number(start)] => (index(start)?, v.len()),
+        [Expr::Number(start), Expr::Number(end)] => (index(start)?, index(end)?),
+        _ => return Err(Error::new("expected optional start and end indexes")),
+    };
+    if start > end || end > v.len() {
+        return Err(Error::new("index out of range"));
+    }
+    Ok((start, end))
+}
 
-/// Return a newly allocated `ByteVector` filled with all `u8` arguments.
-pub fn new_bytevector(args: &[Expr], _: EnvRef) -> Result {
-    let vector = ByteVector::new(args.len());
-    for (i, arg) in args.iter().enumerate() {
-        match arg {
-            Expr::Number(n) if n.is_byte() => {
-                let byte = n.to_u8().expect("value should have been converted to byte");
-                vector.set(i, byte).expect("index should be in bounds");
+/// Return a copy of the vector elements given by optional start and end arguments.
+fn vector_range(v: &Vector, range: &[Expr]) -> std::result::Result<Vec<Expr>, Error> {
+    let (start, end) = vector_bounds(v, range)?;
+    Ok(v.elements.borrow()[start..end].to_vec())
+}
+
+// Hash tables
+
+/// Create a hash table from an optional equivalence procedure and hash function.
+pub fn make_hash_table(args: &[Expr], _: EnvRef) -> Result {
+    match args {
+        [] => Ok(Expr::HashTable(HashTable::new(Equivalence::Equal))),
+        // The hash function is ignored; keys are always hashed to match the equivalence.
+        [equivalence, ..] if args.len() <= 2 => Ok(Expr::HashTable(HashTable::new(
+            table_equivalence(equivalence)?,
+        ))),
+        _ => Err(Error::new(
+            "expected equivalence procedure and hash function",
+        )),
+    }
+}
+
+/// Returns true if arg is a hash table.
+pub fn is_hash_table(args: &[Expr], _: EnvRef) -> Result {
+    match args {
+        [Expr::HashTable(_)] => Ok(Expr::Boolean(true)),
+        [_] => Ok(Expr::Boolean(false)),
+        _ => Err(Error::Message(format!(
+            "expected 1 argument, got {}",
+            args.len()
+        ))),
+    }
+}
+
+/// Return the value for a key, calling the optional failure thunk or success procedure.
+pub fn hash_table_ref(args: &[Expr], env: EnvRef) -> Result {
+    match args {
+        [Expr::HashTable(table), key, rest @ ..] if rest.len() <= 2 => {
+            match (table.get(key)?, rest) {
+                (Some(value), [_, success]) => call_procedure(success, vec![value], env),
+                (Some(value), _) => Ok(value),
+                (None, [failure, ..]) => call_thunk(failure, env),
+                (None, []) => Err(Error::Message(format!("key not found: {}", key))),
             }
-            _ => return Err(Error::new("expected byte")),
         }
+        _ => Err(Error::new("expected hash table and key")),
     }
-    Ok(Expr::ByteVector(vector))
 }
 
-/// Return a newly allocated `ByteVector` of a given size and an optional value. Defaults to 0 if no value is provided.
-pub fn make_bytevector(args: &[Expr], _: EnvRef) -> Result {
+/// Return the value for a key, or a default if the key is missing.
+pub fn hash_table_ref_default(args: &[Expr], _: EnvRef) -> Result {
     match args {
-        [Expr::Number(size), Expr::Number(default)] if size.is_usize() && default.is_byte() => {
-            let byte = default
-                .to_u8()
-                .expect("value should have converted to byte");
-            let size = size
-                .to_usize()
-                .expect("value should have converted to usize");
-            let vec = vec![byte.clone(); size];
-            Ok(Expr::ByteVector(ByteVector::from(vec.as_slice())))
+        [Expr::HashTable(table), key, default] => {
+            Ok(table.get(key)?.unwrap_or_else(|| default.clone()))
         }
-        _ => Err(Error::new("expected size and optional byte value")),
+        _ => Err(Error::new("expected hash table, key and default")),
     }
 }
 
-/// Return the length of a `ByteVector`.
-pub fn bytevector_length(args: &[Expr], _: EnvRef) -> Result {
+/// Associate each key with the value that follows it.
+pub fn hash_table_set(args: &[Expr], _: EnvRef) -> Result {
     match args {
-        [Expr::ByteVector(bv)] => Ok(Expr::Number(Number::from_usize(bv.len()))),
-        _ => Err(Error::new("expected bytevector")),
+        [Expr::HashTable(table), entries @ ..] if entries.len() % 2 == 0 => {
+            for entry in entries.chunks(2) {
+                table.insert(entry[0].clone(), entry[1].clone())?;
+            }
+            Ok(Expr::Void())
+        }
+        _ => Err(Error::new(
+            "expected hash table followed by keys and values",
+        )),
     }
 }
 
-/// Return byte at given index.
-pub fn bytevector_ref(args: &[Expr], _: EnvRef) -> Result {
+/// Remove each key and return the number of entries removed.
+pub fn hash_table_delete(args: &[Expr], _: EnvRef) -> Result {
     match args {
-        [Expr::ByteVector(bv), Expr::Number(n_index)] if n_index.is_usize() => {
-            let index = n_index
-                .to_usize()
-                .expect("value should have been converted to usize");
-            match bv.get(index) {
-                Some(byte) => Ok(Expr::Number(Number::from_u8(byte))),
-                None => Err(Error::new("index out of range")),
+        [Expr::HashTable(table), keys @ ..] => {
+            let mut removed = 0;
+            for key in keys {
+                if table.remove(key)? {
+                    removed += 1;
+                }
             }
+            Ok(Expr::Number(Number::from_usize(removed)))
         }
-        _ => Err(Error::new("expected bytevector")),
+        _ => Err(Error::new("expected hash table and keys")),
     }
 }
 
-/// Set byte at index to new value.
-pub fn bytevector_set(args: &[Expr], _: EnvRef) -> Result {
+/// Returns true if the hash table has an entry for the key.
+pub fn hash_table_contains(args: &[Expr], _: EnvRef) -> Result {
     match args {
-        [
-            Expr::ByteVector(bv),
-            Expr::Number(n_index),
-            Expr::Number(n_byte),
-        ] if n_index.is_usize() && n_byte.is_byte() => {
-            let index = n_index
-                .to_usize()
-                .expect("value should have been converted to usize");
-            let byte = n_byte
-                .to_u8()
-                .expect("value should have been converted to byte");
-            if index < bv.len() {
-                bv.set(index, byte)?;
-                return Ok(Expr::Void());
-            }
-            Err(Error::new("index out of range"))
+        [Expr::HashTable(table), key] => Ok(Expr::Boolean(table.get(key)?.is_some())),
+        _ => Err(Error::new("expected hash table and key")),
+    }
+}
+
+/// Return the number of entries in a hash table.
+pub fn hash_table_size(args: &[Expr], _: EnvRef) -> Result {
+    match args {
+        [Expr::HashTable(table)] => Ok(Expr::Number(Number::from_usize(table.len()))),
+        _ => Err(Error::new("expected hash table")),
+    }
+}
+
+/// Replace the value for a key with the result of calling a procedure on it.
+pub fn hash_table_update(args: &[Expr], env: EnvRef) -> Result {
+    match args {
+        [Expr::HashTable(table), key, update, rest @ ..] if rest.len() <= 2 => {
+            let value = hash_table_ref(&[&args[..2], rest].concat(), env.clone())?;
+            table.insert(key.clone(), call_procedure(update, vec![value], env)?)?;
+            Ok(Expr::Void())
         }
-        _ => Err(Error::new("expected bytevector")),
+        _ => Err(Error::new("expected hash table, key and procedure")),
     }
 }
 
-/// Return a newly allocated copy of a `ByteVector`
-pub fn bytevector_copy(args: &[Expr], _: EnvRef) -> Result {
+/// Replace the value for a key, starting from a default if the key is missing.
+pub fn hash_table_update_default(args: &[Expr], env: EnvRef) -> Result {
     match args {
-        [Expr::ByteVector(bv)] => Ok(Expr::ByteVector(bv.clone())),
-        [Expr::ByteVector(vec), Expr::Number(start)] => {
-            let start = start
-                .to_usize()
-                .ok_or_else(|| Error::new("invalid index, expected int or float"))?;
+        [Expr::HashTable(table), key, update, default] => {
+            let value = table.get(key)?.unwrap_or_else(|| default.clone());
+            table.insert(key.clone(), call_procedure(update, vec![value], env)?)?;
+            Ok(Expr::Void())
+        }
+        _ => Err(Error::new(
+            "expected hash table, key, procedure and default",
+        )),
+    }
+}
 
-            if start == vec.len() {
-                return Ok(Expr::ByteVector(ByteVector::new(0)));
+/// Call a procedure with the key and value of every entry.
+pub fn hash_table_walk(args: &[Expr], env: EnvRef) -> Result {
+    match args {
+        [Expr::HashTable(table), procedure] => {
+            for (key, value) in table.entries() {
+                call_procedure(procedure, vec![key, value], env.clone())?;
             }
+            Ok(Expr::Void())
+        }
+        _ => Err(Error::new("expected hash table and procedure")),
+    }
+}
 
-            match vec.sub_bytevector(start, vec.len()) {
-                Some(v) => Ok(Expr::ByteVector(v.clone())),
-                None => Err(Error::new("out of range")),
-            }
+/// Fold a procedure over the key and value of every entry.
+pub fn hash_table_fold(args: &[Expr], env: EnvRef) -> Result {
+    // Accept both the SRFI 69 and SRFI 125 argument orders.
+    let (table, kons, knil) = match args {
+        [Expr::HashTable(table), kons, knil] | [kons, knil, Expr::HashTable(table)] => {
+            (table, kons, knil)
         }
-        [
-            Expr::ByteVector(vec),
-            Expr::Number(start),
-            Expr::Number(end),
-        ] => {
-            let v_len = Number::from_usize(vec.len());
-            if *start == v_len && *end == v_len {
-                return Ok(Expr::Null);
-            }
-            let start = start
-                .to_usize()
-                .ok_or_else(|| Error::new("invalid index, expected int or float"))?;
-            let end = end
-                .to_usize()
-                .ok_or_else(|| Error::new("invalid index, expected int or float"))?;
-            match vec.sub_bytevector(start, end) {
-                Some(v) => Ok(Expr::ByteVector(v.clone())),
-                None => Err(Error::new("out of range")),
-            }
+        _ => {
+            return Err(Error::new(
+                "expected hash table, procedure and initial value",
+            ));
         }
-        _ => Err(Error::new("expected vector")),
+    };
+    let mut acc = knil.clone();
+    for (key, value) in table.entries() {
+        acc = call_procedure(kons, vec![key, value, acc], env.clone())?;
     }
+    Ok(acc)
 }
 
-/// Copy elements from one `ByteVector` into another using range indexes.
-pub fn bytevector_copy_from(args: &[Expr], _: EnvRef) -> Result {
+/// Return a list of the keys in a hash table.
+pub fn hash_table_keys(args: &[Expr], _: EnvRef) -> Result {
     match args {
-        [
-            Expr::ByteVector(dest),
-            Expr::Number(at),
-            Expr::ByteVector(from),
-        ] => {
-            let at = at
-                .to_usize()
-                .ok_or_else(|| Error::new("invalid index, expected int or float"))?;
+        [Expr::HashTable(table)] => {
+            let keys: Vec<Expr> = table.entries().into_iter().map(|(k, _)| k).collect();
+            Ok(Pair::list(&keys))
+        }
+        _ => Err(Error::new("expected hash table")),
+    }
+}
+
+/// Return a list of the values in a hash table.
+pub fn hash_table_values(args: &[Expr], _: EnvRef) -> Result {
+    match args {
+        [Expr::HashTable(table)] => {
+            let values: Vec<Expr> = table.entries().into_iter().map(|(_, v)| v).collect();
+            Ok(Pair::list(&values))
+        }
+        _ => Err(Error::new("expected hash table")),
+    }
+}
+
+/// Return an association list of the entries in a hash table.
+pub fn hash_table_to_alist(args: &[Expr], _: EnvRef) -> Result {
+    match args {
+        [Expr::HashTable(table)] => {
+            let entries: Vec<Expr> = table
+                .entries()
+                .into_iter()
+                .map(|(k, v)| Expr::Pair(Pair::cons((k, v))))
+                .collect();
+            Ok(Pair::list(&entries))
+        }
+        _ => Err(Error::new("expected hash table")),
+    }
+}
 
-            if at > from.len() {
-                return Err(Error::new("out of range"));
+/// Create a hash table from an association list. Earlier entries take precedence.
+pub fn alist_to_hash_table(args: &[Expr], env: EnvRef) -> Result {
+    match args {
+        [alist, options @ ..] => {
+            let Expr::HashTable(table) = make_hash_table(options, env)? else {
+                unreachable!("make-hash-table returns a hash table");
+            };
+            for entry in list_elements(alist)?.into_iter().rev() {
+                let Expr::Pair(entry) = entry else {
+                    return Err(Error::new("expected association list"));
+                };
+                table.insert(entry.car(), entry.cdr())?;
             }
+            Ok(Expr::HashTable(table))
+        }
+        _ => Err(Error::new("expected association list")),
+    }
+}
 
-            for (i, byte) in from.to_slice().iter().enumerate() {
-                dest.set(i + at, *byte)?;
-            }
+/// Return a new hash table with the same equivalence and entries.
+pub fn hash_table_copy(args: &[Expr], _: EnvRef) -> Result {
+    match args {
+        [Expr::HashTable(table)] | [Expr::HashTable(table), _] => Ok(Expr::HashTable(table.copy())),
+        _ => Err(Error::new("expected hash table")),
+    }
+}
 
+/// Remove every entry from a hash table.
+pub fn hash_table_clear(args: &[Expr], _: EnvRef) -> Result {
+    match args {
+        [Expr::HashTable(table)] => {
+            table.clear();
             Ok(Expr::Void())
         }
-        [
-            Expr::ByteVector(dest),
-            Expr::Number(at),
-            Expr::ByteVector(from),
-            Expr::Number(start),
-        ] => {
-            let at = at
-                .to_usize()
-                .ok_or_else(|| Error::new("invalid index, expected int or float"))?;
+        _ => Err(Error::new("expected hash table")),
+    }
+}
 
-            let start = start
-                .to_usize()
-                .ok_or_else(|| Error::new("invalid index, expected int or float"))?;
+/// Return a hash of any value that is consistent with `equal?`.
+pub fn equal_hash(args: &[Expr], _: EnvRef) -> Result {
+    match args {
+        [obj] | [obj, _] => bounded_hash(hash_table::equal_hash(obj), args.get(1)),
+        _ => Err(Error::new("expected object and optional bound")),
+    }
+}
 
-            if at > dest.len() || (dest.len() - at) < (from.len() - start) {
-                return Err(Error::new("out of range"));
-            }
+/// Return a hash of any value that is consistent with `eqv?`.
+pub fn eqv_hash(args: &[Expr], _: EnvRef) -> Result {
+    match args {
+        [obj] | [obj, _] => bounded_hash(hash_table::eqv_hash(obj), args.get(1)),
+        _ => Err(Error::new("expected object and optional bound")),
+    }
+}
 
-            let from_slice = from.to_slice();
-            for (i, byte) in from_slice[start..].iter().enumerate() {
-                dest.set(i + at, *byte)?;
-            }
+/// Return a hash of a string that is consistent with `string=?`.
+pub fn string_hash(args: &[Expr], _: EnvRef) -> Result {
+    match args {
+        [Expr::String(s)] | [Expr::String(s), _] => {
+            bounded_hash(hash_table::string_hash(&s.to_string()), args.get(1))
+        }
+        _ => Err(Error::new("expected string and optional bound")),
+    }
+}
 
-            Ok(Expr::Void())
+/// Map an equivalence procedure to the `Equivalence` it implements.
+fn table_equivalence(equivalence: &Expr) -> std::result::Result<Equivalence, Error> {
+    match equivalence {
+        Expr::Procedure(f) if std::ptr::fn_addr_eq(*f, are_eq as Procedure) => Ok(Equivalence::Eq),
+        Expr::Procedure(f) if std::ptr::fn_addr_eq(*f, are_eqv as Procedure) => {
+            Ok(Equivalence::Eqv)
         }
-        [
-            Expr::ByteVector(dest),
-            Expr::Number(at),
-            Expr::ByteVector(from),
-            Expr::Number(start),
-            Expr::Number(end),
-        ] => {
-            let at = at
-                .to_usize()
-                .ok_or_else(|| Error::new("invalid index, expected int or float"))?;
+        Expr::Procedure(f) if std::ptr::fn_addr_eq(*f, are_equal as Procedure) => {
+            Ok(Equivalence::Equal)
+        }
+        Expr::Procedure(f) if std::ptr::fn_addr_eq(*f, string_eq as Procedure) => {
+            Ok(Equivalence::String)
+        }
+        _ => Err(Error::new("expected eq?, eqv?, equal? or string=?")),
+    }
+}
 
-            let start = start
-                .to_usize()
-                .ok_or_else(|| Error::new("invalid index, expected int or float"))?;
+/// Convert a hash to a non-negative exact integer below the optional bound.
+fn bounded_hash(hash: u64, bound: Option<&Expr>) -> Result {
+    let hash = match bound {
+        None => hash & i64::MAX as u64,
+        Some(Expr::Number(Number::Int(n))) if !n.is_negative() && n.to_u64() != Some(0) => {
+            hash % n.to_u64().unwrap_or(u64::MAX)
+        }
+        Some(_) => return Err(Error::new("expected positive exact integer bound")),
+    };
+    Ok(Expr::Number(Number::from_u64(hash)))
+}
 
-            let end = end
-                .to_usize()
-                .ok_or_else(|| Error::new("invalid index, expected int or float"))?;
+// Lazy
 
-            if at > dest.len()
-                || end > from.len()
-                || start > from.len()
-                || (dest.len() - at) < (end - start)
-            {
-                return Err(Error::new("out of range"));
-            }
+/// Force a promise and return its value. Non-promise values are returned as is.
+pub fn force(args: &[Expr], _: EnvRef) -> Result {
+    match args {
+        [Expr::Promise(promise)] => loop {
+            let (expr, env, delay_force) = match promise.state() {
+                PromiseState::Done(value) => return Ok(value),
+                PromiseState::Delayed {
+                    expr,
+                    env,
+                    delay_force,
+                } => (expr, env, delay_force),
+            };
+            let value = parser::eval(&expr, env)?;
 
-            let from_slice = from.to_slice();
-            for (i, byte) in from_slice[start..end].iter().enumerate() {
-                dest.set(i + at, *byte)?;
+            // Forcing the expression may have forced this promise already.
+            if promise.is_done() {
+                continue;
+            }
+            match value {
+                Expr::Promise(next) if delay_force => promise.update_from(&next),
+                _ if delay_force => return Err(Error::new("delay-force expects a promise")),
+                value => promise.set_value(value),
             }
+        },
+        [value] => Ok(value.clone()),
+        _ => Err(Error::Message(format!(
+            "expected 1 argument, got {}",
+            args.len()
+        ))),
+    }
+}
 
-            Ok(Expr::Void())
-        }
-        _ => Err(Error::new("expected a bytevector, index, and bytevector")),
+/// Return a promise that is already forced to a value. Promises are returned as is.
+pub fn make_promise(args: &[Expr], _: EnvRef) -> Result {
+    match args {
+        [promise @ Expr::Promise(_)] => Ok(promise.clone()),
+        [value] => Ok(Expr::Promise(Promise::done(value.clone()))),
+        _ => Err(Error::Message(format!(
+            "expected 1 argument, got {}",
+            args.len()
+        ))),
     }
 }
 
-/// Return a newly allocated `ByteVector` created from concatenating 2 `ByteVector`.
-pub fn bytevector_append(args: &[Expr], _: EnvRef) -> Result {
+/// Returns true if arg is a promise.
+pub fn is_promise(args: &[Expr], _: EnvRef) -> Result {
     match args {
-        [Expr::ByteVector(a), Expr::ByteVector(b)] => {
-            let new_slice = [a.to_slice(), b.to_slice()];
-            let bytevector = ByteVector::from(new_slice.concat().as_slice());
-            Ok(Expr::ByteVector(bytevector))
-        }
-        _ => Err(Error::new("expected 2 bytevectors")),
+        [Expr::Promise(_)] => Ok(Expr::Boolean(true)),
+        [_] => Ok(Expr::Boolean(false)),
+        _ => Err(Error::Message(format!(
+            "expected 1 argument, got {}",
+            args.len()
+        ))),
     }
 }
 
 // Ports
 
 /// Open textual input file `Port`.
+/// Accepts optional `'encoding`, `'eol` and `'errors` options, which default to UTF-8.
 pub fn open_input_file(args: &[Expr], _: EnvRef) -> Result {
     match args {
-        [Expr::String(path)] => Ok(Expr::Port(Port::text_input_file(path)?)),
+        [Expr::String(path), options @ ..] => {
+            let transcoder = transcoder_options(options, Transcoder::default())?;
+            Ok(Expr::Port(Port::text_input_file(
+                &path.to_string(),
+                transcoder,
+            )?))
+        }
         _ => Err(Error::new("expected file path string")),
     }
 }
 
 /// Open textual output file `Port`.
+/// Accepts optional `'encoding`, `'eol` and `'errors` options, which default to UTF-8.
 pub fn open_output_file(args: &[Expr], _: EnvRef) -> Result {
     match args {
-        [Expr::String(path)] => Ok(Expr::Port(Port::text_output_file(path)?)),
+        [Expr::String(path), options @ ..] => {
+            let transcoder = transcoder_options(options, Transcoder::default())?;
+            Ok(Expr::Port(Port::text_output_file(
+                &path.to_string(),
+                transcoder,
+            )?))
+        }
         _ => Err(Error::new("expected file path string")),
     }
 }
 
+/// Return a textual `Port` that decodes or encodes a binary port with a codec.
+/// Accepts optional `'eol` and `'errors` options.
+pub fn transcoded_port(args: &[Expr], _: EnvRef) -> Result {
+    match args {
+        [Expr::Port(port), Expr::Symbol(codec), options @ ..] if port.is_binary() => {
+            let transcoder = Transcoder {
+                codec: Codec::from_name(&codec.name())?,
+                ..Transcoder::default()
+            };
+            let transcoder = transcoder_options(options, transcoder)?;
+            Ok(Expr::Port(port.transcoded(transcoder)?))
+        }
+        _ => Err(Error::new(
+            "expected binary port, codec and optional options",
+        )),
+    }
+}
+
+/// Apply `'encoding`, `'eol` and `'errors` option pairs to a `Transcoder`.
+fn transcoder_options(
+    options: &[Expr],
+    mut transcoder: Transcoder,
+) -> std::result::Result<Transcoder, Error> {
+    if !options.len().is_multiple_of(2) {
+        return Err(Error::new("expected option name and value pairs"));
+    }
+    for option in options.chunks(2) {
+        let [Expr::Symbol(name), Expr::Symbol(value)] = option else {
+            return Err(Error::new("expected option name and value symbols"));
+        };
+        let value = value.name();
+        match &*name.name() {
+            "encoding" => transcoder.codec = Codec::from_name(&value)?,
+            "eol" => transcoder.eol_style = EolStyle::from_name(&value)?,
+            "errors" => transcoder.error_mode = ErrorMode::from_name(&value)?,
+            _ => return Err(Error::Message(format!("unknown port option: {}", name))),
+        }
+    }
+    Ok(transcoder)
+}
+
+/// Return true if a port supports `port-position`.
+pub fn port_has_port_position(args: &[Expr], _: EnvRef) -> Result {
+    match args {
+        [Expr::Port(port)] => Ok(Expr::Boolean(port.has_position()?)),
+        _ => Err(Error::new("expected port")),
+    }
+}
+
+/// Return true if a port supports `set-port-position!`.
+pub fn port_has_set_port_position(args: &[Expr], _: EnvRef) -> Result {
+    match args {
+        [Expr::Port(port)] => Ok(Expr::Boolean(port.has_set_position()?)),
+        _ => Err(Error::new("expected port")),
+    }
+}
+
+/// Return the position of a port that supports it.
+pub fn port_position(args: &[Expr], _: EnvRef) -> Result {
+    match args {
+        [Expr::Port(port)] => Ok(Expr::Number(Number::from_usize(port.position()?))),
+        _ => Err(Error::new("expected port")),
+    }
+}
+
+/// Move a port that supports it to a position.
+pub fn set_port_position(args: &[Expr], _: EnvRef) -> Result {
+    match args {
+        [Expr::Port(port), Expr::Number(n)] if n.is_exact() && !n.is_negative() => {
+            let position = n.to_usize().ok_or_else(|| Error::new("invalid position"))?;
+            port.set_position(position)?;
+            Ok(Expr::Void())
+        }
+        _ => Err(Error::new("expected port and exact non-negative integer")),
+    }
+}
+
+/// Return a textual input `Port` whose chars come from a `read!` procedure.
+/// Takes an ID, `read!`, and `get-position`, `set-position!` and `close` procedures or `#f`.
+pub fn make_custom_textual_input_port(args: &[Expr], env: EnvRef) -> Result {
+    Ok(Expr::Port(Port::custom_textual_input(custom_port(
+        args, env,
+    )?)))
+}
+
+/// Return a textual output `Port` whose chars go to a `write!` procedure.
+pub fn make_custom_textual_output_port(args: &[Expr], env: EnvRef) -> Result {
+    Ok(Expr::Port(Port::custom_textual_output(custom_port(
+        args, env,
+    )?)))
+}
+
+/// Return a binary input `Port` whose bytes come from a `read!` procedure.
+pub fn make_custom_binary_input_port(args: &[Expr], env: EnvRef) -> Result {
+    Ok(Expr::Port(Port::custom_binary_input(custom_port(
+        args, env,
+    )?)))
+}
+
+/// Return a binary output `Port` whose bytes go to a `write!` procedure.
+pub fn make_custom_binary_output_port(args: &[Expr], env: EnvRef) -> Result {
+    Ok(Expr::Port(Port::custom_binary_output(custom_port(
+        args, env,
+    )?)))
+}
+
+/// Create a `CustomPort` from an ID, a transfer procedure and three optional procedures.
+fn custom_port<T: CustomItem>(
+    args: &[Expr],
+    env: EnvRef,
+) -> std::result::Result<CustomPort<T>, Error> {
+    let [
+        Expr::String(id),
+        transfer @ (Expr::Procedure(_) | Expr::Closure(_)),
+        get_position,
+        set_position,
+        close,
+    ] = args
+    else {
+        return Err(Error::new("expected id, procedure and 3 procedures or #f"));
+    };
+    let optional = |expr: &Expr| match expr {
+        Expr::Boolean(false) => Ok(None),
+        Expr::Procedure(_) | Expr::Closure(_) => Ok(Some(expr.clone())),
+        _ => Err(Error::new("expected procedure or #f")),
+    };
+    Ok(CustomPort::new(
+        id.to_string(),
+        transfer.clone(),
+        optional(get_position)?,
+        optional(set_position)?,
+        optional(close)?,
+        env,
+    ))
+}
+
 /// Open textual string input `Port`.
 pub fn open_input_string(args: &[Expr], _: EnvRef) -> Result {
     match args {
-        [Expr::String(s)] => Ok(Expr::Port(Port::text_input_string(s.clone()))),
+        [Expr::String(s)] => Ok(Expr::Port(Port::text_input_string(s.to_string()))),
         _ => Err(Error::new("expected string")),
     }
 }
@@ -1210,8 +5611,8 @@ pub fn open_output_string(args: &[Expr], _: EnvRef) -> Result {
 /// Get string output of `Port`.
 pub fn get_output_string(args: &[Expr], _: EnvRef) -> Result {
     match args {
-        [Expr::Port(Port::TextOutput(p))] => match p.borrow().get_output_string() {
-            Some(s) => Ok(Expr::String(s.to_string())),
+        [Expr::Port(Port::TextOutput(p))] => match borrow_port(p)?.get_output_string() {
+            Some(s) => Ok(Expr::String(SchemeString::new(s))),
             None => Err(Error::new("not a string output port")),
         },
         _ => Err(Error::new("expected string output port")),
@@ -1221,7 +5622,7 @@ pub fn get_output_string(args: &[Expr], _: EnvRef) -> Result {
 /// Open binary input file `Port`.
 pub fn open_binary_input_file(args: &[Expr], _: EnvRef) -> Result {
     match args {
-        [Expr::String(path)] => Ok(Expr::Port(Port::binary_input_file(path)?)),
+        [Expr::String(path)] => Ok(Expr::Port(Port::binary_input_file(&path.to_string())?)),
         _ => Err(Error::new("expected file path string")),
     }
 }
@@ -1229,7 +5630,7 @@ pub fn open_binary_input_file(args: &[Expr], _: EnvRef) -> Result {
 /// Open binary output file `Port`.
 pub fn open_binary_output_file(args: &[Expr], _: EnvRef) -> Result {
     match args {
-        [Expr::String(path)] => Ok(Expr::Port(Port::binary_output_file(path)?)),
+        [Expr::String(path)] => Ok(Expr::Port(Port::binary_output_file(&path.to_string())?)),
         _ => Err(Error::new("expected file path string")),
     }
 }
@@ -1253,7 +5654,7 @@ pub fn open_output_bytevector(args: &[Expr], _: EnvRef) -> Result {
 /// Return `ByteVector` from bytes read in output port.
 pub fn get_output_bytevector(args: &[Expr], _: EnvRef) -> Result {
     match args {
-        [Expr::Port(Port::BinaryOutput(output))] => match output.borrow().deref() {
+        [Expr::Port(Port::BinaryOutput(output))] => match borrow_port(output)?.deref() {
             BinaryOutputPort::ByteVector(bv) => {
                 let new_byte_vec = match bv.get_bytes() {
                     Some(b) => b,
@@ -1273,13 +5674,35 @@ pub fn get_output_bytevector(args: &[Expr], _: EnvRef) -> Result {
 pub fn close_port(args: &[Expr], _: EnvRef) -> Result {
     match args {
         [Expr::Port(port)] => {
-            port.close();
+            port.close()?;
             Ok(Expr::Void())
         }
         _ => Err(Error::new("expected port")),
     }
 }
 
+/// Close input `Port`.
+pub fn close_input_port(args: &[Expr], _: EnvRef) -> Result {
+    match args {
+        [Expr::Port(port)] if port.is_input() => {
+            port.close()?;
+            Ok(Expr::Void())
+        }
+        _ => Err(Error::new("expected input port")),
+    }
+}
+
+/// Close output `Port`.
+pub fn close_output_port(args: &[Expr], _: EnvRef) -> Result {
+    match args {
+        [Expr::Port(port)] if port.is_output() => {
+            port.close()?;
+            Ok(Expr::Void())
+        }
+        _ => Err(Error::new("expected output port")),
+    }
+}
+
 // Ports input
 
 /// Read a char from a `Port`.
@@ -1293,7 +5716,7 @@ pub fn read_char(args: &[Expr], env: EnvRef) -> Result {
                 .ok_or_else(|| Error::new("current-input-port is not initialized"))?;
 
             if let Expr::Port(Port::TextInput(port)) = port {
-                let mut port = port.borrow_mut();
+                let mut port = borrow_port_mut(&port)?;
                 return match port.read_char()? {
                     Some(c) => Ok(Expr::Char(c)),
                     None => Ok(Expr::Eof),
@@ -1303,7 +5726,7 @@ pub fn read_char(args: &[Expr], env: EnvRef) -> Result {
             Err(Error::new("expected textual input port"))
         }
         [Expr::Port(Port::TextInput(port_ref))] => {
-            let mut port = port_ref.borrow_mut();
+            let mut port = borrow_port_mut(port_ref)?;
             match port.read_char()? {
                 Some(c) => Ok(Expr::Char(c)),
                 None => Ok(Expr::Eof),
@@ -1324,7 +5747,7 @@ pub fn peek_char(args: &[Expr], env: EnvRef) -> Result {
                 .ok_or_else(|| Error::new("current-input-port is not initialized"))?;
 
             if let Expr::Port(Port::TextInput(port)) = port {
-                let mut port = port.borrow_mut();
+                let mut port = borrow_port_mut(&port)?;
                 return match port.peek_char()? {
                     Some(c) => Ok(Expr::Char(c)),
                     None => Ok(Expr::Eof),
@@ -1334,7 +5757,7 @@ pub fn peek_char(args: &[Expr], env: EnvRef) -> Result {
             Err(Error::new("expected textual input port"))
         }
         [Expr::Port(Port::TextInput(port_ref))] => {
-            let mut port = port_ref.borrow_mut();
+            let mut port = borrow_port_mut(port_ref)?;
             match port.peek_char()? {
                 Some(c) => Ok(Expr::Char(c)),
                 None => Ok(Expr::Eof),
@@ -1355,9 +5778,9 @@ pub fn read_string(args: &[Expr], env: EnvRef) -> Result {
                 .ok_or_else(|| Error::new("current-input-port is not initialized"))?;
 
             if let Expr::Port(Port::TextInput(port)) = port {
-                let mut port = port.borrow_mut();
+                let mut port = borrow_port_mut(&port)?;
                 return match port.read_string()? {
-                    Some(s) => Ok(Expr::String(s)),
+                    Some(s) => Ok(Expr::String(SchemeString::new(&s))),
                     None => Ok(Expr::Eof),
                 };
             }
@@ -1365,9 +5788,9 @@ pub fn read_string(args: &[Expr], env: EnvRef) -> Result {
             Err(Error::new("expected textual input port"))
         }
         [Expr::Port(Port::TextInput(port_ref))] => {
-            let mut port = port_ref.borrow_mut();
+            let mut port = borrow_port_mut(port_ref)?;
             return match port.read_string()? {
-                Some(s) => Ok(Expr::String(s)),
+                Some(s) => Ok(Expr::String(SchemeString::new(&s))),
                 None => Ok(Expr::Eof),
             };
         }
@@ -1386,16 +5809,16 @@ pub fn read_line(args: &[Expr], env: EnvRef) -> Result {
                 .ok_or_else(|| Error::new("current-input-port is not initialized"))?;
 
             if let Expr::Port(Port::TextInput(port)) = port {
-                return match port.borrow_mut().read_line()? {
-                    Some(line) => Ok(Expr::String(line)),
+                return match borrow_port_mut(&port)?.read_line()? {
+                    Some(line) => Ok(Expr::String(SchemeString::new(&line))),
                     None => Ok(Expr::Eof),
                 };
             }
 
             Err(Error::new("expected textual input port"))
         }
-        [Expr::Port(Port::TextInput(port))] => match port.borrow_mut().read_line()? {
-            Some(line) => Ok(Expr::String(line)),
+        [Expr::Port(Port::TextInput(port))] => match borrow_port_mut(port)?.read_line()? {
+            Some(line) => Ok(Expr::String(SchemeString::new(&line))),
             None => Ok(Expr::Eof),
         },
         _ => Err(Error::new("expected textual input port")),
@@ -1413,7 +5836,7 @@ pub fn read_u8(args: &[Expr], env: EnvRef) -> Result {
                 .ok_or_else(|| Error::new("current-input-port is not initialized"))?;
 
             if let Expr::Port(Port::BinaryInput(port)) = input_port {
-                let mut port = port.borrow_mut();
+                let mut port = borrow_port_mut(&port)?;
                 return match port.read_byte()? {
                     Some(byte) => Ok(Expr::Number(Number::from_u8(byte))),
                     None => Ok(Expr::Eof),
@@ -1423,7 +5846,7 @@ pub fn read_u8(args: &[Expr], env: EnvRef) -> Result {
             Err(Error::new("expected binary input port"))
         }
         [Expr::Port(Port::BinaryInput(port_ref))] => {
-            let mut port = port_ref.borrow_mut();
+            let mut port = borrow_port_mut(port_ref)?;
             match port.read_byte() {
                 Ok(Some(byte)) => Ok(Expr::Number(Number::from_u8(byte))),
                 Ok(None) => Ok(Expr::Eof),
@@ -1445,7 +5868,7 @@ pub fn peek_u8(args: &[Expr], env: EnvRef) -> Result {
                 .ok_or_else(|| Error::new("current-input-port is not initialized"))?;
 
             if let Expr::Port(Port::BinaryInput(port)) = input_port {
-                let mut port = port.borrow_mut();
+                let mut port = borrow_port_mut(&port)?;
                 return match port.peek_byte()? {
                     Some(byte) => Ok(Expr::Number(Number::from_u8(byte))),
                     None => Ok(Expr::Eof),
@@ -1455,7 +5878,7 @@ pub fn peek_u8(args: &[Expr], env: EnvRef) -> Result {
             Err(Error::new("expected binary input port"))
         }
         [Expr::Port(Port::BinaryInput(port_ref))] => {
-            let mut port = port_ref.borrow_mut();
+            let mut port = borrow_port_mut(port_ref)?;
             match port.peek_byte()? {
                 Some(byte) => Ok(Expr::Number(Number::from_u8(byte))),
                 None => Ok(Expr::Eof),
@@ -1476,7 +5899,7 @@ pub fn read_bytevector(args: &[Expr], env: EnvRef) -> Result {
                 .ok_or_else(|| Error::new("current-input-port is not initialized"))?;
 
             if let Expr::Port(Port::BinaryInput(port)) = input_port {
-                let mut port = port.borrow_mut();
+                let mut port = borrow_port_mut(&port)?;
                 return match port.read_bytevector(None)? {
                     Some(bv) => Ok(Expr::ByteVector(bv)),
                     None => Ok(Expr::Eof),
@@ -1486,7 +5909,7 @@ pub fn read_bytevector(args: &[Expr], env: EnvRef) -> Result {
             Err(Error::new("expected binary input port"))
         }
         [Expr::Port(Port::BinaryInput(port_ref))] => {
-            let mut port = port_ref.borrow_mut();
+            let mut port = borrow_port_mut(port_ref)?;
             match port.read_bytevector(None)? {
                 Some(bv) => Ok(Expr::ByteVector(bv)),
                 None => Ok(Expr::Eof),
@@ -1538,7 +5961,7 @@ pub fn read_into_bytevector(args: &[Expr], env: EnvRef) -> Result {
     };
 
     // Copy bytes from port into bytevector.
-    if let Some(input_vec) = port.borrow_mut().read_bytevector(Some(bv.len()))? {
+    if let Some(input_vec) = borrow_port_mut(port)?.read_bytevector(Some(bv.len()))? {
         let bytes_read = bv.copy_into(input_vec, start, end)?;
         return Ok(Expr::Number(Number::from_usize(bytes_read)));
     }
@@ -1558,7 +5981,7 @@ pub fn write_char(args: &[Expr], env: EnvRef) -> Result {
                 .ok_or_else(|| Error::new("current-input-port is not initialized"))?;
 
             if let Expr::Port(Port::TextOutput(port)) = port {
-                let mut port = port.borrow_mut();
+                let mut port = borrow_port_mut(&port)?;
                 port.write_char(*c)?;
                 return Ok(Expr::Void());
             }
@@ -1566,7 +5989,7 @@ pub fn write_char(args: &[Expr], env: EnvRef) -> Result {
             Err(Error::new("expected textual input port"))
         }
         [Expr::Char(ch), Expr::Port(Port::TextOutput(port_ref))] => {
-            let mut port = port_ref.borrow_mut();
+            let mut port = borrow_port_mut(port_ref)?;
             port.write_char(*ch)?;
             port.flush().map_err(|e| e)?;
             Ok(Expr::Void())
@@ -1586,16 +6009,16 @@ pub fn write_string(args: &[Expr], env: EnvRef) -> Result {
                 .ok_or_else(|| Error::new("current-input-port is not initialized"))?;
 
             if let Expr::Port(Port::TextOutput(port)) = port {
-                let mut port = port.borrow_mut();
-                port.write_string(s)?;
+                let mut port = borrow_port_mut(&port)?;
+                port.write_string(&s.to_string())?;
                 return Ok(Expr::Void());
             }
 
             Err(Error::new("expected textual input port"))
         }
         [Expr::String(s), Expr::Port(Port::TextOutput(input))] => {
-            let mut port = input.borrow_mut();
-            port.write_string(s)?;
+            let mut port = borrow_port_mut(input)?;
+            port.write_string(&s.to_string())?;
             Ok(Expr::Void())
         }
         [
@@ -1605,12 +6028,12 @@ pub fn write_string(args: &[Expr], env: EnvRef) -> Result {
         ] => {
             let start = start
                 .to_usize()
-                .ok_or_else(|| Error::new("invalid index, expected int or float"))?;
+                .ok_or_else(|| Error::new("invalid index, expected non-negative exact integer"))?;
             if start >= s.len() {
                 return Err(Error::new("index out of range"));
             }
-            let mut port = input.borrow_mut();
-            port.write_string(&s[start..])?;
+            let mut port = borrow_port_mut(input)?;
+            port.write_string(&s.chars()[start..].iter().collect::<String>())?;
             Ok(Expr::Void())
         }
         [
@@ -1621,15 +6044,15 @@ pub fn write_string(args: &[Expr], env: EnvRef) -> Result {
         ] => {
             let start = start
                 .to_usize()
-                .ok_or_else(|| Error::new("invalid index, expected int or float"))?;
+                .ok_or_else(|| Error::new("invalid index, expected non-negative exact integer"))?;
             let end = end
                 .to_usize()
-                .ok_or_else(|| Error::new("invalid index, expected int or float"))?;
+                .ok_or_else(|| Error::new("invalid index, expected non-negative exact integer"))?;
             if start > end || start >= s.len() || end >= s.len() {
                 return Err(Error::new("index out of range"));
             }
-            let mut port = input.borrow_mut();
-            port.write_string(&s[start..end + 1])?;
+            let mut port = borrow_port_mut(input)?;
+            port.write_string(&s.chars()[start..end + 1].iter().collect::<String>())?;
             Ok(Expr::Void())
         }
         _ => Err(Error::new("expected string and text output port")),
@@ -1650,7 +6073,7 @@ pub fn write_u8(args: &[Expr], env: EnvRef) -> Result {
                 .ok_or_else(|| Error::new("unable to convert num to byte"))?;
 
             if let Expr::Port(Port::BinaryOutput(port)) = port {
-                let mut port = port.borrow_mut();
+                let mut port = borrow_port_mut(&port)?;
                 port.write_byte(byte)?;
                 return Ok(Expr::Void());
             }
@@ -1658,7 +6081,7 @@ pub fn write_u8(args: &[Expr], env: EnvRef) -> Result {
             Err(Error::new("expected textual input port"))
         }
         [Expr::Number(byte), Expr::Port(Port::BinaryOutput(port_ref))] => {
-            let mut port = port_ref.borrow_mut();
+            let mut port = borrow_port_mut(port_ref)?;
             let byte = byte
                 .to_u8()
                 .ok_or_else(|| Error::new("unable to convert num to byte"))?;
@@ -1682,14 +6105,14 @@ pub fn write(args: &[Expr], env: EnvRef) -> Result {
                 .ok_or_else(|| Error::new("current-output-port is not initialized"))?;
 
             if let Expr::Port(Port::TextOutput(port)) = port {
-                let mut port = port.borrow_mut();
+                let mut port = borrow_port_mut(&port)?;
                 port.write_string(&expr.with_cycle_labels())?;
                 return Ok(Expr::Void());
             }
             Err(Error::new("current-output-port is not initialized"))
         }
         [expr, Expr::Port(Port::TextOutput(port))] => {
-            let mut port = port.borrow_mut();
+            let mut port = borrow_port_mut(port)?;
             port.write_string(&expr.with_cycle_labels())?;
             Ok(Expr::Void())
         }
@@ -1708,14 +6131,14 @@ pub fn write_simple(args: &[Expr], env: EnvRef) -> Result {
                 .ok_or_else(|| Error::new("current-output-port is not initialized"))?;
 
             if let Expr::Port(Port::TextOutput(port)) = port {
-                let mut port = port.borrow_mut();
+                let mut port = borrow_port_mut(&port)?;
                 port.write_string(&expr.to_string())?;
                 return Ok(Expr::Void());
             }
             Err(Error::new("current-output-port is not initialized"))
         }
         [expr, Expr::Port(Port::TextOutput(port))] => {
-            let mut port = port.borrow_mut();
+            let mut port = borrow_port_mut(port)?;
             port.write_string(&expr.to_string())?;
             Ok(Expr::Void())
         }
@@ -1735,14 +6158,14 @@ pub fn write_shared(args: &[Expr], env: EnvRef) -> Result {
                 .ok_or_else(|| Error::new("current-output-port is not initialized"))?;
 
             if let Expr::Port(Port::TextOutput(port)) = port {
-                let mut port = port.borrow_mut();
+                let mut port = borrow_port_mut(&port)?;
                 port.write_string(&expr.with_datum_labels())?;
                 return Ok(Expr::Void());
             }
             Err(Error::new("current-output-port is not initialized"))
         }
         [expr, Expr::Port(Port::TextOutput(port))] => {
-            let mut port = port.borrow_mut();
+            let mut port = borrow_port_mut(port)?;
             port.write_string(&expr.with_datum_labels())?;
             return Ok(Expr::Void());
         }
@@ -1807,6 +6230,20 @@ pub fn call_with_output_file(args: &[Expr], env: EnvRef) -> Result {
     }
 }
 
+/// Apply a `Procedure` or `Closure` to `args`.
+pub(crate) fn call_procedure(procedure: &Expr, args: Vec<Expr>, env: EnvRef) -> Result {
+    match procedure {
+        Expr::Procedure(f) => f(&args, env),
+        Expr::Closure(c) => apply_lambda(c, args),
+        _ => Err(Error::new("expected a procedure")),
+    }
+}
+
+/// Return false only for `#f`; every other value counts as true.
+fn is_true(expr: &Expr) -> bool {
+    !matches!(expr, Expr::Boolean(false))
+}
+
 // Helper function to evaluate thunks (either in lambda or `Procedure` form)
 fn call_thunk(thunk: &Expr, env: EnvRef) -> Result {
     match thunk {
@@ -1873,11 +6310,11 @@ pub fn eof_object(_: &[Expr], _: EnvRef) -> Result {
 /// Evaluate the contents of a file.
 pub fn load_file(args: &[Expr], env: EnvRef) -> Result {
     let file = match args.first() {
-        Some(Expr::String(f)) => f,
+        Some(Expr::String(f)) => f.to_string(),
         _ => return Err(Error::new("expected a string path")),
     };
 
-    let expressions = io::file_input(file.to_owned());
+    let expressions = io::file_input(file);
     io::process_file_input(expressions, env);
 
     Ok(Expr::Void())
@@ -1887,7 +6324,7 @@ pub fn load_file(args: &[Expr], env: EnvRef) -> Result {
 pub fn delete_file(args: &[Expr], _: EnvRef) -> Result {
     match args {
         [Expr::String(file_name)] => {
-            fs::remove_file(file_name)
+            fs::remove_file(file_name.to_string())
                 .map_err(|e| Error::new(&format!("unable to delete file: {}", e)))?;
             Ok(Expr::Void())
         }
@@ -1900,15 +6337,23 @@ pub fn delete_file(args: &[Expr], _: EnvRef) -> Result {
 /// Convert a `Number` into a `String`.
 pub fn num_to_string(args: &[Expr], _: EnvRef) -> Result {
     match args {
-        [Expr::Number(num)] => Ok(Expr::String(String::from(num.to_string()))),
-        _ => Err(Error::new("expected string")),
+        [Expr::Number(num)] => Ok(Expr::String(SchemeString::new(&num.to_string()))),
+        [Expr::Number(num), Expr::Number(radix)] => {
+            let radix = radix
+                .to_u32()
+                .ok_or(Error::new("radix must be 2, 8, 10 or 16"))?;
+            Ok(Expr::String(SchemeString::new(
+                &num.to_string_radix(radix)?,
+            )))
+        }
+        _ => Err(Error::new("expected number and optional radix")),
     }
 }
 
 /// Convert a `String` into a `Number`.
 pub fn string_to_num(args: &[Expr], _: EnvRef) -> Result {
     match args {
-        [Expr::String(num_str)] => match Number::from_token(&num_str) {
+        [Expr::String(num_str)] => match Number::from_token(&num_str.to_string()) {
             Ok(n) => Ok(Expr::Number(n)),
             Err(e) => Err(e),
         },
@@ -1919,41 +6364,41 @@ pub fn string_to_num(args: &[Expr], _: EnvRef) -> Result {
 /// Convert a `String` into a `Symbol`.
 pub fn string_to_symbol(args: &[Expr], _: EnvRef) -> Result {
     match args {
-        [Expr::String(s)] => Ok(Expr::Symbol(s.to_owned())),
+        [Expr::String(s)] => Ok(Expr::Symbol(Symbol::intern(&s.to_string()))),
+        _ => Err(Error::new("expected string")),
+    }
+}
+
+/// Convert a `String` into a `Symbol` that is distinct from every other symbol.
+pub fn string_to_uninterned_symbol(args: &[Expr], _: EnvRef) -> Result {
+    match args {
+        [Expr::String(s)] => Ok(Expr::Symbol(Symbol::uninterned(&s.to_string()))),
         _ => Err(Error::new("expected string")),
     }
 }
 
+/// Create a fresh uninterned `Symbol`, named with an optional string or symbol prefix.
+pub fn generate_uninterned_symbol(args: &[Expr], _: EnvRef) -> Result {
+    let prefix = match args {
+        [] => "g".to_string(),
+        [Expr::String(s)] => s.to_string(),
+        [Expr::Symbol(s)] => s.to_string(),
+        _ => return Err(Error::new("expected optional string or symbol prefix")),
+    };
+    Ok(Expr::Symbol(Symbol::gensym(&prefix)))
+}
+
 /// Convert a `String` into a `Pair` list.
 pub fn string_to_list(args: &[Expr], _: EnvRef) -> Result {
     match args {
-        [Expr::String(s)] => {
-            let chars: Vec<Expr> = s.chars().map(|c| Expr::Char(c)).collect::<Vec<Expr>>();
+        [Expr::String(s), range @ ..] => {
+            let chars: Vec<Expr> = string_range(s, range)?
+                .chars()
+                .into_iter()
+                .map(Expr::Char)
+                .collect();
             Ok(Pair::list(chars.as_slice()))
         }
-        [Expr::String(s), Expr::Number(start)] => {
-            let start = start
-                .to_usize()
-                .ok_or_else(|| Error::new("invalid index, expected int or float"))?;
-            if start < s.len() {
-                let chars: Vec<Expr> = s[start..].chars().map(|c| Expr::Char(c)).collect();
-                return Ok(Pair::list(chars.as_slice()));
-            }
-            Err(Error::new("out of range"))
-        }
-        [Expr::String(s), Expr::Number(start), Expr::Number(end)] => {
-            let start = start
-                .to_usize()
-                .ok_or_else(|| Error::new("invalid index, expected int or float"))?;
-            let end = end
-                .to_usize()
-                .ok_or_else(|| Error::new("invalid index, expected int or float"))?;
-            if start < s.len() && end < s.len() {
-                let chars: Vec<Expr> = s[start..end].chars().map(|c| Expr::Char(c)).collect();
-                return Ok(Pair::list(chars.as_slice()));
-            }
-            Err(Error::new("out of range"))
-        }
         _ => Err(Error::new("expected string")),
     }
 }
@@ -1961,29 +6406,13 @@ pub fn string_to_list(args: &[Expr], _: EnvRef) -> Result {
 /// Convert a `String` into a `Vector`.
 pub fn string_to_vector(args: &[Expr], _: EnvRef) -> Result {
     match args {
-        [Expr::String(s)] => Ok(Expr::Vector(Vector::from_string(s.clone()))),
-        [Expr::String(s), Expr::Number(start)] => {
-            let start = start
-                .to_usize()
-                .ok_or_else(|| Error::new("invalid index, expected int or float"))?;
-            if start < s.len() {
-                let chars: Vec<Expr> = s[start..].chars().map(|c| Expr::Char(c)).collect();
-                return Ok(Expr::Vector(Vector::from(chars.as_slice())));
-            }
-            Err(Error::new("out of range"))
-        }
-        [Expr::String(s), Expr::Number(start), Expr::Number(end)] => {
-            let start = start
-                .to_usize()
-                .ok_or_else(|| Error::new("invalid index, expected int or float"))?;
-            let end = end
-                .to_usize()
-                .ok_or_else(|| Error::new("invalid index, expected int or float"))?;
-            if start < s.len() && end < s.len() {
-                let chars: Vec<Expr> = s[start..end].chars().map(|c| Expr::Char(c)).collect();
-                return Ok(Expr::Vector(Vector::from(chars.as_slice())));
-            }
-            Err(Error::new("out of range"))
+        [Expr::String(s), range @ ..] => {
+            let chars: Vec<Expr> = string_range(s, range)?
+                .chars()
+                .into_iter()
+                .map(Expr::Char)
+                .collect();
+            Ok(Expr::Vector(Vector::from(chars.as_slice())))
         }
         _ => Err(Error::new("expected string")),
     }
@@ -1992,32 +6421,9 @@ pub fn string_to_vector(args: &[Expr], _: EnvRef) -> Result {
 /// Convert a `String` into a `ByteVector`.
 pub fn string_to_utf8(args: &[Expr], _: EnvRef) -> Result {
     match args {
-        [Expr::String(s)] => Ok(Expr::ByteVector(ByteVector::from_string(s.clone()))),
-        [Expr::String(s), Expr::Number(start)] => {
-            let start = start
-                .to_usize()
-                .ok_or_else(|| Error::new("invalid index, expected int or float"))?;
-            if start < s.len() {
-                return Ok(Expr::ByteVector(ByteVector::from_string(
-                    s[start..].to_string(),
-                )));
-            }
-            Err(Error::new("out of range"))
-        }
-        [Expr::String(s), Expr::Number(start), Expr::Number(end)] => {
-            let start = start
-                .to_usize()
-                .ok_or_else(|| Error::new("invalid index, expected int or float"))?;
-            let end = end
-                .to_usize()
-                .ok_or_else(|| Error::new("invalid index, expected int or float"))?;
-            let len = s.len();
-            if start < len && end < len {
-                return Ok(Expr::ByteVector(ByteVector::from_string(
-                    s[start..end].to_string(),
-                )));
-            }
-            Err(Error::new("out of range"))
+        [Expr::String(s), range @ ..] => {
+            let s = string_range(s, range)?.to_string();
+            Ok(Expr::ByteVector(ByteVector::from(s.as_bytes())))
         }
         _ => Err(Error::new("expected string")),
     }
@@ -2026,7 +6432,7 @@ pub fn string_to_utf8(args: &[Expr], _: EnvRef) -> Result {
 /// Convert a `String` into a `Symbol`.
 pub fn symbol_to_string(args: &[Expr], _: EnvRef) -> Result {
     match args {
-        [Expr::Symbol(s)] => Ok(Expr::String(s.to_owned())),
+        [Expr::Symbol(s)] => Ok(Expr::String(SchemeString::literal(&s.name()))),
         _ => Err(Error::new("expected string")),
     }
 }
@@ -2043,7 +6449,7 @@ pub fn list_to_string(args: &[Expr], _: EnvRef) -> Result {
                 Some(s) => s,
                 None => {
                     return Err(Error::Message(
-                        "invalid index, expected int or float".to_string(),
+                        "invalid index, expected non-negative exact integer".to_string(),
                     ));
                 }
             };
@@ -2063,7 +6469,7 @@ pub fn list_to_string(args: &[Expr], _: EnvRef) -> Result {
                 Some(s) => s,
                 None => {
                     return Err(Error::Message(
-                        "invalid index, expected int or float".to_string(),
+                        "invalid index, expected non-negative exact integer".to_string(),
                     ));
                 }
             };
@@ -2071,7 +6477,7 @@ pub fn list_to_string(args: &[Expr], _: EnvRef) -> Result {
                 Some(s) => s,
                 None => {
                     return Err(Error::Message(
-                        "invalid index, expected int or float".to_string(),
+                        "invalid index, expected non-negative exact integer".to_string(),
                     ));
                 }
             };
@@ -2099,7 +6505,7 @@ pub fn list_to_vector(args: &[Expr], _: EnvRef) -> Result {
                 Some(s) => s,
                 None => {
                     return Err(Error::Message(
-                        "invalid index, expected int or float".to_string(),
+                        "invalid index, expected non-negative exact integer".to_string(),
                     ));
                 }
             };
@@ -2116,7 +6522,7 @@ pub fn list_to_vector(args: &[Expr], _: EnvRef) -> Result {
                 Some(s) => s,
                 None => {
                     return Err(Error::Message(
-                        "invalid index, expected int or float".to_string(),
+                        "invalid index, expected non-negative exact integer".to_string(),
                     ));
                 }
             };
@@ -2124,7 +6530,7 @@ pub fn list_to_vector(args: &[Expr], _: EnvRef) -> Result {
                 Some(s) => s,
                 None => {
                     return Err(Error::Message(
-                        "invalid index, expected int or float".to_string(),
+                        "invalid index, expected non-negative exact integer".to_string(),
                     ));
                 }
             };
@@ -2154,7 +6560,7 @@ pub fn vector_to_list(args: &[Expr], _: EnvRef) -> Result {
                 Some(s) => s,
                 None => {
                     return Err(Error::Message(
-                        "invalid index, expected int or float".to_string(),
+                        "invalid index, expected non-negative exact integer".to_string(),
                     ));
                 }
             };
@@ -2172,7 +6578,7 @@ pub fn vector_to_list(args: &[Expr], _: EnvRef) -> Result {
                 Some(s) => s,
                 None => {
                     return Err(Error::Message(
-                        "invalid index, expected int or float".to_string(),
+                        "invalid index, expected non-negative exact integer".to_string(),
                     ));
                 }
             };
@@ -2180,7 +6586,7 @@ pub fn vector_to_list(args: &[Expr], _: EnvRef) -> Result {
                 Some(s) => s,
                 None => {
                     return Err(Error::Message(
-                        "invalid index, expected int or float".to_string(),
+                        "invalid index, expected non-negative exact integer".to_string(),
                     ));
                 }
             };
@@ -2205,7 +6611,7 @@ pub fn vector_to_string(args: &[Expr], _: EnvRef) -> Result {
                 Some(s) => s,
                 None => {
                     return Err(Error::Message(
-                        "invalid index, expected int or float".to_string(),
+                        "invalid index, expected non-negative exact integer".to_string(),
                     ));
                 }
             };
@@ -2223,7 +6629,7 @@ pub fn vector_to_string(args: &[Expr], _: EnvRef) -> Result {
                 Some(s) => s,
                 None => {
                     return Err(Error::Message(
-                        "invalid index, expected int or float".to_string(),
+                        "invalid index, expected non-negative exact integer".to_string(),
                     ));
                 }
             };
@@ -2231,7 +6637,7 @@ pub fn vector_to_string(args: &[Expr], _: EnvRef) -> Result {
                 Some(s) => s,
                 None => {
                     return Err(Error::Message(
-                        "invalid index, expected int or float".to_string(),
+                        "invalid index, expected non-negative exact integer".to_string(),
                     ));
                 }
             };
@@ -2254,12 +6660,12 @@ pub fn utf8_to_string(args: &[Expr], _: EnvRef) -> Result {
                 .map(|byte| ByteVector::utf8_to_hex_str(*byte))
                 .collect::<String>();
 
-            Ok(Expr::String(hex_str))
+            Ok(Expr::String(SchemeString::new(&hex_str)))
         }
         [Expr::ByteVector(b), Expr::Number(start)] => {
             let start = start
                 .to_usize()
-                .ok_or_else(|| Error::new("invalid index, expected int or float"))?;
+                .ok_or_else(|| Error::new("invalid index, expected non-negative exact integer"))?;
 
             let len = b.len();
             if start > len {
@@ -2273,16 +6679,16 @@ pub fn utf8_to_string(args: &[Expr], _: EnvRef) -> Result {
                 .map(|byte| ByteVector::utf8_to_hex_str(*byte))
                 .collect::<String>();
 
-            Ok(Expr::String(hex_str))
+            Ok(Expr::String(SchemeString::new(&hex_str)))
         }
         [Expr::ByteVector(b), Expr::Number(start), Expr::Number(end)] => {
             let start = start
                 .to_usize()
-                .ok_or_else(|| Error::new("invalid index, expected int or float"))?;
+                .ok_or_else(|| Error::new("invalid index, expected non-negative exact integer"))?;
 
             let end = end
                 .to_usize()
-                .ok_or_else(|| Error::new("invalid index, expected int or float"))?;
+                .ok_or_else(|| Error::new("invalid index, expected non-negative exact integer"))?;
 
             let len = b.len();
             if start > len || end > len {
@@ -2297,7 +6703,7 @@ pub fn utf8_to_string(args: &[Expr], _: EnvRef) -> Result {
                 .map(|byte| ByteVector::utf8_to_hex_str(*byte))
                 .collect::<String>();
 
-            Ok(Expr::String(hex_str))
+            Ok(Expr::String(SchemeString::new(&hex_str)))
         }
         _ => Err(Error::new("expected bytevector")),
     }
@@ -2317,10 +6723,12 @@ pub fn is_number(args: &[Expr], _: EnvRef) -> Result {
     }
 }
 
-/// Returns true if arg is a real number.
+/// Returns true if arg is a real number, i.e. a number with an exact zero imaginary part.
 pub fn is_real(args: &[Expr], _: EnvRef) -> Result {
     match args {
-        [Expr::Number(Number::Float(_))] => Ok(Expr::Boolean(true)),
+        [Expr::Number(Number::ExactComplex(c))] => Ok(Expr::Boolean(c.im.is_zero())),
+        [Expr::Number(Number::Complex(_))] => Ok(Expr::Boolean(false)),
+        [Expr::Number(_)] => Ok(Expr::Boolean(true)),
         [_] => Ok(Expr::Boolean(false)),
         _ => Err(Error::Message(format!(
             "expected 1 argument, got {}",
@@ -2329,10 +6737,12 @@ pub fn is_real(args: &[Expr], _: EnvRef) -> Result {
     }
 }
 
-/// Returns true if arg is a rational number.
+/// Returns true if arg is a rational number, i.e. an exact real or a finite inexact real.
 pub fn is_rational(args: &[Expr], _: EnvRef) -> Result {
     match args {
-        [Expr::Number(Number::Rational(_))] => Ok(Expr::Boolean(true)),
+        [Expr::Number(Number::Int(_) | Number::Rational(_))] => Ok(Expr::Boolean(true)),
+        [Expr::Number(Number::Float(f))] => Ok(Expr::Boolean(f.is_finite())),
+        [Expr::Number(Number::ExactComplex(c))] => Ok(Expr::Boolean(c.im.is_zero())),
         [_] => Ok(Expr::Boolean(false)),
         _ => Err(Error::Message(format!(
             "expected 1 argument, got {}",
@@ -2341,10 +6751,10 @@ pub fn is_rational(args: &[Expr], _: EnvRef) -> Result {
     }
 }
 
-/// Returns true if arg is a complex number.
+/// Returns true if arg is a complex number. Every number is complex.
 pub fn is_complex(args: &[Expr], _: EnvRef) -> Result {
     match args {
-        [Expr::Number(Number::Complex(_))] => Ok(Expr::Boolean(true)),
+        [Expr::Number(_)] => Ok(Expr::Boolean(true)),
         [_] => Ok(Expr::Boolean(false)),
         _ => Err(Error::Message(format!(
             "expected 1 argument, got {}",
@@ -2353,10 +6763,14 @@ pub fn is_complex(args: &[Expr], _: EnvRef) -> Result {
     }
 }
 
-/// Returns true if arg is an integer.
+/// Returns true if arg is an integer, including inexact integers such as `2.0`.
 pub fn is_integer(args: &[Expr], _: EnvRef) -> Result {
     match args {
         [Expr::Number(Number::Int(_))] => Ok(Expr::Boolean(true)),
+        [Expr::Number(Number::Float(f))] => Ok(Expr::Boolean(f.is_finite() && f.fract() == 0.0)),
+        [Expr::Number(Number::ExactComplex(c))] => {
+            Ok(Expr::Boolean(c.im.is_zero() && c.re.is_integer()))
+        }
         [_] => Ok(Expr::Boolean(false)),
         _ => Err(Error::Message(format!(
             "expected 1 argument, got {}",
@@ -2390,9 +6804,9 @@ pub fn is_odd(args: &[Expr], _: EnvRef) -> Result {
 /// Returns true if number is exact.
 pub fn is_exact(args: &[Expr], _: EnvRef) -> Result {
     match args {
-        [Expr::Number(Number::Int(_))] | [Expr::Number(Number::Rational(_))] => {
-            Ok(Expr::Boolean(true))
-        }
+        [Expr::Number(Number::Int(_))]
+        | [Expr::Number(Number::Rational(_))]
+        | [Expr::Number(Number::ExactComplex(_))] => Ok(Expr::Boolean(true)),
         [Expr::Number(Number::Float(_))] | [Expr::Number(Number::Complex(_))] => {
             Ok(Expr::Boolean(false))
         }
@@ -2406,9 +6820,9 @@ pub fn is_inexact(args: &[Expr], _: EnvRef) -> Result {
         [Expr::Number(Number::Float(_))] | [Expr::Number(Number::Complex(_))] => {
             Ok(Expr::Boolean(true))
         }
-        [Expr::Number(Number::Int(_))] | [Expr::Number(Number::Rational(_))] => {
-            Ok(Expr::Boolean(false))
-        }
+        [Expr::Number(Number::Int(_))]
+        | [Expr::Number(Number::Rational(_))]
+        | [Expr::Number(Number::ExactComplex(_))] => Ok(Expr::Boolean(false)),
         _ => Err(Error::new("expected a number")),
     }
 }
@@ -2422,6 +6836,30 @@ pub fn is_exact_integer(args: &[Expr], _: EnvRef) -> Result {
     }
 }
 
+/// Returns true if number is neither infinite nor NaN.
+pub fn is_finite(args: &[Expr], _: EnvRef) -> Result {
+    match args {
+        [Expr::Number(n)] => Ok(Expr::Boolean(n.is_finite())),
+        _ => Err(Error::new("expected a number")),
+    }
+}
+
+/// Returns true if number is infinite.
+pub fn is_infinite(args: &[Expr], _: EnvRef) -> Result {
+    match args {
+        [Expr::Number(n)] => Ok(Expr::Boolean(n.is_infinite())),
+        _ => Err(Error::new("expected a number")),
+    }
+}
+
+/// Returns true if number is NaN.
+pub fn is_nan(args: &[Expr], _: EnvRef) -> Result {
+    match args {
+        [Expr::Number(n)] => Ok(Expr::Boolean(n.is_nan())),
+        _ => Err(Error::new("expected a number")),
+    }
+}
+
 /// Returns true if arg is a symbol.
 pub fn is_symbol(args: &[Expr], _: EnvRef) -> Result {
     match args {
@@ -2434,6 +6872,14 @@ pub fn is_symbol(args: &[Expr], _: EnvRef) -> Result {
     }
 }
 
+/// Returns true if arg is an interned symbol.
+pub fn is_symbol_interned(args: &[Expr], _: EnvRef) -> Result {
+    match args {
+        [Expr::Symbol(s)] => Ok(Expr::Boolean(s.is_interned())),
+        _ => Err(Error::new("expected symbol")),
+    }
+}
+
 /// Returns true if arg is a string.
 pub fn is_string(args: &[Expr], _: EnvRef) -> Result {
     match args {
@@ -2474,7 +6920,7 @@ pub fn is_char_alphabetic(args: &[Expr], _: EnvRef) -> Result {
 pub fn is_char_numeric(args: &[Expr], _: EnvRef) -> Result {
     if let Some(arg) = args.first() {
         return match arg {
-            Expr::Char(c) => Ok(Expr::Boolean(c.is_numeric())),
+            Expr::Char(c) => Ok(Expr::Boolean(chars::digit_value(*c).is_some())),
             _ => Ok(Expr::Boolean(false)),
         };
     }
@@ -2604,6 +7050,18 @@ pub fn is_output_port(args: &[Expr], _: EnvRef) -> Result {
     }
 }
 
+/// Return true if arg is a `Port`.
+pub fn is_port(args: &[Expr], _: EnvRef) -> Result {
+    match args {
+        [Expr::Port(_)] => Ok(Expr::Boolean(true)),
+        [_] => Ok(Expr::Boolean(false)),
+        _ => Err(Error::Message(format!(
+            "expected 1 argument, got {}",
+            args.len()
+        ))),
+    }
+}
+
 /// Return true if arg is an input `Port`.
 pub fn is_input_port(args: &[Expr], _: EnvRef) -> Result {
     match args {
@@ -2642,7 +7100,7 @@ pub fn is_binary_port(args: &[Expr], _: EnvRef) -> Result {
 /// Return true if `Port` has a readable character.
 pub fn is_char_ready(args: &[Expr], _: EnvRef) -> Result {
     match args {
-        [Expr::Port(Port::TextInput(p))] => match p.borrow_mut().peek_char().map_err(|e| e) {
+        [Expr::Port(Port::TextInput(p))] => match borrow_port_mut(p)?.peek_char() {
             Ok(None) => Ok(Expr::Boolean(false)),
             Ok(_) => Ok(Expr::Boolean(true)),
             Err(e) => Err(e),
@@ -2658,7 +7116,7 @@ pub fn is_char_ready(args: &[Expr], _: EnvRef) -> Result {
 /// Return true if `Port` has a readable byte.
 pub fn is_byte_ready(args: &[Expr], _: EnvRef) -> Result {
     match args {
-        [Expr::Port(Port::BinaryInput(p))] => match p.borrow_mut().peek_byte().map_err(|e| e) {
+        [Expr::Port(Port::BinaryInput(p))] => match borrow_port_mut(p)?.peek_byte() {
             Ok(None) => Ok(Expr::Boolean(false)),
             Ok(_) => Ok(Expr::Boolean(true)),
             Err(e) => Err(e),
@@ -2719,6 +7177,17 @@ pub fn is_parameter(args: &[Expr], _: EnvRef) -> Result {
     }
 }
 
+/// Return true if arguments are the same object.
+pub fn are_eq(args: &[Expr], _: EnvRef) -> Result {
+    match args {
+        [a, b] => Ok(Expr::Boolean(a.identical(b))),
+        _ => Err(Error::new(&format!(
+            "expected 2 arguments, got {}",
+            args.len()
+        ))),
+    }
+}
+
 /// Return true if arguments have equivalent identities.
 pub fn are_eqv(args: &[Expr], _: EnvRef) -> Result {
     match args {
@@ -2767,7 +7236,7 @@ pub fn is_null(args: &[Expr], _: EnvRef) -> Result {
 pub fn file_exists(args: &[Expr], _: EnvRef) -> Result {
     match args {
         [Expr::String(file_name)] => {
-            let exists = fs::exists(file_name)
+            let exists = fs::exists(file_name.to_string())
                 .map_err(|e| Error::new(&format!("unable to delete file: {}", e)))?;
             Ok(Expr::Boolean(exists))
         }
@@ -14,6 +14,7 @@ use crate::env::EnvRef;
 use crate::error::Error;
 use crate::parser;
 use crate::types::Expr;
+use crate::types::ports::{self, Port};
 
 pub const COPPER_VERSION: &str = env!("CARGO_PKG_VERSION");
 
@@ -78,11 +79,24 @@ pub fn process_file_input(expressions: Vec<String>, env: EnvRef) {
         match parser::parse_and_eval(expr, env.clone()) {
             Ok(Expr::Void()) => continue,
             Ok(result) => println!("{}", result),
-            Err(Error::Message(e)) => println!("error: {}", e),
+            Err(Error::Message(e)) => print_error(&e, &env),
         }
     }
 }
 
+/// Print an error message to `current-error-port`, falling back to stderr.
+pub fn print_error(message: &str, env: &EnvRef) {
+    let line = format!("error: {}\n", message);
+    let error_port = env.borrow().find_param("current-error-port");
+    if let Some(Expr::Port(Port::TextOutput(port))) = error_port
+        && let Ok(mut port) = ports::borrow_port_mut(&port)
+        && port.write_string(&line).and_then(|_| port.flush()).is_ok()
+    {
+        return;
+    }
+    eprint!("{}", line);
+}
+
 /// Print REPL greeting.
 pub fn print_greeting() {
     let banner = r#"
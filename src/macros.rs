@@ -6,6 +6,8 @@
 
 use crate::env::{Env, EnvRef, try_borrow_env};
 use crate::parser;
+use crate::types::promise::Promise;
+use crate::types::symbol::Symbol;
 use crate::types::{Pair, Vector};
 use crate::{error::Error, types::Closure, types::Expr, types::Parameter};
 use std::rc::Rc;
@@ -15,7 +17,7 @@ pub fn define(args: &[Expr], env: EnvRef) -> Result<Expr, Error> {
     match args {
         [Expr::Symbol(name), expr] => {
             let value = parser::eval(&expr, env.clone())?;
-            env.borrow_mut().data.insert(name.to_owned(), value);
+            env.borrow_mut().data.insert(*name, value);
         }
         [Expr::Pair(pair), body_expressions @ ..] => {
             let proc_name = match pair.car() {
@@ -28,7 +30,7 @@ pub fn define(args: &[Expr], env: EnvRef) -> Result<Expr, Error> {
             // Lambda parameters (cdr) and body expressions.
             let lambda_args = [&[pair.cdr()], body_expressions].concat();
             let value = lambda(&lambda_args, env.clone())?;
-            env.borrow_mut().data.insert(proc_name.to_owned(), value);
+            env.borrow_mut().data.insert(proc_name, value);
         }
         _ => {
             return Err(Error::new("ill-formed special form"));
@@ -53,7 +55,7 @@ pub fn let_binding(args: &[Expr], env: EnvRef) -> Result<Expr, Error> {
                                 binding_env
                                     .try_borrow_mut()
                                     .map_err(|_| Error::new("unable to borrow local env"))?
-                                    .insert_expr(&s, value);
+                                    .insert_expr(*s, value);
                             }
                             _ => return Err(Error::new("ill-formed special form")),
                         }
@@ -100,7 +102,7 @@ pub fn let_star_binding(args: &[Expr], env: EnvRef) -> Result<Expr, Error> {
                                 local_env
                                     .try_borrow_mut()
                                     .map_err(|_| Error::new("unable to borrow local env"))?
-                                    .insert_expr(&s, value);
+                                    .insert_expr(*s, value);
                                 binding_envs.push(local_env);
                             }
                             _ => return Err(Error::new("ill-formed let binding")),
@@ -137,7 +139,7 @@ pub fn letrec_binding(args: &[Expr], env: EnvRef) -> Result<Expr, Error> {
         [Expr::Pair(binding_pairs), body_expressions @ ..] => {
             let binding_env = Env::local_env(env.clone());
 
-            let mut bindings: Vec<(String, Expr)> = Vec::new();
+            let mut bindings: Vec<(Symbol, Expr)> = Vec::new();
 
             // Initialize each variable to `Expr::Null`.
             for binding_pair in binding_pairs.clone().iter() {
@@ -146,11 +148,11 @@ pub fn letrec_binding(args: &[Expr], env: EnvRef) -> Result<Expr, Error> {
                         let binding_items: Vec<Expr> = b.iter().collect();
                         match binding_items.as_slice() {
                             [Expr::Symbol(s), value] => {
-                                bindings.push((s.clone(), value.clone()));
+                                bindings.push((*s, value.clone()));
                                 binding_env
                                     .try_borrow_mut()
                                     .map_err(|_| Error::new("unable to borrow local env"))?
-                                    .insert_expr(s, Expr::Null)
+                                    .insert_expr(*s, Expr::Null)
                             }
                             _ => return Err(Error::new("ill-formed special form")),
                         }
@@ -166,7 +168,7 @@ pub fn letrec_binding(args: &[Expr], env: EnvRef) -> Result<Expr, Error> {
                 binding_env
                     .try_borrow_mut()
                     .map_err(|_| Error::new("unable to borrow local env"))?
-                    .insert_expr(&name, value);
+                    .insert_expr(name, value);
             }
 
             // Eval body.
@@ -204,33 +206,38 @@ pub fn lambda(args: &[Expr], env: EnvRef) -> Result<Expr, Error> {
 
     let mut iter = args.iter();
 
-    // Get argument symbols.
-    let params: Vec<String> = match iter.next() {
-        Some(Expr::Null) => vec![],
-        Some(Expr::Pair(p)) => p
-            .iter()
-            .map(|arg| {
-                if let Expr::Symbol(s) = arg {
-                    Ok(s.clone())
-                } else {
-                    Err(Error::Message(format!(
+    // Get argument symbols. A dotted tail or a lone symbol takes the rest of the arguments.
+    let mut params: Vec<Symbol> = Vec::new();
+    let mut formals = iter.next().cloned().unwrap_or(Expr::Null);
+    let rest = loop {
+        match formals {
+            Expr::Null => break None,
+            Expr::Symbol(s) => break Some(s),
+            Expr::Pair(p) => match p.car() {
+                Expr::Symbol(s) => {
+                    params.push(s);
+                    formals = p.cdr();
+                }
+                arg => {
+                    return Err(Error::Message(format!(
                         "lambda params must be symbols: {:?}",
                         arg
-                    )))
+                    )));
                 }
-            })
-            .collect::<Result<_, _>>()?,
-        _ => return Err(Error::Message(format!("ill-formed lambda"))),
+            },
+            _ => return Err(Error::new("ill-formed lambda")),
+        }
     };
 
     let body_expressions: Vec<Expr> = args[1..].to_vec();
-    let closure = Rc::new(Closure::init(env.clone(), params, body_expressions));
+    let closure = Rc::new(Closure::init(env.clone(), params, rest, body_expressions));
     Ok(Expr::Closure(closure))
 }
 
 // /// Evaluate lambda with arguments.
 pub fn apply_lambda(closure: &Closure, args: Vec<Expr>) -> Result<Expr, Error> {
-    if args.len() != closure.parameters.len() {
+    let count = closure.parameters.len();
+    if args.len() < count || (closure.rest.is_none() && args.len() > count) {
         return Err(Error::Message(format!(
             "wrong number of arguments passed to procedure"
         )));
@@ -241,14 +248,41 @@ pub fn apply_lambda(closure: &Closure, args: Vec<Expr>) -> Result<Expr, Error> {
 
     {
         let mut env_mut = new_env.borrow_mut();
+        if let Some(rest) = closure.rest {
+            env_mut.data.insert(rest, Pair::list(&args[count..]));
+        }
         for (param, arg) in closure.parameters.iter().zip(args.into_iter()) {
-            env_mut.data.insert(param.clone(), arg);
+            env_mut.data.insert(*param, arg);
         }
     }
 
     begin(&closure.body, new_env)
 }
 
+/// Create a promise that evaluates an expression when forced.
+pub fn delay(args: &[Expr], env: EnvRef) -> Result<Expr, Error> {
+    match args {
+        [expr] => Ok(Expr::Promise(Promise::delayed(expr.clone(), env, false))),
+        _ => Err(Error::new("delay expects 1 expression")),
+    }
+}
+
+/// Create a promise that evaluates an expression to another promise and forces it.
+pub fn delay_force(args: &[Expr], env: EnvRef) -> Result<Expr, Error> {
+    match args {
+        [expr] => Ok(Expr::Promise(Promise::delayed(expr.clone(), env, true))),
+        _ => Err(Error::new("delay-force expects 1 expression")),
+    }
+}
+
+/// Return an endianness symbol, checking that it is `big` or `little`.
+pub fn endianness(args: &[Expr], _: EnvRef) -> Result<Expr, Error> {
+    match args {
+        [Expr::Symbol(s @ (Symbol::BIG | Symbol::LITTLE))] => Ok(Expr::Symbol(*s)),
+        _ => Err(Error::new("endianness expects big or little")),
+    }
+}
+
 /// Process literal into expression.
 pub fn quote(args: &[Expr], _: EnvRef) -> Result<Expr, Error> {
     match args {
@@ -270,12 +304,13 @@ pub fn quasiquote(args: &[Expr], env: EnvRef) -> Result<Expr, Error> {
 fn unquote(expr: &Expr, env: EnvRef) -> Result<Expr, Error> {
     match expr {
         Expr::Symbol(s) => {
-            if s == "," {
+            let name = s.name();
+            if &*name == "," {
                 return Err(Error::new("expected symbol after ','"));
             }
 
-            if s.starts_with(",") {
-                let value = resolve_unquoted_symbol(s, env)?;
+            if name.starts_with(",") {
+                let value = resolve_unquoted_symbol(&name, env)?;
                 return Ok(value);
             }
 
@@ -284,7 +319,7 @@ fn unquote(expr: &Expr, env: EnvRef) -> Result<Expr, Error> {
         Expr::Pair(p) if p.is_pair() => {
             let pair = match p.car() {
                 // Edgecase where dotted pair has 1 unquoted element. Example: `(,a)
-                Expr::Symbol(s) if s == "," => {
+                Expr::Symbol(Symbol::UNQUOTE) => {
                     let car = unquote(&p.cdr(), env)?;
                     Expr::Pair(Pair::cons((car, Expr::Null)))
                 }
@@ -303,13 +338,13 @@ fn unquote(expr: &Expr, env: EnvRef) -> Result<Expr, Error> {
                     Expr::Pair(elem_pair) => match elem_pair.car() {
                         // Case where pair is like this: `(,example)
                         // The car becomes ',' and the cdr is 'example'.
-                        Expr::Symbol(s) if s == "," => {
+                        Expr::Symbol(Symbol::UNQUOTE) => {
                             list.push(unquote(&elem_pair.cdr(), env.clone())?);
                         }
                         _ => list.push(unquote(&Expr::Pair(elem_pair), env.clone())?),
                     },
-                    Expr::Symbol(s) if s.starts_with(",") => {
-                        let value = resolve_unquoted_symbol(&s, env.clone())?;
+                    Expr::Symbol(s) if s.name().starts_with(",") => {
+                        let value = resolve_unquoted_symbol(&s.name(), env.clone())?;
                         list.push(value);
                     }
                     _ => list.push(elem),
@@ -332,7 +367,7 @@ fn unquote(expr: &Expr, env: EnvRef) -> Result<Expr, Error> {
 }
 
 /// Resolve a symbol that starts with a comma.
-fn resolve_unquoted_symbol(symbol: &String, env: EnvRef) -> Result<Expr, Error> {
+fn resolve_unquoted_symbol(symbol: &str, env: EnvRef) -> Result<Expr, Error> {
     let symbol = &symbol[1..];
     let env = try_borrow_env(&env)?;
 
@@ -69,7 +69,7 @@ fn main() {
         prompt,
         banner,
         welcome_msg,
-        process_line(env),
+        process_line(env.clone()),
         expression_closed(),
     ) {
         Ok(r) => r,
@@ -84,9 +84,7 @@ fn main() {
 
         match repl.process_input() {
             Ok(line) => println!("{}", line),
-            Err(e) => {
-                eprintln!("error: {}", e);
-            }
+            Err(e) => io::print_error(&e.to_string(), &env),
         }
     }
 }
@@ -7,7 +7,10 @@
 use crate::env::EnvRef;
 use crate::error::Error;
 use crate::macros;
-use crate::types::{BOOLEAN_FALSE_STR, BOOLEAN_TRUE_STR, Expr, Number, Pair, Parameter};
+use crate::types::symbol::Symbol;
+use crate::types::{
+    BOOLEAN_FALSE_STR, BOOLEAN_TRUE_STR, Expr, Number, Pair, Parameter, SchemeString,
+};
 
 /// Parse s-expression, evaluate it, and return result.
 pub fn parse_and_eval(expr: String, env: EnvRef) -> Result<Expr, Error> {
@@ -29,10 +32,14 @@ pub fn eval(expr: &Expr, env: EnvRef) -> Result<Expr, Error> {
         | Expr::Procedure(_)
         | Expr::Closure(_)
         | Expr::Port(_)
-        | Expr::Parameter(_) => Ok(expr.clone()),
+        | Expr::Parameter(_)
+        | Expr::RandomSource(_)
+        | Expr::HashTable(_)
+        | Expr::Promise(_)
+        | Expr::CharSet(_) => Ok(expr.clone()),
         Expr::Symbol(k) => env
             .borrow()
-            .find_value(k)
+            .find_value(*k)
             .ok_or(Error::Message(format!("unbound symbol '{}'", k))),
         Expr::Pair(pair) => {
             let list_elements: Vec<Expr> = pair.iter().collect();
@@ -44,18 +51,23 @@ pub fn eval(expr: &Expr, env: EnvRef) -> Result<Expr, Error> {
 
             // Check for special forms (like define).
             if let Expr::Symbol(s) = first {
-                match s.as_str() {
-                    "define" => return macros::define(args, env),
-                    "begin" => return macros::begin(args, env),
-                    "lambda" => return macros::lambda(args, env),
-                    "quote" => return macros::quote(args, env),
-                    "quasiquote" => return macros::quasiquote(args, env),
-                    "if" => return macros::if_statement(args, env),
-                    "cond" => return macros::cond(args, env),
-                    "parameterize" => return macros::parameterize(args, env),
-                    "let" => return macros::let_binding(args, env),
-                    "let*" => return macros::let_star_binding(args, env),
-                    "letrec" | "letrec*" => return macros::letrec_binding(args, env),
+                match *s {
+                    Symbol::DEFINE => return macros::define(args, env),
+                    Symbol::BEGIN => return macros::begin(args, env),
+                    Symbol::LAMBDA => return macros::lambda(args, env),
+                    Symbol::QUOTE => return macros::quote(args, env),
+                    Symbol::QUASIQUOTE => return macros::quasiquote(args, env),
+                    Symbol::IF => return macros::if_statement(args, env),
+                    Symbol::COND => return macros::cond(args, env),
+                    Symbol::PARAMETERIZE => return macros::parameterize(args, env),
+                    Symbol::DELAY => return macros::delay(args, env),
+                    Symbol::DELAY_FORCE => return macros::delay_force(args, env),
+                    Symbol::ENDIANNESS => return macros::endianness(args, env),
+                    Symbol::LET => return macros::let_binding(args, env),
+                    Symbol::LET_STAR => return macros::let_star_binding(args, env),
+                    Symbol::LETREC | Symbol::LETREC_STAR => {
+                        return macros::letrec_binding(args, env);
+                    }
                     _ => {}
                 }
             }
@@ -131,12 +143,12 @@ pub fn parse(tokens: &[String]) -> Result<(Expr, &[String]), Error> {
         ")" => Err(Error::new("invalid ')'")),
         "'" => {
             let (quoted_expr, remaining) = parse(right_expr)?;
-            let slice = vec![Expr::Symbol("quote".to_string()), quoted_expr];
+            let slice = vec![Expr::Symbol(Symbol::QUOTE), quoted_expr];
             Ok((Pair::list(slice.as_slice()), remaining))
         }
         "`" => {
             let (quasiquoted_expr, remaining) = parse(right_expr)?;
-            let slice = vec![Expr::Symbol("quasiquote".to_string()), quasiquoted_expr];
+            let slice = vec![Expr::Symbol(Symbol::QUASIQUOTE), quasiquoted_expr];
             Ok((Pair::list(slice.as_slice()), remaining))
         }
         "#(" => {
@@ -166,6 +178,20 @@ pub fn parse_right_expr(tokens: &[String]) -> Result<(Expr, &[String]), Error> {
         if car == ")" {
             return Ok((Pair::list(expressions.as_slice()), cdr));
         }
+        // Dotted tail (example: '(a b . c)')
+        if car == "." && !expressions.is_empty() {
+            let (tail, rest) = parse(cdr)?;
+            return match rest.split_first() {
+                Some((close, rest)) if close == ")" => {
+                    let list = expressions
+                        .into_iter()
+                        .rev()
+                        .fold(tail, |tail, e| Expr::Pair(Pair::cons((e, tail))));
+                    Ok((list, rest))
+                }
+                _ => Err(Error::new("expected ')' after dotted tail")),
+            };
+        }
         let (expr, new_copy) = parse(&tokens_copy)?;
         expressions.push(expr);
         tokens_copy = new_copy;
@@ -181,7 +207,7 @@ pub fn parse_literal(tokens: &[String], constructor: String) -> Result<(Expr, &[
             .split_first()
             .ok_or(Error::new("unable to parse literal"))?;
         if car == ")" {
-            let mut vector_form = vec![Expr::Symbol(constructor.to_string())];
+            let mut vector_form = vec![Expr::Symbol(Symbol::intern(&constructor))];
             vector_form.extend(expressions);
             return Ok((Pair::list(vector_form.as_slice()), cdr));
         }
@@ -208,7 +234,7 @@ pub fn eval_atom(token: &str) -> crate::types::Result {
     // String
     if token.starts_with('"') && token.ends_with('"') && token.len() >= 2 {
         let inner_string = &token[1..token.len() - 1];
-        return Ok(Expr::String(inner_string.to_string()));
+        return Ok(Expr::String(SchemeString::literal(inner_string)));
     }
 
     // Char
@@ -216,7 +242,9 @@ pub fn eval_atom(token: &str) -> crate::types::Result {
     if token.starts_with(char_delim) && token.len() > char_delim.len() {
         let literal = &token[char_delim.len()..];
         // #\x[hex value] (example: '#\x123')
-        if let Some(hex_str) = literal.strip_prefix('x') {
+        if let Some(hex_str) = literal.strip_prefix('x')
+            && !hex_str.is_empty()
+        {
             let codepoint = u32::from_str_radix(hex_str, 16)
                 .map_err(|_| Error::Message(format!("invalid hex value: {hex_str}")))?;
             return char::from_u32(codepoint)
@@ -249,7 +277,7 @@ pub fn eval_atom(token: &str) -> crate::types::Result {
         return Ok(Expr::Number(num));
     }
 
-    return Ok(Expr::Symbol(token.to_string()));
+    return Ok(Expr::Symbol(Symbol::intern(token)));
 }
 
 /// Get vec of numbers from an s-expression.
@@ -716,6 +716,35 @@ fn test_lambda_immediate_invocation_multiple_body_expressions() {
     assert_eq!(result.to_string(), "50");
 }
 
+#[test]
+fn test_define_lambda_rest_parameter() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    parse_and_eval("(define (f a . b) b)".to_string(), env.clone()).unwrap();
+    let result = parse_and_eval("(f 1 2 3)".to_string(), env.clone()).unwrap();
+    assert_eq!(result.to_string(), "(2 3)");
+    let result = parse_and_eval("(f 1)".to_string(), env.clone()).unwrap();
+    assert_eq!(result.to_string(), "()");
+    let result = parse_and_eval("(f)".to_string(), env);
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_lambda_rest_parameters() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("((lambda args args) 1 2)".to_string(), env.clone()).unwrap();
+    assert_eq!(result.to_string(), "(1 2)");
+    let result = parse_and_eval(
+        "((lambda (a b . c) (list a b c)) 1 2 3 4)".to_string(),
+        env.clone(),
+    )
+    .unwrap();
+    assert_eq!(result.to_string(), "(1 2 (3 4))");
+    let result = parse_and_eval("((lambda (a . 1) a) 1)".to_string(), env);
+    assert!(result.is_err());
+}
+
 #[test]
 fn test_new_list() {
     use crate::{env::Env, parser::parse_and_eval};
@@ -893,7 +922,7 @@ fn test_ceil() {
     use crate::{env::Env, parser::parse_and_eval};
     let env = Env::standard_env();
     let result = parse_and_eval("(ceiling 3.2)".to_string(), env).unwrap();
-    assert_eq!(result.to_string(), "4");
+    assert_eq!(result.to_string(), "4.0");
 }
 
 #[test]
@@ -901,7 +930,7 @@ fn test_floor() {
     use crate::{env::Env, parser::parse_and_eval};
     let env = Env::standard_env();
     let result = parse_and_eval("(floor 3.8)".to_string(), env).unwrap();
-    assert_eq!(result.to_string(), "3");
+    assert_eq!(result.to_string(), "3.0");
 }
 
 #[test]
@@ -1325,6 +1354,54 @@ fn test_integer_predicate() {
     assert_eq!(result.to_string(), "#t");
 }
 
+#[test]
+fn test_complex_every_number() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval(
+        "(list (complex? 1) (complex? 1/2) (complex? 1.5) (complex? 1+2i) (complex? (make-rectangular 1 0)) (complex? 'a))".to_string(),
+        env,
+    )
+    .unwrap();
+    assert_eq!(result.to_string(), "(#t #t #t #t #t #f)");
+}
+
+#[test]
+fn test_real_zero_imaginary() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval(
+        "(list (real? 1) (real? 1/2) (real? 1+0i) (real? (make-rectangular 1/2 0)) (real? 1+2i) (real? 1.5+0.0i))".to_string(),
+        env,
+    )
+    .unwrap();
+    assert_eq!(result.to_string(), "(#t #t #t #t #f #f)");
+}
+
+#[test]
+fn test_rational_exact_and_finite() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval(
+        "(list (rational? 1) (rational? 1.5) (rational? +inf.0) (rational? +nan.0) (rational? 1+2i))".to_string(),
+        env,
+    )
+    .unwrap();
+    assert_eq!(result.to_string(), "(#t #t #f #f #f)");
+}
+
+#[test]
+fn test_integer_inexact() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval(
+        "(list (integer? 2.0) (integer? 2.5) (integer? 1/2) (integer? +inf.0))".to_string(),
+        env,
+    )
+    .unwrap();
+    assert_eq!(result.to_string(), "(#t #f #f #f)");
+}
+
 #[test]
 fn test_even_predicate() {
     use crate::{env::Env, parser::parse_and_eval};
@@ -3263,3 +3340,5154 @@ fn test_with_output_to_file_wrong_arg_count() {
     let result = parse_and_eval("(with-output-to-file)".to_string(), env);
     assert!(result.is_err());
 }
+
+#[test]
+fn test_rational_bignum_denominator() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(/ 1 (expt 2 70))".to_string(), env).unwrap();
+    assert_eq!(result.to_string(), "1/1180591620717411303424");
+}
+
+#[test]
+fn test_rational_sum_demotes_to_integer() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(+ 1/3 2/3)".to_string(), env).unwrap();
+    assert_eq!(result.to_string(), "1");
+}
+
+#[test]
+fn test_expt_negative_exponent() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(expt 2 -2)".to_string(), env).unwrap();
+    assert_eq!(result.to_string(), "1/4");
+}
+
+#[test]
+fn test_exact_float_to_rational() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(exact 0.1)".to_string(), env).unwrap();
+    assert_eq!(result.to_string(), "3602879701896397/36028797018963968");
+}
+
+#[test]
+fn test_exact_infinity_errors() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(exact +inf.0)".to_string(), env);
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_inexact_rational() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(inexact 1/4)".to_string(), env).unwrap();
+    assert_eq!(result.to_string(), "0.25");
+}
+
+#[test]
+fn test_numerator() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(numerator 6/4)".to_string(), env).unwrap();
+    assert_eq!(result.to_string(), "3");
+}
+
+#[test]
+fn test_denominator() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(denominator 6/4)".to_string(), env).unwrap();
+    assert_eq!(result.to_string(), "2");
+}
+
+#[test]
+fn test_denominator_integer() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(denominator 5)".to_string(), env).unwrap();
+    assert_eq!(result.to_string(), "1");
+}
+
+#[test]
+fn test_round_half_to_even() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(round 5/2)".to_string(), env).unwrap();
+    assert_eq!(result.to_string(), "2");
+}
+
+#[test]
+fn test_round_half_to_even_odd() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(round 7/2)".to_string(), env).unwrap();
+    assert_eq!(result.to_string(), "4");
+}
+
+#[test]
+fn test_truncate_negative_rational() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(truncate -7/2)".to_string(), env).unwrap();
+    assert_eq!(result.to_string(), "-3");
+}
+
+#[test]
+fn test_floor_rational_exact() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(floor -7/2)".to_string(), env).unwrap();
+    assert_eq!(result.to_string(), "-4");
+}
+
+#[test]
+fn test_rationalize_exact() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(rationalize (exact 0.3) 1/10)".to_string(), env).unwrap();
+    assert_eq!(result.to_string(), "1/3");
+}
+
+#[test]
+fn test_rationalize_inexact() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(rationalize 0.3 1/10)".to_string(), env).unwrap();
+    assert_eq!(result.to_string(), "0.3333333333333333");
+}
+
+#[test]
+fn test_sqrt_exact_perfect_square() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(sqrt 16)".to_string(), env).unwrap();
+    assert_eq!(result.to_string(), "4");
+}
+
+#[test]
+fn test_sqrt_exact_rational() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(sqrt 1/4)".to_string(), env).unwrap();
+    assert_eq!(result.to_string(), "1/2");
+}
+
+#[test]
+fn test_sqrt_exact_bignum() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(sqrt (expt 10 40))".to_string(), env).unwrap();
+    assert_eq!(result.to_string(), "100000000000000000000");
+}
+
+#[test]
+fn test_sqrt_inexact_bignum() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval(
+        "(list (sqrt (expt 10 401)) (sqrt (/ 1 (expt 10 401))))".to_string(),
+        env,
+    )
+    .unwrap();
+    assert_eq!(
+        result.to_string(),
+        "(3.1622776601683794e200 3.1622776601683792e-201)"
+    );
+}
+
+#[test]
+fn test_sqrt_inexact() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(sqrt 2)".to_string(), env).unwrap();
+    assert_eq!(result.to_string(), "1.4142135623730951");
+}
+
+#[test]
+fn test_sqrt_negative_is_complex() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(sqrt -4.0)".to_string(), env).unwrap();
+    assert_eq!(result.to_string(), "0.0+2.0i");
+}
+
+#[test]
+fn test_exp_exact_zero() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(exp 0)".to_string(), env).unwrap();
+    assert_eq!(result.to_string(), "1");
+}
+
+#[test]
+fn test_exp() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(exp 1)".to_string(), env).unwrap();
+    assert_eq!(result.to_string(), "2.718281828459045");
+}
+
+#[test]
+fn test_log_two_args() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(exact (log 8 2))".to_string(), env).unwrap();
+    assert_eq!(result.to_string(), "3");
+}
+
+#[test]
+fn test_log_exact_zero_errors() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(log 0)".to_string(), env);
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_log_bignum() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval(
+        "(list (log (expt 10 400)) (log (/ 1 (expt 10 400))))".to_string(),
+        env,
+    )
+    .unwrap();
+    assert_eq!(result.to_string(), "(921.0340371976182 -921.0340371976182)");
+}
+
+#[test]
+fn test_log_negative_bignum() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(log (- (expt 10 400)))".to_string(), env).unwrap();
+    assert_eq!(result.to_string(), "921.0340371976182+3.141592653589793i");
+}
+
+#[test]
+fn test_atan_two_args() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(atan 1 1)".to_string(), env).unwrap();
+    assert_eq!(result.to_string(), "0.7853981633974483");
+}
+
+#[test]
+fn test_sin_exact_zero() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(sin 0)".to_string(), env).unwrap();
+    assert_eq!(result.to_string(), "0");
+}
+
+#[test]
+fn test_nan() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(nan? (/ 0. 0.))".to_string(), env).unwrap();
+    assert_eq!(result.to_string(), "#t");
+}
+
+#[test]
+fn test_infinite() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(infinite? (/ -1. 0.))".to_string(), env).unwrap();
+    assert_eq!(result.to_string(), "#t");
+}
+
+#[test]
+fn test_finite_rational() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(finite? 1/2)".to_string(), env).unwrap();
+    assert_eq!(result.to_string(), "#t");
+}
+
+#[test]
+fn test_exact_complex_multiplication() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(* 1+2i 3-i)".to_string(), env).unwrap();
+    assert_eq!(result.to_string(), "5+5i");
+}
+
+#[test]
+fn test_exact_complex_sum_demotes_to_real() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(+ 1/2+i 1/2-i)".to_string(), env).unwrap();
+    assert_eq!(result.to_string(), "1");
+}
+
+#[test]
+fn test_exact_complex_division() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(/ 1+2i 3-i)".to_string(), env).unwrap();
+    assert_eq!(result.to_string(), "1/10+7/10i");
+}
+
+#[test]
+fn test_exact_complex_is_exact() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(exact? 1+2i)".to_string(), env).unwrap();
+    assert_eq!(result.to_string(), "#t");
+}
+
+#[test]
+fn test_exact_complex_expt() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(expt 1+i 2)".to_string(), env).unwrap();
+    assert_eq!(result.to_string(), "+2i");
+}
+
+#[test]
+fn test_sqrt_negative_exact_square() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(sqrt -4)".to_string(), env).unwrap();
+    assert_eq!(result.to_string(), "+2i");
+}
+
+#[test]
+fn test_make_rectangular() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(make-rectangular 1 2)".to_string(), env).unwrap();
+    assert_eq!(result.to_string(), "1+2i");
+}
+
+#[test]
+fn test_make_rectangular_zero_imaginary() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(make-rectangular 1/2 0)".to_string(), env).unwrap();
+    assert_eq!(result.to_string(), "1/2");
+}
+
+#[test]
+fn test_make_polar_zero_angle() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(make-polar 2 0)".to_string(), env).unwrap();
+    assert_eq!(result.to_string(), "2");
+}
+
+#[test]
+fn test_real_part() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(real-part 3+4i)".to_string(), env).unwrap();
+    assert_eq!(result.to_string(), "3");
+}
+
+#[test]
+fn test_imag_part() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(imag-part 3+4i)".to_string(), env).unwrap();
+    assert_eq!(result.to_string(), "4");
+}
+
+#[test]
+fn test_imag_part_real() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(imag-part 5)".to_string(), env).unwrap();
+    assert_eq!(result.to_string(), "0");
+}
+
+#[test]
+fn test_magnitude_exact() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(magnitude 3+4i)".to_string(), env).unwrap();
+    assert_eq!(result.to_string(), "5");
+}
+
+#[test]
+fn test_magnitude_negative_real() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(magnitude -5)".to_string(), env).unwrap();
+    assert_eq!(result.to_string(), "5");
+}
+
+#[test]
+fn test_angle_imaginary_unit() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(angle +i)".to_string(), env).unwrap();
+    assert_eq!(result.to_string(), "1.5707963267948966");
+}
+
+#[test]
+fn test_exact_inexact_complex() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(exact (inexact 1+2i))".to_string(), env).unwrap();
+    assert_eq!(result.to_string(), "1+2i");
+}
+
+#[test]
+fn test_make_rectangular_complex_errors() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(make-rectangular 1+i 2)".to_string(), env);
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_display_float_integral() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("2.0".to_string(), env).unwrap();
+    assert_eq!(result.to_string(), "2.0");
+}
+
+#[test]
+fn test_display_float_exponent() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("1e21".to_string(), env).unwrap();
+    assert_eq!(result.to_string(), "1e21");
+}
+
+#[test]
+fn test_display_float_small_exponent() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("1e-7".to_string(), env).unwrap();
+    assert_eq!(result.to_string(), "1e-7");
+}
+
+#[test]
+fn test_display_float_shortest() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(+ 0.1 0.2)".to_string(), env).unwrap();
+    assert_eq!(result.to_string(), "0.30000000000000004");
+}
+
+#[test]
+fn test_display_negative_zero() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("-0.0".to_string(), env).unwrap();
+    assert_eq!(result.to_string(), "-0.0");
+}
+
+#[test]
+fn test_display_infinities() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(list (/ 1. 0.) (/ -1. 0.))".to_string(), env).unwrap();
+    assert_eq!(result.to_string(), "(+inf.0 -inf.0)");
+}
+
+#[test]
+fn test_display_nan() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(/ 0. 0.)".to_string(), env).unwrap();
+    assert_eq!(result.to_string(), "+nan.0");
+}
+
+#[test]
+fn test_read_special_floats() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(list +inf.0 -inf.0 +nan.0)".to_string(), env).unwrap();
+    assert_eq!(result.to_string(), "(+inf.0 -inf.0 +nan.0)");
+}
+
+#[test]
+fn test_display_inexact_complex() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("1.5-2.5i".to_string(), env).unwrap();
+    assert_eq!(result.to_string(), "1.5-2.5i");
+}
+
+#[test]
+fn test_display_exact_complex_unit() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(list +i -i 3+i)".to_string(), env).unwrap();
+    assert_eq!(result.to_string(), "(+i -i 3+i)");
+}
+
+#[test]
+fn test_number_to_string_hex() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(number->string 255 16)".to_string(), env).unwrap();
+    assert_eq!(result.to_string(), "\"ff\"");
+}
+
+#[test]
+fn test_number_to_string_binary() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(number->string -10 2)".to_string(), env).unwrap();
+    assert_eq!(result.to_string(), "\"-1010\"");
+}
+
+#[test]
+fn test_number_to_string_rational_octal() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(number->string 1/8 8)".to_string(), env).unwrap();
+    assert_eq!(result.to_string(), "\"1/10\"");
+}
+
+#[test]
+fn test_number_to_string_bignum_hex() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(number->string (expt 2 70) 16)".to_string(), env).unwrap();
+    assert_eq!(result.to_string(), "\"400000000000000000\"");
+}
+
+#[test]
+fn test_number_to_string_inexact_radix_errors() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(number->string 1.5 2)".to_string(), env);
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_number_to_string_invalid_radix() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(number->string 10 3)".to_string(), env);
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_add_fixnum_overflow_promotes() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(+ 9223372036854775807 1)".to_string(), env).unwrap();
+    assert_eq!(result.to_string(), "9223372036854775808");
+}
+
+#[test]
+fn test_sub_fixnum_overflow_promotes() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(- -9223372036854775808 1)".to_string(), env).unwrap();
+    assert_eq!(result.to_string(), "-9223372036854775809");
+}
+
+#[test]
+fn test_mult_fixnum_overflow_promotes() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(* 4294967296 4294967296)".to_string(), env).unwrap();
+    assert_eq!(result.to_string(), "18446744073709551616");
+}
+
+#[test]
+fn test_bignum_sum_demotes_to_fixnum() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(- (+ 9223372036854775807 1) 1)".to_string(), env).unwrap();
+    assert_eq!(result.to_string(), "9223372036854775807");
+}
+
+#[test]
+fn test_mult_no_args() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(*)".to_string(), env).unwrap();
+    assert_eq!(result.to_string(), "1");
+}
+
+#[test]
+fn test_sub_negate() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(- 5)".to_string(), env).unwrap();
+    assert_eq!(result.to_string(), "-5");
+}
+
+#[test]
+fn test_div_fixnum_inexact_quotient() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(/ 7 2)".to_string(), env).unwrap();
+    assert_eq!(result.to_string(), "7/2");
+}
+
+#[test]
+fn test_div_reciprocal() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(/ 4)".to_string(), env).unwrap();
+    assert_eq!(result.to_string(), "1/4");
+}
+
+#[test]
+fn test_div_by_zero_errors() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(/ 1 0)".to_string(), env);
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_add_mixed_types() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(+ 1 1/2 0.5)".to_string(), env).unwrap();
+    assert_eq!(result.to_string(), "2.0");
+}
+
+#[test]
+fn test_add_non_number_errors() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(+ 1 \"a\")".to_string(), env);
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_num_eq() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(= 1 1.0 2/2)".to_string(), env).unwrap();
+    assert_eq!(result.to_string(), "#t");
+}
+
+#[test]
+fn test_num_lt() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(< 1 2 3)".to_string(), env).unwrap();
+    assert_eq!(result.to_string(), "#t");
+}
+
+#[test]
+fn test_num_lt_not_monotonic() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(< 1 3 2)".to_string(), env).unwrap();
+    assert_eq!(result.to_string(), "#f");
+}
+
+#[test]
+fn test_num_ge() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(>= 3 3 2.5 -1/2)".to_string(), env).unwrap();
+    assert_eq!(result.to_string(), "#t");
+}
+
+#[test]
+fn test_num_lt_nan() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(< 1 +nan.0)".to_string(), env).unwrap();
+    assert_eq!(result.to_string(), "#f");
+}
+
+#[test]
+fn test_num_lt_complex_errors() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(< 1 +i)".to_string(), env);
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_num_lt_bignum() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(> (expt 2 70) 9223372036854775807)".to_string(), env).unwrap();
+    assert_eq!(result.to_string(), "#t");
+}
+
+#[test]
+fn test_fib() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval(
+        "(begin (define (fib n) (if (< n 2) n (+ (fib (- n 1)) (fib (- n 2))))) (fib 20))"
+            .to_string(),
+        env,
+    )
+    .unwrap();
+    assert_eq!(result.to_string(), "6765");
+}
+
+#[test]
+fn test_bitwise_and() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(bitwise-and 12 10)".to_string(), env).unwrap();
+    assert_eq!(result.to_string(), "8");
+}
+
+#[test]
+fn test_bitwise_ior() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(bitwise-ior 12 10)".to_string(), env).unwrap();
+    assert_eq!(result.to_string(), "14");
+}
+
+#[test]
+fn test_bitwise_xor() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(bitwise-xor 12 10)".to_string(), env).unwrap();
+    assert_eq!(result.to_string(), "6");
+}
+
+#[test]
+fn test_bitwise_and_identity() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(bitwise-and)".to_string(), env).unwrap();
+    assert_eq!(result.to_string(), "-1");
+}
+
+#[test]
+fn test_bitwise_not() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(bitwise-not 0)".to_string(), env).unwrap();
+    assert_eq!(result.to_string(), "-1");
+}
+
+#[test]
+fn test_bitwise_and_negative_bignum() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(bitwise-and -1 (expt 2 70))".to_string(), env).unwrap();
+    assert_eq!(result.to_string(), "1180591620717411303424");
+}
+
+#[test]
+fn test_bitwise_xor_bignum_demotes() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval(
+        "(bitwise-xor (expt 2 70) (+ (expt 2 70) 5))".to_string(),
+        env,
+    )
+    .unwrap();
+    assert_eq!(result.to_string(), "5");
+}
+
+#[test]
+fn test_arithmetic_shift_left_promotes() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(arithmetic-shift 3 62)".to_string(), env).unwrap();
+    assert_eq!(result.to_string(), "13835058055282163712");
+}
+
+#[test]
+fn test_arithmetic_shift_right_floors() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(arithmetic-shift -5 -1)".to_string(), env).unwrap();
+    assert_eq!(result.to_string(), "-3");
+}
+
+#[test]
+fn test_arithmetic_shift_bignum_right() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(arithmetic-shift (expt 2 70) -68)".to_string(), env).unwrap();
+    assert_eq!(result.to_string(), "4");
+}
+
+#[test]
+fn test_bit_count_negative() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(bit-count -8)".to_string(), env).unwrap();
+    assert_eq!(result.to_string(), "3");
+}
+
+#[test]
+fn test_integer_length() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(integer-length 255)".to_string(), env).unwrap();
+    assert_eq!(result.to_string(), "8");
+}
+
+#[test]
+fn test_integer_length_negative() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(integer-length -256)".to_string(), env).unwrap();
+    assert_eq!(result.to_string(), "8");
+}
+
+#[test]
+fn test_bit_set_negative_bignum_index() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(bit-set? 70 -1)".to_string(), env).unwrap();
+    assert_eq!(result.to_string(), "#t");
+}
+
+#[test]
+fn test_copy_bit() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(copy-bit 0 6 #t)".to_string(), env).unwrap();
+    assert_eq!(result.to_string(), "7");
+}
+
+#[test]
+fn test_copy_bit_clear_promotes() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(copy-bit 63 -1 #f)".to_string(), env).unwrap();
+    assert_eq!(result.to_string(), "-9223372036854775809");
+}
+
+#[test]
+fn test_bit_field() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(bit-field 6 1 3)".to_string(), env).unwrap();
+    assert_eq!(result.to_string(), "3");
+}
+
+#[test]
+fn test_bitwise_and_inexact_errors() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(bitwise-and 1.0 3)".to_string(), env);
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_random_integer_in_range() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval(
+        "(let ((n (random-integer 10))) (and (>= n 0) (< n 10)))".to_string(),
+        env,
+    )
+    .unwrap();
+    assert_eq!(result.to_string(), "#t");
+}
+
+#[test]
+fn test_random_integer_bignum_range() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval(
+        "(let ((n (random-integer (expt 2 100)))) (and (>= n 0) (< n (expt 2 100))))".to_string(),
+        env,
+    )
+    .unwrap();
+    assert_eq!(result.to_string(), "#t");
+}
+
+#[test]
+fn test_random_integer_non_positive_range() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(random-integer 0)".to_string(), env);
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_random_real_in_unit_interval() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval(
+        "(let ((x (random-real))) (and (> x 0) (< x 1)))".to_string(),
+        env,
+    )
+    .unwrap();
+    assert_eq!(result.to_string(), "#t");
+}
+
+#[test]
+fn test_random_source_predicate() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(random-source? (make-random-source))".to_string(), env).unwrap();
+    assert_eq!(result.to_string(), "#t");
+}
+
+#[test]
+fn test_random_source_predicate_false() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(random-source? 1)".to_string(), env).unwrap();
+    assert_eq!(result.to_string(), "#f");
+}
+
+#[test]
+fn test_random_source_pseudo_randomize_reproducible() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval(
+        "(let ((a (make-random-source)) (b (make-random-source))) (random-source-pseudo-randomize! a 3 4) (random-source-pseudo-randomize! b 3 4) (= ((random-source-make-integers a) 1000000) ((random-source-make-integers b) 1000000)))".to_string(),
+        env,
+    )
+    .unwrap();
+    assert_eq!(result.to_string(), "#t");
+}
+
+#[test]
+fn test_random_source_state_restore() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval(
+        "(let* ((s (make-random-source)) (state (random-source-state-ref s)) (next (random-source-make-integers s)) (a (next 1000000))) (random-source-state-set! s state) (= a (next 1000000)))".to_string(),
+        env,
+    )
+    .unwrap();
+    assert_eq!(result.to_string(), "#t");
+}
+
+#[test]
+fn test_random_source_state_set_invalid() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval(
+        "(random-source-state-set! (make-random-source) '(foo 1 2 3 4))".to_string(),
+        env,
+    );
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_random_source_make_reals() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval(
+        "(let ((x ((random-source-make-reals (make-random-source))))) (and (> x 0) (< x 1)))"
+            .to_string(),
+        env,
+    )
+    .unwrap();
+    assert_eq!(result.to_string(), "#t");
+}
+
+#[test]
+fn test_make_string() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(make-string 3 #\\a)".to_string(), env).unwrap();
+    assert_eq!(result.to_string(), "\"aaa\"");
+}
+
+#[test]
+fn test_make_string_length() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(string-length (make-string 4))".to_string(), env).unwrap();
+    assert_eq!(result.to_string(), "4");
+}
+
+#[test]
+fn test_string_from_chars() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(string #\\a #\\b #\\c)".to_string(), env).unwrap();
+    assert_eq!(result.to_string(), "\"abc\"");
+}
+
+#[test]
+fn test_string_ref() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(string-ref \"héllo\" 1)".to_string(), env).unwrap();
+    assert_eq!(result.to_string(), "#\\é");
+}
+
+#[test]
+fn test_string_ref_out_of_range() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(string-ref \"abc\" 3)".to_string(), env);
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_string_set_shared() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval(
+        "(let* ((s (make-string 3 #\\a)) (t s)) (string-set! s 1 #\\b) t)".to_string(),
+        env,
+    )
+    .unwrap();
+    assert_eq!(result.to_string(), "\"aba\"");
+}
+
+#[test]
+fn test_string_set_literal_errors() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(string-set! \"abc\" 0 #\\z)".to_string(), env);
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_string_fill_literal_errors() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(string-fill! \"abc\" #\\z)".to_string(), env);
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_string_set_copy_of_literal() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval(
+        "(let ((s (string-copy \"abc\"))) (string-set! s 0 #\\z) s)".to_string(),
+        env,
+    )
+    .unwrap();
+    assert_eq!(result.to_string(), "\"zbc\"");
+}
+
+#[test]
+fn test_substring() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(substring \"hello\" 1 3)".to_string(), env).unwrap();
+    assert_eq!(result.to_string(), "\"el\"");
+}
+
+#[test]
+fn test_substring_out_of_range() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(substring \"hello\" 2 6)".to_string(), env);
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_string_copy_range() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(string-copy \"hello\" 2)".to_string(), env).unwrap();
+    assert_eq!(result.to_string(), "\"llo\"");
+}
+
+#[test]
+fn test_string_copy_to_overlapping() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval(
+        "(let ((s (string-copy \"abcde\"))) (string-copy! s 1 s 0 3) s)".to_string(),
+        env,
+    )
+    .unwrap();
+    assert_eq!(result.to_string(), "\"aabce\"");
+}
+
+#[test]
+fn test_string_fill_range() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval(
+        "(let ((s (make-string 4 #\\a))) (string-fill! s #\\b 1 3) s)".to_string(),
+        env,
+    )
+    .unwrap();
+    assert_eq!(result.to_string(), "\"abba\"");
+}
+
+#[test]
+fn test_string_append_variadic() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(string-append \"a\" \"b\" \"c\")".to_string(), env).unwrap();
+    assert_eq!(result.to_string(), "\"abc\"");
+}
+
+#[test]
+fn test_string_length_unicode() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(string-length \"héllo\")".to_string(), env).unwrap();
+    assert_eq!(result.to_string(), "5");
+}
+
+#[test]
+fn test_string_eq() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(string=? \"abc\" \"abc\" \"abc\")".to_string(), env).unwrap();
+    assert_eq!(result.to_string(), "#t");
+}
+
+#[test]
+fn test_string_lt() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(string<? \"abc\" \"abd\" \"abe\")".to_string(), env).unwrap();
+    assert_eq!(result.to_string(), "#t");
+}
+
+#[test]
+fn test_string_ge_false() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(string>=? \"abc\" \"abd\")".to_string(), env).unwrap();
+    assert_eq!(result.to_string(), "#f");
+}
+
+#[test]
+fn test_string_ci_eq() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(string-ci=? \"ABC\" \"abc\")".to_string(), env).unwrap();
+    assert_eq!(result.to_string(), "#t");
+}
+
+#[test]
+fn test_string_foldcase() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(string-foldcase \"HeLLo\")".to_string(), env).unwrap();
+    assert_eq!(result.to_string(), "\"hello\"");
+}
+
+#[test]
+fn test_string_to_list_range() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(string->list \"hello\" 1 3)".to_string(), env).unwrap();
+    assert_eq!(result.to_string(), "(#\\e #\\l)");
+}
+
+#[test]
+fn test_string_eqv_identity() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(let ((s (make-string 1))) (eqv? s s))".to_string(), env).unwrap();
+    assert_eq!(result.to_string(), "#t");
+}
+
+#[test]
+fn test_char_eq() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(char=? #\\a #\\a #\\a)".to_string(), env).unwrap();
+    assert_eq!(result.to_string(), "#t");
+}
+
+#[test]
+fn test_char_lt() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(char<? #\\a #\\b #\\c)".to_string(), env).unwrap();
+    assert_eq!(result.to_string(), "#t");
+}
+
+#[test]
+fn test_char_ge_false() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(char>=? #\\a #\\b)".to_string(), env).unwrap();
+    assert_eq!(result.to_string(), "#f");
+}
+
+#[test]
+fn test_char_ci_eq() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(char-ci=? #\\a #\\A)".to_string(), env).unwrap();
+    assert_eq!(result.to_string(), "#t");
+}
+
+#[test]
+fn test_char_ci_eq_final_sigma() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(char-ci=? #\\ς #\\Σ)".to_string(), env).unwrap();
+    assert_eq!(result.to_string(), "#t");
+}
+
+#[test]
+fn test_char_to_integer() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(char->integer #\\A)".to_string(), env).unwrap();
+    assert_eq!(result.to_string(), "65");
+}
+
+#[test]
+fn test_integer_to_char() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(integer->char 955)".to_string(), env).unwrap();
+    assert_eq!(result.to_string(), "#\\λ");
+}
+
+#[test]
+fn test_integer_to_char_surrogate() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(integer->char 55296)".to_string(), env);
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_char_upcase_unicode() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(char-upcase #\\ä)".to_string(), env).unwrap();
+    assert_eq!(result.to_string(), "#\\Ä");
+}
+
+#[test]
+fn test_char_upcase_sharp_s() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(char-upcase #\\ß)".to_string(), env).unwrap();
+    assert_eq!(result.to_string(), "#\\ß");
+}
+
+#[test]
+fn test_char_downcase() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(char-downcase #\\Σ)".to_string(), env).unwrap();
+    assert_eq!(result.to_string(), "#\\σ");
+}
+
+#[test]
+fn test_char_foldcase() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(char-foldcase #\\ς)".to_string(), env).unwrap();
+    assert_eq!(result.to_string(), "#\\σ");
+}
+
+#[test]
+fn test_char_foldcase_dotless_i() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval(
+        "(list (char-foldcase (integer->char 305)) (char-ci=? (integer->char 305) #\\i))"
+            .to_string(),
+        env,
+    )
+    .unwrap();
+    assert_eq!(result.to_string(), "(#\\ı #f)");
+}
+
+#[test]
+fn test_char_downcase_dotted_capital_i() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(char-downcase (integer->char 304))".to_string(), env).unwrap();
+    assert_eq!(result.to_string(), "#\\i");
+}
+
+#[test]
+fn test_char_upcase_simple_only() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval(
+        "(list (char-upcase (integer->char 8064)) (char-upcase (integer->char 8115)))".to_string(),
+        env,
+    )
+    .unwrap();
+    assert_eq!(result.to_string(), "(#\\ᾈ #\\ᾼ)");
+}
+
+#[test]
+fn test_char_foldcase_cherokee() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(char-foldcase (integer->char 43888))".to_string(), env).unwrap();
+    assert_eq!(result.to_string(), "#\\Ꭰ");
+}
+
+#[test]
+fn test_digit_value_ascii() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(digit-value #\\7)".to_string(), env).unwrap();
+    assert_eq!(result.to_string(), "7");
+}
+
+#[test]
+fn test_digit_value_arabic_indic() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(digit-value #\\x0664)".to_string(), env).unwrap();
+    assert_eq!(result.to_string(), "4");
+}
+
+#[test]
+fn test_digit_value_not_digit() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(digit-value #\\a)".to_string(), env).unwrap();
+    assert_eq!(result.to_string(), "#f");
+}
+
+#[test]
+fn test_char_numeric_devanagari() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(char-numeric? #\\x0967)".to_string(), env).unwrap();
+    assert_eq!(result.to_string(), "#t");
+}
+
+#[test]
+fn test_char_x_literal() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(char->integer #\\x)".to_string(), env).unwrap();
+    assert_eq!(result.to_string(), "120");
+}
+
+#[test]
+fn test_string_ci_eq_unicode() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(string-ci=? \"Straße\" \"STRAßE\")".to_string(), env).unwrap();
+    assert_eq!(result.to_string(), "#t");
+}
+
+#[test]
+fn test_memq_found() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(memq 'c '(a b c d))".to_string(), env).unwrap();
+    assert_eq!(result.to_string(), "(c d)");
+}
+
+#[test]
+fn test_memq_not_found() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(memq 'z '(a b c))".to_string(), env).unwrap();
+    assert_eq!(result.to_string(), "#f");
+}
+
+#[test]
+fn test_memv_number() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(memv 101 '(100 101 102))".to_string(), env).unwrap();
+    assert_eq!(result.to_string(), "(101 102)");
+}
+
+#[test]
+fn test_member_equal() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(member (list 'a) '(b (a) c))".to_string(), env).unwrap();
+    assert_eq!(result.to_string(), "((a) c)");
+}
+
+#[test]
+fn test_member_custom_procedure() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(member 2.0 '(1 2 3) =)".to_string(), env).unwrap();
+    assert_eq!(result.to_string(), "(2 3)");
+}
+
+#[test]
+fn test_member_custom_closure() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval(
+        "(member 5 '(1 7 3) (lambda (x y) (< x y)))".to_string(),
+        env,
+    )
+    .unwrap();
+    assert_eq!(result.to_string(), "(7 3)");
+}
+
+#[test]
+fn test_member_improper_list() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(member 'z '(a . b))".to_string(), env);
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_assq() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(assq 'b '((a 1) (b 2)))".to_string(), env).unwrap();
+    assert_eq!(result.to_string(), "(b 2)");
+}
+
+#[test]
+fn test_assv_not_found() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(assv 5 '((2 3) (7 11)))".to_string(), env).unwrap();
+    assert_eq!(result.to_string(), "#f");
+}
+
+#[test]
+fn test_assoc_equal() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(assoc (list 'a) '(((a)) ((b)) ((c))))".to_string(), env).unwrap();
+    assert_eq!(result.to_string(), "((a))");
+}
+
+#[test]
+fn test_assoc_custom_closure() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval(
+        "(assoc 2.0 '((1 1) (2 4) (3 9)) (lambda (a b) (= a b)))".to_string(),
+        env,
+    )
+    .unwrap();
+    assert_eq!(result.to_string(), "(2 4)");
+}
+
+#[test]
+fn test_assoc_non_pair_element() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(assoc 'a '(a b))".to_string(), env);
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_list_tail() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(list-tail '(a b c d) 2)".to_string(), env).unwrap();
+    assert_eq!(result.to_string(), "(c d)");
+}
+
+#[test]
+fn test_list_tail_out_of_range() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(list-tail '(a b) 3)".to_string(), env);
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_list_ref() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(list-ref '(a b c d) 2)".to_string(), env).unwrap();
+    assert_eq!(result.to_string(), "c");
+}
+
+#[test]
+fn test_list_ref_out_of_range() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(list-ref '(a b) 2)".to_string(), env);
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_list_set() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval(
+        "(let ((ls (list 'one 'two 'five))) (list-set! ls 2 'three) ls)".to_string(),
+        env,
+    )
+    .unwrap();
+    assert_eq!(result.to_string(), "(one two three)");
+}
+
+#[test]
+fn test_list_copy_is_fresh() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval(
+        "(let* ((a (list 1 2 3)) (b (list-copy a))) (set-car! b 9) a)".to_string(),
+        env,
+    )
+    .unwrap();
+    assert_eq!(result.to_string(), "(1 2 3)");
+}
+
+#[test]
+fn test_list_copy_improper_tail() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(list-copy '(1 2 . 3))".to_string(), env).unwrap();
+    assert_eq!(result.to_string(), "(1 2 . 3)");
+}
+
+#[test]
+fn test_list_copy_non_pair() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(list-copy 5)".to_string(), env).unwrap();
+    assert_eq!(result.to_string(), "5");
+}
+
+#[test]
+fn test_make_list() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(make-list 3 'x)".to_string(), env).unwrap();
+    assert_eq!(result.to_string(), "(x x x)");
+}
+
+#[test]
+fn test_make_list_empty() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(make-list 0 'x)".to_string(), env).unwrap();
+    assert_eq!(result.to_string(), "()");
+}
+
+#[test]
+fn test_parse_dotted_pair() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(cdr '(a . b))".to_string(), env).unwrap();
+    assert_eq!(result.to_string(), "b");
+}
+
+#[test]
+fn test_parse_dotted_tail_missing_close() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(car '(a . b c))".to_string(), env);
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_apply_builtin() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(apply + (list 3 4))".to_string(), env).unwrap();
+    assert_eq!(result.to_string(), "7");
+}
+
+#[test]
+fn test_apply_spreads_final_list() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(apply + 1 2 '(3 4))".to_string(), env).unwrap();
+    assert_eq!(result.to_string(), "10");
+}
+
+#[test]
+fn test_apply_closure() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(apply (lambda (a b) (- a b)) '(10 3))".to_string(), env).unwrap();
+    assert_eq!(result.to_string(), "7");
+}
+
+#[test]
+fn test_apply_improper_list() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(apply + 1 2)".to_string(), env);
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_map_builtin() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(map cadr '((a b) (d e) (g h)))".to_string(), env).unwrap();
+    assert_eq!(result.to_string(), "(b e h)");
+}
+
+#[test]
+fn test_map_closure() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(map (lambda (n) (* n n)) '(1 2 3 4))".to_string(), env).unwrap();
+    assert_eq!(result.to_string(), "(1 4 9 16)");
+}
+
+#[test]
+fn test_map_multiple_lists_shortest() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(map + '(1 2 3) '(10 20))".to_string(), env).unwrap();
+    assert_eq!(result.to_string(), "(11 22)");
+}
+
+#[test]
+fn test_map_empty() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(map car '())".to_string(), env).unwrap();
+    assert_eq!(result.to_string(), "()");
+}
+
+#[test]
+fn test_for_each() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval(
+        "(let ((v (vector 0 0 0))) (for-each (lambda (i x) (vector-set! v i x)) '(0 1 2) '(a b c)) v)".to_string(),
+        env,
+    )
+    .unwrap();
+    assert_eq!(result.to_string(), "#(a b c)");
+}
+
+#[test]
+fn test_string_map() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(string-map char-upcase \"abc\")".to_string(), env).unwrap();
+    assert_eq!(result.to_string(), "\"ABC\"");
+}
+
+#[test]
+fn test_string_map_multiple() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval(
+        "(string-map (lambda (a b) (if (char<? a b) a b)) \"adc\" \"bbbz\")".to_string(),
+        env,
+    )
+    .unwrap();
+    assert_eq!(result.to_string(), "\"abb\"");
+}
+
+#[test]
+fn test_string_map_non_char_result() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(string-map (lambda (c) 1) \"abc\")".to_string(), env);
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_string_for_each() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval(
+        "(let ((v (vector 0))) (string-for-each (lambda (c) (vector-set! v 0 (+ (vector-ref v 0) (char->integer c)))) \"ab\") (vector-ref v 0))"
+            .to_string(),
+        env,
+    )
+    .unwrap();
+    assert_eq!(result.to_string(), "195");
+}
+
+#[test]
+fn test_vector_map() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(vector-map + #(1 2) #(10 20 30))".to_string(), env).unwrap();
+    assert_eq!(result.to_string(), "#(11 22)");
+}
+
+#[test]
+fn test_vector_for_each() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval(
+        "(let ((v (vector 0))) (vector-for-each (lambda (x) (vector-set! v 0 (+ (vector-ref v 0) x))) #(1 2 3)) (vector-ref v 0))"
+            .to_string(),
+        env,
+    )
+    .unwrap();
+    assert_eq!(result.to_string(), "6");
+}
+
+#[test]
+fn test_xcons() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(xcons '(b c) 'a)".to_string(), env).unwrap();
+    assert_eq!(result.to_string(), "(a b c)");
+}
+
+#[test]
+fn test_cons_star() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(cons* 1 2 '(3 4))".to_string(), env).unwrap();
+    assert_eq!(result.to_string(), "(1 2 3 4)");
+}
+
+#[test]
+fn test_cons_star_dotted() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(cons* 1 2 3)".to_string(), env).unwrap();
+    assert_eq!(result.to_string(), "(1 2 . 3)");
+}
+
+#[test]
+fn test_list_tabulate() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(list-tabulate 4 (lambda (i) (* i i)))".to_string(), env).unwrap();
+    assert_eq!(result.to_string(), "(0 1 4 9)");
+}
+
+#[test]
+fn test_iota() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(iota 5)".to_string(), env).unwrap();
+    assert_eq!(result.to_string(), "(0 1 2 3 4)");
+}
+
+#[test]
+fn test_iota_start_step() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(iota 3 1 2)".to_string(), env).unwrap();
+    assert_eq!(result.to_string(), "(1 3 5)");
+}
+
+#[test]
+fn test_circular_list_take() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(take (circular-list 1 2) 5)".to_string(), env).unwrap();
+    assert_eq!(result.to_string(), "(1 2 1 2 1)");
+}
+
+#[test]
+fn test_proper_list_predicates() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval(
+        "(list (proper-list? '(1 2)) (proper-list? '(1 . 2)) (proper-list? (circular-list 1)))"
+            .to_string(),
+        env,
+    )
+    .unwrap();
+    assert_eq!(result.to_string(), "(#t #f #f)");
+}
+
+#[test]
+fn test_circular_list_predicate() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval(
+        "(list (circular-list? (circular-list 1 2)) (circular-list? '(1 2)))".to_string(),
+        env,
+    )
+    .unwrap();
+    assert_eq!(result.to_string(), "(#t #f)");
+}
+
+#[test]
+fn test_dotted_list_predicate() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval(
+        "(list (dotted-list? '(1 . 2)) (dotted-list? 3) (dotted-list? '(1)))".to_string(),
+        env,
+    )
+    .unwrap();
+    assert_eq!(result.to_string(), "(#t #t #f)");
+}
+
+#[test]
+fn test_length_plus_circular() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(length+ (circular-list 1 2 3))".to_string(), env).unwrap();
+    assert_eq!(result.to_string(), "#f");
+}
+
+#[test]
+fn test_length_plus_proper() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(length+ '(1 2 3))".to_string(), env).unwrap();
+    assert_eq!(result.to_string(), "3");
+}
+
+#[test]
+fn test_first_and_fifth() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval(
+        "(list (first '(a b c d e)) (fifth '(a b c d e)))".to_string(),
+        env,
+    )
+    .unwrap();
+    assert_eq!(result.to_string(), "(a e)");
+}
+
+#[test]
+fn test_take_dotted() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(take '(1 2 . d) 2)".to_string(), env).unwrap();
+    assert_eq!(result.to_string(), "(1 2)");
+}
+
+#[test]
+fn test_drop_dotted() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(drop '(1 2 . d) 2)".to_string(), env).unwrap();
+    assert_eq!(result.to_string(), "d");
+}
+
+#[test]
+fn test_take_right() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(take-right '(a b c d e) 2)".to_string(), env).unwrap();
+    assert_eq!(result.to_string(), "(d e)");
+}
+
+#[test]
+fn test_drop_right() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(drop-right '(a b c d e) 2)".to_string(), env).unwrap();
+    assert_eq!(result.to_string(), "(a b c)");
+}
+
+#[test]
+fn test_split_at() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(split-at '(a b c d e) 2)".to_string(), env).unwrap();
+    assert_eq!(result.to_string(), "((a b) (c d e))");
+}
+
+#[test]
+fn test_last() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(last '(1 2 3))".to_string(), env).unwrap();
+    assert_eq!(result.to_string(), "3");
+}
+
+#[test]
+fn test_last_pair_dotted() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(last-pair '(a b . c))".to_string(), env).unwrap();
+    assert_eq!(result.to_string(), "(b . c)");
+}
+
+#[test]
+fn test_last_circular_errors() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(last (circular-list 1 2))".to_string(), env);
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_append_variadic_dotted() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(append '(1) '(2 3) 4)".to_string(), env).unwrap();
+    assert_eq!(result.to_string(), "(1 2 3 . 4)");
+}
+
+#[test]
+fn test_append_empty() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(append)".to_string(), env).unwrap();
+    assert_eq!(result.to_string(), "()");
+}
+
+#[test]
+fn test_concatenate() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(concatenate '((1 2) (3) ()))".to_string(), env).unwrap();
+    assert_eq!(result.to_string(), "(1 2 3)");
+}
+
+#[test]
+fn test_append_reverse() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(append-reverse '(3 2 1) '(4 5))".to_string(), env).unwrap();
+    assert_eq!(result.to_string(), "(1 2 3 4 5)");
+}
+
+#[test]
+fn test_zip() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(zip '(one two three) '(1 2 3))".to_string(), env).unwrap();
+    assert_eq!(result.to_string(), "((one 1) (two 2) (three 3))");
+}
+
+#[test]
+fn test_unzip1() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(unzip1 '((1 a) (2 b) (3 c)))".to_string(), env).unwrap();
+    assert_eq!(result.to_string(), "(1 2 3)");
+}
+
+#[test]
+fn test_unzip2() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(unzip2 '((1 a) (2 b) (3 c)))".to_string(), env).unwrap();
+    assert_eq!(result.to_string(), "((1 2 3) (a b c))");
+}
+
+#[test]
+fn test_unzip3() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(unzip3 '((1 a x) (2 b y)))".to_string(), env).unwrap();
+    assert_eq!(result.to_string(), "((1 2) (a b) (x y))");
+}
+
+#[test]
+fn test_unzip2_short_element() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(unzip2 '((1) (2 b)))".to_string(), env);
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_count() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(count even? '(3 1 4 1 5 9 2 5 6))".to_string(), env).unwrap();
+    assert_eq!(result.to_string(), "3");
+}
+
+#[test]
+fn test_count_circular_and_finite() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result =
+        parse_and_eval("(count < '(1 2 4 8) (circular-list 2 3))".to_string(), env).unwrap();
+    assert_eq!(result.to_string(), "2");
+}
+
+#[test]
+fn test_fold() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(fold cons '() '(a b c))".to_string(), env).unwrap();
+    assert_eq!(result.to_string(), "(c b a)");
+}
+
+#[test]
+fn test_fold_multiple_lists() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(fold cons* '() '(a b c) '(1 2 3 4 5))".to_string(), env).unwrap();
+    assert_eq!(result.to_string(), "(c 3 b 2 a 1)");
+}
+
+#[test]
+fn test_fold_right() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(fold-right cons '() '(a b c))".to_string(), env).unwrap();
+    assert_eq!(result.to_string(), "(a b c)");
+}
+
+#[test]
+fn test_fold_all_circular_errors() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(fold + 0 (circular-list 1))".to_string(), env);
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_pair_fold() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(pair-fold cons '() '(a b c))".to_string(), env).unwrap();
+    assert_eq!(result.to_string(), "((c) (b c) (a b c))");
+}
+
+#[test]
+fn test_pair_fold_right() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(pair-fold-right cons '() '(a b c))".to_string(), env).unwrap();
+    assert_eq!(result.to_string(), "((a b c) (b c) (c))");
+}
+
+#[test]
+fn test_pair_fold_right_multiple_lists() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval(
+        "(pair-fold-right (lambda (p q acc) (cons (list (car p) (car q)) acc)) '() '(1 2 3) '(a b))".to_string(),
+        env,
+    )
+    .unwrap();
+    assert_eq!(result.to_string(), "((1 a) (2 b))");
+}
+
+#[test]
+fn test_reduce() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(reduce + 0 '(1 2 3 4))".to_string(), env).unwrap();
+    assert_eq!(result.to_string(), "10");
+}
+
+#[test]
+fn test_reduce_empty() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(reduce + 0 '())".to_string(), env).unwrap();
+    assert_eq!(result.to_string(), "0");
+}
+
+#[test]
+fn test_reduce_right() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval(
+        "(reduce-right append '() '((1 2) (3) (4 5)))".to_string(),
+        env,
+    )
+    .unwrap();
+    assert_eq!(result.to_string(), "(1 2 3 4 5)");
+}
+
+#[test]
+fn test_unfold() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval(
+        "(unfold (lambda (x) (> x 5)) (lambda (x) (* x x)) (lambda (x) (+ x 1)) 1)".to_string(),
+        env,
+    )
+    .unwrap();
+    assert_eq!(result.to_string(), "(1 4 9 16 25)");
+}
+
+#[test]
+fn test_unfold_right() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval(
+        "(unfold-right (lambda (x) (= x 0)) (lambda (x) (* x x)) (lambda (x) (- x 1)) 5)"
+            .to_string(),
+        env,
+    )
+    .unwrap();
+    assert_eq!(result.to_string(), "(1 4 9 16 25)");
+}
+
+#[test]
+fn test_append_map() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval(
+        "(append-map (lambda (x) (list x (- x))) '(1 3 8))".to_string(),
+        env,
+    )
+    .unwrap();
+    assert_eq!(result.to_string(), "(1 -1 3 -3 8 -8)");
+}
+
+#[test]
+fn test_filter_map() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval(
+        "(filter-map (lambda (x) (if (number? x) (* x x) #f)) '(a 1 b 3 c 7))".to_string(),
+        env,
+    )
+    .unwrap();
+    assert_eq!(result.to_string(), "(1 9 49)");
+}
+
+#[test]
+fn test_map_circular_and_finite() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(map + '(1 2 3) (circular-list 10))".to_string(), env).unwrap();
+    assert_eq!(result.to_string(), "(11 12 13)");
+}
+
+#[test]
+fn test_filter() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(filter even? '(0 7 8 8 43 -4))".to_string(), env).unwrap();
+    assert_eq!(result.to_string(), "(0 8 8 -4)");
+}
+
+#[test]
+fn test_remove() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(remove even? '(0 7 8 8 43 -4))".to_string(), env).unwrap();
+    assert_eq!(result.to_string(), "(7 43)");
+}
+
+#[test]
+fn test_partition() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval(
+        "(partition symbol? '(one 2 3 four five 6))".to_string(),
+        env,
+    )
+    .unwrap();
+    assert_eq!(result.to_string(), "((one four five) (2 3 6))");
+}
+
+#[test]
+fn test_find() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(find even? '(3 1 4 1 5 9))".to_string(), env).unwrap();
+    assert_eq!(result.to_string(), "4");
+}
+
+#[test]
+fn test_find_circular() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(find even? (circular-list 1 2))".to_string(), env).unwrap();
+    assert_eq!(result.to_string(), "2");
+}
+
+#[test]
+fn test_find_tail() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(find-tail even? '(3 1 37 -8 -5 0 0))".to_string(), env).unwrap();
+    assert_eq!(result.to_string(), "(-8 -5 0 0)");
+}
+
+#[test]
+fn test_find_tail_not_found() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(find-tail even? '(3 1 37 -5))".to_string(), env).unwrap();
+    assert_eq!(result.to_string(), "#f");
+}
+
+#[test]
+fn test_any() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(any integer? '(a 3 b 2.7))".to_string(), env).unwrap();
+    assert_eq!(result.to_string(), "#t");
+}
+
+#[test]
+fn test_any_multiple() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(any < '(3 1 4 1 5) '(2 7 1 8 2))".to_string(), env).unwrap();
+    assert_eq!(result.to_string(), "#t");
+}
+
+#[test]
+fn test_every() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval(
+        "(every (lambda (x) (if (integer? x) x #f)) '(1 2 3))".to_string(),
+        env,
+    )
+    .unwrap();
+    assert_eq!(result.to_string(), "3");
+}
+
+#[test]
+fn test_every_empty() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(every even? '())".to_string(), env).unwrap();
+    assert_eq!(result.to_string(), "#t");
+}
+
+#[test]
+fn test_list_index() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(list-index even? '(3 1 4 1 5 9))".to_string(), env).unwrap();
+    assert_eq!(result.to_string(), "2");
+}
+
+#[test]
+fn test_take_while() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(take-while even? '(2 18 3 10 22 9))".to_string(), env).unwrap();
+    assert_eq!(result.to_string(), "(2 18)");
+}
+
+#[test]
+fn test_drop_while() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(drop-while even? '(2 18 3 10 22 9))".to_string(), env).unwrap();
+    assert_eq!(result.to_string(), "(3 10 22 9)");
+}
+
+#[test]
+fn test_span() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(span even? '(2 18 3 10 22 9))".to_string(), env).unwrap();
+    assert_eq!(result.to_string(), "((2 18) (3 10 22 9))");
+}
+
+#[test]
+fn test_break() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(break even? '(3 1 4 1 5 9 2 6))".to_string(), env).unwrap();
+    assert_eq!(result.to_string(), "((3 1) (4 1 5 9 2 6))");
+}
+
+#[test]
+fn test_delete() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(delete 5 '(1 5 2 5 3))".to_string(), env).unwrap();
+    assert_eq!(result.to_string(), "(1 2 3)");
+}
+
+#[test]
+fn test_delete_custom() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(delete 5 '(1 6 2 7 3) <)".to_string(), env).unwrap();
+    assert_eq!(result.to_string(), "(1 2 3)");
+}
+
+#[test]
+fn test_delete_duplicates() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(delete-duplicates '(a b a c a b c z))".to_string(), env).unwrap();
+    assert_eq!(result.to_string(), "(a b c z)");
+}
+
+#[test]
+fn test_alist_cons() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(alist-cons 'a 1 '((b . 2)))".to_string(), env).unwrap();
+    assert_eq!(result.to_string(), "((a . 1) (b . 2))");
+}
+
+#[test]
+fn test_alist_copy_fresh() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval(
+        "(let* ((a (list (cons 'a 1))) (b (alist-copy a))) (set-cdr! (car b) 2) a)".to_string(),
+        env,
+    )
+    .unwrap();
+    assert_eq!(result.to_string(), "((a . 1))");
+}
+
+#[test]
+fn test_alist_delete() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval(
+        "(alist-delete 'a '((a . 1) (b . 2) (a . 3)))".to_string(),
+        env,
+    )
+    .unwrap();
+    assert_eq!(result.to_string(), "((b . 2))");
+}
+
+#[test]
+fn test_lset_le() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(lset<= eq? '(a) '(a b a) '(a b c c))".to_string(), env).unwrap();
+    assert_eq!(result.to_string(), "#t");
+}
+
+#[test]
+fn test_lset_eq() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result =
+        parse_and_eval("(lset= eq? '(b e a) '(a e b) '(e e b a))".to_string(), env).unwrap();
+    assert_eq!(result.to_string(), "#t");
+}
+
+#[test]
+fn test_lset_adjoin() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval(
+        "(lset-adjoin eq? '(a b c d c e) 'a 'e 'i 'o 'u)".to_string(),
+        env,
+    )
+    .unwrap();
+    assert_eq!(result.to_string(), "(u o i a b c d c e)");
+}
+
+#[test]
+fn test_lset_union() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval(
+        "(lset-union eq? '(a b c d e) '(a e i o u))".to_string(),
+        env,
+    )
+    .unwrap();
+    assert_eq!(result.to_string(), "(u o i a b c d e)");
+}
+
+#[test]
+fn test_lset_intersection() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval(
+        "(lset-intersection eq? '(a b c d e) '(a e i o u))".to_string(),
+        env,
+    )
+    .unwrap();
+    assert_eq!(result.to_string(), "(a e)");
+}
+
+#[test]
+fn test_lset_difference() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval(
+        "(lset-difference eq? '(a b c d e) '(a e i o u))".to_string(),
+        env,
+    )
+    .unwrap();
+    assert_eq!(result.to_string(), "(b c d)");
+}
+
+#[test]
+fn test_lset_diff_intersection() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval(
+        "(lset-diff+intersection eq? '(a b c d e) '(a e i o u))".to_string(),
+        env,
+    )
+    .unwrap();
+    assert_eq!(result.to_string(), "((b c d) (a e))");
+}
+
+#[test]
+fn test_lset_diff_intersection_no_others() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(lset-diff+intersection eq? '(a b))".to_string(), env).unwrap();
+    assert_eq!(result.to_string(), "((a b) ())");
+}
+
+#[test]
+fn test_lset_xor() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result =
+        parse_and_eval("(lset-xor eq? '(a b c d e) '(a e i o u))".to_string(), env).unwrap();
+    assert_eq!(result.to_string(), "(b c d i o u)");
+}
+
+#[test]
+fn test_reverse_empty() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(reverse '())".to_string(), env).unwrap();
+    assert_eq!(result.to_string(), "()");
+}
+
+#[test]
+fn test_hash_table_set_ref() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval(
+        "(let ((h (make-hash-table))) (hash-table-set! h 'a 1 'b 2) (list (hash-table-ref h 'a) (hash-table-ref h 'b)))".to_string(),
+        env,
+    )
+    .unwrap();
+    assert_eq!(result.to_string(), "(1 2)");
+}
+
+#[test]
+fn test_hash_table_ref_missing() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(hash-table-ref (make-hash-table) 'a)".to_string(), env);
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_hash_table_ref_failure() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval(
+        "(hash-table-ref (make-hash-table) 'a (lambda () 'none))".to_string(),
+        env,
+    )
+    .unwrap();
+    assert_eq!(result.to_string(), "none");
+}
+
+#[test]
+fn test_hash_table_ref_success() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval(
+        "(let ((h (make-hash-table))) (hash-table-set! h 'a 1) (hash-table-ref h 'a (lambda () 0) (lambda (x) (+ x 10))))".to_string(),
+        env,
+    )
+    .unwrap();
+    assert_eq!(result.to_string(), "11");
+}
+
+#[test]
+fn test_hash_table_ref_default() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval(
+        "(hash-table-ref/default (make-hash-table) 'a 42)".to_string(),
+        env,
+    )
+    .unwrap();
+    assert_eq!(result.to_string(), "42");
+}
+
+#[test]
+fn test_hash_table_equal_keys() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval(
+        "(let ((h (make-hash-table equal?))) (hash-table-set! h (list 1 2) 'x) (hash-table-ref/default h (list 1 2) #f))".to_string(),
+        env,
+    )
+    .unwrap();
+    assert_eq!(result.to_string(), "x");
+}
+
+#[test]
+fn test_hash_table_eqv_keys() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval(
+        "(let ((h (make-hash-table eqv?))) (hash-table-set! h (list 1 2) 'x) (hash-table-set! h 3 'y) (list (hash-table-ref/default h (list 1 2) #f) (hash-table-ref/default h 3 #f)))".to_string(),
+        env,
+    )
+    .unwrap();
+    assert_eq!(result.to_string(), "(#f y)");
+}
+
+#[test]
+fn test_hash_table_string_keys() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval(
+        "(let ((h (make-hash-table string=?))) (hash-table-set! h \"key\" 1) (hash-table-ref/default h (string-copy \"key\") #f))".to_string(),
+        env,
+    )
+    .unwrap();
+    assert_eq!(result.to_string(), "1");
+}
+
+#[test]
+fn test_hash_table_string_keys_non_string() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval(
+        "(let ((h (make-hash-table string=?))) (hash-table-set! h 'key 1))".to_string(),
+        env,
+    );
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_hash_table_exact_inexact_keys() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval(
+        "(let ((h (make-hash-table eqv?))) (hash-table-set! h 1 'exact 1.0 'inexact) (list (hash-table-ref h 1) (hash-table-ref h 1.0) (hash-table-size h)))".to_string(),
+        env,
+    )
+    .unwrap();
+    assert_eq!(result.to_string(), "(exact inexact 2)");
+}
+
+#[test]
+fn test_hash_table_delete() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval(
+        "(let ((h (make-hash-table))) (hash-table-set! h 'a 1 'b 2) (list (hash-table-delete! h 'a 'c) (hash-table-contains? h 'a) (hash-table-size h)))".to_string(),
+        env,
+    )
+    .unwrap();
+    assert_eq!(result.to_string(), "(1 #f 1)");
+}
+
+#[test]
+fn test_hash_table_update() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval(
+        "(let ((h (make-hash-table))) (hash-table-set! h 'a 1) (hash-table-update! h 'a (lambda (x) (+ x 1))) (hash-table-ref h 'a))".to_string(),
+        env,
+    )
+    .unwrap();
+    assert_eq!(result.to_string(), "2");
+}
+
+#[test]
+fn test_hash_table_update_missing() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval(
+        "(let ((h (make-hash-table))) (hash-table-update! h 'a (lambda (x) (+ x 1))))".to_string(),
+        env,
+    );
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_hash_table_update_default() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval(
+        "(let ((h (make-hash-table))) (hash-table-update!/default h 'a (lambda (x) (cons 1 x)) '()) (hash-table-update!/default h 'a (lambda (x) (cons 2 x)) '()) (hash-table-ref h 'a))".to_string(),
+        env,
+    )
+    .unwrap();
+    assert_eq!(result.to_string(), "(2 1)");
+}
+
+#[test]
+fn test_hash_table_walk() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval(
+        "(let ((h (make-hash-table)) (v (vector 0))) (hash-table-set! h 'a 1 'b 2 'c 3) (hash-table-walk h (lambda (k x) (vector-set! v 0 (+ x (vector-ref v 0))))) (vector-ref v 0))".to_string(),
+        env,
+    )
+    .unwrap();
+    assert_eq!(result.to_string(), "6");
+}
+
+#[test]
+fn test_hash_table_fold() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval(
+        "(let ((h (make-hash-table))) (hash-table-set! h 'a 1 'b 2) (hash-table-fold h (lambda (k v acc) (+ v acc)) 0))".to_string(),
+        env,
+    )
+    .unwrap();
+    assert_eq!(result.to_string(), "3");
+}
+
+#[test]
+fn test_hash_table_keys_values() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval(
+        "(let ((h (make-hash-table))) (hash-table-set! h 1 10 2 20 3 30) (list (apply + (hash-table-keys h)) (apply + (hash-table-values h))))".to_string(),
+        env,
+    )
+    .unwrap();
+    assert_eq!(result.to_string(), "(6 60)");
+}
+
+#[test]
+fn test_hash_table_to_alist() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval(
+        "(let ((h (alist->hash-table '((a . 1) (a . 2))))) (hash-table->alist h))".to_string(),
+        env,
+    )
+    .unwrap();
+    assert_eq!(result.to_string(), "((a . 1))");
+}
+
+#[test]
+fn test_hash_table_copy_clear() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval(
+        "(let* ((h (make-hash-table)) (c (hash-table-copy h))) (hash-table-set! h 'a 1) (hash-table-set! c 'b 2) (hash-table-clear! h) (list (hash-table-size h) (hash-table-size c)))".to_string(),
+        env,
+    )
+    .unwrap();
+    assert_eq!(result.to_string(), "(0 1)");
+}
+
+#[test]
+fn test_is_hash_table() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval(
+        "(list (hash-table? (make-hash-table)) (hash-table? '()))".to_string(),
+        env,
+    )
+    .unwrap();
+    assert_eq!(result.to_string(), "(#t #f)");
+}
+
+#[test]
+fn test_equal_hash_structural() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval(
+        "(= (equal-hash (list 1 \"a\" #(2))) (equal-hash (list 1 \"a\" #(2))))".to_string(),
+        env,
+    )
+    .unwrap();
+    assert_eq!(result.to_string(), "#t");
+}
+
+#[test]
+fn test_equal_hash_bignum() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval(
+        "(= (equal-hash (* 99999999999 99999999999)) (equal-hash (* 99999999999 99999999999)))"
+            .to_string(),
+        env,
+    )
+    .unwrap();
+    assert_eq!(result.to_string(), "#t");
+}
+
+#[test]
+fn test_string_hash_bound() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval(
+        "(let ((n (string-hash \"abc\" 10))) (if (< n 10) (>= n 0) #f))".to_string(),
+        env,
+    )
+    .unwrap();
+    assert_eq!(result.to_string(), "#t");
+}
+
+#[test]
+fn test_symbol_eq_interned() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(eq? 'abc (string->symbol \"abc\"))".to_string(), env).unwrap();
+    assert_eq!(result.to_string(), "#t");
+}
+
+#[test]
+fn test_string_to_uninterned_symbol() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval(
+        "(let ((s (string->uninterned-symbol \"abc\"))) (list s (eq? s 'abc) (symbol? s) (symbol-interned? s) (symbol-interned? 'abc)))".to_string(),
+        env,
+    )
+    .unwrap();
+    assert_eq!(result.to_string(), "(abc #f #t #f #t)");
+}
+
+#[test]
+fn test_uninterned_symbols_distinct() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval(
+        "(eq? (string->uninterned-symbol \"a\") (string->uninterned-symbol \"a\"))".to_string(),
+        env,
+    )
+    .unwrap();
+    assert_eq!(result.to_string(), "#f");
+}
+
+#[test]
+fn test_gensym_unique() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval(
+        "(let ((a (gensym)) (b (gensym))) (list (eq? a b) (eq? a a) (symbol-interned? a)))"
+            .to_string(),
+        env,
+    )
+    .unwrap();
+    assert_eq!(result.to_string(), "(#f #t #f)");
+}
+
+#[test]
+fn test_predefined_symbols_are_interned() {
+    use crate::types::symbol::Symbol;
+    assert_eq!(Symbol::intern("define"), Symbol::DEFINE);
+    assert_eq!(Symbol::intern("letrec*"), Symbol::LETREC_STAR);
+    assert_eq!(Symbol::intern(","), Symbol::UNQUOTE);
+    assert_eq!(Symbol::LITTLE.to_string(), "little");
+    assert_ne!(Symbol::uninterned("define"), Symbol::DEFINE);
+}
+
+#[test]
+fn test_generate_uninterned_symbol_prefix() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval(
+        "(let ((s (generate-uninterned-symbol \"tmp\"))) (string=? (substring (symbol->string s) 0 3) \"tmp\"))".to_string(),
+        env,
+    )
+    .unwrap();
+    assert_eq!(result.to_string(), "#t");
+}
+
+#[test]
+fn test_symbol_to_string_uninterned() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval(
+        "(symbol->string (string->uninterned-symbol \"xyz\"))".to_string(),
+        env,
+    )
+    .unwrap();
+    assert_eq!(result.to_string(), "\"xyz\"");
+}
+
+#[test]
+fn test_force_delay() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(force (delay (+ 1 2)))".to_string(), env).unwrap();
+    assert_eq!(result.to_string(), "3");
+}
+
+#[test]
+fn test_delay_memoizes() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval(
+        "(let* ((n (vector 0)) (p (delay (begin (vector-set! n 0 (+ (vector-ref n 0) 1)) 'x)))) (force p) (force p) (vector-ref n 0))".to_string(),
+        env,
+    )
+    .unwrap();
+    assert_eq!(result.to_string(), "1");
+}
+
+#[test]
+fn test_delay_force() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(force (delay-force (delay 'done)))".to_string(), env).unwrap();
+    assert_eq!(result.to_string(), "done");
+}
+
+#[test]
+fn test_delay_force_non_promise() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(force (delay-force 1))".to_string(), env);
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_force_non_promise() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(force 7)".to_string(), env).unwrap();
+    assert_eq!(result.to_string(), "7");
+}
+
+#[test]
+fn test_make_promise() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval(
+        "(let ((p (make-promise 5))) (list (promise? p) (force p) (eq? p (make-promise p))))"
+            .to_string(),
+        env,
+    )
+    .unwrap();
+    assert_eq!(result.to_string(), "(#t 5 #t)");
+}
+
+#[test]
+fn test_is_promise() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result =
+        parse_and_eval("(list (promise? (delay 1)) (promise? 1))".to_string(), env).unwrap();
+    assert_eq!(result.to_string(), "(#t #f)");
+}
+
+#[test]
+fn test_force_reentrant() {
+    use crate::{env::Env, parser::parse_and_eval};
+    // R7RS 4.2.5: a promise forced again while it is being forced keeps its first value.
+    let env = Env::standard_env();
+    parse_and_eval("(define count (vector 0))".to_string(), env.clone()).unwrap();
+    parse_and_eval("(define x (vector 5))".to_string(), env.clone()).unwrap();
+    parse_and_eval(
+        "(define p (delay (begin (vector-set! count 0 (+ (vector-ref count 0) 1)) (if (> (vector-ref count 0) (vector-ref x 0)) (vector-ref count 0) (force p)))))".to_string(),
+        env.clone(),
+    )
+    .unwrap();
+    let result = parse_and_eval("(force p)".to_string(), env.clone()).unwrap();
+    assert_eq!(result.to_string(), "6");
+    parse_and_eval("(vector-set! x 0 10)".to_string(), env.clone()).unwrap();
+    let result = parse_and_eval("(force p)".to_string(), env).unwrap();
+    assert_eq!(result.to_string(), "6");
+}
+
+#[test]
+fn test_delay_force_iterative() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    parse_and_eval(
+        "(define (loop n) (delay-force (if (= n 0) (delay 'done) (loop (- n 1)))))".to_string(),
+        env.clone(),
+    )
+    .unwrap();
+    let result = parse_and_eval("(force (loop 100000))".to_string(), env).unwrap();
+    assert_eq!(result.to_string(), "done");
+}
+
+#[test]
+fn test_lazy_stream_filter() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    parse_and_eval(
+        "(define (from n) (delay (cons n (from (+ n 1)))))".to_string(),
+        env.clone(),
+    )
+    .unwrap();
+    parse_and_eval(
+        "(define (stream-filter keep? s) (delay-force (let ((h (car (force s))) (t (cdr (force s)))) (if (keep? h) (delay (cons h (stream-filter keep? t))) (stream-filter keep? t)))))".to_string(),
+        env.clone(),
+    )
+    .unwrap();
+    let result = parse_and_eval(
+        "(car (force (stream-filter (lambda (n) (= n 5000)) (from 0))))".to_string(),
+        env,
+    )
+    .unwrap();
+    assert_eq!(result.to_string(), "5000");
+}
+
+#[test]
+fn test_eq_same_pair() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(let ((p (list 1 2))) (eq? p p))".to_string(), env).unwrap();
+    assert_eq!(result.to_string(), "#t");
+}
+
+#[test]
+fn test_eq_distinct_pairs() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(eq? (list 1 2) (list 1 2))".to_string(), env).unwrap();
+    assert_eq!(result.to_string(), "#f");
+}
+
+#[test]
+fn test_eq_strings() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval(
+        "(let ((s (string-copy \"abc\"))) (list (eq? s s) (eq? s (string-copy \"abc\"))))"
+            .to_string(),
+        env,
+    )
+    .unwrap();
+    assert_eq!(result.to_string(), "(#t #f)");
+}
+
+#[test]
+fn test_eq_closures() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval(
+        "(let ((f (lambda (x) x))) (list (eq? f f) (eq? f (lambda (x) x))))".to_string(),
+        env,
+    )
+    .unwrap();
+    assert_eq!(result.to_string(), "(#t #f)");
+}
+
+#[test]
+fn test_eq_builtin_procedures() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(list (eq? car car) (eq? car cdr))".to_string(), env).unwrap();
+    assert_eq!(result.to_string(), "(#t #f)");
+}
+
+#[test]
+fn test_eq_ports() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval(
+        "(list (eq? (current-output-port) (current-output-port)) (eq? (current-output-port) (open-output-string)))".to_string(),
+        env,
+    )
+    .unwrap();
+    assert_eq!(result.to_string(), "(#t #f)");
+}
+
+#[test]
+fn test_eq_empty_list_and_symbols() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval(
+        "(list (eq? '() '()) (eq? 'a 'a) (eq? 'a 'b))".to_string(),
+        env,
+    )
+    .unwrap();
+    assert_eq!(result.to_string(), "(#t #t #f)");
+}
+
+#[test]
+fn test_eq_chars() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(eq? #\\a #\\a)".to_string(), env).unwrap();
+    assert_eq!(result.to_string(), "#t");
+}
+
+#[test]
+fn test_eqv_exactness() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval(
+        "(list (eqv? 2 2.0) (eqv? 2 2) (eqv? 2.0 2.0) (eqv? 1/2 0.5))".to_string(),
+        env,
+    )
+    .unwrap();
+    assert_eq!(result.to_string(), "(#f #t #t #f)");
+}
+
+#[test]
+fn test_eqv_negative_zero() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval(
+        "(list (eqv? 0.0 -0.0) (eqv? -0.0 -0.0) (= 0.0 -0.0))".to_string(),
+        env,
+    )
+    .unwrap();
+    assert_eq!(result.to_string(), "(#f #t #t)");
+}
+
+#[test]
+fn test_eqv_nan() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(eqv? +nan.0 +nan.0)".to_string(), env).unwrap();
+    assert_eq!(result.to_string(), "#t");
+}
+
+#[test]
+fn test_eqv_rationals() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(eqv? 1/3 (/ 2 6))".to_string(), env).unwrap();
+    assert_eq!(result.to_string(), "#t");
+}
+
+#[test]
+fn test_eqv_bignums() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval(
+        "(eqv? (* 99999999999 99999999999) (* 99999999999 99999999999))".to_string(),
+        env,
+    )
+    .unwrap();
+    assert_eq!(result.to_string(), "#t");
+}
+
+#[test]
+fn test_eqv_complex() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval(
+        "(list (eqv? 1+2i 1+2i) (eqv? 1.0+2.0i 1+2i))".to_string(),
+        env,
+    )
+    .unwrap();
+    assert_eq!(result.to_string(), "(#t #f)");
+}
+
+#[test]
+fn test_memq_uses_eq() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(memq (list 1) (list (list 1) 2))".to_string(), env).unwrap();
+    assert_eq!(result.to_string(), "#f");
+}
+
+#[test]
+fn test_equal_long_lists() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(equal? (iota 5000) (iota 5000))".to_string(), env).unwrap();
+    assert_eq!(result.to_string(), "#t");
+}
+
+#[test]
+fn test_equal_cyclic_lists() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval(
+        "(let ((a (list 1 2)) (b (list 1 2 1 2))) (set-cdr! (cdr a) a) (set-cdr! (cdddr b) b) (equal? a b))".to_string(),
+        env,
+    )
+    .unwrap();
+    assert_eq!(result.to_string(), "#t");
+}
+
+#[test]
+fn test_equal_cyclic_lists_differ() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval(
+        "(let ((a (list 1 2)) (b (list 1 3))) (set-cdr! (cdr a) a) (set-cdr! (cdr b) b) (equal? a b))".to_string(),
+        env,
+    )
+    .unwrap();
+    assert_eq!(result.to_string(), "#f");
+}
+
+#[test]
+fn test_hash_table_eq_keys() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval(
+        "(let ((h (make-hash-table eq?)) (k (string-copy \"k\"))) (hash-table-set! h k 1) (list (hash-table-ref/default h k #f) (hash-table-ref/default h (string-copy \"k\") #f)))".to_string(),
+        env,
+    )
+    .unwrap();
+    assert_eq!(result.to_string(), "(1 #f)");
+}
+
+#[test]
+fn test_char_set_contains() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval(
+        "(let ((cs (char-set #\\a #\\b))) (list (char-set-contains? cs #\\a) (char-set-contains? cs #\\c)))".to_string(),
+        env,
+    )
+    .unwrap();
+    assert_eq!(result.to_string(), "(#t #f)");
+}
+
+#[test]
+fn test_is_char_set() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval(
+        "(list (char-set? (char-set)) (char-set? \"abc\"))".to_string(),
+        env,
+    )
+    .unwrap();
+    assert_eq!(result.to_string(), "(#t #f)");
+}
+
+#[test]
+fn test_char_set_union() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval(
+        "(char-set->string (char-set-union (char-set #\\a #\\c) (string->char-set \"cb\")))"
+            .to_string(),
+        env,
+    )
+    .unwrap();
+    assert_eq!(result.to_string(), "\"abc\"");
+}
+
+#[test]
+fn test_char_set_intersection() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval(
+        "(char-set->string (char-set-intersection (string->char-set \"abcd\") (string->char-set \"bcde\")))".to_string(),
+        env,
+    )
+    .unwrap();
+    assert_eq!(result.to_string(), "\"bcd\"");
+}
+
+#[test]
+fn test_char_set_difference() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval(
+        "(char-set->string (char-set-difference (string->char-set \"abcd\") (char-set #\\b) (char-set #\\d)))".to_string(),
+        env,
+    )
+    .unwrap();
+    assert_eq!(result.to_string(), "\"ac\"");
+}
+
+#[test]
+fn test_char_set_xor() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval(
+        "(char-set->string (char-set-xor (string->char-set \"abc\") (string->char-set \"bcd\")))"
+            .to_string(),
+        env,
+    )
+    .unwrap();
+    assert_eq!(result.to_string(), "\"ad\"");
+}
+
+#[test]
+fn test_char_set_complement() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval(
+        "(let ((cs (char-set-complement (char-set #\\a)))) (list (char-set-contains? cs #\\a) (char-set-contains? cs #\\b) (char-set-size (char-set-complement cs))))".to_string(),
+        env,
+    )
+    .unwrap();
+    assert_eq!(result.to_string(), "(#f #t 1)");
+}
+
+#[test]
+fn test_char_set_full_size() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(char-set-size char-set:full)".to_string(), env).unwrap();
+    assert_eq!(result.to_string(), "1112064");
+}
+
+#[test]
+fn test_ucs_range_to_char_set() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval(
+        "(char-set->list (ucs-range->char-set 97 100))".to_string(),
+        env,
+    )
+    .unwrap();
+    assert_eq!(result.to_string(), "(#\\a #\\b #\\c)");
+}
+
+#[test]
+fn test_list_to_char_set_base() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval(
+        "(char-set->string (list->char-set (list #\\z) (char-set #\\a)))".to_string(),
+        env,
+    )
+    .unwrap();
+    assert_eq!(result.to_string(), "\"az\"");
+}
+
+#[test]
+fn test_char_set_adjoin_delete() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval(
+        "(char-set->string (char-set-delete (char-set-adjoin (char-set #\\a) #\\b #\\c) #\\a))"
+            .to_string(),
+        env,
+    )
+    .unwrap();
+    assert_eq!(result.to_string(), "\"bc\"");
+}
+
+#[test]
+fn test_char_set_equal() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval(
+        "(list (char-set= (string->char-set \"ab\") (char-set #\\b #\\a)) (char-set<= (char-set #\\a) (string->char-set \"ab\")) (char-set<= (string->char-set \"ab\") (char-set #\\a)))".to_string(),
+        env,
+    )
+    .unwrap();
+    assert_eq!(result.to_string(), "(#t #t #f)");
+}
+
+#[test]
+fn test_char_set_predefined_letter() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval(
+        "(list (char-set-contains? char-set:letter #\\a) (char-set-contains? char-set:letter #\\λ) (char-set-contains? char-set:letter #\\1))".to_string(),
+        env,
+    )
+    .unwrap();
+    assert_eq!(result.to_string(), "(#t #t #f)");
+}
+
+#[test]
+fn test_char_set_predefined_digit() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval(
+        "(list (char-set-contains? char-set:digit #\\7) (char-set-contains? char-set:digit #\\٣) (char-set-contains? char-set:digit #\\a))".to_string(),
+        env,
+    )
+    .unwrap();
+    assert_eq!(result.to_string(), "(#t #t #f)");
+}
+
+#[test]
+fn test_char_set_predefined_whitespace() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval(
+        "(list (char-set-contains? char-set:whitespace #\\space) (char-set-contains? char-set:whitespace #\\x3000) (char-set-contains? char-set:blank #\\newline))".to_string(),
+        env,
+    )
+    .unwrap();
+    assert_eq!(result.to_string(), "(#t #t #f)");
+}
+
+#[test]
+fn test_char_set_predefined_case() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval(
+        "(list (char-set-contains? char-set:upper-case #\\Ä) (char-set-contains? char-set:lower-case #\\ß) (char-set-contains? char-set:title-case #\\x01C5))".to_string(),
+        env,
+    )
+    .unwrap();
+    assert_eq!(result.to_string(), "(#t #t #t)");
+}
+
+#[test]
+fn test_char_set_hex_digit_size() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(char-set-size char-set:hex-digit)".to_string(), env).unwrap();
+    assert_eq!(result.to_string(), "22");
+}
+
+#[test]
+fn test_char_set_fold() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval(
+        "(char-set-fold cons '() (string->char-set \"abc\"))".to_string(),
+        env,
+    )
+    .unwrap();
+    assert_eq!(result.to_string(), "(#\\c #\\b #\\a)");
+}
+
+#[test]
+fn test_char_set_count_filter() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval(
+        "(list (char-set-count char-upper-case? (string->char-set \"aBcD\")) (char-set->string (char-set-filter char-upper-case? (string->char-set \"aBcD\"))))".to_string(),
+        env,
+    )
+    .unwrap();
+    assert_eq!(result.to_string(), "(2 \"BD\")");
+}
+
+#[test]
+fn test_char_set_map() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval(
+        "(char-set->string (char-set-map char-upcase (string->char-set \"abc\")))".to_string(),
+        env,
+    )
+    .unwrap();
+    assert_eq!(result.to_string(), "\"ABC\"");
+}
+
+#[test]
+fn test_char_set_any_every() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval(
+        "(list (char-set-any char-upper-case? (string->char-set \"aB\")) (char-set-every char-upper-case? (string->char-set \"aB\")))".to_string(),
+        env,
+    )
+    .unwrap();
+    assert_eq!(result.to_string(), "(#t #f)");
+}
+
+#[test]
+fn test_char_set_cursor() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval(
+        "(let* ((cs (string->char-set \"xa\")) (c (char-set-cursor cs)) (d (char-set-cursor-next cs c))) (list (char-set-ref cs c) (char-set-ref cs d) (end-of-char-set? (char-set-cursor-next cs d))))".to_string(),
+        env,
+    )
+    .unwrap();
+    assert_eq!(result.to_string(), "(#\\a #\\x #t)");
+}
+
+#[test]
+fn test_char_set_equal_structural() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval(
+        "(equal? (char-set #\\a) (string->char-set \"a\"))".to_string(),
+        env,
+    )
+    .unwrap();
+    assert_eq!(result.to_string(), "#t");
+}
+
+#[test]
+fn test_char_set_contains_non_char() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval(
+        "(char-set-contains? (char-set #\\a) \"a\")".to_string(),
+        env,
+    );
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_string_index_char() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(string-index \"hello\" #\\l)".to_string(), env).unwrap();
+    assert_eq!(result.to_string(), "2");
+}
+
+#[test]
+fn test_string_index_predicate() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result =
+        parse_and_eval("(string-index \"abc123\" char-numeric?)".to_string(), env).unwrap();
+    assert_eq!(result.to_string(), "3");
+}
+
+#[test]
+fn test_string_index_not_found() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(string-index \"hello\" #\\z)".to_string(), env).unwrap();
+    assert_eq!(result.to_string(), "#f");
+}
+
+#[test]
+fn test_string_index_right() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(string-index-right \"hello\" #\\l)".to_string(), env).unwrap();
+    assert_eq!(result.to_string(), "3");
+}
+
+#[test]
+fn test_string_skip() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result =
+        parse_and_eval("(string-skip \"   hi\" char-whitespace?)".to_string(), env).unwrap();
+    assert_eq!(result.to_string(), "3");
+}
+
+#[test]
+fn test_string_index_unicode() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(string-index \"λx→y\" #\\→)".to_string(), env).unwrap();
+    assert_eq!(result.to_string(), "2");
+}
+
+#[test]
+fn test_string_contains() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result =
+        parse_and_eval("(string-contains \"hello world\" \"o w\")".to_string(), env).unwrap();
+    assert_eq!(result.to_string(), "4");
+}
+
+#[test]
+fn test_string_contains_range() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(string-contains \"abcabc\" \"abc\" 1)".to_string(), env).unwrap();
+    assert_eq!(result.to_string(), "3");
+}
+
+#[test]
+fn test_string_contains_right() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result =
+        parse_and_eval("(string-contains-right \"abcabc\" \"bc\")".to_string(), env).unwrap();
+    assert_eq!(result.to_string(), "4");
+}
+
+#[test]
+fn test_string_search_forward() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval(
+        "(string-search-forward \"na\" \"banana\" 3)".to_string(),
+        env,
+    )
+    .unwrap();
+    assert_eq!(result.to_string(), "4");
+}
+
+#[test]
+fn test_string_search_backward() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval(
+        "(string-search-backward \"na\" \"banana\" 6)".to_string(),
+        env,
+    )
+    .unwrap();
+    assert_eq!(result.to_string(), "6");
+}
+
+#[test]
+fn test_string_search_all() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(string-search-all \"a\" \"banana\")".to_string(), env).unwrap();
+    assert_eq!(result.to_string(), "(1 3 5)");
+}
+
+#[test]
+fn test_string_prefix() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval(
+        "(list (string-prefix? \"he\" \"hello\") (string-prefix? \"lo\" \"hello\"))".to_string(),
+        env,
+    )
+    .unwrap();
+    assert_eq!(result.to_string(), "(#t #f)");
+}
+
+#[test]
+fn test_string_suffix() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval(
+        "(list (string-suffix? \"lo\" \"hello\") (string-suffix-ci? \"LO\" \"hello\"))".to_string(),
+        env,
+    )
+    .unwrap();
+    assert_eq!(result.to_string(), "(#t #t)");
+}
+
+#[test]
+fn test_string_split_char() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(string-split \"a,b,,c\" #\\,)".to_string(), env).unwrap();
+    assert_eq!(result.to_string(), "(\"a\" \"b\" \"\" \"c\")");
+}
+
+#[test]
+fn test_string_split_string() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(string-split \"a::b::c\" \"::\")".to_string(), env).unwrap();
+    assert_eq!(result.to_string(), "(\"a\" \"b\" \"c\")");
+}
+
+#[test]
+fn test_string_split_limit() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval(
+        "(string-split \"a b c\" #\\space 'infix 1)".to_string(),
+        env,
+    )
+    .unwrap();
+    assert_eq!(result.to_string(), "(\"a\" \"b c\")");
+}
+
+#[test]
+fn test_string_split_suffix() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(string-split \"a;b;\" #\\; 'suffix)".to_string(), env).unwrap();
+    assert_eq!(result.to_string(), "(\"a\" \"b\")");
+}
+
+#[test]
+fn test_string_split_empty() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(string-split \"\" #\\,)".to_string(), env).unwrap();
+    assert_eq!(result.to_string(), "()");
+}
+
+#[test]
+fn test_string_split_empty_delimiter() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(string-split \"abc\" \"\")".to_string(), env);
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_string_join() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result =
+        parse_and_eval("(string-join '(\"a\" \"b\" \"c\") \", \")".to_string(), env).unwrap();
+    assert_eq!(result.to_string(), "\"a, b, c\"");
+}
+
+#[test]
+fn test_string_join_default() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(string-join '(\"a\" \"b\"))".to_string(), env).unwrap();
+    assert_eq!(result.to_string(), "\"a b\"");
+}
+
+#[test]
+fn test_string_join_prefix() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval(
+        "(string-join '(\"usr\" \"lib\") \"/\" 'prefix)".to_string(),
+        env,
+    )
+    .unwrap();
+    assert_eq!(result.to_string(), "\"/usr/lib\"");
+}
+
+#[test]
+fn test_string_join_strict_infix_empty() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(string-join '() \",\" 'strict-infix)".to_string(), env);
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_string_trim() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval(
+        "(list (string-trim \"  hi  \") (string-trim-right \"  hi  \") (string-trim-both \"  hi  \"))".to_string(),
+        env,
+    )
+    .unwrap();
+    assert_eq!(result.to_string(), "(\"hi  \" \"  hi\" \"hi\")");
+}
+
+#[test]
+fn test_string_trim_char() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(string-trim-both \"xxhixx\" #\\x)".to_string(), env).unwrap();
+    assert_eq!(result.to_string(), "\"hi\"");
+}
+
+#[test]
+fn test_string_pad() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval(
+        "(list (string-pad \"42\" 5 #\\0) (string-pad \"12345\" 3))".to_string(),
+        env,
+    )
+    .unwrap();
+    assert_eq!(result.to_string(), "(\"00042\" \"345\")");
+}
+
+#[test]
+fn test_string_pad_right() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval(
+        "(list (string-pad-right \"ab\" 4) (string-pad-right \"abcd\" 2))".to_string(),
+        env,
+    )
+    .unwrap();
+    assert_eq!(result.to_string(), "(\"ab  \" \"ab\")");
+}
+
+#[test]
+fn test_string_replace() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval(
+        "(string-replace \"hello world\" \"there\" 6 11)".to_string(),
+        env,
+    )
+    .unwrap();
+    assert_eq!(result.to_string(), "\"hello there\"");
+}
+
+#[test]
+fn test_string_count() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(string-count \"banana\" #\\a)".to_string(), env).unwrap();
+    assert_eq!(result.to_string(), "3");
+}
+
+#[test]
+fn test_string_count_char_set() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval(
+        "(string-count \"a1b2c3\" char-set:digit 2)".to_string(),
+        env,
+    )
+    .unwrap();
+    assert_eq!(result.to_string(), "2");
+}
+
+#[test]
+fn test_string_tokenize() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result =
+        parse_and_eval("(string-tokenize \"  the quick  fox \")".to_string(), env).unwrap();
+    assert_eq!(result.to_string(), "(\"the\" \"quick\" \"fox\")");
+}
+
+#[test]
+fn test_string_tokenize_char_set() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval(
+        "(string-tokenize \"a1,b22;c\" char-set:letter)".to_string(),
+        env,
+    )
+    .unwrap();
+    assert_eq!(result.to_string(), "(\"a\" \"b\" \"c\")");
+}
+
+#[test]
+fn test_list_sort() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(list-sort < '(3 1 2 5 4))".to_string(), env).unwrap();
+    assert_eq!(result.to_string(), "(1 2 3 4 5)");
+}
+
+#[test]
+fn test_list_sort_stable() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval(
+        "(list-sort (lambda (a b) (< (car a) (car b))) '((1 . a) (0 . b) (1 . c) (0 . d)))"
+            .to_string(),
+        env,
+    )
+    .unwrap();
+    assert_eq!(result.to_string(), "((0 . b) (0 . d) (1 . a) (1 . c))");
+}
+
+#[test]
+fn test_list_sort_empty() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(list-sort < '())".to_string(), env).unwrap();
+    assert_eq!(result.to_string(), "()");
+}
+
+#[test]
+fn test_list_sort_strings() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval(
+        "(list-sort string<? '(\"pear\" \"apple\" \"fig\"))".to_string(),
+        env,
+    )
+    .unwrap();
+    assert_eq!(result.to_string(), "(\"apple\" \"fig\" \"pear\")");
+}
+
+#[test]
+fn test_list_sort_comparator_error() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(list-sort < '(1 a 2))".to_string(), env);
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_list_sorted() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval(
+        "(list (list-sorted? < '(1 2 2 3)) (list-sorted? < '(2 1)))".to_string(),
+        env,
+    )
+    .unwrap();
+    assert_eq!(result.to_string(), "(#t #f)");
+}
+
+#[test]
+fn test_list_merge() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(list-merge < '(1 3 5) '(2 3 4))".to_string(), env).unwrap();
+    assert_eq!(result.to_string(), "(1 2 3 3 4 5)");
+}
+
+#[test]
+fn test_vector_sort() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(vector-sort > #(1 4 2 3))".to_string(), env).unwrap();
+    assert_eq!(result.to_string(), "#(4 3 2 1)");
+}
+
+#[test]
+fn test_vector_sort_range() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(vector-sort < #(9 3 1 2 0) 1 4)".to_string(), env).unwrap();
+    assert_eq!(result.to_string(), "#(1 2 3)");
+}
+
+#[test]
+fn test_vector_sorted() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval(
+        "(list (vector-sorted? < #(1 2 3)) (vector-sorted? < #(3 1 2) 1))".to_string(),
+        env,
+    )
+    .unwrap();
+    assert_eq!(result.to_string(), "(#t #t)");
+}
+
+#[test]
+fn test_vector_merge() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(vector-merge < #(1 4) #(2 3 5))".to_string(), env).unwrap();
+    assert_eq!(result.to_string(), "#(1 2 3 4 5)");
+}
+
+#[test]
+fn test_vector_delete_neighbor_dups() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval(
+        "(vector-delete-neighbor-dups = #(1 1 2 3 3 3 1))".to_string(),
+        env,
+    )
+    .unwrap();
+    assert_eq!(result.to_string(), "#(1 2 3 1)");
+}
+
+#[test]
+fn test_vector_sort_in_place_range() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval(
+        "(let ((v (vector 5 4 3 2 1))) (vector-sort! v < 1 4) v)".to_string(),
+        env,
+    )
+    .unwrap();
+    assert_eq!(result.to_string(), "#(5 2 3 4 1)");
+}
+
+#[test]
+fn test_vector_sort_in_place_shared() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    parse_and_eval("(define v (vector 3 1 2))".to_string(), env.clone()).unwrap();
+    parse_and_eval("(define w v)".to_string(), env.clone()).unwrap();
+    parse_and_eval("(vector-sort! v <)".to_string(), env.clone()).unwrap();
+    let result = parse_and_eval("w".to_string(), env).unwrap();
+    assert_eq!(result.to_string(), "#(1 2 3)");
+}
+
+#[test]
+fn test_list_sort_closure_error_propagates() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval(
+        "(list-sort (lambda (a b) (error \"bad compare\")) '(2 1))".to_string(),
+        env,
+    );
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_endianness() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval(
+        "(list (endianness big) (endianness little))".to_string(),
+        env,
+    )
+    .unwrap();
+    assert_eq!(result.to_string(), "(big little)");
+}
+
+#[test]
+fn test_endianness_invalid() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(endianness middle)".to_string(), env);
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_native_endianness() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(symbol? (native-endianness))".to_string(), env).unwrap();
+    assert_eq!(result.to_string(), "#t");
+}
+
+#[test]
+fn test_bytevector_u16_ref() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval(
+        "(list (bytevector-u16-ref #u8(1 2) 0 (endianness big)) (bytevector-u16-ref #u8(1 2) 0 (endianness little)))".to_string(),
+        env,
+    )
+    .unwrap();
+    assert_eq!(result.to_string(), "(258 513)");
+}
+
+#[test]
+fn test_bytevector_s16_ref() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result =
+        parse_and_eval("(bytevector-s16-ref #u8(255 254) 0 'big)".to_string(), env).unwrap();
+    assert_eq!(result.to_string(), "-2");
+}
+
+#[test]
+fn test_bytevector_u32_ref_little() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval(
+        "(bytevector-u32-ref #u8(0 16 0 0 0) 1 'little)".to_string(),
+        env,
+    )
+    .unwrap();
+    assert_eq!(result.to_string(), "16");
+}
+
+#[test]
+fn test_bytevector_s32_ref() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval(
+        "(bytevector-s32-ref #u8(255 255 255 255) 0 'little)".to_string(),
+        env,
+    )
+    .unwrap();
+    assert_eq!(result.to_string(), "-1");
+}
+
+#[test]
+fn test_bytevector_u64_ref_max() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval(
+        "(bytevector-u64-ref #u8(255 255 255 255 255 255 255 255) 0 'big)".to_string(),
+        env,
+    )
+    .unwrap();
+    assert_eq!(result.to_string(), "18446744073709551615");
+}
+
+#[test]
+fn test_bytevector_s64_ref_min() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval(
+        "(bytevector-s64-ref #u8(128 0 0 0 0 0 0 0) 0 'big)".to_string(),
+        env,
+    )
+    .unwrap();
+    assert_eq!(result.to_string(), "-9223372036854775808");
+}
+
+#[test]
+fn test_bytevector_u32_set() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval(
+        "(let ((bv (make-bytevector 4 0))) (bytevector-u32-set! bv 0 305419896 'little) bv)"
+            .to_string(),
+        env,
+    )
+    .unwrap();
+    assert_eq!(result.to_string(), "#u8(120 86 52 18)");
+}
+
+#[test]
+fn test_bytevector_s16_set_negative() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval(
+        "(let ((bv (make-bytevector 2 0))) (bytevector-s16-set! bv 0 -2 'big) bv)".to_string(),
+        env,
+    )
+    .unwrap();
+    assert_eq!(result.to_string(), "#u8(255 254)");
+}
+
+#[test]
+fn test_bytevector_u16_set_out_of_range() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval(
+        "(bytevector-u16-set! (make-bytevector 2 0) 0 65536 'big)".to_string(),
+        env,
+    );
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_bytevector_s8_set_out_of_range() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval(
+        "(bytevector-s8-set! (make-bytevector 1 0) 0 128)".to_string(),
+        env,
+    );
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_bytevector_s8_ref() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(bytevector-s8-ref #u8(200) 0)".to_string(), env).unwrap();
+    assert_eq!(result.to_string(), "-56");
+}
+
+#[test]
+fn test_bytevector_u32_ref_out_of_bounds() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(bytevector-u32-ref #u8(1 2 3) 0 'big)".to_string(), env);
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_bytevector_u16_ref_bad_endianness() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(bytevector-u16-ref #u8(1 2) 0 'middle)".to_string(), env);
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_bytevector_uint_ref_bignum() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval(
+        "(bytevector-uint-ref #u8(1 0 0 0 0 0 0 0 0) 0 'big 9)".to_string(),
+        env,
+    )
+    .unwrap();
+    assert_eq!(result.to_string(), "18446744073709551616");
+}
+
+#[test]
+fn test_bytevector_sint_ref() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval(
+        "(bytevector-sint-ref #u8(255 255 127) 0 'little 3)".to_string(),
+        env,
+    )
+    .unwrap();
+    assert_eq!(result.to_string(), "8388607");
+}
+
+#[test]
+fn test_bytevector_uint_set_roundtrip() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval(
+        "(let ((bv (make-bytevector 12 0))) (bytevector-uint-set! bv 1 (expt 2 80) 'little 11) (bytevector-uint-ref bv 1 'little 11))".to_string(),
+        env,
+    )
+    .unwrap();
+    assert_eq!(result.to_string(), "1208925819614629174706176");
+}
+
+#[test]
+fn test_bytevector_sint_set_roundtrip() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval(
+        "(let ((bv (make-bytevector 3 0))) (bytevector-sint-set! bv 0 -300 'big 3) (bytevector-sint-ref bv 0 'big 3))".to_string(),
+        env,
+    )
+    .unwrap();
+    assert_eq!(result.to_string(), "-300");
+}
+
+#[test]
+fn test_bytevector_ieee_double() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval(
+        "(let ((bv (make-bytevector 8 0))) (bytevector-ieee-double-set! bv 0 1.5 'little) (bytevector-ieee-double-ref bv 0 'little))".to_string(),
+        env,
+    )
+    .unwrap();
+    assert_eq!(result.to_string(), "1.5");
+}
+
+#[test]
+fn test_bytevector_ieee_single_big() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval(
+        "(let ((bv (make-bytevector 4 0))) (bytevector-ieee-single-set! bv 0 -2.5 'big) bv)"
+            .to_string(),
+        env,
+    )
+    .unwrap();
+    assert_eq!(result.to_string(), "#u8(192 32 0 0)");
+}
+
+#[test]
+fn test_bytevector_ieee_single_ref() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval(
+        "(bytevector-ieee-single-ref #u8(0 0 128 63) 0 'little)".to_string(),
+        env,
+    )
+    .unwrap();
+    assert_eq!(result.to_string(), "1.0");
+}
+
+#[test]
+fn test_file_input_port_decodes_utf8() {
+    use crate::{env::Env, parser::parse_and_eval};
+    use std::fs;
+    let path = std::env::temp_dir().join("copper_test_file_input_utf8.txt");
+    fs::write(&path, "é日🦀").unwrap();
+    let env = Env::standard_env();
+    let result = parse_and_eval(
+        format!(
+            "(let ((p (open-input-file \"{}\"))) (list (peek-char p) (read-char p) (read-char p) (read-char p) (eof-object? (read-char p))))",
+            path.to_str().unwrap()
+        ),
+        env,
+    )
+    .unwrap();
+    let _ = fs::remove_file(&path);
+    assert_eq!(result.to_string(), "(#\\é #\\é #\\日 #\\🦀 #t)");
+}
+
+#[test]
+fn test_file_input_port_utf8_across_buffer_boundary() {
+    use crate::{env::Env, parser::parse_and_eval};
+    use std::fs;
+    let path = std::env::temp_dir().join("copper_test_file_input_utf8_boundary.txt");
+    fs::write(&path, format!("{}日本", "a".repeat(8191))).unwrap();
+    let env = Env::standard_env();
+    let result = parse_and_eval(
+        format!(
+            "(let ((s (read-line (open-input-file \"{}\")))) (list (string-length s) (string-ref s 8191)))",
+            path.to_str().unwrap()
+        ),
+        env,
+    )
+    .unwrap();
+    let _ = fs::remove_file(&path);
+    assert_eq!(result.to_string(), "(8193 #\\日)");
+}
+
+#[test]
+fn test_file_input_port_invalid_utf8_reports_offset() {
+    use crate::{env::Env, parser::parse_and_eval};
+    use std::fs;
+    let path = std::env::temp_dir().join("copper_test_file_input_invalid_utf8.txt");
+    fs::write(&path, b"ab\xC3(").unwrap();
+    let env = Env::standard_env();
+    let result = parse_and_eval(
+        format!(
+            "(let ((p (open-input-file \"{}\"))) (read-char p) (read-char p) (read-char p))",
+            path.to_str().unwrap()
+        ),
+        env,
+    );
+    let _ = fs::remove_file(&path);
+    let err = result.unwrap_err().to_string();
+    assert!(err.contains("byte offset 2"), "{}", err);
+}
+
+#[test]
+fn test_file_output_port_encodes_utf8() {
+    use crate::{env::Env, parser::parse_and_eval};
+    use std::fs;
+    let path = std::env::temp_dir().join("copper_test_file_output_utf8.txt");
+    let env = Env::standard_env();
+    parse_and_eval(
+        format!(
+            "(let ((p (open-output-file \"{}\"))) (write-string \"ñ→\" p) (write-char #\\🦀 p) (close-port p))",
+            path.to_str().unwrap()
+        ),
+        env,
+    )
+    .unwrap();
+    let contents = fs::read_to_string(&path).unwrap();
+    let _ = fs::remove_file(&path);
+    assert_eq!(contents, "ñ→🦀");
+}
+
+#[test]
+fn test_transcoded_port_utf16be() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval(
+        "(read-line (transcoded-port (open-input-bytevector #u8(0 104 0 105 216 62 221 128)) 'utf-16be))".to_string(),
+        env,
+    )
+    .unwrap();
+    assert_eq!(result.to_string(), "\"hi🦀\"");
+}
+
+#[test]
+fn test_transcoded_port_utf16_bom_little_endian() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval(
+        "(read-line (transcoded-port (open-input-bytevector #u8(255 254 104 0 105 0)) 'utf-16))"
+            .to_string(),
+        env,
+    )
+    .unwrap();
+    assert_eq!(result.to_string(), "\"hi\"");
+}
+
+#[test]
+fn test_transcoded_port_utf16_without_bom() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval(
+        "(read-line (transcoded-port (open-input-bytevector #u8(0 104 0 105)) 'utf-16))"
+            .to_string(),
+        env,
+    )
+    .unwrap();
+    assert_eq!(result.to_string(), "\"hi\"");
+}
+
+#[test]
+fn test_transcoded_port_utf8_bom() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval(
+        "(read-line (transcoded-port (open-input-bytevector #u8(239 187 191 104 105)) 'utf-8))"
+            .to_string(),
+        env,
+    )
+    .unwrap();
+    assert_eq!(result.to_string(), "\"hi\"");
+}
+
+#[test]
+fn test_transcoded_port_utf8_bom_after_start() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval(
+        "(string-length (read-line (transcoded-port (open-input-bytevector #u8(104 239 187 191 105)) 'utf-8)))".to_string(),
+        env,
+    )
+    .unwrap();
+    assert_eq!(result.to_string(), "3");
+}
+
+#[test]
+fn test_open_input_file_skips_utf8_bom() {
+    use crate::{env::Env, parser::parse_and_eval};
+    use std::fs;
+    let path = std::env::temp_dir().join("copper_test_open_input_file_utf8_bom.txt");
+    fs::write(&path, "\u{FEFF}abc").unwrap();
+    let env = Env::standard_env();
+    let result = parse_and_eval(
+        format!(
+            "(read-char (open-input-file \"{}\"))",
+            path.to_str().unwrap()
+        ),
+        env,
+    )
+    .unwrap();
+    let _ = fs::remove_file(&path);
+    assert_eq!(result.to_string(), "#\\a");
+}
+
+#[test]
+fn test_transcoded_port_latin1() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval(
+        "(read-line (transcoded-port (open-input-bytevector #u8(99 97 102 233)) 'latin-1))"
+            .to_string(),
+        env,
+    )
+    .unwrap();
+    assert_eq!(result.to_string(), "\"café\"");
+}
+
+#[test]
+fn test_transcoded_port_ascii_raise() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval(
+        "(read-char (transcoded-port (open-input-bytevector #u8(200)) 'ascii))".to_string(),
+        env,
+    );
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_transcoded_port_ascii_replace() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval(
+        "(char->integer (read-char (transcoded-port (open-input-bytevector #u8(200)) 'ascii 'errors 'replace)))".to_string(),
+        env,
+    )
+    .unwrap();
+    assert_eq!(result.to_string(), "65533");
+}
+
+#[test]
+fn test_transcoded_port_utf8_replace_keeps_next_char() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval(
+        "(let ((p (transcoded-port (open-input-bytevector #u8(195 40)) 'utf-8 'errors 'replace))) (list (char->integer (read-char p)) (read-char p)))".to_string(),
+        env,
+    )
+    .unwrap();
+    assert_eq!(result.to_string(), "(65533 #\\()");
+}
+
+#[test]
+fn test_transcoded_port_unpaired_surrogate() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval(
+        "(read-char (transcoded-port (open-input-bytevector #u8(0 216 65 0)) 'utf-16le))"
+            .to_string(),
+        env,
+    );
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_transcoded_port_crlf_input() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval(
+        "(let ((p (transcoded-port (open-input-bytevector #u8(97 13 10 98 13 99)) 'utf-8 'eol 'crlf))) (list (read-char p) (read-char p) (read-char p) (read-char p) (peek-char p) (read-char p)))".to_string(),
+        env,
+    )
+    .unwrap();
+    assert_eq!(
+        result.to_string(),
+        "(#\\a #\\newline #\\b #\\return #\\c #\\c)"
+    );
+}
+
+#[test]
+fn test_transcoded_port_utf16le_output() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval(
+        "(let* ((bv (make-bytevector 4 0)) (out (open-output-bytevector bv))) (write-string \"hi\" (transcoded-port out 'utf-16le)) (get-output-bytevector out))".to_string(),
+        env,
+    )
+    .unwrap();
+    assert_eq!(result.to_string(), "#u8(104 0 105 0)");
+}
+
+#[test]
+fn test_transcoded_port_utf16_output_writes_bom() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval(
+        "(let* ((bv (make-bytevector 4 0)) (out (open-output-bytevector bv))) (write-char #\\a (transcoded-port out 'utf-16)) (get-output-bytevector out))".to_string(),
+        env,
+    )
+    .unwrap();
+    assert_eq!(result.to_string(), "#u8(254 255 0 97)");
+}
+
+#[test]
+fn test_transcoded_port_crlf_output() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval(
+        "(let* ((bv (make-bytevector 4 0)) (out (open-output-bytevector bv))) (write-string (string #\\a #\\newline #\\b) (transcoded-port out 'ascii 'eol 'crlf)) (get-output-bytevector out))".to_string(),
+        env,
+    )
+    .unwrap();
+    assert_eq!(result.to_string(), "#u8(97 13 10 98)");
+}
+
+#[test]
+fn test_transcoded_port_latin1_output_raise() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval(
+        "(write-char #\\日 (transcoded-port (open-output-bytevector (make-bytevector 4 0)) 'latin-1))".to_string(),
+        env,
+    );
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_transcoded_port_latin1_output_replace() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval(
+        "(let* ((bv (make-bytevector 1 0)) (out (open-output-bytevector bv))) (write-char #\\日 (transcoded-port out 'latin-1 'errors 'replace)) (get-output-bytevector out))".to_string(),
+        env,
+    )
+    .unwrap();
+    assert_eq!(result.to_string(), "#u8(63)");
+}
+
+#[test]
+fn test_transcoded_port_unknown_codec() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval(
+        "(transcoded-port (open-input-bytevector #u8(1)) 'ebcdic)".to_string(),
+        env,
+    );
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_transcoded_port_textual_port() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval(
+        "(transcoded-port (open-input-string \"a\") 'utf-8)".to_string(),
+        env,
+    );
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_open_input_file_with_encoding() {
+    use crate::{env::Env, parser::parse_and_eval};
+    use std::fs;
+    let path = std::env::temp_dir().join("copper_test_open_input_file_utf16le.txt");
+    fs::write(&path, b"\xFF\xFEh\x00\xE9\x00").unwrap();
+    let env = Env::standard_env();
+    let result = parse_and_eval(
+        format!(
+            "(read-line (open-input-file \"{}\" 'encoding 'utf-16))",
+            path.to_str().unwrap()
+        ),
+        env,
+    )
+    .unwrap();
+    let _ = fs::remove_file(&path);
+    assert_eq!(result.to_string(), "\"hé\"");
+}
+
+#[test]
+fn test_open_output_file_with_encoding_and_eol() {
+    use crate::{env::Env, parser::parse_and_eval};
+    use std::fs;
+    let path = std::env::temp_dir().join("copper_test_open_output_file_latin1.txt");
+    let env = Env::standard_env();
+    parse_and_eval(
+        format!(
+            "(let ((p (open-output-file \"{}\" 'encoding 'latin-1 'eol 'crlf))) (write-string \"café\" p) (write-char #\\newline p) (close-port p))",
+            path.to_str().unwrap()
+        ),
+        env,
+    )
+    .unwrap();
+    let contents = fs::read(&path).unwrap();
+    let _ = fs::remove_file(&path);
+    assert_eq!(contents, b"caf\xE9\r\n");
+}
+
+#[test]
+fn test_open_input_file_unknown_option() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval(
+        "(open-input-file \"copper_missing.txt\" 'charset 'utf-8)".to_string(),
+        env,
+    );
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_current_error_port() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval(
+        "(list (output-port? (current-error-port)) (textual-port? (current-error-port)))"
+            .to_string(),
+        env,
+    )
+    .unwrap();
+    assert_eq!(result.to_string(), "(#t #t)");
+}
+
+#[test]
+fn test_port_predicate() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval(
+        "(list (port? (current-input-port)) (port? (open-input-bytevector #u8(1))) (port? \"port\"))".to_string(),
+        env,
+    )
+    .unwrap();
+    assert_eq!(result.to_string(), "(#t #t #f)");
+}
+
+#[test]
+fn test_close_input_port() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval(
+        "(let ((p (open-input-string \"abc\"))) (close-input-port p) (input-port-open? p))"
+            .to_string(),
+        env,
+    )
+    .unwrap();
+    assert_eq!(result.to_string(), "#f");
+}
+
+#[test]
+fn test_close_input_port_output_port() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(close-input-port (open-output-string))".to_string(), env);
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_close_output_port() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval(
+        "(let ((p (open-output-string))) (close-output-port p) (output-port-open? p))".to_string(),
+        env,
+    )
+    .unwrap();
+    assert_eq!(result.to_string(), "#f");
+}
+
+#[test]
+fn test_close_output_port_input_port() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval(
+        "(close-output-port (open-input-string \"abc\"))".to_string(),
+        env,
+    );
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_parameterize_current_error_port() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval(
+        "(let ((p (open-output-string))) (parameterize ((current-error-port p)) (write-string \"oops\" (current-error-port))) (get-output-string p))".to_string(),
+        env,
+    )
+    .unwrap();
+    assert_eq!(result.to_string(), "\"oops\"");
+}
+
+#[test]
+fn test_process_file_input_reports_errors_to_current_error_port() {
+    use crate::types::{Expr, ports::Port};
+    use crate::{env::Env, io};
+    let env = Env::standard_env();
+    let port = Port::text_output_string();
+    let Some(Expr::Parameter(param)) = env.borrow().find_value("current-error-port") else {
+        panic!("current-error-port should be a parameter");
+    };
+    env.borrow_mut()
+        .set_param(&param.id.to_string(), &Expr::Port(port.clone()));
+    io::process_file_input(vec!["(error \"bad input\")".to_string()], env);
+    let Port::TextOutput(output) = port else {
+        panic!("expected textual output port");
+    };
+    assert_eq!(
+        output.borrow().get_output_string(),
+        Some("error: bad input\n")
+    );
+}
+
+#[test]
+fn test_custom_textual_input_port() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let setup = [
+        "(define src (string #\\a #\\b #\\newline #\\c))",
+        "(define pos (vector 0))",
+        "(define (fill buf start count i) (if (and (< i count) (< (vector-ref pos 0) (string-length src))) (begin (string-set! buf (+ start i) (string-ref src (vector-ref pos 0))) (vector-set! pos 0 (+ 1 (vector-ref pos 0))) (fill buf start count (+ i 1))) i))",
+        "(define p (make-custom-textual-input-port \"src\" (lambda (buf start count) (fill buf start count 0)) #f #f #f))",
+    ];
+    for expr in setup {
+        parse_and_eval(expr.to_string(), env.clone()).unwrap();
+    }
+    let result = parse_and_eval(
+        "(list (peek-char p) (read-char p) (read-line p) (read-char p) (eof-object? (read-char p)))"
+            .to_string(),
+        env,
+    )
+    .unwrap();
+    assert_eq!(result.to_string(), "(#\\a #\\a \"b\\n\" #\\c #t)");
+}
+
+#[test]
+fn test_custom_textual_input_port_position() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    parse_and_eval("(define pos (vector 0))".to_string(), env.clone()).unwrap();
+    parse_and_eval(
+        "(define p (make-custom-textual-input-port \"xs\" (lambda (buf start count) (string-set! buf start #\\x) (vector-set! pos 0 (+ 1 (vector-ref pos 0))) 1) (lambda () (vector-ref pos 0)) (lambda (n) (vector-set! pos 0 n)) #f))".to_string(),
+        env.clone(),
+    )
+    .unwrap();
+    let result = parse_and_eval(
+        "(list (read-char p) (peek-char p) (port-position p) (begin (set-port-position! p 10) (port-position p)))"
+            .to_string(),
+        env,
+    )
+    .unwrap();
+    assert_eq!(result.to_string(), "(#\\x #\\x 1 10)");
+}
+
+#[test]
+fn test_custom_textual_output_port_tee() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    parse_and_eval("(define out (open-output-string))".to_string(), env.clone()).unwrap();
+    parse_and_eval("(define closed (vector #f))".to_string(), env.clone()).unwrap();
+    parse_and_eval(
+        "(define q (make-custom-textual-output-port \"tee\" (lambda (buf start count) (write-string (substring buf start (+ start count)) out) count) #f #f (lambda () (vector-set! closed 0 #t))))".to_string(),
+        env.clone(),
+    )
+    .unwrap();
+    let result = parse_and_eval(
+        "(begin (write-string \"abc\" q) (write-char #\\d q) (write 42 q) (close-port q) (list (get-output-string out) (vector-ref closed 0) (output-port-open? q)))"
+            .to_string(),
+        env.clone(),
+    )
+    .unwrap();
+    assert_eq!(result.to_string(), "(\"abcd42\" #t #f)");
+    let result = parse_and_eval("(write-char #\\e q)".to_string(), env);
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_custom_binary_ports() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    parse_and_eval("(define bytes (vector '()))".to_string(), env.clone()).unwrap();
+    parse_and_eval(
+        "(define out (make-custom-binary-output-port \"out\" (lambda (bv start count) (vector-set! bytes 0 (cons (bytevector-u8-ref bv start) (vector-ref bytes 0))) 1) #f #f #f))".to_string(),
+        env.clone(),
+    )
+    .unwrap();
+    parse_and_eval(
+        "(define in (make-custom-binary-input-port \"in\" (lambda (bv start count) (bytevector-u8-set! bv start 7) 1) #f #f #f))".to_string(),
+        env.clone(),
+    )
+    .unwrap();
+    let result = parse_and_eval(
+        "(begin (write-u8 1 out) (write-u8 2 out) (list (vector-ref bytes 0) (peek-u8 in) (read-u8 in) (binary-port? in) (input-port? out)))"
+            .to_string(),
+        env,
+    )
+    .unwrap();
+    assert_eq!(result.to_string(), "((2 1) 7 7 #t #f)");
+}
+
+#[test]
+fn test_custom_port_read_error() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval(
+        "(read-char (make-custom-textual-input-port \"bad\" (lambda (buf start count) (error \"boom\")) #f #f #f))".to_string(),
+        env,
+    );
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_custom_port_procedure_using_own_port() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    parse_and_eval(
+        "(define p (make-custom-textual-output-port \"x\" (lambda (s i c) (close-port p) c) #f #f #f))".to_string(),
+        env.clone(),
+    )
+    .unwrap();
+    let result = parse_and_eval("(write-char #\\a p)".to_string(), env.clone());
+    assert!(result.is_err());
+    let result = parse_and_eval(
+        "(begin (close-port p) (output-port-open? p))".to_string(),
+        env,
+    )
+    .unwrap();
+    assert_eq!(result.to_string(), "#f");
+}
+
+#[test]
+fn test_custom_port_invalid_arguments() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval(
+        "(make-custom-textual-input-port \"bad\" 5 #f #f #f)".to_string(),
+        env.clone(),
+    );
+    assert!(result.is_err());
+    let result = parse_and_eval(
+        "(make-custom-binary-output-port \"out\" (lambda (bv start count) count) 5 #f #f)"
+            .to_string(),
+        env,
+    );
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_custom_port_get_position_using_own_port() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    parse_and_eval(
+        "(define p (make-custom-binary-input-port \"x\" (lambda (bv start count) 0) (lambda () (port-position p)) #f #f))".to_string(),
+        env.clone(),
+    )
+    .unwrap();
+    let result = parse_and_eval("(port-position p)".to_string(), env.clone());
+    assert!(result.is_err());
+    let result = parse_and_eval("(port-has-port-position? p)".to_string(), env).unwrap();
+    assert_eq!(result.to_string(), "#t");
+}
+
+#[test]
+fn test_custom_port_position_unsupported() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval(
+        "(port-position (make-custom-binary-output-port \"out\" (lambda (bv start count) count) #f #f #f))".to_string(),
+        env,
+    );
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_port_position_string_input() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval(
+        "(let ((p (open-input-string \"hello\"))) (read-char p) (let ((pos (port-position p))) (set-port-position! p 4) (list pos (read-char p) (port-position p))))"
+            .to_string(),
+        env,
+    )
+    .unwrap();
+    assert_eq!(result.to_string(), "(1 #\\o 5)");
+}
+
+#[test]
+fn test_set_port_position_out_of_range() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval(
+        "(set-port-position! (open-input-string \"abc\") 4)".to_string(),
+        env,
+    );
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_port_position_string_output_overwrites() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval(
+        "(let ((p (open-output-string))) (write-string \"abcdef\" p) (set-port-position! p 2) (write-string \"XY\" p) (list (port-position p) (get-output-string p)))"
+            .to_string(),
+        env,
+    )
+    .unwrap();
+    assert_eq!(result.to_string(), "(4 \"abXYef\")");
+}
+
+#[test]
+fn test_port_position_bytevector_output() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval(
+        "(let ((p (open-output-bytevector (make-bytevector 4 0)))) (write-u8 1 p) (write-u8 2 p) (write-u8 3 p) (set-port-position! p 0) (write-u8 9 p) (list (port-position p) (get-output-bytevector p)))"
+            .to_string(),
+        env,
+    )
+    .unwrap();
+    assert_eq!(result.to_string(), "(1 #u8(9 2 3))");
+}
+
+#[test]
+fn test_port_position_binary_file_patch() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let path = std::env::temp_dir().join("copper_test_port_position.bin");
+    let path = path.to_str().unwrap();
+    parse_and_eval(
+        format!("(define out (open-binary-output-file \"{}\"))", path),
+        env.clone(),
+    )
+    .unwrap();
+    parse_and_eval(
+        "(begin (write-u8 0 out) (write-u8 7 out) (write-u8 8 out) (set-port-position! out 0) (write-u8 2 out) (close-port out))"
+            .to_string(),
+        env.clone(),
+    )
+    .unwrap();
+    let result = parse_and_eval(
+        format!(
+            "(let ((in (open-binary-input-file \"{}\"))) (read-u8 in) (peek-u8 in) (let ((pos (port-position in))) (set-port-position! in 2) (list pos (read-u8 in))))",
+            path
+        ),
+        env,
+    )
+    .unwrap();
+    assert_eq!(result.to_string(), "(1 8)");
+    let _ = std::fs::remove_file(path);
+}
+
+#[test]
+fn test_port_position_textual_file_bytes() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let path = std::env::temp_dir().join("copper_test_port_position.txt");
+    let path = path.to_str().unwrap();
+    std::fs::write(path, "héllo").unwrap();
+    let result = parse_and_eval(
+        format!(
+            "(let ((p (open-input-file \"{}\"))) (read-char p) (read-char p) (peek-char p) (let ((pos (port-position p))) (set-port-position! p 1) (list pos (read-char p))))",
+            path
+        ),
+        env,
+    )
+    .unwrap();
+    assert_eq!(result.to_string(), "(3 #\\é)");
+    let _ = std::fs::remove_file(path);
+}
+
+#[test]
+fn test_port_has_port_position() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval(
+        "(list (port-has-port-position? (open-input-string \"a\")) (port-has-set-port-position!? (open-output-string)) (port-has-port-position? (current-output-port)) (port-has-set-port-position!? (make-custom-binary-input-port \"in\" (lambda (bv start count) 0) (lambda () 0) #f #f)))"
+            .to_string(),
+        env,
+    )
+    .unwrap();
+    assert_eq!(result.to_string(), "(#t #t #f #f)");
+}
+
+#[test]
+fn test_ucs_range_to_char_set_empty() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result =
+        parse_and_eval("(char-set-size (ucs-range->char-set 0 0))".to_string(), env).unwrap();
+    assert_eq!(result.to_string(), "0");
+}
+
+#[test]
+fn test_ucs_range_to_char_set_clamps_upper() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval(
+        "(= (char-set-size (ucs-range->char-set 0 2097152)) (char-set-size char-set:full))"
+            .to_string(),
+        env,
+    )
+    .unwrap();
+    assert_eq!(result.to_string(), "#t");
+}
+
+#[test]
+fn test_ucs_range_to_char_set_error_flag() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(ucs-range->char-set 0 2097152 #t)".to_string(), env);
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_ucs_range_to_char_set_error_flag_surrogates() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(ucs-range->char-set 55000 57000 #t)".to_string(), env);
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_char_set_letter_excludes_letter_numbers() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval(
+        "(list (char-set-contains? char-set:letter #\\x2160) (char-set-contains? char-set:graphic #\\x2160))".to_string(),
+        env,
+    )
+    .unwrap();
+    assert_eq!(result.to_string(), "(#f #t)");
+}
+
+#[test]
+fn test_char_set_graphic_general_categories() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval(
+        "(list (char-set-contains? char-set:graphic #\\x1F600) (char-set-contains? char-set:graphic #\\x0301) (char-set-contains? char-set:graphic #\\^) (char-set-contains? char-set:graphic #\\-))".to_string(),
+        env,
+    )
+    .unwrap();
+    assert_eq!(result.to_string(), "(#t #t #t #t)");
+}
+
+#[test]
+fn test_char_set_punctuation_symbol_general_categories() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval(
+        "(list (char-set-contains? char-set:punctuation #\\x300A) (char-set-contains? char-set:symbol #\\x1F600) (char-set-contains? char-set:symbol #\\^) (char-set-contains? char-set:symbol #\\-))".to_string(),
+        env,
+    )
+    .unwrap();
+    assert_eq!(result.to_string(), "(#t #t #t #f)");
+}
+
+#[test]
+fn test_string_tokenize_non_bmp() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(string-tokenize \"a😀b c\")".to_string(), env).unwrap();
+    assert_eq!(result.to_string(), "(\"a😀b\" \"c\")");
+}
+
+#[test]
+fn test_string_tokenize_combining_mark() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(string-tokenize \"é x\")".to_string(), env).unwrap();
+    assert_eq!(result.to_string(), "(\"é\" \"x\")");
+}
+
+#[test]
+fn test_string_foldcase_full() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(string-foldcase \"Straße\")".to_string(), env).unwrap();
+    assert_eq!(result.to_string(), "\"strasse\"");
+}
+
+#[test]
+fn test_string_foldcase_full_table() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval(
+        "(list (string-foldcase \"ﬁ ẞ\") (string-ci=? \"ꭰ\" \"Ꭰ\"))".to_string(),
+        env,
+    )
+    .unwrap();
+    assert_eq!(result.to_string(), "(\"fi ss\" #t)");
+}
+
+#[test]
+fn test_string_ci_eq_full_folding() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval(
+        "(list (string-ci=? \"Straße\" \"STRASSE\") (string-ci<? \"ß\" \"st\"))".to_string(),
+        env,
+    )
+    .unwrap();
+    assert_eq!(result.to_string(), "(#t #t)");
+}
+
+#[test]
+fn test_char_foldcase_simple() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(char-foldcase #\\ß)".to_string(), env).unwrap();
+    assert_eq!(result.to_string(), "#\\ß");
+}
+
+#[test]
+fn test_random_source_make_reals_unit() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval(
+        "(< 0 ((random-source-make-reals (make-random-source) 1/10)) 1)".to_string(),
+        env,
+    )
+    .unwrap();
+    assert_eq!(result.to_string(), "#t");
+}
+
+#[test]
+fn test_random_source_make_reals_unit_out_of_range() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval(
+        "(random-source-make-reals (make-random-source) 1)".to_string(),
+        env,
+    );
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_random_source_make_reals_unit_too_fine() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval(
+        "(random-source-make-reals (make-random-source) 1e-20)".to_string(),
+        env,
+    );
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_arithmetic_shift_count_too_large() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(arithmetic-shift 1 (expt 10 12))".to_string(), env);
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_arithmetic_shift_large_right_shift() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval(
+        "(arithmetic-shift (expt 2 100) (- (expt 10 12)))".to_string(),
+        env,
+    )
+    .unwrap();
+    assert_eq!(result.to_string(), "0");
+}
+
+#[test]
+fn test_arithmetic_shift_huge_right_shift() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval(
+        "(list (arithmetic-shift 5 (- (expt 2 40))) (arithmetic-shift -5 (- (expt 2 70))) (arithmetic-shift (expt 3 100) (- (expt 2 70))))".to_string(),
+        env,
+    )
+    .unwrap();
+    assert_eq!(result.to_string(), "(0 -1 0)");
+}
+
+#[test]
+fn test_bit_field_too_wide() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(bit-field 5 0 (expt 10 12))".to_string(), env);
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_copy_bit_large_index_unchanged() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval(
+        "(list (copy-bit (expt 10 12) 0 #f) (copy-bit (expt 10 12) -1 #t))".to_string(),
+        env,
+    )
+    .unwrap();
+    assert_eq!(result.to_string(), "(0 -1)");
+}
+
+#[test]
+fn test_copy_bit_index_too_large() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(copy-bit (expt 10 12) 0 #t)".to_string(), env);
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_iota_negative_count() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(iota -1)".to_string(), env);
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_iota_inexact_count() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(iota 2.0)".to_string(), env);
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_take_negative_count() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(take '(1 2) -1)".to_string(), env);
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_take_huge_count() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(take '(1 2) 100000000000000)".to_string(), env);
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_vector_ref_negative_index() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(vector-ref #(1 2) -1)".to_string(), env);
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_make_string_negative_length() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(make-string -1)".to_string(), env);
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_make_string_inexact_length() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(make-string 2.0 #\\a)".to_string(), env);
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_make_list_default_fill() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(make-list 2)".to_string(), env).unwrap();
+    assert_eq!(result.to_string(), "(#f #f)");
+}
+
+#[test]
+fn test_make_list_negative_length() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(make-list -1)".to_string(), env);
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_string_pad_negative_length() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(string-pad \"abc\" -1)".to_string(), env);
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_string_pad_right_negative_length() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(string-pad-right \"abc\" -1)".to_string(), env);
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_char_set_predefined_identity() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(eq? char-set:letter char-set:letter)".to_string(), env).unwrap();
+    assert_eq!(result.to_string(), "#t");
+}
+
+#[test]
+fn test_char_set_predefined_shadowed() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval(
+        "(begin (define char-set:digit 5) char-set:digit)".to_string(),
+        env,
+    )
+    .unwrap();
+    assert_eq!(result.to_string(), "5");
+}
+
+#[test]
+fn test_char_set_unknown_name_unbound() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("char-set:nope".to_string(), env);
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_lambda_ill_formed_formals() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("(lambda (a . 1) a)".to_string(), env);
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_lambda_symbol_formals_no_args() {
+    use crate::{env::Env, parser::parse_and_eval};
+    let env = Env::standard_env();
+    let result = parse_and_eval("((lambda args args))".to_string(), env).unwrap();
+    assert_eq!(result.to_string(), "()");
+}
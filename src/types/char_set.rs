@@ -0,0 +1,333 @@
+// Copyright (c) 2026 Sebastian Ibanez
+// Author: Sebastian Ibanez
+// Created: 2026-10-18
+
+//! Character sets for SRFI 14.
+
+use crate::types::chars;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::Rc;
+use std::sync::OnceLock;
+use unicode_general_category::{GeneralCategory, get_general_category};
+
+/// Largest Unicode scalar value.
+pub const MAX_CODE_POINT: u32 = 0x10FFFF;
+
+/// Surrogate code points, which are not chars.
+pub const SURROGATES: (u32, u32) = (0xD800, 0xDFFF);
+
+/// An immutable set of chars, stored as sorted, disjoint, non-adjacent
+/// inclusive ranges of code points. Clones share the same ranges.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CharSet {
+    ranges: Rc<Vec<(u32, u32)>>,
+}
+
+impl CharSet {
+    /// Create a new `CharSet` from inclusive code point ranges in any order.
+    pub fn from_ranges(mut ranges: Vec<(u32, u32)>) -> Self {
+        ranges.retain(|(lo, hi)| lo <= hi);
+        ranges.sort_unstable();
+        let mut merged: Vec<(u32, u32)> = Vec::with_capacity(ranges.len());
+        for (lo, hi) in ranges {
+            match merged.last_mut() {
+                Some(last) if lo <= last.1.saturating_add(1) => last.1 = last.1.max(hi),
+                _ => merged.push((lo, hi)),
+            }
+        }
+        // Surrogates are never members, so sets stay comparable after a complement.
+        let merged = subtract(&merged, &[SURROGATES]);
+        CharSet {
+            ranges: Rc::new(merged),
+        }
+    }
+
+    /// Create a new `CharSet` holding `chars`.
+    pub fn from_chars(chars: impl IntoIterator<Item = char>) -> Self {
+        CharSet::from_ranges(chars.into_iter().map(|c| (c as u32, c as u32)).collect())
+    }
+
+    /// Return the empty set.
+    pub fn empty() -> Self {
+        CharSet::from_ranges(Vec::new())
+    }
+
+    /// Return the set of every char.
+    pub fn full() -> Self {
+        CharSet::from_ranges(vec![(0, MAX_CODE_POINT)])
+    }
+
+    /// Return true if `c` is in the set.
+    pub fn contains(&self, c: char) -> bool {
+        let code = c as u32;
+        let index = self.ranges.partition_point(|(_, hi)| *hi < code);
+        self.ranges.get(index).is_some_and(|(lo, _)| *lo <= code)
+    }
+
+    /// Return the number of chars in the set.
+    pub fn len(&self) -> usize {
+        self.ranges
+            .iter()
+            .map(|(lo, hi)| (hi - lo + 1) as usize)
+            .sum()
+    }
+
+    /// Return true if the set has no chars.
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    /// Return the chars in the set in code point order.
+    pub fn chars(&self) -> impl Iterator<Item = char> + '_ {
+        self.ranges
+            .iter()
+            .flat_map(|(lo, hi)| (*lo..=*hi).filter_map(char::from_u32))
+    }
+
+    /// Return the chars in either `self` or `other`.
+    pub fn union(&self, other: &CharSet) -> CharSet {
+        CharSet::from_ranges([self.ranges.as_slice(), other.ranges.as_slice()].concat())
+    }
+
+    /// Return the chars in `self` that are also in `other`.
+    pub fn intersection(&self, other: &CharSet) -> CharSet {
+        self.difference(&other.complement())
+    }
+
+    /// Return the chars in `self` that are not in `other`.
+    pub fn difference(&self, other: &CharSet) -> CharSet {
+        CharSet {
+            ranges: Rc::new(subtract(&self.ranges, &other.ranges)),
+        }
+    }
+
+    /// Return the chars in exactly one of `self` and `other`.
+    pub fn xor(&self, other: &CharSet) -> CharSet {
+        self.difference(other).union(&other.difference(self))
+    }
+
+    /// Return every char that is not in `self`.
+    pub fn complement(&self) -> CharSet {
+        CharSet::full().difference(self)
+    }
+
+    /// Return true if every char of `self` is in `other`.
+    pub fn is_subset(&self, other: &CharSet) -> bool {
+        self.difference(other).is_empty()
+    }
+
+    /// Returns raw pointer to the ranges. Use responsibly.
+    pub fn raw_ptr(&self) -> *const () {
+        Rc::as_ptr(&self.ranges) as *const ()
+    }
+
+    /// Return the ranges of the set.
+    pub fn ranges(&self) -> &[(u32, u32)] {
+        &self.ranges
+    }
+}
+
+/// Return the ranges of `a` with every code point in `b` removed. Both must be normalized.
+fn subtract(a: &[(u32, u32)], b: &[(u32, u32)]) -> Vec<(u32, u32)> {
+    let mut result = Vec::with_capacity(a.len());
+    let mut j = 0;
+    for &(lo, hi) in a {
+        while j < b.len() && b[j].1 < lo {
+            j += 1;
+        }
+        // Emit the gaps of `(lo, hi)` between the ranges of `b` that overlap it.
+        let mut start = lo;
+        let mut covered = false;
+        for &(b_lo, b_hi) in b[j..].iter().take_while(|(b_lo, _)| *b_lo <= hi) {
+            if b_lo > start {
+                result.push((start, b_lo - 1));
+            }
+            if b_hi >= hi {
+                covered = true;
+                break;
+            }
+            start = start.max(b_hi + 1);
+        }
+        if !covered {
+            result.push((start, hi));
+        }
+    }
+    result
+}
+
+/// Inclusive code point ranges.
+type Ranges = Vec<(u32, u32)>;
+
+thread_local! {
+    /// Predefined char sets already looked up on this thread, so every lookup
+    /// of a name returns the same set.
+    static NAMED: RefCell<HashMap<&'static str, CharSet>> = RefCell::new(HashMap::new());
+}
+
+/// Return the predefined char set called `name`, such as `char-set:graphic`.
+///
+/// The ranges are computed on the first lookup of any predefined set.
+pub fn named(name: &str) -> Option<CharSet> {
+    if !name.starts_with("char-set:") {
+        return None;
+    }
+    NAMED.with_borrow_mut(|named| {
+        if let Some(set) = named.get(name) {
+            return Some(set.clone());
+        }
+        let (name, ranges) = predefined_cache()
+            .iter()
+            .find(|(set_name, _)| *set_name == name)?;
+        let set = from_normalized(ranges);
+        named.insert(name, set.clone());
+        Some(set)
+    })
+}
+
+/// Return the ranges of the predefined char sets, computing them once per process.
+fn predefined_cache() -> &'static [(&'static str, Ranges)] {
+    static SETS: OnceLock<Vec<(&'static str, Ranges)>> = OnceLock::new();
+    SETS.get_or_init(predefined_ranges)
+}
+
+/// Create a `CharSet` with a copy of normalized `ranges`.
+fn from_normalized(ranges: &[(u32, u32)]) -> CharSet {
+    CharSet {
+        ranges: Rc::new(ranges.to_vec()),
+    }
+}
+
+/// Compute the ranges of the predefined char sets from Unicode properties and
+/// general categories.
+fn predefined_ranges() -> Vec<(&'static str, Ranges)> {
+    let mut lower_case = Vec::new();
+    let mut upper_case = Vec::new();
+    let mut title_case = Vec::new();
+    let mut letter = Vec::new();
+    let mut digit = Vec::new();
+    let mut graphic = Vec::new();
+    let mut whitespace = Vec::new();
+    let mut punctuation = Vec::new();
+    let mut symbol = Vec::new();
+    for c in (0..=MAX_CODE_POINT).filter_map(char::from_u32) {
+        let category = get_general_category(c);
+        let properties = [
+            (c.is_lowercase(), &mut lower_case),
+            (c.is_uppercase(), &mut upper_case),
+            (
+                category == GeneralCategory::TitlecaseLetter,
+                &mut title_case,
+            ),
+            (is_letter(category), &mut letter),
+            (chars::digit_value(c).is_some(), &mut digit),
+            (is_graphic(category), &mut graphic),
+            (c.is_whitespace(), &mut whitespace),
+            (is_punctuation(category), &mut punctuation),
+            (is_symbol(category), &mut symbol),
+        ];
+        for (has_property, ranges) in properties {
+            if has_property {
+                push_code_point(ranges, c as u32);
+            }
+        }
+    }
+
+    let letter = CharSet::from_ranges(letter);
+    let digit = CharSet::from_ranges(digit);
+    let whitespace = CharSet::from_ranges(whitespace);
+    let graphic = CharSet::from_ranges(graphic);
+    let printing = graphic.union(&whitespace);
+    let line_breaks = CharSet::from_ranges(vec![(0x0A, 0x0D), (0x85, 0x85), (0x2028, 0x2029)]);
+    let blank = whitespace.difference(&line_breaks);
+
+    let sets = [
+        ("char-set:lower-case", CharSet::from_ranges(lower_case)),
+        ("char-set:upper-case", CharSet::from_ranges(upper_case)),
+        ("char-set:title-case", CharSet::from_ranges(title_case)),
+        ("char-set:letter+digit", letter.union(&digit)),
+        ("char-set:letter", letter),
+        ("char-set:digit", digit),
+        ("char-set:graphic", graphic),
+        ("char-set:printing", printing),
+        ("char-set:whitespace", whitespace),
+        (
+            "char-set:iso-control",
+            CharSet::from_ranges(vec![(0x00, 0x1F), (0x7F, 0x9F)]),
+        ),
+        ("char-set:punctuation", CharSet::from_ranges(punctuation)),
+        ("char-set:symbol", CharSet::from_ranges(symbol)),
+        (
+            "char-set:hex-digit",
+            CharSet::from_ranges(vec![(0x30, 0x39), (0x41, 0x46), (0x61, 0x66)]),
+        ),
+        ("char-set:blank", blank),
+        ("char-set:ascii", CharSet::from_ranges(vec![(0x00, 0x7F)])),
+        ("char-set:empty", CharSet::empty()),
+        ("char-set:full", CharSet::full()),
+    ];
+    sets.into_iter()
+        .map(|(name, set)| (name, set.ranges.to_vec()))
+        .collect()
+}
+
+/// Return true for the letter categories `Lu`, `Ll`, `Lt`, `Lm` and `Lo`.
+fn is_letter(category: GeneralCategory) -> bool {
+    matches!(
+        category,
+        GeneralCategory::UppercaseLetter
+            | GeneralCategory::LowercaseLetter
+            | GeneralCategory::TitlecaseLetter
+            | GeneralCategory::ModifierLetter
+            | GeneralCategory::OtherLetter
+    )
+}
+
+/// Return true for the punctuation categories `Pc`, `Pd`, `Ps`, `Pe`, `Pi`, `Pf` and `Po`.
+fn is_punctuation(category: GeneralCategory) -> bool {
+    matches!(
+        category,
+        GeneralCategory::ConnectorPunctuation
+            | GeneralCategory::DashPunctuation
+            | GeneralCategory::OpenPunctuation
+            | GeneralCategory::ClosePunctuation
+            | GeneralCategory::InitialPunctuation
+            | GeneralCategory::FinalPunctuation
+            | GeneralCategory::OtherPunctuation
+    )
+}
+
+/// Return true for the symbol categories `Sm`, `Sc`, `Sk` and `So`.
+fn is_symbol(category: GeneralCategory) -> bool {
+    matches!(
+        category,
+        GeneralCategory::MathSymbol
+            | GeneralCategory::CurrencySymbol
+            | GeneralCategory::ModifierSymbol
+            | GeneralCategory::OtherSymbol
+    )
+}
+
+/// Return true for the graphic categories: letters, marks, numbers, punctuation and symbols.
+fn is_graphic(category: GeneralCategory) -> bool {
+    is_letter(category)
+        || is_punctuation(category)
+        || is_symbol(category)
+        || matches!(
+            category,
+            GeneralCategory::NonspacingMark
+                | GeneralCategory::SpacingMark
+                | GeneralCategory::EnclosingMark
+                | GeneralCategory::DecimalNumber
+                | GeneralCategory::LetterNumber
+                | GeneralCategory::OtherNumber
+        )
+}
+
+/// Add `code` to the end of sorted `ranges`, extending the last range if adjacent.
+fn push_code_point(ranges: &mut Vec<(u32, u32)>, code: u32) {
+    match ranges.last_mut() {
+        Some(last) if last.1 + 1 == code => last.1 = code,
+        _ => ranges.push((code, code)),
+    }
+}
@@ -0,0 +1,67 @@
+// Copyright (c) 2026 Sebastian Ibanez
+// Author: Sebastian Ibanez
+// Created: 2026-10-18
+
+//! Unicode character helpers for the `(scheme char)` library.
+
+use crate::types::case_tables;
+
+/// Code points of the zero digit of every Unicode `Nd` (decimal digit) range.
+/// Each range holds the ten contiguous digits 0-9.
+const DECIMAL_ZEROS: &[u32] = &[
+    0x0030, 0x0660, 0x06F0, 0x07C0, 0x0966, 0x09E6, 0x0A66, 0x0AE6, 0x0B66, 0x0BE6, 0x0C66, 0x0CE6,
+    0x0D66, 0x0DE6, 0x0E50, 0x0ED0, 0x0F20, 0x1040, 0x1090, 0x17E0, 0x1810, 0x1946, 0x19D0, 0x1A80,
+    0x1A90, 0x1B50, 0x1BB0, 0x1C40, 0x1C50, 0xA620, 0xA8D0, 0xA900, 0xA9D0, 0xA9F0, 0xAA50, 0xABF0,
+    0xFF10, 0x104A0, 0x10D30, 0x10D40, 0x11066, 0x110F0, 0x11136, 0x111D0, 0x112F0, 0x11450,
+    0x114D0, 0x11650, 0x116C0, 0x116D0, 0x116DA, 0x11730, 0x118E0, 0x11950, 0x11BF0, 0x11C50,
+    0x11D50, 0x11DA0, 0x11DE0, 0x11F50, 0x16130, 0x16A60, 0x16AC0, 0x16B50, 0x16D70, 0x1CCF0,
+    0x1D7CE, 0x1D7D8, 0x1D7E2, 0x1D7EC, 0x1D7F6, 0x1E140, 0x1E2F0, 0x1E4F0, 0x1E5F1, 0x1E950,
+    0x1FBF0,
+];
+
+/// Return the value of `c` if it is a Unicode decimal digit.
+pub fn digit_value(c: char) -> Option<u32> {
+    let code = c as u32;
+    let index = DECIMAL_ZEROS.partition_point(|zero| *zero <= code);
+    let zero = DECIMAL_ZEROS[index.checked_sub(1)?];
+    (code - zero < 10).then_some(code - zero)
+}
+
+/// Return the simple uppercase mapping of `c`.
+pub fn upcase(c: char) -> char {
+    simple_mapping(case_tables::SIMPLE_UPPERCASE, c)
+}
+
+/// Return the simple lowercase mapping of `c`.
+pub fn downcase(c: char) -> char {
+    simple_mapping(case_tables::SIMPLE_LOWERCASE, c)
+}
+
+/// Return the simple case folding of `c`.
+pub fn foldcase(c: char) -> char {
+    simple_mapping(case_tables::SIMPLE_FOLDING, c)
+}
+
+/// Map `c` through a table of `(first, last, stride, delta)` entries, or return
+/// `c` if no entry covers it.
+fn simple_mapping(table: &[(u32, u32, u32, i32)], c: char) -> char {
+    let code = c as u32;
+    let index = table.partition_point(|(first, ..)| *first <= code);
+    match table[..index].last() {
+        Some((first, last, stride, delta))
+            if code <= *last && (code - first).is_multiple_of(*stride) =>
+        {
+            char::from_u32(code.wrapping_add_signed(*delta)).unwrap_or(c)
+        }
+        _ => c,
+    }
+}
+
+/// Return the full case folding of `c`, which may expand to several chars like `ß` to `ss`.
+pub fn full_foldcase(c: char) -> impl Iterator<Item = char> {
+    let full = case_tables::FULL_FOLDING
+        .binary_search_by_key(&c, |(key, _)| *key)
+        .map(|index| case_tables::FULL_FOLDING[index].1);
+    let simple = full.is_err().then(|| foldcase(c));
+    full.unwrap_or("").chars().chain(simple)
+}
@@ -0,0 +1,296 @@
+// Copyright (c) 2026 Sebastian Ibanez
+// Author: Sebastian Ibanez
+// Created: 2026-10-18
+
+//! Hash tables for SRFI 69 and SRFI 125.
+
+use crate::error::Error;
+use crate::types::Expr;
+use crate::types::number::{IntVariant, Number};
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::hash::{BuildHasherDefault, DefaultHasher, Hash, Hasher};
+use std::rc::Rc;
+
+/// Maximum number of nodes visited when hashing a structure, so cyclic structures terminate.
+const HASH_BUDGET: usize = 64;
+
+/// Equivalence used to compare hash table keys.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Equivalence {
+    Eq,
+    Eqv,
+    Equal,
+    String,
+}
+
+/// A mutable hash table. Clones share the same entries.
+#[derive(Debug, Clone)]
+pub struct HashTable {
+    table: Rc<RefCell<Table>>,
+}
+
+#[derive(Debug)]
+struct Table {
+    equivalence: Equivalence,
+    buckets: HashMap<u64, Vec<(Expr, Expr)>, BuildHasherDefault<DefaultHasher>>,
+    len: usize,
+}
+
+impl HashTable {
+    /// Create a new empty `HashTable` whose keys are compared with `equivalence`.
+    pub fn new(equivalence: Equivalence) -> Self {
+        HashTable {
+            table: Rc::new(RefCell::new(Table {
+                equivalence,
+                buckets: HashMap::default(),
+                len: 0,
+            })),
+        }
+    }
+
+    /// Return the equivalence used to compare keys.
+    pub fn equivalence(&self) -> Equivalence {
+        self.table.borrow().equivalence
+    }
+
+    /// Return the number of entries.
+    pub fn len(&self) -> usize {
+        self.table.borrow().len
+    }
+
+    /// Return true if there are no entries.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Return the value associated with `key`.
+    pub fn get(&self, key: &Expr) -> Result<Option<Expr>, Error> {
+        let hash = self.hash(key)?;
+        let Some(index) = self.position(hash, key)? else {
+            return Ok(None);
+        };
+        Ok(Some(self.table.borrow().buckets[&hash][index].1.clone()))
+    }
+
+    /// Associate `value` with `key`, replacing any previous value.
+    pub fn insert(&self, key: Expr, value: Expr) -> Result<(), Error> {
+        let hash = self.hash(&key)?;
+        let index = self.position(hash, &key)?;
+        let mut table = self.table.borrow_mut();
+        let bucket = table.buckets.entry(hash).or_default();
+        match index {
+            Some(index) => bucket[index].1 = value,
+            None => {
+                bucket.push((key, value));
+                table.len += 1;
+            }
+        }
+        Ok(())
+    }
+
+    /// Remove the entry for `key`. Returns true if an entry was removed.
+    pub fn remove(&self, key: &Expr) -> Result<bool, Error> {
+        let hash = self.hash(key)?;
+        let Some(index) = self.position(hash, key)? else {
+            return Ok(false);
+        };
+        let mut table = self.table.borrow_mut();
+        let bucket = table
+            .buckets
+            .get_mut(&hash)
+            .expect("bucket should contain key");
+        bucket.remove(index);
+        if bucket.is_empty() {
+            table.buckets.remove(&hash);
+        }
+        table.len -= 1;
+        Ok(true)
+    }
+
+    /// Remove every entry.
+    pub fn clear(&self) {
+        let mut table = self.table.borrow_mut();
+        table.buckets.clear();
+        table.len = 0;
+    }
+
+    /// Return a copy of every `(key, value)` entry.
+    pub fn entries(&self) -> Vec<(Expr, Expr)> {
+        self.table
+            .borrow()
+            .buckets
+            .values()
+            .flatten()
+            .cloned()
+            .collect()
+    }
+
+    /// Return a new `HashTable` with the same equivalence and entries.
+    pub fn copy(&self) -> HashTable {
+        let table = self.table.borrow();
+        HashTable {
+            table: Rc::new(RefCell::new(Table {
+                equivalence: table.equivalence,
+                buckets: table.buckets.clone(),
+                len: table.len,
+            })),
+        }
+    }
+
+    /// Return true if `self` and `other` share the same entries.
+    pub fn ptr_eq(&self, other: &HashTable) -> bool {
+        Rc::ptr_eq(&self.table, &other.table)
+    }
+
+    /// Returns raw pointer to the table. Use responsibly.
+    pub fn raw_ptr(&self) -> *const () {
+        self.table.as_ptr() as *const ()
+    }
+
+    /// Hash `key` consistently with the table's equivalence.
+    fn hash(&self, key: &Expr) -> Result<u64, Error> {
+        match (self.equivalence(), key) {
+            (Equivalence::Eq | Equivalence::Eqv, key) => Ok(eqv_hash(key)),
+            (Equivalence::Equal, key) => Ok(equal_hash(key)),
+            (Equivalence::String, Expr::String(s)) => Ok(string_hash(&s.to_string())),
+            (Equivalence::String, _) => Err(Error::new("expected string key")),
+        }
+    }
+
+    /// Return the index of `key` in the bucket for `hash`.
+    fn position(&self, hash: u64, key: &Expr) -> Result<Option<usize>, Error> {
+        let table = self.table.borrow();
+        let Some(bucket) = table.buckets.get(&hash) else {
+            return Ok(None);
+        };
+        for (i, (k, _)) in bucket.iter().enumerate() {
+            let same = match table.equivalence {
+                Equivalence::Eq => k.identical(key),
+                Equivalence::Eqv => k.eqv(key)?,
+                Equivalence::Equal | Equivalence::String => k.equal(key)?,
+            };
+            if same {
+                return Ok(Some(i));
+            }
+        }
+        Ok(None)
+    }
+}
+
+/// Hash `expr` consistently with `eqv?`.
+pub fn eqv_hash(expr: &Expr) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    hash_eqv(expr, &mut hasher);
+    hasher.finish()
+}
+
+/// Hash `expr` consistently with `equal?`.
+pub fn equal_hash(expr: &Expr) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    hash_equal(expr, &mut hasher, &mut HASH_BUDGET.clone());
+    hasher.finish()
+}
+
+/// Hash the contents of a string.
+pub fn string_hash(s: &str) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    s.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Feed the identity of `expr` into `hasher`.
+fn hash_eqv(expr: &Expr, hasher: &mut DefaultHasher) {
+    std::mem::discriminant(expr).hash(hasher);
+    match expr {
+        Expr::Number(n) => hash_number(n, hasher),
+        Expr::Char(c) => c.hash(hasher),
+        Expr::Boolean(b) => b.hash(hasher),
+        Expr::Symbol(s) => s.hash(hasher),
+        Expr::String(s) => s.raw_ptr().hash(hasher),
+        Expr::Pair(p) => p.raw_ptr().hash(hasher),
+        Expr::Vector(v) => v.raw_ptr().hash(hasher),
+        Expr::ByteVector(bv) => bv.raw_ptr().hash(hasher),
+        Expr::Procedure(f) => (*f as usize).hash(hasher),
+        Expr::Closure(c) => Rc::as_ptr(c).hash(hasher),
+        Expr::HashTable(t) => t.raw_ptr().hash(hasher),
+        Expr::Promise(p) => p.raw_ptr().hash(hasher),
+        Expr::CharSet(set) => set.raw_ptr().hash(hasher),
+        // Every remaining value of a type hashes the same.
+        Expr::Port(_)
+        | Expr::Parameter(_)
+        | Expr::RandomSource(_)
+        | Expr::Null
+        | Expr::Eof
+        | Expr::Void() => {}
+    }
+}
+
+/// Feed the structure of `expr` into `hasher`, visiting at most `budget` nodes.
+fn hash_equal(expr: &Expr, hasher: &mut DefaultHasher, budget: &mut usize) {
+    if *budget == 0 {
+        return;
+    }
+    *budget -= 1;
+    match expr {
+        Expr::String(s) => {
+            std::mem::discriminant(expr).hash(hasher);
+            s.to_string().hash(hasher);
+        }
+        Expr::Pair(p) => {
+            std::mem::discriminant(expr).hash(hasher);
+            hash_equal(&p.car(), hasher, budget);
+            hash_equal(&p.cdr(), hasher, budget);
+        }
+        Expr::Vector(v) => {
+            std::mem::discriminant(expr).hash(hasher);
+            v.len().hash(hasher);
+            for e in v.iter() {
+                hash_equal(&e, hasher, budget);
+            }
+        }
+        Expr::ByteVector(bv) => {
+            std::mem::discriminant(expr).hash(hasher);
+            bv.iter().collect::<Vec<u8>>().hash(hasher);
+        }
+        Expr::CharSet(set) => {
+            std::mem::discriminant(expr).hash(hasher);
+            set.ranges().hash(hasher);
+        }
+        _ => hash_eqv(expr, hasher),
+    }
+}
+
+/// Feed a number into `hasher`, keeping exact and inexact numbers distinct like `eqv?`.
+fn hash_number(n: &Number, hasher: &mut DefaultHasher) {
+    std::mem::discriminant(n).hash(hasher);
+    match n {
+        Number::Int(IntVariant::Small(i)) => i.hash(hasher),
+        // Bignums that fit in an `i64` hash like the equivalent fixnum.
+        Number::Int(IntVariant::Big(b)) => match i64::try_from(b) {
+            Ok(i) => i.hash(hasher),
+            Err(_) => b.hash(hasher),
+        },
+        Number::Rational(r) => r.hash(hasher),
+        Number::Float(f) => float_bits(*f).hash(hasher),
+        Number::Complex(c) => {
+            float_bits(c.re).hash(hasher);
+            float_bits(c.im).hash(hasher);
+        }
+        Number::ExactComplex(c) => {
+            c.re.hash(hasher);
+            c.im.hash(hasher);
+        }
+    }
+}
+
+/// Return the bits of `f`, with every zero and every NaN collapsed to one representation.
+fn float_bits(f: f64) -> u64 {
+    if f == 0.0 {
+        0
+    } else if f.is_nan() {
+        f64::NAN.to_bits()
+    } else {
+        f.to_bits()
+    }
+}
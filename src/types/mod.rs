@@ -4,8 +4,16 @@
 
 //! Copper data types.
 
+mod case_tables;
+pub mod char_set;
+pub mod chars;
+pub mod hash_table;
 pub mod number;
 pub mod ports;
+pub mod promise;
+pub mod random;
+pub mod symbol;
+pub mod transcoder;
 
 use num_integer::div_floor;
 use std::cell::RefCell;
@@ -16,7 +24,12 @@ use std::vec::IntoIter;
 
 use crate::env::EnvRef;
 use crate::error::Error;
+use crate::types::char_set::CharSet;
+use crate::types::hash_table::HashTable;
 use crate::types::ports::Port;
+use crate::types::promise::Promise;
+use crate::types::random::RandomSource;
+use crate::types::symbol::Symbol;
 pub(crate) use number::Number;
 
 pub const BOOLEAN_TRUE_STR: &str = "#t";
@@ -28,10 +41,10 @@ pub type Procedure = fn(&[Expr], EnvRef) -> Result;
 #[derive(Debug, Clone)]
 pub enum Expr {
     Number(Number),
-    String(String),
+    String(SchemeString),
     Char(char),
     Boolean(bool),
-    Symbol(String),
+    Symbol(Symbol),
     Pair(Pair),
     Null,
     Vector(Vector),
@@ -40,6 +53,10 @@ pub enum Expr {
     Closure(Rc<Closure>),
     Port(Port),
     Parameter(Parameter),
+    RandomSource(RandomSource),
+    HashTable(HashTable),
+    Promise(Promise),
+    CharSet(CharSet),
     Eof,
     Void(),
 }
@@ -70,7 +87,7 @@ impl Expr {
             Expr::String(s) => format_string(s, Representation::Formatted),
             Expr::Char(c) => format_char(c, Representation::Formatted),
             Expr::Boolean(b) => format_boolean(b),
-            Expr::Symbol(s) => s.clone(),
+            Expr::Symbol(s) => s.to_string(),
             Expr::Pair(p) => format_pair(p, " ", true, Representation::Formatted),
             Expr::Null => format_null(),
             Expr::Vector(v) => format_vector(v, true, Representation::Formatted),
@@ -79,6 +96,10 @@ impl Expr {
             closure @ Expr::Closure(_) => closure.to_string(),
             Expr::Port(p) => format_port(p),
             param @ Expr::Parameter(_) => param.to_string(),
+            source @ Expr::RandomSource(_) => source.to_string(),
+            table @ Expr::HashTable(_) => table.to_string(),
+            promise @ Expr::Promise(_) => promise.to_string(),
+            set @ Expr::CharSet(_) => set.to_string(),
             Expr::Eof => String::from("#!eof"),
             Expr::Void() => String::new(),
         }
@@ -117,24 +138,48 @@ impl Expr {
         format_with_labels(self, &mut label_map, &mut 0)
     }
 
-    /// Return true if `self` and `other` have equivalent identities.
-    pub fn eqv(&self, other: &Expr) -> std::result::Result<bool, Error> {
-        let eqv = match (self, other) {
+    /// Return true if `self` and `other` are the same object.
+    ///
+    /// Heap allocated objects are compared by pointer. Numbers have no identity
+    /// of their own, so they are compared like `eqv?`.
+    pub fn identical(&self, other: &Expr) -> bool {
+        match (self, other) {
             (Expr::Boolean(a), Expr::Boolean(b)) => a == b,
             (Expr::Symbol(a), Expr::Symbol(b)) => a == b,
-            (Expr::Number(a), Expr::Number(b)) => a == b,
+            (Expr::Number(a), Expr::Number(b)) => a.eqv(b),
             (Expr::Char(a), Expr::Char(b)) => a == b,
-            (Expr::Pair(a), Expr::Pair(b)) => Rc::ptr_eq(&a.elements, &b.elements),
-            (Expr::Vector(a), Expr::Vector(b)) => Rc::ptr_eq(&a.elements, &b.elements),
-            (Expr::ByteVector(a), Expr::ByteVector(b)) => Rc::ptr_eq(&a.buffer, &b.buffer),
             (Expr::Procedure(a), Expr::Procedure(b)) => std::ptr::fn_addr_eq(*a, *b),
-            (Expr::Closure(a), Expr::Closure(b)) => Rc::ptr_eq(a, b),
             (Expr::Port(a), Expr::Port(b)) => a.equal(b),
-            (Expr::Null, Expr::Null) => true,
-            _ => false,
-        };
+            (Expr::Parameter(a), Expr::Parameter(b)) => a.id == b.id,
+            (Expr::Null, Expr::Null) | (Expr::Eof, Expr::Eof) | (Expr::Void(), Expr::Void()) => {
+                true
+            }
+            _ => match (self.raw_ptr(), other.raw_ptr()) {
+                (Some(a), Some(b)) => a == b,
+                _ => false,
+            },
+        }
+    }
+
+    /// Return true if `self` and `other` have equivalent identities.
+    pub fn eqv(&self, other: &Expr) -> std::result::Result<bool, Error> {
+        Ok(self.identical(other))
+    }
 
-        Ok(eqv)
+    /// Returns raw pointer to the storage of a heap allocated object. Use responsibly.
+    pub fn raw_ptr(&self) -> Option<*const ()> {
+        match self {
+            Expr::String(s) => Some(s.raw_ptr()),
+            Expr::Pair(p) => Some(p.raw_ptr()),
+            Expr::Vector(v) => Some(v.raw_ptr()),
+            Expr::ByteVector(bv) => Some(bv.raw_ptr()),
+            Expr::Closure(c) => Some(Rc::as_ptr(c) as *const ()),
+            Expr::RandomSource(source) => Some(source.raw_ptr()),
+            Expr::HashTable(table) => Some(table.raw_ptr()),
+            Expr::Promise(promise) => Some(promise.raw_ptr()),
+            Expr::CharSet(set) => Some(set.raw_ptr()),
+            _ => None,
+        }
     }
 
     /// Return true if `self` and `other` have equivalent values.
@@ -154,14 +199,24 @@ impl Expr {
         match (self, other) {
             (Expr::String(a), Expr::String(b)) => Ok(a == b),
             (Expr::Pair(a), Expr::Pair(b)) => {
-                let key = (a.raw_ptr(), b.raw_ptr());
+                // Walk the cdr chain iteratively so long lists don't overflow the stack.
+                let (mut a, mut b) = (a.clone(), b.clone());
+                loop {
+                    let key = (a.raw_ptr(), b.raw_ptr());
 
-                if !node_hashes.insert(key) {
-                    return Ok(true);
-                }
+                    if !node_hashes.insert(key) {
+                        return Ok(true);
+                    }
+
+                    if !a.car().equal_inner(&b.car(), node_hashes)? {
+                        return Ok(false);
+                    }
 
-                Ok(a.car().equal_inner(&b.car(), node_hashes)?
-                    && a.cdr().equal_inner(&b.cdr(), node_hashes)?)
+                    match (a.cdr(), b.cdr()) {
+                        (Expr::Pair(next_a), Expr::Pair(next_b)) => (a, b) = (next_a, next_b),
+                        (a_tail, b_tail) => return a_tail.equal_inner(&b_tail, node_hashes),
+                    }
+                }
             }
             (Expr::Vector(a), Expr::Vector(b)) => {
                 if a.len() != b.len() {
@@ -195,6 +250,7 @@ impl Expr {
 
                 Ok(true)
             }
+            (Expr::CharSet(a), Expr::CharSet(b)) => Ok(a == b),
             _ => self.eqv(other),
         }
     }
@@ -398,7 +454,7 @@ impl fmt::Display for Expr {
             Expr::String(s) => format_string(s, Representation::External),
             Expr::Char(c) => format_char(c, Representation::External),
             Expr::Boolean(b) => format_boolean(b),
-            Expr::Symbol(s) => s.clone(),
+            Expr::Symbol(s) => s.to_string(),
             Expr::Pair(p) => format_pair(p, " ", true, Representation::External),
             Expr::Null => format_null(),
             Expr::Vector(v) => format_vector(v, true, Representation::External),
@@ -407,6 +463,10 @@ impl fmt::Display for Expr {
             Expr::Closure(_) => String::from("#<procedure {}>"),
             Expr::Port(p) => format_port(p),
             Expr::Parameter(p) => format!("#<parameter {}>", p.id),
+            Expr::RandomSource(_) => String::from("#<random-source>"),
+            Expr::HashTable(_) => String::from("#<hash-table>"),
+            Expr::Promise(_) => String::from("#<promise>"),
+            Expr::CharSet(_) => String::from("#<char-set>"),
             Expr::Eof => String::from("#!eof"),
             Expr::Void() => return Ok(()),
         };
@@ -423,7 +483,7 @@ fn format_expr(expr: &Expr, rep: Representation) -> String {
 }
 
 /// Format string into its literal representation.
-fn format_string(s: &String, rep: Representation) -> String {
+fn format_string(s: &SchemeString, rep: Representation) -> String {
     match rep {
         Representation::External => {
             let mut out = String::with_capacity(s.len() + 2);
@@ -446,7 +506,7 @@ fn format_string(s: &String, rep: Representation) -> String {
             out.push('"');
             out
         }
-        Representation::Formatted => s.clone(),
+        Representation::Formatted => s.to_string(),
     }
 }
 
@@ -563,15 +623,23 @@ pub fn format_port(p: &Port) -> String {
 #[derive(Debug, Clone)]
 pub struct Closure {
     pub env: EnvRef,
-    pub parameters: Vec<String>,
+    pub parameters: Vec<Symbol>,
+    /// Parameter bound to a list of the arguments after `parameters`, if any.
+    pub rest: Option<Symbol>,
     pub body: Vec<Expr>,
 }
 
 impl Closure {
-    pub fn init(env: EnvRef, parameters: Vec<String>, body: Vec<Expr>) -> Closure {
+    pub fn init(
+        env: EnvRef,
+        parameters: Vec<Symbol>,
+        rest: Option<Symbol>,
+        body: Vec<Expr>,
+    ) -> Closure {
         Closure {
             env,
             parameters,
+            rest,
             body,
         }
     }
@@ -891,8 +959,8 @@ impl Pair {
                 Expr::Char(c) => Ok(c),
                 _ => return Err(Error::new("expected char")),
             })
-            .collect::<std::result::Result<String, Error>>()?;
-        Ok(Expr::String(pair_elements))
+            .collect::<std::result::Result<Vec<char>, Error>>()?;
+        Ok(Expr::String(SchemeString::from_chars(pair_elements)))
     }
 
     /// Return a new sub `Vector` with the given indices. `start` is inclusive and `end` is exclusive. Return `None` if `&self` is not a list.
@@ -985,6 +1053,149 @@ impl Iterator for PairIterMut {
     }
 }
 
+/// A shared string with O(1) character indexing.
+///
+/// Clones share the same characters, so mutations are visible through every reference.
+#[derive(Debug, Clone)]
+pub struct SchemeString {
+    chars: Rc<RefCell<Vec<char>>>,
+    mutable: bool,
+}
+
+impl SchemeString {
+    /// Create a new mutable string.
+    pub fn new(s: &str) -> SchemeString {
+        SchemeString::from_chars(s.chars().collect())
+    }
+
+    /// Create a new immutable string, such as a literal from the reader.
+    pub fn literal(s: &str) -> SchemeString {
+        SchemeString {
+            chars: Rc::new(RefCell::new(s.chars().collect())),
+            mutable: false,
+        }
+    }
+
+    /// Create a new mutable string from a vector of chars.
+    pub fn from_chars(chars: Vec<char>) -> SchemeString {
+        SchemeString {
+            chars: Rc::new(RefCell::new(chars)),
+            mutable: true,
+        }
+    }
+
+    /// Return number of chars in the string.
+    pub fn len(&self) -> usize {
+        self.chars.borrow().len()
+    }
+
+    /// Return true if the string has no chars.
+    pub fn is_empty(&self) -> bool {
+        self.chars.borrow().is_empty()
+    }
+
+    /// Return true if the string can be mutated.
+    pub fn is_mutable(&self) -> bool {
+        self.mutable
+    }
+
+    /// Get char at `index`. Returns `None` if index is outside string bounds.
+    pub fn get(&self, index: usize) -> Option<char> {
+        self.chars.borrow().get(index).copied()
+    }
+
+    /// Set char at `index` to `c`.
+    pub fn set(&self, index: usize, c: char) -> std::result::Result<(), Error> {
+        self.check_mutable()?;
+        let mut chars = self.chars.borrow_mut();
+        match chars.get_mut(index) {
+            Some(old) => {
+                *old = c;
+                Ok(())
+            }
+            None => Err(Error::new("index out of range")),
+        }
+    }
+
+    /// Set each char from `start` to `end` as `c`.
+    pub fn fill(&self, c: char, start: usize, end: usize) -> std::result::Result<(), Error> {
+        self.check_mutable()?;
+        let mut chars = self.chars.borrow_mut();
+        if start > end || end > chars.len() {
+            return Err(Error::new("index out of range"));
+        }
+        chars[start..end].fill(c);
+        Ok(())
+    }
+
+    /// Copy chars from `start` to `end` of `from` into `&self`, beginning at `at`.
+    pub fn copy_into(
+        &self,
+        at: usize,
+        from: &SchemeString,
+        start: usize,
+        end: usize,
+    ) -> std::result::Result<(), Error> {
+        self.check_mutable()?;
+        // Copy the source first, since it may be the same string as `&self`.
+        let source = from
+            .substring(start, end)
+            .ok_or(Error::new("index out of range"))?;
+        let source = source.chars.borrow();
+        let mut chars = self.chars.borrow_mut();
+        if at + source.len() > chars.len() {
+            return Err(Error::new("index out of range"));
+        }
+        chars[at..at + source.len()].copy_from_slice(&source);
+        Ok(())
+    }
+
+    /// Return a new mutable string with the chars from `start` (inclusive) to `end` (exclusive).
+    pub fn substring(&self, start: usize, end: usize) -> Option<SchemeString> {
+        let chars = self.chars.borrow();
+        if start <= end && end <= chars.len() {
+            return Some(SchemeString::from_chars(chars[start..end].to_vec()));
+        }
+        None
+    }
+
+    /// Return a copy of the string's chars.
+    pub fn chars(&self) -> Vec<char> {
+        self.chars.borrow().clone()
+    }
+
+    /// Return true if `self` and `other` share the same chars.
+    pub fn ptr_eq(&self, other: &SchemeString) -> bool {
+        Rc::ptr_eq(&self.chars, &other.chars)
+    }
+
+    /// Returns raw pointer to `&self.chars`. Use responsibly.
+    pub fn raw_ptr(&self) -> *const () {
+        self.chars.as_ptr() as *const ()
+    }
+
+    /// Return an error if the string is immutable.
+    fn check_mutable(&self) -> std::result::Result<(), Error> {
+        if !self.mutable {
+            return Err(Error::new("cannot mutate immutable string"));
+        }
+        Ok(())
+    }
+}
+
+impl PartialEq for SchemeString {
+    fn eq(&self, other: &Self) -> bool {
+        *self.chars.borrow() == *other.chars.borrow()
+    }
+}
+
+impl fmt::Display for SchemeString {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s: String = self.chars.borrow().iter().collect();
+        write!(f, "{}", s)
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct Vector {
     pub elements: Rc<RefCell<Vec<Expr>>>,
@@ -1055,8 +1266,8 @@ impl Vector {
                 Expr::Char(c) => Ok(*c),
                 _ => return Err(Error::new("expected char")),
             })
-            .collect::<std::result::Result<String, Error>>()?;
-        Ok(Expr::String(str_elements))
+            .collect::<std::result::Result<Vec<char>, Error>>()?;
+        Ok(Expr::String(SchemeString::from_chars(str_elements)))
     }
 
     /// Create new `Vector` from a `String`.
@@ -1152,6 +1363,11 @@ pub struct ByteVector {
 }
 
 impl ByteVector {
+    /// Returns raw pointer to `&self.buffer`. Use responsibly.
+    pub fn raw_ptr(&self) -> *const () {
+        self.buffer.as_ptr() as *const ()
+    }
+
     /// Create new `ByteVector` with the given `size`.
     pub fn new(size: usize) -> Self {
         let buffer = Rc::new(RefCell::new(vec![0; size].into_boxed_slice()));
@@ -1189,6 +1405,30 @@ impl ByteVector {
         self.buffer.borrow().get(index).copied()
     }
 
+    /// Return a copy of `size` bytes starting at `index`. Returns `None` if they are outside vector bounds.
+    pub fn get_bytes(&self, index: usize, size: usize) -> Option<Vec<u8>> {
+        let end = index.checked_add(size)?;
+        self.buffer
+            .borrow()
+            .get(index..end)
+            .map(|bytes| bytes.to_vec())
+    }
+
+    /// Overwrite the bytes starting at `index` with `bytes`.
+    pub fn set_bytes(&self, index: usize, bytes: &[u8]) -> std::result::Result<(), Error> {
+        let mut buffer = self.buffer.borrow_mut();
+        match index
+            .checked_add(bytes.len())
+            .and_then(|end| buffer.get_mut(index..end))
+        {
+            Some(dest) => {
+                dest.copy_from_slice(bytes);
+                Ok(())
+            }
+            None => Err(Error::new("index out of range")),
+        }
+    }
+
     /// Copy bytevector into `self`, using optional start and end indexes.
     pub fn copy_into(
         &self,
@@ -5,14 +5,13 @@
 //! A flexible Number type for integers, real, rational, and complex numbers.
 
 use crate::error::Error;
-use crate::types::Number::{Complex, Float, Int, Rational};
+use crate::types::Number::{Complex, ExactComplex, Float, Int, Rational};
 use num_bigint::BigInt;
-use num_complex::Complex64;
+use num_complex::{Complex as NumComplex, Complex64};
 use num_integer::Integer;
-use num_rational::Rational64;
-use num_traits::{FromPrimitive, Num, Pow, ToPrimitive, Zero};
+use num_rational::BigRational;
+use num_traits::{FromPrimitive, Num, One, Pow, Signed, ToPrimitive, Zero};
 use std::cmp::Ordering;
-use std::num::ParseFloatError;
 use std::ops::Rem;
 use std::{
     fmt::{self},
@@ -22,80 +21,42 @@ use std::{
     ops::Sub,
 };
 
+/// Largest number of bits a shift or bit operation may grow an integer by.
+const MAX_SHIFT_BITS: u64 = 1 << 24;
+
 #[derive(Debug, Clone, PartialEq)]
 pub enum Number {
     Int(IntVariant),
     Float(f64),
     Complex(Complex64),
-    Rational(Rational64),
+    Rational(Box<BigRational>),
+    ExactComplex(Box<NumComplex<BigRational>>),
 }
 
 impl Number {
     /// Convert a string to a number.
     pub fn from_token(s: &str) -> Result<Self, Error> {
         // Complex number
-        if let Some(i_pos) = s.find('i') {
-            if i_pos == s.len() - 1 {
-                let without_i = &s[0..s.len() - 1];
-
-                if without_i.is_empty() || without_i == "+" {
-                    return Ok(Complex(Complex64::new(0.0, 1.0)));
-                } else if without_i == "-" {
-                    return Ok(Complex(Complex64::new(0.0, -1.0)));
-                } else if let Ok(im_val) = without_i.parse::<f64>() {
-                    return Ok(Complex(Complex64::new(0.0, im_val)));
-                }
-
-                let parts: Vec<&str> = without_i
-                    .split(&['+', '-'][..])
-                    .filter(|&p| !p.is_empty())
-                    .collect();
-                if parts.len() == 2 {
-                    let re_part_str;
-                    let im_part_str;
-                    let mut sep_index = 0;
-                    for (i, char) in without_i.chars().enumerate() {
-                        if (char == '+' || char == '-') && i > 0 {
-                            sep_index = i;
-                            break;
-                        }
-                    }
-
-                    if sep_index > 0 {
-                        re_part_str = &without_i[0..sep_index];
-                        im_part_str = &without_i[sep_index..];
-                        let re = re_part_str.parse::<f64>().map_err(ParseFloatError::from);
-                        let im = im_part_str.parse::<f64>().map_err(ParseFloatError::from);
-                        return match (re, im) {
-                            (Ok(re), Ok(im)) => Ok(Complex(Complex64::new(re, im))),
-                            _ => Err(Error::new("unable to parse complex number")),
-                        };
-                    }
-                } else if parts.len() == 1
-                    && (without_i.starts_with('+') || without_i.starts_with('-'))
-                    && without_i.contains(|c: char| c.is_digit(10) || c == '.')
-                {
-                    if let Ok(im_val) = without_i.parse::<f64>() {
-                        return Ok(Complex(Complex64::new(0.0, im_val)));
-                    }
-                }
+        if s.ends_with('i') || s.contains('@') {
+            if let Some(n) = Number::parse_complex(s) {
+                return Ok(n);
             }
         }
 
         // Rational number
         if let Some(slash_pos) = s.find('/') {
             if slash_pos > 0 && slash_pos < s.len() - 1 {
-                let num_parse_result = s[0..slash_pos].parse::<i64>();
-                let den_parse_result = s[slash_pos + 1..].parse::<i64>();
+                let num_parse_result = BigInt::from_str_radix(&s[0..slash_pos], 10);
+                let den_parse_result = BigInt::from_str_radix(&s[slash_pos + 1..], 10);
 
                 match (num_parse_result, den_parse_result) {
                     (Ok(num), Ok(den)) => {
-                        if den == 0 {
+                        if den.is_zero() {
                             return Err(Error::Message(
                                 "division by zero in rational number".to_string(),
                             ));
                         }
-                        return Ok(Number::from_rational(num, den));
+                        return Ok(Number::from_big_rational(BigRational::new(num, den)));
                     }
                     (Err(e), _) => Err(Error::Message(format!(
                         "invalid rational numerator format: {}",
@@ -110,7 +71,13 @@ impl Number {
         }
 
         // Real number
-        if s.contains('.') {
+        match s {
+            "+inf.0" => return Ok(Float(f64::INFINITY)),
+            "-inf.0" => return Ok(Float(f64::NEG_INFINITY)),
+            "+nan.0" | "-nan.0" => return Ok(Float(f64::NAN)),
+            _ => {}
+        }
+        if s.contains(['.', 'e', 'E']) && s.contains(|c: char| c.is_ascii_digit()) {
             if let Ok(f) = s.parse::<f64>() {
                 return Ok(Float(f));
             }
@@ -130,6 +97,40 @@ impl Number {
         Err(Error::Message(m))
     }
 
+    /// Parse a complex number in rectangular (`a+bi`) or polar (`r@theta`) notation.
+    fn parse_complex(s: &str) -> Option<Number> {
+        if let Some((magnitude, angle)) = s.split_once('@') {
+            let magnitude = Number::parse_real(magnitude)?;
+            let angle = Number::parse_real(angle)?;
+            return Number::make_polar(&magnitude, &angle).ok();
+        }
+
+        // Split before the sign of the imaginary part, skipping signs that belong to an exponent.
+        let body = s.strip_suffix('i')?;
+        let bytes = body.as_bytes();
+        let split = (1..bytes.len())
+            .rev()
+            .find(|&i| matches!(bytes[i], b'+' | b'-') && !matches!(bytes[i - 1], b'e' | b'E'));
+        let (re, im) = match split {
+            Some(i) => (Number::parse_real(&body[..i])?, &body[i..]),
+            None => (Number::from_i64(0), body),
+        };
+        let im = match im {
+            "+" => Number::from_i64(1),
+            "-" => Number::from_i64(-1),
+            _ => Number::parse_real(im)?,
+        };
+        Number::make_rectangular(&re, &im).ok()
+    }
+
+    /// Parse a real number component of a complex number.
+    fn parse_real(s: &str) -> Option<Number> {
+        match Number::from_token(s) {
+            Ok(n) if n.is_real() => Some(n),
+            _ => None,
+        }
+    }
+
     /// Create `Number` from `i64`.
     pub fn from_i64(value: i64) -> Self {
         Int(IntVariant::Small(value))
@@ -156,7 +157,7 @@ impl Number {
                     None
                 }
             }
-            Complex(_) => None,
+            Complex(_) | ExactComplex(_) => None,
         }
     }
 
@@ -174,18 +175,16 @@ impl Number {
             },
             Float(f) => Some(*f),
             Rational(r) => r.to_f64(),
-            Complex(_) => None,
+            Complex(_) | ExactComplex(_) => None,
         }
     }
 
-    /// Convert `Number` to `usize`.
+    /// Convert an exact non-negative integer to `usize`, returning `None` for
+    /// negative, inexact and out of range numbers.
     pub fn to_usize(&self) -> Option<usize> {
         match self {
-            Int(int_var) => match int_var {
-                IntVariant::Small(i) => Some(*i as usize),
-                IntVariant::Big(b) => b.to_usize(),
-            },
-            Float(f) => Some(*f as usize),
+            Int(IntVariant::Small(i)) => usize::try_from(*i).ok(),
+            Int(IntVariant::Big(b)) => b.to_usize(),
             _ => None,
         }
     }
@@ -194,12 +193,19 @@ impl Number {
         if den_val == 0 {
             Float(f64::NAN)
         } else {
-            let rational = Rational64::new(num_val, den_val);
-            if rational.denom() == &1 {
-                Int(IntVariant::Small(*rational.numer()))
-            } else {
-                Rational(rational)
-            }
+            Number::from_big_rational(BigRational::new(
+                BigInt::from(num_val),
+                BigInt::from(den_val),
+            ))
+        }
+    }
+
+    /// Create `Number` from `BigRational`. Demotes to an integer if the denominator is 1.
+    pub fn from_big_rational(value: BigRational) -> Self {
+        if value.is_integer() {
+            Number::from_bigint(value.to_integer())
+        } else {
+            Rational(Box::new(value))
         }
     }
 
@@ -211,6 +217,14 @@ impl Number {
         }
     }
 
+    /// Create `Number` from `u64`.
+    pub fn from_u64(value: u64) -> Self {
+        match i64::try_from(value) {
+            Ok(i) => Int(IntVariant::Small(i)),
+            Err(_) => Int(IntVariant::Big(BigInt::from(value))),
+        }
+    }
+
     pub fn from_usize(size: usize) -> Self {
         Int(IntVariant::Small(size as i64))
     }
@@ -222,6 +236,7 @@ impl Number {
             Float(f) => f.to_u8(),
             Rational(r) => r.to_u8(),
             Complex(c) => c.to_u8(),
+            ExactComplex(_) => None,
         }
     }
 
@@ -244,11 +259,19 @@ impl Number {
         }
     }
 
-    /// Raise a number to the exponent of another number. Complex numbers are unsupported.
+    /// Raise a number to the exponent of another number.
     pub fn pow(&self, exponent: &Number) -> Result<Number, Error> {
         match (self, exponent) {
             // Integer base
             (Int(base), Int(exponent)) => {
+                if exponent.is_negative() {
+                    let denominator = base.clone().pow(exponent.abs())?;
+                    if denominator.is_zero() {
+                        return Err(Error::new("unable to divide by 0"));
+                    }
+                    let result = BigRational::new(BigInt::one(), denominator.to_bigint());
+                    return Ok(Number::from_big_rational(result));
+                }
                 let result = base.clone().pow(exponent.clone())?;
                 Ok(Int(result))
             }
@@ -278,29 +301,16 @@ impl Number {
             }
             // Rational base
             (Rational(base), Int(exponent)) => {
-                let exp_i64 = match exponent {
-                    IntVariant::Small(i) => *i,
-                    IntVariant::Big(_) => {
-                        return self.pow_via_float(&Int(exponent.clone()));
-                    }
+                let exp_i32 = match exponent.to_i64().and_then(|e| i32::try_from(e).ok()) {
+                    Some(e) => e,
+                    None => return self.pow_via_float(&Int(exponent.clone())),
                 };
 
-                if exp_i64 == 0 {
-                    return Ok(Number::from_i64(1));
+                if base.is_zero() && exp_i32 < 0 {
+                    return Err(Error::new("unable to divide by 0"));
                 }
 
-                let result = if exp_i64 < 0 {
-                    let inverted = Rational64::new(*base.denom(), *base.numer());
-                    inverted.pow((-exp_i64) as u32)
-                } else {
-                    base.pow(exp_i64 as i32)
-                };
-
-                if result.is_integer() {
-                    Ok(Number::from_i64(*result.numer()))
-                } else {
-                    Ok(Rational(result))
-                }
+                Ok(Number::from_big_rational(Pow::pow(&**base, exp_i32)))
             }
             (Rational(base), Rational(exponent)) => {
                 let base_float = base
@@ -341,9 +351,17 @@ impl Number {
                 let result = base.powf(*exponent);
                 Ok(Number::rationalize_float(result))
             }
-            _ => Err(Error::Message(
-                "pow is not implemented for this number type".to_string(),
-            )),
+            // Complex base or exponent
+            (ExactComplex(base), Int(exponent)) => {
+                match exponent.to_i64().and_then(|e| i32::try_from(e).ok()) {
+                    Some(e) => Ok(Number::from_exact_complex(Pow::pow(&**base, e))),
+                    None => Err(Error::new("exponent is too large")),
+                }
+            }
+            _ => {
+                let result = self.to_complex64()?.powc(exponent.to_complex64()?);
+                Ok(Number::from_complex64(result))
+            }
         }
     }
 
@@ -358,6 +376,512 @@ impl Number {
         Ok(Number::rationalize_float(result))
     }
 
+    /// Return true if `&self` is an exact number.
+    pub fn is_exact(&self) -> bool {
+        matches!(self, Int(_) | Rational(_) | ExactComplex(_))
+    }
+
+    /// Return true if `&self` and `other` are equivalent under `eqv?`.
+    ///
+    /// Numbers of different exactness are never equivalent. Inexact numbers are
+    /// compared by representation, so `0.0` and `-0.0` differ and NaNs match.
+    pub fn eqv(&self, other: &Number) -> bool {
+        match (self, other) {
+            (Int(a), Int(b)) => a.partial_cmp(b) == Some(Ordering::Equal),
+            (Int(_) | Rational(_), Int(_) | Rational(_)) => {
+                self.to_big_rational().ok() == other.to_big_rational().ok()
+            }
+            (ExactComplex(a), ExactComplex(b)) => a == b,
+            (Float(a), Float(b)) => float_eqv(*a, *b),
+            (Complex(a), Complex(b)) => float_eqv(a.re, b.re) && float_eqv(a.im, b.im),
+            _ => false,
+        }
+    }
+
+    /// Convert `&self` to an exact number.
+    /// Finite floats are converted to the rational they represent exactly.
+    pub fn to_exact(&self) -> Result<Number, Error> {
+        match self {
+            Int(_) | Rational(_) => Ok(self.clone()),
+            Float(f) => BigRational::from_float(*f)
+                .map(Number::from_big_rational)
+                .ok_or_else(|| Error::Message(format!("no exact representation for {}", f))),
+            Complex(c) => {
+                Number::make_rectangular(&Float(c.re).to_exact()?, &Float(c.im).to_exact()?)
+            }
+            ExactComplex(_) => Ok(self.clone()),
+        }
+    }
+
+    /// Convert `&self` to an inexact number.
+    pub fn to_inexact(&self) -> Result<Number, Error> {
+        match self {
+            Int(_) | Rational(_) => self
+                .to_f64()
+                .map(Float)
+                .ok_or(Error::new("unable to convert number to float")),
+            ExactComplex(_) => Ok(Complex(self.to_complex64()?)),
+            Float(_) | Complex(_) => Ok(self.clone()),
+        }
+    }
+
+    /// Return the numerator of `&self` in lowest terms.
+    /// The result is inexact if `&self` is inexact.
+    pub fn numerator(&self) -> Result<Number, Error> {
+        match self {
+            Int(_) => Ok(self.clone()),
+            Rational(r) => Ok(Number::from_bigint(r.numer().clone())),
+            Float(_) => self.to_exact()?.numerator()?.to_inexact(),
+            Complex(_) | ExactComplex(_) => Err(Error::new("expected rational number")),
+        }
+    }
+
+    /// Return the denominator of `&self` in lowest terms.
+    /// The result is inexact if `&self` is inexact.
+    pub fn denominator(&self) -> Result<Number, Error> {
+        match self {
+            Int(_) => Ok(Number::from_i64(1)),
+            Rational(r) => Ok(Number::from_bigint(r.denom().clone())),
+            Float(_) => self.to_exact()?.denominator()?.to_inexact(),
+            Complex(_) | ExactComplex(_) => Err(Error::new("expected rational number")),
+        }
+    }
+
+    /// Round to the nearest integer, rounding to even when halfway between two integers.
+    pub fn round(&self) -> Result<Number, Error> {
+        match self {
+            Int(_) => Ok(self.clone()),
+            Rational(r) => Ok(Number::from_big_rational(round_half_even(r))),
+            Float(f) => Ok(Float(f.round_ties_even())),
+            Complex(_) | ExactComplex(_) => Err(Error::new("unable to round complex number")),
+        }
+    }
+
+    /// Round towards zero.
+    pub fn truncate(&self) -> Result<Number, Error> {
+        match self {
+            Int(_) => Ok(self.clone()),
+            Rational(r) => Ok(Number::from_big_rational(r.trunc())),
+            Float(f) => Ok(Float(f.trunc())),
+            Complex(_) | ExactComplex(_) => Err(Error::new("unable to round complex number")),
+        }
+    }
+
+    /// Round towards negative infinity.
+    pub fn floor(&self) -> Result<Number, Error> {
+        match self {
+            Int(_) => Ok(self.clone()),
+            Rational(r) => Ok(Number::from_big_rational(r.floor())),
+            Float(f) => Ok(Float(f.floor())),
+            Complex(_) | ExactComplex(_) => Err(Error::new("unable to round complex number")),
+        }
+    }
+
+    /// Round towards positive infinity.
+    pub fn ceiling(&self) -> Result<Number, Error> {
+        match self {
+            Int(_) => Ok(self.clone()),
+            Rational(r) => Ok(Number::from_big_rational(r.ceil())),
+            Float(f) => Ok(Float(f.ceil())),
+            Complex(_) | ExactComplex(_) => Err(Error::new("unable to round complex number")),
+        }
+    }
+
+    /// Return the simplest rational number that differs from `&self` by no more than `tolerance`.
+    /// The result is inexact if either argument is inexact.
+    pub fn rationalize(&self, tolerance: &Number) -> Result<Number, Error> {
+        let (x, y) = match (self, tolerance) {
+            (Complex(_), _) | (_, Complex(_)) => {
+                return Err(Error::new("expected real numbers"));
+            }
+            (Float(x), _) if x.is_nan() => return Ok(Float(f64::NAN)),
+            (_, Float(y)) if y.is_nan() => return Ok(Float(f64::NAN)),
+            (_, Float(y)) if y.is_infinite() => {
+                return match self {
+                    Float(x) if x.is_infinite() => Ok(Float(f64::NAN)),
+                    _ => Ok(Float(0.0)),
+                };
+            }
+            (Float(x), _) if x.is_infinite() => return Ok(Float(*x)),
+            (x, y) => (x.to_exact()?, y.to_exact()?),
+        };
+
+        let x = x.to_big_rational()?;
+        let y = y.to_big_rational()?.abs();
+        let result = Number::from_big_rational(simplest_rational(&(&x - &y), &(&x + &y)));
+
+        if self.is_exact() && tolerance.is_exact() {
+            Ok(result)
+        } else {
+            result.to_inexact()
+        }
+    }
+
+    /// Return `e` raised to the power of `&self`.
+    pub fn exp(&self) -> Result<Number, Error> {
+        match self {
+            Int(i) if i.is_zero() => Ok(Number::from_i64(1)),
+            Complex(_) | ExactComplex(_) => Ok(Number::from_complex64(self.to_complex64()?.exp())),
+            _ => Ok(Float(self.to_real_f64()?.exp())),
+        }
+    }
+
+    /// Return the natural logarithm of `&self`.
+    /// Negative real numbers produce a complex result.
+    pub fn ln(&self) -> Result<Number, Error> {
+        match self {
+            Int(i) if i.is_zero() => Err(Error::new("logarithm of exact zero is undefined")),
+            Int(IntVariant::Small(1)) => Ok(Number::from_i64(0)),
+            Complex(_) | ExactComplex(_) => Ok(Number::from_complex64(self.to_complex64()?.ln())),
+            Int(_) | Rational(_) if !self.to_real_f64()?.is_normal() => {
+                // Too large or small for `f64`, so take the logarithm of the scaled value.
+                let r = self.to_big_rational()?;
+                let (m, e) = split_big_rational(&r);
+                let ln = m.ln() + e as f64 * std::f64::consts::LN_2;
+                if r.is_negative() {
+                    Ok(Number::from_complex64(Complex64::new(
+                        ln,
+                        std::f64::consts::PI,
+                    )))
+                } else {
+                    Ok(Float(ln))
+                }
+            }
+            _ => {
+                let x = self.to_real_f64()?;
+                if x < 0.0 {
+                    Ok(Number::from_complex64(Complex64::new(x, 0.0).ln()))
+                } else {
+                    Ok(Float(x.ln()))
+                }
+            }
+        }
+    }
+
+    /// Return the logarithm of `&self` in base `base`.
+    pub fn log(&self, base: &Number) -> Result<Number, Error> {
+        let numerator = self.ln()?;
+        let denominator = base.ln()?;
+        if denominator.is_zero() {
+            return Err(Error::new("logarithm base must not be 1"));
+        }
+        (numerator.to_inexact()? / denominator.to_inexact()?).map(|n| n.demote_complex())
+    }
+
+    /// Return the sine of `&self`.
+    pub fn sin(&self) -> Result<Number, Error> {
+        match self {
+            Int(i) if i.is_zero() => Ok(Number::from_i64(0)),
+            Complex(_) | ExactComplex(_) => Ok(Number::from_complex64(self.to_complex64()?.sin())),
+            _ => Ok(Float(self.to_real_f64()?.sin())),
+        }
+    }
+
+    /// Return the cosine of `&self`.
+    pub fn cos(&self) -> Result<Number, Error> {
+        match self {
+            Int(i) if i.is_zero() => Ok(Number::from_i64(1)),
+            Complex(_) | ExactComplex(_) => Ok(Number::from_complex64(self.to_complex64()?.cos())),
+            _ => Ok(Float(self.to_real_f64()?.cos())),
+        }
+    }
+
+    /// Return the tangent of `&self`.
+    pub fn tan(&self) -> Result<Number, Error> {
+        match self {
+            Int(i) if i.is_zero() => Ok(Number::from_i64(0)),
+            Complex(_) | ExactComplex(_) => Ok(Number::from_complex64(self.to_complex64()?.tan())),
+            _ => Ok(Float(self.to_real_f64()?.tan())),
+        }
+    }
+
+    /// Return the arcsine of `&self`.
+    /// Real arguments outside of `[-1, 1]` produce a complex result.
+    pub fn asin(&self) -> Result<Number, Error> {
+        match self {
+            Int(i) if i.is_zero() => Ok(Number::from_i64(0)),
+            Complex(_) | ExactComplex(_) => Ok(Number::from_complex64(self.to_complex64()?.asin())),
+            _ => {
+                let x = self.to_real_f64()?;
+                if (-1.0..=1.0).contains(&x) {
+                    Ok(Float(x.asin()))
+                } else {
+                    Ok(Number::from_complex64(Complex64::new(x, 0.0).asin()))
+                }
+            }
+        }
+    }
+
+    /// Return the arccosine of `&self`.
+    /// Real arguments outside of `[-1, 1]` produce a complex result.
+    pub fn acos(&self) -> Result<Number, Error> {
+        match self {
+            Int(IntVariant::Small(1)) => Ok(Number::from_i64(0)),
+            Complex(_) | ExactComplex(_) => Ok(Number::from_complex64(self.to_complex64()?.acos())),
+            _ => {
+                let x = self.to_real_f64()?;
+                if (-1.0..=1.0).contains(&x) {
+                    Ok(Float(x.acos()))
+                } else {
+                    Ok(Number::from_complex64(Complex64::new(x, 0.0).acos()))
+                }
+            }
+        }
+    }
+
+    /// Return the arctangent of `&self`.
+    pub fn atan(&self) -> Result<Number, Error> {
+        match self {
+            Int(i) if i.is_zero() => Ok(Number::from_i64(0)),
+            Complex(_) | ExactComplex(_) => Ok(Number::from_complex64(self.to_complex64()?.atan())),
+            _ => Ok(Float(self.to_real_f64()?.atan())),
+        }
+    }
+
+    /// Return the angle of the point `(x, y)` where `&self` is `y`.
+    pub fn atan2(&self, x: &Number) -> Result<Number, Error> {
+        let y = self.to_real_f64()?;
+        let x = x.to_real_f64()?;
+        Ok(Float(y.atan2(x)))
+    }
+
+    /// Return the principal square root of `&self`.
+    /// Exact perfect squares produce exact results, negative real numbers produce complex results.
+    pub fn sqrt(&self) -> Result<Number, Error> {
+        match self {
+            Int(_) | Rational(_) => {
+                let r = self.to_big_rational()?;
+                let (numer, denom) = (exact_sqrt(&r.numer().abs()), exact_sqrt(r.denom()));
+                if let (Some(numer), Some(denom)) = (numer, denom) {
+                    let root = Number::from_big_rational(BigRational::new(numer, denom));
+                    return if r.is_negative() {
+                        Number::make_rectangular(&Number::from_i64(0), &root)
+                    } else {
+                        Ok(root)
+                    };
+                }
+                if self.to_real_f64()?.is_normal() {
+                    return Float(self.to_real_f64()?).sqrt();
+                }
+                // Too large or small for `f64`, so halve the power of two before converting.
+                let (m, e) = split_big_rational(&r);
+                let half = (e / 2).clamp(i32::MIN.into(), i32::MAX.into()) as i32;
+                let root = Float(m.sqrt() * 2f64.powi(half / 2) * 2f64.powi(half - half / 2));
+                if r.is_negative() {
+                    Number::make_rectangular(&Number::from_i64(0), &root)
+                } else {
+                    Ok(root)
+                }
+            }
+            Float(f) if *f < 0.0 => Ok(Complex(Complex64::new(0.0, (-f).sqrt()))),
+            Float(f) => Ok(Float(f.sqrt())),
+            Complex(_) | ExactComplex(_) => Ok(Number::from_complex64(self.to_complex64()?.sqrt())),
+        }
+    }
+
+    /// Return true if `&self` is neither infinite nor NaN.
+    pub fn is_finite(&self) -> bool {
+        match self {
+            Int(_) | Rational(_) | ExactComplex(_) => true,
+            Float(f) => f.is_finite(),
+            Complex(c) => c.is_finite(),
+        }
+    }
+
+    /// Return true if the real or imaginary part of `&self` is infinite.
+    pub fn is_infinite(&self) -> bool {
+        match self {
+            Int(_) | Rational(_) | ExactComplex(_) => false,
+            Float(f) => f.is_infinite(),
+            Complex(c) => c.is_infinite(),
+        }
+    }
+
+    /// Return true if the real or imaginary part of `&self` is NaN.
+    pub fn is_nan(&self) -> bool {
+        match self {
+            Int(_) | Rational(_) | ExactComplex(_) => false,
+            Float(f) => f.is_nan(),
+            Complex(c) => c.is_nan(),
+        }
+    }
+
+    /// Return true if `&self` is zero.
+    pub fn is_zero(&self) -> bool {
+        match self {
+            Int(i) => i.is_zero(),
+            Rational(r) => r.is_zero(),
+            Float(f) => *f == 0.0,
+            Complex(c) => c.is_zero(),
+            ExactComplex(c) => c.is_zero(),
+        }
+    }
+
+    /// Create a complex number from real and imaginary parts.
+    /// The result is exact if both parts are exact.
+    pub fn make_rectangular(re: &Number, im: &Number) -> Result<Number, Error> {
+        if !re.is_real() || !im.is_real() {
+            return Err(Error::new("expected real numbers"));
+        }
+        if im.is_exact() && im.is_zero() {
+            return Ok(re.clone());
+        }
+        if re.is_exact() && im.is_exact() {
+            let value = NumComplex::new(re.to_big_rational()?, im.to_big_rational()?);
+            return Ok(Number::from_exact_complex(value));
+        }
+        Ok(Complex(Complex64::new(
+            re.to_real_f64()?,
+            im.to_real_f64()?,
+        )))
+    }
+
+    /// Create a complex number from a magnitude and an angle.
+    pub fn make_polar(magnitude: &Number, angle: &Number) -> Result<Number, Error> {
+        if !magnitude.is_real() || !angle.is_real() {
+            return Err(Error::new("expected real numbers"));
+        }
+        if angle.is_exact() && angle.is_zero() {
+            return Ok(magnitude.clone());
+        }
+        let (r, theta) = (magnitude.to_real_f64()?, angle.to_real_f64()?);
+        Ok(Complex(Complex64::from_polar(r, theta)))
+    }
+
+    /// Return the real part of `&self`.
+    pub fn real_part(&self) -> Number {
+        match self {
+            Complex(c) => Float(c.re),
+            ExactComplex(c) => Number::from_big_rational(c.re.clone()),
+            _ => self.clone(),
+        }
+    }
+
+    /// Return the imaginary part of `&self`.
+    pub fn imag_part(&self) -> Number {
+        match self {
+            Complex(c) => Float(c.im),
+            ExactComplex(c) => Number::from_big_rational(c.im.clone()),
+            _ => Number::from_i64(0),
+        }
+    }
+
+    /// Return the magnitude of `&self`.
+    /// Exact complex numbers with a perfect square norm have an exact magnitude.
+    pub fn magnitude(&self) -> Result<Number, Error> {
+        match self {
+            Complex(c) => Ok(Float(c.norm())),
+            ExactComplex(c) => Number::from_big_rational(c.norm_sqr()).sqrt(),
+            Float(f) => Ok(Float(f.abs())),
+            Int(i) => Ok(Int(i.abs())),
+            Rational(r) => Ok(Rational(Box::new(r.abs()))),
+        }
+    }
+
+    /// Return the angle of `&self` in the complex plane.
+    pub fn angle(&self) -> Result<Number, Error> {
+        match self {
+            Complex(_) | ExactComplex(_) => Ok(Float(self.to_complex64()?.arg())),
+            Float(f) => Ok(Float(0.0_f64.atan2(*f))),
+            _ if self.is_negative() => Ok(Float(std::f64::consts::PI)),
+            _ => Ok(Number::from_i64(0)),
+        }
+    }
+
+    /// Return true if `&self` has no imaginary part.
+    pub fn is_real(&self) -> bool {
+        !matches!(self, Complex(_) | ExactComplex(_))
+    }
+
+    /// Return true if `&self` is a real number less than zero.
+    pub fn is_negative(&self) -> bool {
+        match self {
+            Int(i) => i.is_negative(),
+            Rational(r) => r.is_negative(),
+            Float(f) => *f < 0.0,
+            Complex(_) | ExactComplex(_) => false,
+        }
+    }
+
+    /// Create `Number` from an exact complex number. Demotes to a real number if the imaginary part is 0.
+    fn from_exact_complex(value: NumComplex<BigRational>) -> Self {
+        if value.im.is_zero() {
+            Number::from_big_rational(value.re)
+        } else {
+            ExactComplex(Box::new(value))
+        }
+    }
+
+    /// Convert an exact `Number` to an exact complex number.
+    fn to_exact_complex(&self) -> Result<NumComplex<BigRational>, Error> {
+        match self {
+            ExactComplex(c) => Ok(*c.clone()),
+            _ => Ok(NumComplex::new(
+                self.to_big_rational()?,
+                BigRational::zero(),
+            )),
+        }
+    }
+
+    /// Convert `Number` to `Complex64`.
+    fn to_complex64(&self) -> Result<Complex64, Error> {
+        match self {
+            Complex(c) => Ok(*c),
+            ExactComplex(c) => match (c.re.to_f64(), c.im.to_f64()) {
+                (Some(re), Some(im)) => Ok(Complex64::new(re, im)),
+                _ => Err(Error::new("unable to convert complex number to f64")),
+            },
+            _ => Ok(Complex64::new(self.to_real_f64()?, 0.0)),
+        }
+    }
+
+    /// Create `Number` from `Complex64`. Demotes to a real number if the imaginary part is 0.
+    fn from_complex64(value: Complex64) -> Self {
+        if value.im == 0.0 {
+            Float(value.re)
+        } else {
+            Complex(value)
+        }
+    }
+
+    /// Demote a complex `Number` with a zero imaginary part to a real number.
+    fn demote_complex(self) -> Self {
+        match self {
+            Complex(c) => Number::from_complex64(c),
+            n => n,
+        }
+    }
+
+    /// Convert a real `Number` to `f64`.
+    fn to_real_f64(&self) -> Result<f64, Error> {
+        self.to_f64().ok_or(Error::new("expected real number"))
+    }
+
+    /// Convert an exact `Number` to `BigRational`.
+    fn to_big_rational(&self) -> Result<BigRational, Error> {
+        match self {
+            Int(i) => Ok(i.to_big_rational()),
+            Rational(r) => Ok(*r.clone()),
+            _ => Err(Error::new("expected exact rational number")),
+        }
+    }
+
+    /// Write `&self` in the given radix. Inexact numbers can only be written in radix 10.
+    pub fn to_string_radix(&self, radix: u32) -> Result<String, Error> {
+        if !matches!(radix, 2 | 8 | 10 | 16) {
+            return Err(Error::new("radix must be 2, 8, 10 or 16"));
+        }
+        match self {
+            Int(i) => Ok(i.to_bigint().to_str_radix(radix)),
+            Rational(r) => Ok(format_rational(r, radix)),
+            ExactComplex(c) => Ok(format_exact_complex(c, radix)),
+            Float(_) | Complex(_) if radix == 10 => Ok(self.to_string()),
+            Float(_) | Complex(_) => Err(Error::new(
+                "inexact numbers can only be written in radix 10",
+            )),
+        }
+    }
+
     /// Return if `&self` is a byte compatible number.
     pub fn is_byte(&self) -> bool {
         let lower_bound = Number::from_i64(0);
@@ -378,56 +902,19 @@ impl Add for Number {
     type Output = Result<Number, Error>;
     fn add(self, other: Number) -> Self::Output {
         match (self, other) {
-            // Case 1: Complex + Any
-            (Complex(c1), Complex(c2)) => Ok(Complex(c1 + c2)),
-            (Complex(c1), Float(r2)) => Ok(Complex(c1 + Complex64::new(r2, 0.0))),
-            (Complex(c1), Rational(r2)) => {
-                Ok(Complex(c1 + Complex64::new(r2.to_f64().unwrap(), 0.0)))
-            }
-            (Complex(c1), Int(i2)) => Ok(Complex(c1 + Complex64::new(i2.to_f64().unwrap(), 0.0))),
-
-            // Case 2: Real + Any (that hasn't been handled by Complex + Any)
-            (Float(r1), Complex(c2)) => Ok(Complex(Complex64::new(r1, 0.0) + c2)),
+            // Case 1: Real + Any
             (Float(r1), Float(r2)) => Ok(Float(r1 + r2)),
             (Float(r1), Rational(r2)) => Ok(Float(r1 + r2.to_f64().unwrap())),
             (Float(r1), Int(i2)) => Ok(Float(r1 + i2.to_f64().unwrap())),
 
-            // Case 3: Rational + Any (that hasn't been handled by Complex/Real + Any)
-            (Rational(r1), Complex(c2)) => {
-                Ok(Complex(Complex64::new(r1.to_f64().unwrap(), 0.0) + c2))
-            }
+            // Case 2: Rational + Any
             (Rational(r1), Float(r2)) => Ok(Float(r1.to_f64().unwrap() + r2)),
-            (Rational(r1), Rational(r2)) => Ok(Rational(r1 + r2)),
-            (Rational(r1), Int(i2)) => {
-                let i2_rational = match i2 {
-                    IntVariant::Small(f) => Rational64::new(f, 1),
-                    IntVariant::Big(b) => {
-                        if let Some(f) = b.to_i64() {
-                            Rational64::new(f, 1)
-                        } else {
-                            return Ok(Float(r1.to_f64().unwrap() + b.to_f64().unwrap()));
-                        }
-                    }
-                };
-                Ok(Rational(r1 + i2_rational))
-            }
+            (Rational(r1), Rational(r2)) => Ok(Number::from_big_rational(*r1 + *r2)),
+            (Rational(r1), Int(i2)) => Ok(Number::from_big_rational(*r1 + i2.to_big_rational())),
 
-            // Case 4: Integer + Any (that hasn't been handled by higher types)
-            (Int(i1), Complex(c2)) => Ok(Complex(Complex64::new(i1.to_f64().unwrap(), 0.0) + c2)),
+            // Case 3: Integer + Any
             (Int(i1), Float(r2)) => Ok(Float(i1.to_f64().unwrap() + r2)),
-            (Int(i1), Rational(r2)) => {
-                let i1_rational = match i1 {
-                    IntVariant::Small(f) => Rational64::new(f, 1),
-                    IntVariant::Big(b) => {
-                        if let Some(f) = b.to_i64() {
-                            Rational64::new(f, 1)
-                        } else {
-                            return Ok(Float(b.to_f64().unwrap() + r2.to_f64().unwrap()));
-                        }
-                    }
-                };
-                Ok(Rational(i1_rational + r2))
-            }
+            (Int(i1), Rational(r2)) => Ok(Number::from_big_rational(i1.to_big_rational() + *r2)),
             (Int(i1), Int(i2)) => match (i1, i2) {
                 (IntVariant::Small(f1), IntVariant::Small(f2)) => {
                     let sum = f1.checked_add(f2);
@@ -450,6 +937,9 @@ impl Add for Number {
                     Ok(Number::from_bigint(b1 + b2))
                 }
             },
+
+            // Case 4: Complex + Any (either operand has an imaginary part)
+            (lhs, rhs) => complex_arith(lhs, rhs, |a, b| a + b, |a, b| a + b),
         }
     }
 }
@@ -458,57 +948,19 @@ impl Sub for Number {
     type Output = Result<Number, Error>;
     fn sub(self, other: Number) -> Self::Output {
         match (self, other) {
-            // Complex - Any
-            (Complex(c1), Complex(c2)) => Ok(Complex(c1 - c2)),
-            (Complex(c1), Float(r2)) => Ok(Complex(c1 - Complex64::new(r2, 0.0))),
-            (Complex(c1), Rational(r2)) => {
-                Ok(Complex(c1 - Complex64::new(r2.to_f64().unwrap(), 0.0)))
-            }
-            (Complex(c1), Int(i2)) => Ok(Complex(c1 - Complex64::new(i2.to_f64().unwrap(), 0.0))),
-
             // Real - Any
-            (Float(r1), Complex(c2)) => Ok(Complex(Complex64::new(r1, 0.0) - c2)),
             (Float(r1), Float(r2)) => Ok(Float(r1 - r2)),
             (Float(r1), Rational(r2)) => Ok(Float(r1 - r2.to_f64().unwrap())),
             (Float(r1), Int(i2)) => Ok(Float(r1 - i2.to_f64().unwrap())),
 
             //Rational - Any
-            (Rational(r1), Complex(c2)) => {
-                Ok(Complex(Complex64::new(r1.to_f64().unwrap(), 0.0) - c2))
-            }
             (Rational(r1), Float(r2)) => Ok(Float(r1.to_f64().unwrap() - r2)),
-            (Rational(r1), Rational(r2)) => Ok(Rational(r1 - r2)),
-            (Rational(r1), Int(i2)) => {
-                let i2_rational = match i2 {
-                    IntVariant::Small(f) => Rational64::new(f, 1),
-                    IntVariant::Big(b) => {
-                        if let Some(f) = b.to_i64() {
-                            Rational64::new(f, 1)
-                        } else {
-                            return Ok(Float(r1.to_f64().unwrap() - b.to_f64().unwrap()));
-                        }
-                    }
-                };
-                Ok(Rational(r1 - i2_rational))
-            }
+            (Rational(r1), Rational(r2)) => Ok(Number::from_big_rational(*r1 - *r2)),
+            (Rational(r1), Int(i2)) => Ok(Number::from_big_rational(*r1 - i2.to_big_rational())),
 
             // Integer - Any
-            (Int(i1), Complex(c2)) => Ok(Complex(Complex64::new(i1.to_f64().unwrap(), 0.0) - c2)),
             (Int(i1), Float(r2)) => Ok(Float(i1.to_f64().unwrap() - r2)),
-            (Int(i1), Rational(r2)) => {
-                // Promote integer to rational
-                let i1_rational = match i1 {
-                    IntVariant::Small(f) => Rational64::new(f, 1),
-                    IntVariant::Big(b) => {
-                        if let Some(f) = b.to_i64() {
-                            Rational64::new(f, 1)
-                        } else {
-                            return Ok(Float(b.to_f64().unwrap() - r2.to_f64().unwrap()));
-                        }
-                    }
-                };
-                Ok(Rational(i1_rational - r2))
-            }
+            (Int(i1), Rational(r2)) => Ok(Number::from_big_rational(i1.to_big_rational() - *r2)),
             (Int(i1), Int(i2)) => {
                 match (i1, i2) {
                     (IntVariant::Small(f1), IntVariant::Small(f2)) => {
@@ -534,6 +986,9 @@ impl Sub for Number {
                     }
                 }
             }
+
+            // Complex - Any (either operand has an imaginary part)
+            (lhs, rhs) => complex_arith(lhs, rhs, |a, b| a - b, |a, b| a - b),
         }
     }
 }
@@ -541,56 +996,19 @@ impl Mul for Number {
     type Output = Result<Number, Error>;
     fn mul(self, other: Number) -> Self::Output {
         match (self, other) {
-            // Complex * Any
-            (Complex(c1), Complex(c2)) => Ok(Complex(c1 * c2)),
-            (Complex(c1), Float(r2)) => Ok(Complex(c1 * Complex64::new(r2, 0.0))),
-            (Complex(c1), Rational(r2)) => {
-                Ok(Complex(c1 * Complex64::new(r2.to_f64().unwrap(), 0.0)))
-            }
-            (Complex(c1), Int(i2)) => Ok(Complex(c1 * Complex64::new(i2.to_f64().unwrap(), 0.0))),
-
             // Real * Any
-            (Float(r1), Complex(c2)) => Ok(Complex(Complex64::new(r1, 0.0) * c2)),
             (Float(r1), Float(r2)) => Ok(Float(r1 * r2)),
             (Float(r1), Rational(r2)) => Ok(Float(r1 * r2.to_f64().unwrap())),
             (Float(r1), Int(i2)) => Ok(Float(r1 * i2.to_f64().unwrap())),
 
             // Rational * Any
-            (Rational(r1), Complex(c2)) => {
-                Ok(Complex(Complex64::new(r1.to_f64().unwrap(), 0.0) * c2))
-            }
             (Rational(r1), Float(r2)) => Ok(Float(r1.to_f64().unwrap() * r2)),
-            (Rational(r1), Rational(r2)) => Ok(Rational(r1 * r2)),
-            (Rational(r1), Int(i2)) => {
-                let i2_rational = match i2 {
-                    IntVariant::Small(f) => Rational64::new(f, 1),
-                    IntVariant::Big(b) => {
-                        b.to_i64()
-                            .map(|f| Rational64::new(f, 1))
-                            .ok_or(Error::Message(
-                                "unable to create rational number from i64".to_string(),
-                            ))?
-                    }
-                };
-                Ok(Rational(r1 * i2_rational))
-            }
+            (Rational(r1), Rational(r2)) => Ok(Number::from_big_rational(*r1 * *r2)),
+            (Rational(r1), Int(i2)) => Ok(Number::from_big_rational(*r1 * i2.to_big_rational())),
 
             // Integer * Any
-            (Int(i1), Complex(c2)) => Ok(Complex(Complex64::new(i1.to_f64().unwrap(), 0.0) * c2)),
             (Int(i1), Float(r2)) => Ok(Float(i1.to_f64().unwrap() * r2)),
-            (Int(i1), Rational(r2)) => {
-                let i1_rational = match i1 {
-                    IntVariant::Small(f) => Rational64::new(f, 1),
-                    IntVariant::Big(b) => {
-                        b.to_i64()
-                            .map(|f| Rational64::new(f, 1))
-                            .ok_or(Error::Message(
-                                "unable to create rational number from i64".to_string(),
-                            ))?
-                    }
-                };
-                Ok(Rational(i1_rational * r2))
-            }
+            (Int(i1), Rational(r2)) => Ok(Number::from_big_rational(i1.to_big_rational() * *r2)),
             (Int(i1), Int(i2)) => match (i1, i2) {
                 (IntVariant::Small(f1), IntVariant::Small(f2)) => {
                     let prod = f1.checked_mul(f2);
@@ -607,6 +1025,9 @@ impl Mul for Number {
                     Ok(Number::from_bigint(b1 * BigInt::from(f2)))
                 }
             },
+
+            // Complex * Any (either operand has an imaginary part)
+            (lhs, rhs) => complex_arith(lhs, rhs, |a, b| a * b, |a, b| a * b),
         }
     }
 }
@@ -629,106 +1050,33 @@ impl Div for Number {
         }
 
         match (self, other) {
-            // Complex / Any
-            (Complex(c1), Complex(c2)) => Ok(Complex(c1 / c2)),
-            (Complex(c1), Float(r2)) => Ok(Complex(c1 / Complex64::new(r2, 0.0))),
-            (Complex(c1), Rational(r2)) => {
-                Ok(Complex(c1 / Complex64::new(r2.to_f64().unwrap(), 0.0)))
-            }
-            (Complex(c1), Int(i2)) => Ok(Complex(c1 / Complex64::new(i2.to_f64().unwrap(), 0.0))),
-
             // Real / Any
-            (Float(r1), Complex(c2)) => Ok(Complex(Complex64::new(r1, 0.0) / c2)),
             (Float(r1), Float(r2)) => Ok(Float(r1 / r2)),
             (Float(r1), Rational(r2)) => Ok(Float(r1 / r2.to_f64().unwrap())),
             (Float(r1), Int(i2)) => Ok(Float(r1 / i2.to_f64().unwrap())),
 
             // Rational / Any
-            (Rational(r1), Complex(c2)) => {
-                Ok(Complex(Complex64::new(r1.to_f64().unwrap(), 0.0) / c2))
-            }
             (Rational(r1), Float(r2)) => Ok(Float(r1.to_f64().unwrap() / r2)),
-            (Rational(r1), Rational(r2)) => Ok(Rational(r1 / r2)),
-            (Rational(r1), Int(i2)) => {
-                let i2_rational = match i2 {
-                    IntVariant::Small(f) => Rational64::new(f, 1),
-                    IntVariant::Big(b) => {
-                        b.to_i64()
-                            .map(|f| Rational64::new(f, 1))
-                            .ok_or(Error::Message(
-                                "unable to create rational number from i64".to_string(),
-                            ))?
-                    }
-                };
-                Ok(Rational(r1 / i2_rational))
-            }
+            (Rational(r1), Rational(r2)) => Ok(Number::from_big_rational(*r1 / *r2)),
+            (Rational(r1), Int(i2)) => Ok(Number::from_big_rational(*r1 / i2.to_big_rational())),
 
             // Integer / Any
-            (Int(i1), Complex(c2)) => Ok(Complex(Complex64::new(i1.to_f64().unwrap(), 0.0) / c2)),
             (Int(i1), Float(r2)) => Ok(Float(i1.to_f64().unwrap() / r2)),
-            (Int(i1), Rational(r2)) => {
-                let i1_rational = match i1 {
-                    IntVariant::Small(f) => Rational64::new(f, 1),
-                    IntVariant::Big(b) => {
-                        b.to_i64()
-                            .map(|f| Rational64::new(f, 1))
-                            .ok_or(Error::Message(
-                                "unable to create rational number from i64".to_string(),
-                            ))?
-                    }
-                };
-                Ok(Rational(i1_rational / r2))
-            }
-            (Int(i1), Int(i2)) => match (i1, i2) {
-                (IntVariant::Small(f1), IntVariant::Small(f2)) => {
-                    if f1 % f2 == 0 {
-                        Ok(Number::from_i64(f1 / f2))
-                    } else {
-                        Ok(Rational(Rational64::new(f1, f2)))
-                    }
-                }
-                (IntVariant::Big(b1), IntVariant::Big(b2)) => {
-                    if b1.is_multiple_of(&b2) {
-                        Ok(Number::from_bigint(b1 / b2))
-                    } else {
-                        let r_num = b1.to_i64().ok_or(Error::Message(
-                            "number too large for rational conversion".to_string(),
-                        ))?;
-                        let r_den = b2.to_i64().ok_or(Error::Message(
-                            "number too large for rational conversion".to_string(),
-                        ))?;
-                        Ok(Rational(Rational64::new(r_num, r_den)))
-                    }
-                }
-                (IntVariant::Small(f1), IntVariant::Big(b2)) => {
-                    let b1 = BigInt::from(f1);
-                    if b1.is_multiple_of(&b2) {
-                        Ok(Number::from_bigint(b1 / b2))
-                    } else {
-                        let r_num = b1.to_i64().ok_or(Error::Message(
-                            "number too large for rational conversion".to_string(),
-                        ))?;
-                        let r_den = b2.to_i64().ok_or(Error::Message(
-                            "number too large for rational conversion".to_string(),
-                        ))?;
-                        Ok(Rational(Rational64::new(r_num, r_den)))
-                    }
-                }
-                (IntVariant::Big(b1), IntVariant::Small(f2)) => {
-                    let b2 = BigInt::from(f2);
-                    if b1.is_multiple_of(&b2) {
-                        Ok(Number::from_bigint(b1 / b2))
-                    } else {
-                        let r_num = b1.to_i64().ok_or(Error::Message(
-                            "number too large for rational conversion".to_string(),
-                        ))?;
-                        let r_den = b2.to_i64().ok_or(Error::Message(
-                            "number too large for rational conversion".to_string(),
-                        ))?;
-                        Ok(Rational(Rational64::new(r_num, r_den)))
-                    }
-                }
+            (Int(i1), Rational(r2)) => Ok(Number::from_big_rational(i1.to_big_rational() / *r2)),
+            (Int(IntVariant::Small(f1)), Int(IntVariant::Small(f2))) => match f1.checked_rem(f2) {
+                Some(0) => Ok(Number::from_i64(f1 / f2)),
+                _ => Ok(Number::from_big_rational(BigRational::new(
+                    BigInt::from(f1),
+                    BigInt::from(f2),
+                ))),
             },
+            (Int(i1), Int(i2)) => Ok(Number::from_big_rational(BigRational::new(
+                i1.to_bigint(),
+                i2.to_bigint(),
+            ))),
+
+            // Complex / Any (either operand has an imaginary part)
+            (lhs, rhs) => complex_arith(lhs, rhs, |a, b| a / b, |a, b| a / b),
         }
     }
 }
@@ -756,8 +1104,9 @@ impl fmt::Display for Number {
             Int(IntVariant::Small(i)) => write!(f, "{}", i),
             Int(IntVariant::Big(b)) => write!(f, "{}", b),
             Rational(r) => write!(f, "{}", r),
-            Float(r) => write!(f, "{}", r),
-            Complex(c) => write!(f, "{}", c),
+            Float(r) => write!(f, "{}", format_float(*r)),
+            Complex(c) => write!(f, "{}{}i", format_float(c.re), format_imaginary(c.im)),
+            ExactComplex(c) => write!(f, "{}", format_exact_complex(c, 10)),
         }
     }
 }
@@ -777,36 +1126,16 @@ impl PartialOrd for Number {
 
                 Some(Ordering::Equal)
             }
-            (Int(i1), Rational(r2)) => {
-                let i1_rational = match i1 {
-                    IntVariant::Small(s) => Rational64::from_i64(*s)?,
-                    IntVariant::Big(h) => Rational64::from_i64(h.to_i64()?)?,
-                };
-
-                i1_rational.partial_cmp(&r2)
-            }
+            (Int(i1), Rational(r2)) => i1.to_big_rational().partial_cmp(r2),
             (Float(f1), Float(f2)) => f1.partial_cmp(f2),
             (Float(f1), Int(i2)) => {
                 let i2_float = i2.to_f64()?;
                 f1.partial_cmp(&i2_float)
             }
-            (Float(f1), Rational(r2)) => {
-                let f1_rational = Rational64::from_f64(*f1)?;
-                f1_rational.partial_cmp(&r2)
-            }
+            (Float(f1), Rational(r2)) => cmp_float_rational(*f1, r2),
             (Rational(r1), Rational(r2)) => r1.partial_cmp(r2),
-            (Rational(r1), Int(i2)) => {
-                let i2_rational = match i2 {
-                    IntVariant::Small(s) => Rational64::from_i64(*s)?,
-                    IntVariant::Big(h) => Rational64::from_i64(h.to_i64()?)?,
-                };
-
-                r1.partial_cmp(&i2_rational)
-            }
-            (Rational(r1), Float(f2)) => {
-                let f2_rational = Rational64::from_f64(*f2)?;
-                r1.partial_cmp(&f2_rational)
-            }
+            (Rational(r1), Int(i2)) => (**r1).partial_cmp(&i2.to_big_rational()),
+            (Rational(r1), Float(f2)) => cmp_float_rational(*f2, r1).map(Ordering::reverse),
             // Complex numbers cannot be ordered, only compared for equality.
             (Int(i1), Complex(c2)) => {
                 let i1_complex = match i1 {
@@ -830,7 +1159,7 @@ impl PartialOrd for Number {
                 None
             }
             (Rational(r1), Complex(c2)) => {
-                let r1_complex = Complex64::from_i64(r1.to_i64()?)?;
+                let r1_complex = Complex64::new(r1.to_f64()?, 0.0);
 
                 if r1_complex == *c2 {
                     return Some(Ordering::Equal);
@@ -845,11 +1174,178 @@ impl PartialOrd for Number {
 
                 None
             }
+            (ExactComplex(c1), ExactComplex(c2)) => {
+                if c1 == c2 {
+                    return Some(Ordering::Equal);
+                }
+
+                None
+            }
+            (ExactComplex(_), Complex(c2)) => {
+                if self.to_complex64().ok()? == *c2 {
+                    return Some(Ordering::Equal);
+                }
+
+                None
+            }
+            (Complex(c1), ExactComplex(_)) => {
+                if *c1 == other.to_complex64().ok()? {
+                    return Some(Ordering::Equal);
+                }
+
+                None
+            }
             _ => None,
         }
     }
 }
 
+/// Apply an arithmetic operation where at least one operand is complex.
+/// The result is exact if both operands are exact.
+fn complex_arith(
+    lhs: Number,
+    rhs: Number,
+    exact_op: fn(NumComplex<BigRational>, NumComplex<BigRational>) -> NumComplex<BigRational>,
+    inexact_op: fn(Complex64, Complex64) -> Complex64,
+) -> Result<Number, Error> {
+    if lhs.is_exact() && rhs.is_exact() {
+        let result = exact_op(lhs.to_exact_complex()?, rhs.to_exact_complex()?);
+        Ok(Number::from_exact_complex(result))
+    } else {
+        Ok(Complex(inexact_op(
+            lhs.to_complex64()?,
+            rhs.to_complex64()?,
+        )))
+    }
+}
+
+/// Format a float in the shortest form that reads back to the same value.
+/// The result always has a decimal point or exponent, or is one of `+inf.0`, `-inf.0`, `+nan.0`.
+fn format_float(value: f64) -> String {
+    if value.is_nan() {
+        "+nan.0".to_string()
+    } else if value.is_infinite() {
+        if value > 0.0 { "+inf.0" } else { "-inf.0" }.to_string()
+    } else {
+        format!("{:?}", value)
+    }
+}
+
+/// Format the imaginary part of an inexact complex number with an explicit sign.
+fn format_imaginary(value: f64) -> String {
+    if value.is_sign_negative() || !value.is_finite() {
+        format_float(value)
+    } else {
+        format!("+{}", format_float(value))
+    }
+}
+
+/// Format an exact rational in the given radix.
+fn format_rational(value: &BigRational, radix: u32) -> String {
+    if value.is_integer() {
+        value.numer().to_str_radix(radix)
+    } else {
+        format!(
+            "{}/{}",
+            value.numer().to_str_radix(radix),
+            value.denom().to_str_radix(radix)
+        )
+    }
+}
+
+/// Format an exact complex number in the given radix, omitting a zero real part.
+fn format_exact_complex(value: &NumComplex<BigRational>, radix: u32) -> String {
+    let re = if value.re.is_zero() {
+        String::new()
+    } else {
+        format_rational(&value.re, radix)
+    };
+    let im = if value.im.is_one() {
+        "+".to_string()
+    } else if (-&value.im).is_one() {
+        "-".to_string()
+    } else if value.im.is_negative() {
+        format_rational(&value.im, radix)
+    } else {
+        format!("+{}", format_rational(&value.im, radix))
+    };
+    format!("{}{}i", re, im)
+}
+
+/// Round a rational to the nearest integer, rounding to even when halfway between two integers.
+fn round_half_even(r: &BigRational) -> BigRational {
+    let floor = r.floor();
+    let half = BigRational::new(BigInt::one(), BigInt::from(2));
+    match (r - &floor).cmp(&half) {
+        Ordering::Less => floor,
+        Ordering::Greater => floor + BigRational::one(),
+        Ordering::Equal if floor.to_integer().is_even() => floor,
+        Ordering::Equal => floor + BigRational::one(),
+    }
+}
+
+/// Return the simplest rational in the closed interval [`low`, `high`].
+fn simplest_rational(low: &BigRational, high: &BigRational) -> BigRational {
+    if low.is_positive() {
+        simplest_positive_rational(low, high)
+    } else if high.is_negative() {
+        -simplest_positive_rational(&-high, &-low)
+    } else {
+        BigRational::zero()
+    }
+}
+
+/// Return the simplest rational in [`low`, `high`], where `0 < low <= high`.
+fn simplest_positive_rational(low: &BigRational, high: &BigRational) -> BigRational {
+    let floor = low.floor();
+    if &floor == low {
+        floor
+    } else if floor < high.floor() {
+        floor + BigRational::one()
+    } else {
+        let rest = simplest_positive_rational(&(high - &floor).recip(), &(low - &floor).recip());
+        floor + rest.recip()
+    }
+}
+
+/// Return `(m, e)` where the magnitude of `r` is about `m * 2^e`, `m` converts to
+/// `f64` without overflow and `e` is even.
+fn split_big_rational(r: &BigRational) -> (f64, i64) {
+    let split = |n: &BigInt| {
+        let e = n.bits().saturating_sub(64) & !1;
+        ((n >> e).to_f64().unwrap_or(f64::NAN), e as i64)
+    };
+    let (numer, numer_exp) = split(&r.numer().abs());
+    let (denom, denom_exp) = split(r.denom());
+    (numer / denom, numer_exp - denom_exp)
+}
+
+/// Return the square root of `n` if it is a perfect square.
+fn exact_sqrt(n: &BigInt) -> Option<BigInt> {
+    if n.is_negative() {
+        return None;
+    }
+    let root = n.sqrt();
+    if &(&root * &root) == n {
+        Some(root)
+    } else {
+        None
+    }
+}
+
+/// Return true if `a` and `b` have the same representation, treating every NaN as the same.
+fn float_eqv(a: f64, b: f64) -> bool {
+    a.to_bits() == b.to_bits() || (a.is_nan() && b.is_nan())
+}
+
+/// Compare a float with a rational exactly. Infinities and NaN are compared as floats.
+fn cmp_float_rational(f: f64, r: &BigRational) -> Option<Ordering> {
+    match BigRational::from_float(f) {
+        Some(f_rational) => f_rational.partial_cmp(r),
+        None => f.partial_cmp(&r.to_f64()?),
+    }
+}
+
 impl ToPrimitive for Number {
     fn to_i64(&self) -> Option<i64> {
         match self {
@@ -857,6 +1353,7 @@ impl ToPrimitive for Number {
             Number::Float(f) => f.to_i64(),
             Number::Rational(r) => r.to_i64(),
             Number::Complex(c) => c.to_i64(),
+            Number::ExactComplex(_) => None,
         }
     }
 
@@ -866,6 +1363,7 @@ impl ToPrimitive for Number {
             Number::Float(f) => f.to_u64(),
             Number::Rational(r) => r.to_u64(),
             Number::Complex(c) => c.to_u64(),
+            Number::ExactComplex(_) => None,
         }
     }
 
@@ -875,6 +1373,7 @@ impl ToPrimitive for Number {
             Number::Float(f) => f.to_f64(),
             Number::Rational(r) => r.to_f64(),
             Number::Complex(c) => c.to_f64(),
+            Number::ExactComplex(_) => None,
         }
     }
 
@@ -884,6 +1383,7 @@ impl ToPrimitive for Number {
             Number::Float(f) => f.to_u8(),
             Number::Rational(r) => r.to_u8(),
             Number::Complex(c) => c.to_u8(),
+            Number::ExactComplex(_) => None,
         }
     }
 
@@ -893,6 +1393,7 @@ impl ToPrimitive for Number {
             Number::Float(f) => f.to_usize(),
             Number::Rational(r) => r.to_usize(),
             Number::Complex(c) => c.to_usize(),
+            Number::ExactComplex(_) => None,
         }
     }
 }
@@ -904,6 +1405,176 @@ pub enum IntVariant {
     Big(BigInt),
 }
 
+impl IntVariant {
+    /// Create `IntVariant` from `BigInt`, using `Small` if the value fits in an `i64`.
+    pub fn from_bigint(value: BigInt) -> IntVariant {
+        match value.to_i64() {
+            Some(i) => IntVariant::Small(i),
+            None => IntVariant::Big(value),
+        }
+    }
+
+    /// Convert to `BigInt`.
+    pub fn to_bigint(&self) -> BigInt {
+        match self {
+            IntVariant::Small(i) => BigInt::from(*i),
+            IntVariant::Big(b) => b.clone(),
+        }
+    }
+
+    /// Convert to `BigRational` with a denominator of 1.
+    pub fn to_big_rational(&self) -> BigRational {
+        BigRational::from_integer(self.to_bigint())
+    }
+
+    /// Return true if the integer is 0.
+    pub fn is_zero(&self) -> bool {
+        match self {
+            IntVariant::Small(i) => *i == 0,
+            IntVariant::Big(b) => b.is_zero(),
+        }
+    }
+
+    /// Return true if the integer is less than 0.
+    pub fn is_negative(&self) -> bool {
+        match self {
+            IntVariant::Small(i) => *i < 0,
+            IntVariant::Big(b) => b.is_negative(),
+        }
+    }
+
+    /// Return the absolute value of the integer.
+    pub fn abs(&self) -> IntVariant {
+        match self {
+            IntVariant::Small(i) => match i.checked_abs() {
+                Some(a) => IntVariant::Small(a),
+                None => IntVariant::Big(BigInt::from(*i).abs()),
+            },
+            IntVariant::Big(b) => IntVariant::from_bigint(b.abs()),
+        }
+    }
+
+    /// Return the bitwise AND of two integers in two's complement.
+    pub fn bit_and(&self, other: &IntVariant) -> IntVariant {
+        match (self, other) {
+            (IntVariant::Small(a), IntVariant::Small(b)) => IntVariant::Small(a & b),
+            _ => IntVariant::from_bigint(self.to_bigint() & other.to_bigint()),
+        }
+    }
+
+    /// Return the bitwise inclusive OR of two integers in two's complement.
+    pub fn bit_or(&self, other: &IntVariant) -> IntVariant {
+        match (self, other) {
+            (IntVariant::Small(a), IntVariant::Small(b)) => IntVariant::Small(a | b),
+            _ => IntVariant::from_bigint(self.to_bigint() | other.to_bigint()),
+        }
+    }
+
+    /// Return the bitwise exclusive OR of two integers in two's complement.
+    pub fn bit_xor(&self, other: &IntVariant) -> IntVariant {
+        match (self, other) {
+            (IntVariant::Small(a), IntVariant::Small(b)) => IntVariant::Small(a ^ b),
+            _ => IntVariant::from_bigint(self.to_bigint() ^ other.to_bigint()),
+        }
+    }
+
+    /// Return the bitwise NOT of the integer in two's complement, i.e. `-n - 1`.
+    pub fn bit_not(&self) -> IntVariant {
+        match self {
+            IntVariant::Small(i) => IntVariant::Small(!i),
+            IntVariant::Big(b) => IntVariant::from_bigint(!b),
+        }
+    }
+
+    /// Shift the integer left by `count` bits, or right when `count` is negative.
+    /// Right shifts round towards negative infinity and are not limited, but left
+    /// shifts past `MAX_SHIFT_BITS` return an error.
+    pub fn shift(&self, count: i64) -> Result<IntVariant, Error> {
+        if count > MAX_SHIFT_BITS as i64 {
+            return Err(Error::new("shift count is too large"));
+        }
+        if count >= 0 {
+            if let IntVariant::Small(i) = self
+                && count < 64
+                && (i << count) >> count == *i
+            {
+                return Ok(IntVariant::Small(i << count));
+            }
+            let count =
+                usize::try_from(count).map_err(|_| Error::new("shift count is too large"))?;
+            Ok(IntVariant::from_bigint(self.to_bigint() << count))
+        } else {
+            let count = count.unsigned_abs();
+            match self {
+                IntVariant::Small(i) => Ok(IntVariant::Small(i >> count.min(63))),
+                IntVariant::Big(b) => {
+                    let count = usize::try_from(count).unwrap_or(usize::MAX);
+                    Ok(IntVariant::from_bigint(b >> count))
+                }
+            }
+        }
+    }
+
+    /// Return the number of 1 bits in a non-negative integer, or 0 bits in a negative integer.
+    pub fn bit_count(&self) -> u64 {
+        match self {
+            IntVariant::Small(i) if *i < 0 => u64::from((!i).count_ones()),
+            IntVariant::Small(i) => u64::from(i.count_ones()),
+            IntVariant::Big(b) if b.is_negative() => (!b).magnitude().count_ones(),
+            IntVariant::Big(b) => b.magnitude().count_ones(),
+        }
+    }
+
+    /// Return the number of bits needed to represent the integer, excluding the sign bit.
+    pub fn integer_length(&self) -> u64 {
+        match self {
+            IntVariant::Small(i) if *i < 0 => u64::from(64 - (!i).leading_zeros()),
+            IntVariant::Small(i) => u64::from(64 - i.leading_zeros()),
+            IntVariant::Big(b) if b.is_negative() => (!b).bits(),
+            IntVariant::Big(b) => b.bits(),
+        }
+    }
+
+    /// Return true if the bit at `index` is set in two's complement.
+    pub fn bit_is_set(&self, index: u64) -> bool {
+        match self {
+            IntVariant::Small(i) => (i >> index.min(63)) & 1 == 1,
+            IntVariant::Big(b) => b.bit(index),
+        }
+    }
+
+    /// Return a copy of the integer with the bit at `index` set to `value`.
+    pub fn with_bit(&self, index: u64, value: bool) -> Result<IntVariant, Error> {
+        match self {
+            IntVariant::Small(i) if index < 63 => {
+                let mask = 1 << index;
+                Ok(IntVariant::Small(if value { i | mask } else { i & !mask }))
+            }
+            _ if self.bit_is_set(index) == value => Ok(self.clone()),
+            _ if index > MAX_SHIFT_BITS => Err(Error::new("bit index is too large")),
+            _ => {
+                let mut result = self.to_bigint();
+                result.set_bit(index, value);
+                Ok(IntVariant::from_bigint(result))
+            }
+        }
+    }
+
+    /// Return the bits from `start` (inclusive) to `end` (exclusive) as a non-negative integer.
+    pub fn bit_field(&self, start: u64, end: u64) -> Result<IntVariant, Error> {
+        if end < start {
+            return Err(Error::new("end must not be less than start"));
+        }
+        if end - start > MAX_SHIFT_BITS {
+            return Err(Error::new("bit field is too wide"));
+        }
+        let width = (end - start) as usize;
+        let shifted = self.shift(-i64::try_from(start).unwrap_or(i64::MAX))?;
+        let mask = (BigInt::one() << width) - BigInt::one();
+        Ok(IntVariant::from_bigint(shifted.to_bigint() & mask))
+    }
+}
+
 impl PartialOrd for IntVariant {
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
         match (self, other) {
@@ -930,7 +1601,7 @@ impl ToPrimitive for IntVariant {
     }
     fn to_u64(&self) -> Option<u64> {
         match self {
-            IntVariant::Small(f) => Some(*f as u64),
+            IntVariant::Small(f) => u64::try_from(*f).ok(),
             IntVariant::Big(b) => b.to_u64(),
         }
     }
@@ -944,7 +1615,7 @@ impl ToPrimitive for IntVariant {
 
     fn to_u8(&self) -> Option<u8> {
         match self {
-            IntVariant::Small(f) => Some(*f as u8),
+            IntVariant::Small(f) => u8::try_from(*f).ok(),
             IntVariant::Big(b) => b.to_u8(),
         }
     }
@@ -953,41 +1624,18 @@ impl ToPrimitive for IntVariant {
 impl Pow<IntVariant> for IntVariant {
     type Output = Result<IntVariant, Error>;
     fn pow(self, rhs: IntVariant) -> Self::Output {
-        match (self, rhs) {
-            (IntVariant::Small(f), IntVariant::Small(r)) => {
-                let mut result = 1;
-                for _ in 0..r {
-                    result *= f;
-                }
-                Ok(IntVariant::Small(result))
-            }
-            (IntVariant::Big(b), IntVariant::Small(r)) => {
-                let mut result = BigInt::from(1);
-                for _ in 0..r {
-                    result *= b.clone();
-                }
-                Ok(IntVariant::Big(result))
-            }
-            (IntVariant::Small(f), IntVariant::Big(r)) => {
-                let r = r.to_u64().ok_or(Error::Message(
-                    "number too large for rational conversion".to_string(),
-                ))?;
-                let mut result = BigInt::from(1);
-                for _ in 0..r {
-                    result *= BigInt::from(f);
-                }
-                Ok(IntVariant::Big(result))
-            }
-            (IntVariant::Big(b), IntVariant::Big(r)) => {
-                let r = r.to_u64().ok_or(Error::Message(
-                    "number too large for rational conversion".to_string(),
-                ))?;
-                let mut result = BigInt::from(1);
-                for _ in 0..r {
-                    result *= b.clone();
-                }
-                Ok(IntVariant::Big(result))
-            }
+        let exponent = rhs.to_u32().ok_or(Error::new(
+            "exponent must be a non-negative integer below 2^32",
+        ))?;
+        match self {
+            IntVariant::Small(base) => match base.checked_pow(exponent) {
+                Some(result) => Ok(IntVariant::Small(result)),
+                None => Ok(IntVariant::from_bigint(Pow::pow(
+                    BigInt::from(base),
+                    exponent,
+                ))),
+            },
+            IntVariant::Big(base) => Ok(IntVariant::from_bigint(Pow::pow(base, exponent))),
         }
     }
 }
@@ -4,16 +4,31 @@
 
 //! I/O port types.
 
+use crate::env::{EnvRef, call_procedure};
 use crate::error::Error;
-use crate::types::ByteVector;
-use std::cell::RefCell;
+use crate::types::number::Number;
+use crate::types::transcoder::{Codec, EolStyle, ErrorMode, Transcoder};
+use crate::types::{ByteVector, Expr, SchemeString};
+use std::cell::{Ref, RefCell, RefMut};
 use std::collections::VecDeque;
 use std::fs::File;
 use std::io;
-use std::io::{BufRead, BufReader, BufWriter, Read, Write};
+use std::io::{BufRead, BufReader, BufWriter, Read, Seek, SeekFrom, Write};
 use std::rc::Rc;
 
 type RcRef<T> = Rc<RefCell<T>>;
+
+/// Borrow a shared port. Returns `Error` if the port is in use, such as when one
+/// of its own custom port procedures refers to it.
+pub fn borrow_port<T>(port: &RcRef<T>) -> Result<Ref<'_, T>, Error> {
+    port.try_borrow().map_err(|_| Error::new("port is in use"))
+}
+
+/// Mutably borrow a shared port. Returns `Error` if the port is in use.
+pub fn borrow_port_mut<T>(port: &RcRef<T>) -> Result<RefMut<'_, T>, Error> {
+    port.try_borrow_mut()
+        .map_err(|_| Error::new("port is in use"))
+}
 pub type FileInputBuf = BufReader<File>;
 pub type FileOutputBuf = BufWriter<File>;
 pub type BinaryInputBuf = BufReader<Box<[u8]>>;
@@ -27,15 +42,15 @@ pub enum Port {
 }
 
 impl Port {
-    /// Create new `Port::TextInput` from file path.
-    pub fn text_input_file(path: &str) -> Result<Self, Error> {
-        let input = TextInputPort::from_file(path)?;
+    /// Create new `Port::TextInput` from file path, decoded with `transcoder`.
+    pub fn text_input_file(path: &str, transcoder: Transcoder) -> Result<Self, Error> {
+        let input = TextInputPort::from_file(path, transcoder)?;
         Ok(Port::TextInput(Rc::new(RefCell::new(input))))
     }
 
-    /// Create new `Port::TextOutput` from file path.
-    pub fn text_output_file(path: &str) -> Result<Self, Error> {
-        let output = TextOutputPort::from_file(path)?;
+    /// Create new `Port::TextOutput` from file path, encoded with `transcoder`.
+    pub fn text_output_file(path: &str, transcoder: Transcoder) -> Result<Self, Error> {
+        let output = TextOutputPort::from_file(path, transcoder)?;
         Ok(Port::TextOutput(Rc::new(RefCell::new(output))))
     }
 
@@ -51,7 +66,7 @@ impl Port {
 
     /// Create new `Port::TextInput` from stdin.
     pub fn text_input_stdin() -> Self {
-        Port::TextInput(Rc::new(RefCell::new(TextInputPort::Stdin)))
+        Port::TextInput(Rc::new(RefCell::new(TextInputPort::from_stdin())))
     }
 
     /// Create new `Port::TextOutput` to stdout.
@@ -59,6 +74,11 @@ impl Port {
         Port::TextOutput(Rc::new(RefCell::new(TextOutputPort::Stdout)))
     }
 
+    /// Create new `Port::TextOutput` to stderr.
+    pub fn text_output_stderr() -> Self {
+        Port::TextOutput(Rc::new(RefCell::new(TextOutputPort::Stderr)))
+    }
+
     /// Create new `Port::BinaryInput` from file path.
     pub fn binary_input_file(path: &str) -> Result<Self, Error> {
         let input = BinaryInputPort::from_file(path)?;
@@ -83,31 +103,105 @@ impl Port {
         Ok(Port::BinaryOutput(Rc::new(RefCell::new(output))))
     }
 
+    /// Create new `Port::TextInput` that reads chars with a custom port's `read!`.
+    pub fn custom_textual_input(port: CustomPort<char>) -> Self {
+        Port::TextInput(Rc::new(RefCell::new(TextInputPort::Custom(port))))
+    }
+
+    /// Create new `Port::TextOutput` that writes chars with a custom port's `write!`.
+    pub fn custom_textual_output(port: CustomPort<char>) -> Self {
+        Port::TextOutput(Rc::new(RefCell::new(TextOutputPort::Custom(port))))
+    }
+
+    /// Create new `Port::BinaryInput` that reads bytes with a custom port's `read!`.
+    pub fn custom_binary_input(port: CustomPort<u8>) -> Self {
+        Port::BinaryInput(Rc::new(RefCell::new(BinaryInputPort::Custom(port))))
+    }
+
+    /// Create new `Port::BinaryOutput` that writes bytes with a custom port's `write!`.
+    pub fn custom_binary_output(port: CustomPort<u8>) -> Self {
+        Port::BinaryOutput(Rc::new(RefCell::new(BinaryOutputPort::Custom(port))))
+    }
+
+    /// Create new textual `Port` that decodes or encodes a binary port with `transcoder`.
+    pub fn transcoded(&self, transcoder: Transcoder) -> Result<Self, Error> {
+        match self {
+            Port::BinaryInput(port) => Ok(Port::TextInput(Rc::new(RefCell::new(
+                TextInputPort::from_binary(port.clone(), transcoder),
+            )))),
+            Port::BinaryOutput(port) => Ok(Port::TextOutput(Rc::new(RefCell::new(
+                TextOutputPort::from_binary(port.clone(), transcoder),
+            )))),
+            _ => Err(Error::new("expected binary port")),
+        }
+    }
+
     pub fn flush(&mut self) -> Result<(), Error> {
         match self {
-            Port::TextOutput(port) => port.borrow_mut().flush(),
-            Port::BinaryOutput(port) => port.borrow_mut().flush(),
+            Port::TextOutput(port) => borrow_port_mut(port)?.flush(),
+            Port::BinaryOutput(port) => borrow_port_mut(port)?.flush(),
             _ => Err(Error::new("expected output port")),
         }
     }
 
     /// Close port.
-    pub fn close(&self) {
+    pub fn close(&self) -> Result<(), Error> {
         match self {
-            Self::TextInput(p) => p.borrow_mut().close(),
-            Self::TextOutput(p) => p.borrow_mut().close(),
-            Self::BinaryInput(p) => p.borrow_mut().close(),
-            Self::BinaryOutput(p) => p.borrow_mut().close(),
+            Self::TextInput(p) => borrow_port_mut(p)?.close(),
+            Self::TextOutput(p) => borrow_port_mut(p)?.close(),
+            Self::BinaryInput(p) => borrow_port_mut(p)?.close(),
+            Self::BinaryOutput(p) => borrow_port_mut(p)?.close(),
         }
     }
 
-    /// Return if port is open.
+    /// Return if port is open. A port that is in use is open.
     pub fn is_open(&self) -> bool {
+        let open = match self {
+            Self::TextInput(p) => borrow_port(p).map(|p| p.is_open()),
+            Self::TextOutput(p) => borrow_port(p).map(|p| p.is_open()),
+            Self::BinaryInput(p) => borrow_port(p).map(|p| p.is_open()),
+            Self::BinaryOutput(p) => borrow_port(p).map(|p| p.is_open()),
+        };
+        open.unwrap_or(true)
+    }
+
+    /// Return if `position` is supported by the port.
+    pub fn has_position(&self) -> Result<bool, Error> {
+        match self {
+            Self::TextInput(p) => borrow_port(p).map(|p| p.has_position()),
+            Self::TextOutput(p) => borrow_port(p).map(|p| p.has_position()),
+            Self::BinaryInput(p) => borrow_port(p).map(|p| p.has_position()),
+            Self::BinaryOutput(p) => borrow_port(p).map(|p| p.has_position()),
+        }
+    }
+
+    /// Return if `set_position` is supported by the port.
+    pub fn has_set_position(&self) -> Result<bool, Error> {
+        match self {
+            Self::TextInput(p) => borrow_port(p).map(|p| p.has_set_position()),
+            Self::TextOutput(p) => borrow_port(p).map(|p| p.has_set_position()),
+            Self::BinaryInput(p) => borrow_port(p).map(|p| p.has_set_position()),
+            Self::BinaryOutput(p) => borrow_port(p).map(|p| p.has_set_position()),
+        }
+    }
+
+    /// Return the current position of the port.
+    pub fn position(&self) -> Result<usize, Error> {
         match self {
-            Self::TextInput(p) => p.borrow().is_open(),
-            Self::TextOutput(p) => p.borrow().is_open(),
-            Self::BinaryInput(p) => p.borrow().is_open(),
-            Self::BinaryOutput(p) => p.borrow().is_open(),
+            Self::TextInput(p) => borrow_port_mut(p)?.position(),
+            Self::TextOutput(p) => borrow_port_mut(p)?.position(),
+            Self::BinaryInput(p) => borrow_port_mut(p)?.position(),
+            Self::BinaryOutput(p) => borrow_port_mut(p)?.position(),
+        }
+    }
+
+    /// Move the port to `position`.
+    pub fn set_position(&self, position: usize) -> Result<(), Error> {
+        match self {
+            Self::TextInput(p) => borrow_port_mut(p)?.set_position(position),
+            Self::TextOutput(p) => borrow_port_mut(p)?.set_position(position),
+            Self::BinaryInput(p) => borrow_port_mut(p)?.set_position(position),
+            Self::BinaryOutput(p) => borrow_port_mut(p)?.set_position(position),
         }
     }
 
@@ -145,26 +239,44 @@ impl Port {
 
 #[derive(Debug)]
 pub enum TextInputPort {
-    File(Option<FileInputBuf>),
-    String(Option<VecDeque<char>>),
-    Stdin,
+    File(Option<CharDecoder<FileInputBuf>>),
+    String(Option<StringReader>),
+    Stdin(CharDecoder<io::Stdin>),
+    Transcoded(Option<CharDecoder<BinaryPortReader>>),
+    Custom(CustomPort<char>),
 }
 
 impl TextInputPort {
-    /// Create new `TextInputPort::File` from file path.
-    pub fn from_file(path: &str) -> Result<Self, Error> {
+    /// Create new `TextInputPort::File` from file path, decoded with `transcoder`.
+    pub fn from_file(path: &str, transcoder: Transcoder) -> Result<Self, Error> {
         let file =
             File::open(path).map_err(|e| Error::Message(format!("unable to open file: {}", e)))?;
-        Ok(Self::File(Some(BufReader::new(file))))
+        let reader = BufReader::new(file);
+        Ok(Self::File(Some(CharDecoder::with_transcoder(
+            reader, transcoder,
+        ))))
     }
 
     /// Create new `TextInputPort::String` from string.
     pub fn from_string(s: String) -> Self {
-        Self::String(Some(s.chars().collect()))
+        Self::String(Some(StringReader::from(&s)))
+    }
+
+    /// Create new `TextInputPort::Stdin`.
+    pub fn from_stdin() -> Self {
+        Self::Stdin(CharDecoder::new(io::stdin()))
+    }
+
+    /// Create new `TextInputPort::Transcoded` that decodes a binary input port with `transcoder`.
+    pub fn from_binary(port: RcRef<BinaryInputPort>, transcoder: Transcoder) -> Self {
+        Self::Transcoded(Some(CharDecoder::with_transcoder(
+            BinaryPortReader(port),
+            transcoder,
+        )))
     }
 
     /// Close port.
-    pub fn close(&mut self) {
+    pub fn close(&mut self) -> Result<(), Error> {
         match self {
             Self::File(stream) => {
                 stream.take();
@@ -172,8 +284,13 @@ impl TextInputPort {
             Self::String(stream) => {
                 stream.take();
             }
-            Self::Stdin => {}
+            Self::Stdin(_) => {}
+            Self::Transcoded(stream) => {
+                stream.take();
+            }
+            Self::Custom(port) => return port.close(),
         }
+        Ok(())
     }
 
     /// Return if port is open.
@@ -181,87 +298,96 @@ impl TextInputPort {
         match self {
             Self::File(stream) => stream.is_some(),
             Self::String(stream) => stream.is_some(),
-            Self::Stdin => true,
+            Self::Stdin(_) => true,
+            Self::Transcoded(stream) => stream.is_some(),
+            Self::Custom(port) => port.is_open(),
+        }
+    }
+
+    /// Return if `position` is supported.
+    pub fn has_position(&self) -> bool {
+        match self {
+            Self::File(_) | Self::String(_) => true,
+            Self::Stdin(_) | Self::Transcoded(_) => false,
+            Self::Custom(port) => port.has_position(),
+        }
+    }
+
+    /// Return if `set_position` is supported.
+    pub fn has_set_position(&self) -> bool {
+        match self {
+            Self::File(_) | Self::String(_) => true,
+            Self::Stdin(_) | Self::Transcoded(_) => false,
+            Self::Custom(port) => port.has_set_position(),
+        }
+    }
+
+    /// Return the current position, for ports that support `port-position`.
+    pub fn position(&mut self) -> Result<usize, Error> {
+        match self {
+            Self::File(Some(decoder)) => Ok(decoder.position()),
+            Self::String(Some(stream)) => Ok(stream.position()),
+            Self::File(None) | Self::String(None) => Err(Error::new("port is closed")),
+            Self::Custom(port) => port.position(),
+            _ => Err(Error::new("port does not support port-position")),
+        }
+    }
+
+    /// Move to `position`, for ports that support `set-port-position!`.
+    pub fn set_position(&mut self, position: usize) -> Result<(), Error> {
+        match self {
+            Self::File(Some(decoder)) => decoder.set_position(position),
+            Self::String(Some(stream)) => stream.set_position(position),
+            Self::File(None) | Self::String(None) => Err(Error::new("port is closed")),
+            Self::Custom(port) => port.set_position(position),
+            _ => Err(Error::new("port does not support set-port-position!")),
         }
     }
 
     /// Read next char from input port.
-    /// Returns `Error` if port is empty or byte could not be read.
+    /// Returns `Error` if port is empty or input is not valid UTF-8.
     pub fn read_char(&mut self) -> Result<Option<char>, Error> {
         match self {
-            Self::File(Some(reader)) => {
-                let mut buf = [0u8; 1];
-                match reader.read(&mut buf) {
-                    Ok(1) => Ok(Some(buf[0] as char)),
-                    Ok(0) => Ok(None),
-                    _ => Err(Error::new("unable to read from file")),
-                }
-            }
+            Self::File(Some(decoder)) => decoder.read_char(),
             Self::File(None) => Err(Error::new("port is closed")),
             Self::String(Some(stream)) => {
                 let c = stream
-                    .pop_front()
+                    .read_char()
                     .ok_or_else(|| Error::new("port is empty"))?;
                 Ok(Some(c))
             }
             Self::String(None) => Err(Error::new("port is closed")),
-            Self::Stdin => {
-                let mut buf = [0u8; 1];
-                // Returns error if byte can't be read from stdin.
-                // I don't think it's possible to get an EoF from stdin.
-                io::stdin()
-                    .read_exact(&mut buf)
-                    .map_err(|_| Error::new("unable to read from stdin"))?;
-                Ok(Some(buf[0] as char))
-            }
+            Self::Stdin(decoder) => decoder.read_char(),
+            Self::Transcoded(Some(decoder)) => decoder.read_char(),
+            Self::Transcoded(None) => Err(Error::new("port is closed")),
+            Self::Custom(port) => port.read_item(),
         }
     }
 
     /// Peek next char from input port without consuming it.
-    /// Returns `Error` if port is empty or byte could not be read.
+    /// Returns `Error` if input is not valid UTF-8.
     pub fn peek_char(&mut self) -> Result<Option<char>, Error> {
         match self {
-            Self::File(Some(reader)) => match reader.fill_buf() {
-                Ok(bytes) if bytes.is_empty() => Ok(None),
-                Ok(bytes) => Ok(Some(bytes[0] as char)),
-                Err(e) => Err(Error::Message(format!("unable to peek: {}", e))),
-            },
+            Self::File(Some(decoder)) => decoder.peek_char(),
             Self::File(None) => Err(Error::new("port is closed")),
-            Self::String(Some(stream)) => Ok(stream.front().copied()),
+            Self::String(Some(stream)) => Ok(stream.peek()),
             Self::String(None) => Err(Error::new("port is closed")),
-            Self::Stdin => Ok(None), // stdin doesn't support peek
+            Self::Stdin(decoder) => decoder.peek_char(),
+            Self::Transcoded(Some(decoder)) => decoder.peek_char(),
+            Self::Transcoded(None) => Err(Error::new("port is closed")),
+            Self::Custom(port) => port.peek_item(),
         }
     }
 
     /// Read next string from input port.
-    /// Returns `Error` if port is empty or byte could not be read.
+    /// Returns `Error` if input is not valid UTF-8.
     pub fn read_string(&mut self) -> Result<Option<String>, Error> {
         match self {
-            Self::File(Some(reader)) => {
-                let mut word = String::new();
-                let mut buf = [0u8; 1];
-                loop {
-                    match reader.read(&mut buf) {
-                        Ok(1) => {
-                            let ch = buf[0] as char;
-                            if ch.is_whitespace() {
-                                break;
-                            }
-                            word.push(ch);
-                        }
-                        Ok(0) => break,
-                        _ => return Err(Error::new("unable to read from file")),
-                    }
-                }
-                if word.is_empty() {
-                    return Ok(None);
-                }
-                Ok(Some(word))
-            }
+            Self::File(Some(decoder)) => decoder.read_word(),
             Self::File(None) => Err(Error::new("port is closed")),
             Self::String(Some(stream)) => {
                 let mut line = String::new();
-                while let Some(c) = stream.pop_front() {
+                while let Some(c) = stream.read_char() {
                     if c.is_whitespace() {
                         break;
                     }
@@ -273,48 +399,22 @@ impl TextInputPort {
                 Ok(Some(line))
             }
             Self::String(None) => Err(Error::new("port is closed")),
-            Self::Stdin => {
-                let mut word = String::new();
-                let mut buf = [0u8; 1];
-                loop {
-                    match io::stdin().read(&mut buf) {
-                        Ok(1) => {
-                            let ch = buf[0] as char;
-                            if ch.is_whitespace() {
-                                break;
-                            }
-                            word.push(ch);
-                        }
-                        Ok(0) => break,
-                        _ => return Err(Error::new("unable to read from stdin")),
-                    }
-                }
-                if word.is_empty() {
-                    return Ok(None);
-                }
-                Ok(Some(word))
-            }
+            Self::Stdin(decoder) => decoder.read_word(),
+            Self::Transcoded(Some(decoder)) => decoder.read_word(),
+            Self::Transcoded(None) => Err(Error::new("port is closed")),
+            Self::Custom(port) => port.read_word(),
         }
     }
 
     /// Peek next line from port. Returns `None` if port has reached `eof`.
     pub fn read_line(&mut self) -> Result<Option<String>, Error> {
         match self {
-            Self::File(Some(reader)) => {
-                let mut line = String::new();
-                reader
-                    .read_line(&mut line)
-                    .map_err(|e| Error::Message(format!("unable to read line: {}", e)))?;
-                if line.is_empty() {
-                    return Ok(None);
-                }
-                Ok(Some(line))
-            }
+            Self::File(Some(decoder)) => decoder.read_line(),
             Self::File(None) => Err(Error::new("port is closed")),
 
             Self::String(Some(stream)) => {
                 let mut line = String::new();
-                while let Some(c) = stream.pop_front() {
+                while let Some(c) = stream.read_char() {
                     if c == '\n' {
                         break;
                     }
@@ -327,37 +427,22 @@ impl TextInputPort {
             }
             Self::String(None) => Err(Error::new("port is closed")),
 
-            Self::Stdin => {
-                let mut line = String::new();
-                io::stdin()
-                    .read_line(&mut line)
-                    .map_err(|_| Error::new("unable to read from stdin"))?;
-                if line.is_empty() {
-                    return Ok(None);
-                }
-                Ok(Some(line))
-            }
+            Self::Stdin(decoder) => decoder.read_line(),
+            Self::Transcoded(Some(decoder)) => decoder.read_line(),
+            Self::Transcoded(None) => Err(Error::new("port is closed")),
+            Self::Custom(port) => port.read_line(),
         }
     }
 
     /// Peek all lines from port. Returns `None` if port has reached `eof`.
     pub fn read_lines(&mut self) -> Result<Option<Vec<String>>, Error> {
         match self {
-            Self::File(Some(reader)) => {
-                let mut lines = Vec::new();
-                for line in reader.lines() {
-                    lines.push(line.map_err(|e| Error::Message(format!("read error: {}", e)))?);
-                }
-                if lines.is_empty() {
-                    return Ok(None);
-                }
-                Ok(Some(lines))
-            }
+            Self::File(Some(decoder)) => decoder.read_lines(),
             Self::File(None) => Err(Error::new("port is closed")),
             Self::String(Some(stream)) => {
                 let mut lines = Vec::new();
                 let mut line = String::new();
-                while let Some(c) = stream.pop_front() {
+                while let Some(c) = stream.read_char() {
                     if c == '\n' {
                         lines.push(line.clone());
                         line.clear();
@@ -374,47 +459,736 @@ impl TextInputPort {
                 Ok(Some(lines))
             }
             Self::String(None) => Err(Error::new("port is closed")),
-            Self::Stdin => {
-                let lines = io::stdin()
-                    .lines()
-                    .collect::<Result<Vec<_>, _>>()
-                    .map_err(|e| Error::Message(format!("unable to read lines: {}", e)))?;
-                if lines.is_empty() {
-                    return Ok(None);
+            Self::Stdin(decoder) => decoder.read_lines(),
+            Self::Transcoded(Some(decoder)) => decoder.read_lines(),
+            Self::Transcoded(None) => Err(Error::new("port is closed")),
+            Self::Custom(port) => port.read_lines(),
+        }
+    }
+}
+
+/// Decodes chars one at a time from a byte stream with a `Transcoder`.
+/// Multi-byte chars are assembled byte by byte, so they may span buffer boundaries.
+#[derive(Debug)]
+pub struct CharDecoder<R> {
+    reader: R,
+    transcoder: Transcoder,
+    /// Decoded chars that have been peeked but not consumed, with the byte offset each starts at.
+    pending: VecDeque<(char, u64)>,
+    /// Bytes pushed back onto the stream, last byte first.
+    unread: Vec<u8>,
+    /// Byte order of a `Codec::Utf16` stream, once its byte order mark has been checked.
+    utf16_big_endian: Option<bool>,
+    offset: u64,
+}
+
+impl<R: Read> CharDecoder<R> {
+    /// Create `CharDecoder` that reads UTF-8 from `reader`, skipping a leading byte order mark.
+    pub fn new(reader: R) -> Self {
+        CharDecoder::with_transcoder(reader, Transcoder::default())
+    }
+
+    /// Create `CharDecoder` that reads from `reader` with `transcoder`.
+    pub fn with_transcoder(reader: R, transcoder: Transcoder) -> Self {
+        Self {
+            reader,
+            transcoder,
+            pending: VecDeque::new(),
+            unread: Vec::new(),
+            utf16_big_endian: None,
+            offset: 0,
+        }
+    }
+
+    /// Consume and return the next char. Returns `Ok(None)` at end of input.
+    pub fn read_char(&mut self) -> Result<Option<char>, Error> {
+        match self.pending.pop_front() {
+            Some((c, _)) => Ok(Some(c)),
+            None => self.next_char(),
+        }
+    }
+
+    /// Return the next char without consuming it. Returns `Ok(None)` at end of input.
+    pub fn peek_char(&mut self) -> Result<Option<char>, Error> {
+        let start = self.offset;
+        if self.pending.is_empty()
+            && let Some(c) = self.next_char()?
+        {
+            self.pending.push_front((c, start));
+        }
+        Ok(self.pending.front().map(|(c, _)| *c))
+    }
+
+    /// Return the byte offset of the next char to be consumed.
+    pub fn position(&self) -> usize {
+        match self.pending.front() {
+            Some((_, start)) => *start as usize,
+            None => self.offset as usize,
+        }
+    }
+
+    /// Decode the next char, reading `\r\n` as `\n` for `EolStyle::Crlf`.
+    fn next_char(&mut self) -> Result<Option<char>, Error> {
+        let c = self.decode()?;
+        if c == Some('\r') && self.transcoder.eol_style == EolStyle::Crlf {
+            let start = self.offset;
+            match self.decode()? {
+                Some('\n') => return Ok(Some('\n')),
+                Some(next) => self.pending.push_back((next, start)),
+                None => {}
+            }
+        }
+        Ok(c)
+    }
+
+    /// Decode the next char from the byte stream with the transcoder's codec.
+    fn decode(&mut self) -> Result<Option<char>, Error> {
+        let start = self.offset;
+        let decoded = match self.transcoder.codec {
+            Codec::Utf8 => match self.decode_utf8()? {
+                // A byte order mark at the start of the stream is not part of the text.
+                Some(Some('\u{FEFF}')) if start == 0 => self.decode_utf8()?,
+                decoded => decoded,
+            },
+            Codec::Utf16 => {
+                let big_endian = self.utf16_byte_order()?;
+                self.decode_utf16(big_endian)?
+            }
+            Codec::Utf16Le => self.decode_utf16(false)?,
+            Codec::Utf16Be => self.decode_utf16(true)?,
+            Codec::Latin1 => self.read_byte()?.map(|byte| Some(byte as char)),
+            Codec::Ascii => self
+                .read_byte()?
+                .map(|byte| byte.is_ascii().then_some(byte as char)),
+        };
+        match (decoded, self.transcoder.error_mode) {
+            (None, _) => Ok(None),
+            (Some(Some(c)), _) => Ok(Some(c)),
+            (Some(None), ErrorMode::Replace) => Ok(Some(char::REPLACEMENT_CHARACTER)),
+            (Some(None), ErrorMode::Raise) => Err(Error::Message(format!(
+                "invalid {} sequence at byte offset {}",
+                self.transcoder.codec.name(),
+                start
+            ))),
+        }
+    }
+
+    /// Decode a UTF-8 char. Returns `Some(None)` for a malformed sequence.
+    fn decode_utf8(&mut self) -> Result<Option<Option<char>>, Error> {
+        let Some(first) = self.read_byte()? else {
+            return Ok(None);
+        };
+        let len = match first {
+            0x00..=0x7F => return Ok(Some(Some(first as char))),
+            0xC2..=0xDF => 2,
+            0xE0..=0xEF => 3,
+            0xF0..=0xF4 => 4,
+            _ => return Ok(Some(None)),
+        };
+        let mut bytes = [first, 0, 0, 0];
+        for byte in bytes.iter_mut().take(len).skip(1) {
+            match self.read_byte()? {
+                Some(b) if b & 0xC0 == 0x80 => *byte = b,
+                Some(b) => {
+                    // The byte may start the next char.
+                    self.unread_byte(b);
+                    return Ok(Some(None));
                 }
-                Ok(Some(lines))
+                None => return Ok(Some(None)),
+            }
+        }
+        // Rejects overlong encodings and surrogates.
+        let c = std::str::from_utf8(&bytes[..len])
+            .ok()
+            .and_then(|s| s.chars().next());
+        Ok(Some(c))
+    }
+
+    /// Decode a UTF-16 char. Returns `Some(None)` for a malformed sequence.
+    fn decode_utf16(&mut self, big_endian: bool) -> Result<Option<Option<char>>, Error> {
+        let Some(unit) = self.read_unit(big_endian)? else {
+            return Ok(None);
+        };
+        let Some(high) = unit else {
+            return Ok(Some(None));
+        };
+        if !(0xD800..=0xDBFF).contains(&high) {
+            return Ok(Some(char::from_u32(high as u32)));
+        }
+        match self.read_unit(big_endian)? {
+            Some(Some(low)) if (0xDC00..=0xDFFF).contains(&low) => Ok(Some(
+                char::decode_utf16([high, low]).next().and_then(|c| c.ok()),
+            )),
+            Some(Some(other)) => {
+                // The unit may start the next char.
+                let bytes = match big_endian {
+                    true => other.to_be_bytes(),
+                    false => other.to_le_bytes(),
+                };
+                self.unread_byte(bytes[1]);
+                self.unread_byte(bytes[0]);
+                Ok(Some(None))
+            }
+            _ => Ok(Some(None)),
+        }
+    }
+
+    /// Read a 16-bit code unit. Returns `Some(None)` if the stream ends after one byte.
+    fn read_unit(&mut self, big_endian: bool) -> Result<Option<Option<u16>>, Error> {
+        let Some(first) = self.read_byte()? else {
+            return Ok(None);
+        };
+        let Some(second) = self.read_byte()? else {
+            return Ok(Some(None));
+        };
+        match big_endian {
+            true => Ok(Some(Some(u16::from_be_bytes([first, second])))),
+            false => Ok(Some(Some(u16::from_le_bytes([first, second])))),
+        }
+    }
+
+    /// Return the byte order of a `Codec::Utf16` stream, consuming its byte order mark.
+    fn utf16_byte_order(&mut self) -> Result<bool, Error> {
+        if let Some(big_endian) = self.utf16_big_endian {
+            return Ok(big_endian);
+        }
+        let mut mark = Vec::new();
+        for _ in 0..2 {
+            if let Some(byte) = self.read_byte()? {
+                mark.push(byte);
+            }
+        }
+        let big_endian = match mark.as_slice() {
+            [0xFE, 0xFF] => true,
+            [0xFF, 0xFE] => false,
+            _ => {
+                for byte in mark.into_iter().rev() {
+                    self.unread_byte(byte);
+                }
+                true
+            }
+        };
+        self.utf16_big_endian = Some(big_endian);
+        Ok(big_endian)
+    }
+
+    /// Read the next byte. Returns `Ok(None)` at end of input.
+    fn read_byte(&mut self) -> Result<Option<u8>, Error> {
+        if let Some(byte) = self.unread.pop() {
+            self.offset += 1;
+            return Ok(Some(byte));
+        }
+        let mut buf = [0u8; 1];
+        loop {
+            match self.reader.read(&mut buf) {
+                Ok(0) => return Ok(None),
+                Ok(_) => {
+                    self.offset += 1;
+                    return Ok(Some(buf[0]));
+                }
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+                Err(e) => return Err(Error::Message(format!("unable to read: {}", e))),
             }
         }
     }
+
+    /// Push `byte` back so it is read next.
+    fn unread_byte(&mut self, byte: u8) {
+        self.unread.push(byte);
+        self.offset -= 1;
+    }
+}
+
+impl<R: Read + Seek> CharDecoder<R> {
+    /// Move to byte offset `position`, dropping peeked chars.
+    pub fn set_position(&mut self, position: usize) -> Result<(), Error> {
+        seek_stream(&mut self.reader, position)?;
+        self.pending.clear();
+        self.unread.clear();
+        self.offset = position as u64;
+        // A byte order mark is checked again when reading from the start.
+        if position == 0 {
+            self.utf16_big_endian = None;
+        }
+        Ok(())
+    }
+}
+
+/// Source of chars for textual input ports, with the word and line readers they share.
+trait CharSource {
+    /// Consume and return the next char. Returns `Ok(None)` at end of input.
+    fn take_char(&mut self) -> Result<Option<char>, Error>;
+
+    /// Read chars up to the next whitespace char, which is consumed.
+    /// Returns `Ok(None)` if no chars were read.
+    fn read_word(&mut self) -> Result<Option<String>, Error> {
+        let mut word = String::new();
+        while let Some(c) = self.take_char()? {
+            if c.is_whitespace() {
+                break;
+            }
+            word.push(c);
+        }
+        if word.is_empty() {
+            return Ok(None);
+        }
+        Ok(Some(word))
+    }
+
+    /// Read chars up to and including the next newline.
+    /// Returns `Ok(None)` at end of input.
+    fn read_line(&mut self) -> Result<Option<String>, Error> {
+        let mut line = String::new();
+        while let Some(c) = self.take_char()? {
+            line.push(c);
+            if c == '\n' {
+                break;
+            }
+        }
+        if line.is_empty() {
+            return Ok(None);
+        }
+        Ok(Some(line))
+    }
+
+    /// Read the remaining lines without their `\n` or `\r\n` line endings.
+    /// Returns `Ok(None)` at end of input.
+    fn read_lines(&mut self) -> Result<Option<Vec<String>>, Error> {
+        let mut lines = Vec::new();
+        while let Some(mut line) = self.read_line()? {
+            if line.ends_with('\n') {
+                line.pop();
+                if line.ends_with('\r') {
+                    line.pop();
+                }
+            }
+            lines.push(line);
+        }
+        if lines.is_empty() {
+            return Ok(None);
+        }
+        Ok(Some(lines))
+    }
+}
+
+impl<R: Read> CharSource for CharDecoder<R> {
+    fn take_char(&mut self) -> Result<Option<char>, Error> {
+        self.read_char()
+    }
+}
+
+impl CharSource for CustomPort<char> {
+    fn take_char(&mut self) -> Result<Option<char>, Error> {
+        self.read_item()
+    }
+}
+
+/// Encodes chars to a byte stream with a `Transcoder`.
+#[derive(Debug)]
+pub struct CharEncoder<W> {
+    writer: W,
+    transcoder: Transcoder,
+    /// True once a `Codec::Utf16` byte order mark has been written.
+    wrote_mark: bool,
+}
+
+impl<W: Write> CharEncoder<W> {
+    /// Create `CharEncoder` that writes UTF-8 to `writer`.
+    pub fn new(writer: W) -> Self {
+        CharEncoder::with_transcoder(writer, Transcoder::default())
+    }
+
+    /// Create `CharEncoder` that writes to `writer` with `transcoder`.
+    pub fn with_transcoder(writer: W, transcoder: Transcoder) -> Self {
+        Self {
+            writer,
+            transcoder,
+            wrote_mark: false,
+        }
+    }
+
+    /// Encode and write `c`, writing `\n` as `\r\n` for `EolStyle::Crlf`.
+    pub fn write_char(&mut self, c: char) -> Result<(), Error> {
+        if c == '\n' && self.transcoder.eol_style == EolStyle::Crlf {
+            self.encode('\r')?;
+        }
+        self.encode(c)
+    }
+
+    /// Flush the underlying writer.
+    pub fn flush(&mut self) -> Result<(), Error> {
+        self.writer
+            .flush()
+            .map_err(|_| Error::new("unable to flush"))
+    }
+
+    fn encode(&mut self, c: char) -> Result<(), Error> {
+        let codec = self.transcoder.codec;
+        if codec == Codec::Utf16 && !self.wrote_mark {
+            self.write_bytes(&[0xFE, 0xFF])?;
+            self.wrote_mark = true;
+        }
+        let bytes = match (codec.encode(c), self.transcoder.error_mode) {
+            (Some(bytes), _) => bytes,
+            (None, ErrorMode::Replace) => codec.encode('?').expect("codecs should encode '?'"),
+            (None, ErrorMode::Raise) => {
+                return Err(Error::Message(format!(
+                    "unable to encode {:?} as {}",
+                    c,
+                    codec.name()
+                )));
+            }
+        };
+        self.write_bytes(&bytes)
+    }
+
+    fn write_bytes(&mut self, bytes: &[u8]) -> Result<(), Error> {
+        self.writer
+            .write_all(bytes)
+            .map_err(|e| Error::Message(format!("write failed: {}", e)))
+    }
+}
+
+impl<W: Write + Seek> CharEncoder<W> {
+    /// Return the byte offset of the next byte to be written.
+    pub fn position(&mut self) -> Result<usize, Error> {
+        stream_position(&mut self.writer)
+    }
+
+    /// Move to byte offset `position`, flushing buffered bytes first.
+    pub fn set_position(&mut self, position: usize) -> Result<(), Error> {
+        seek_stream(&mut self.writer, position)
+    }
+}
+
+/// Return the byte offset of a seekable stream.
+fn stream_position(stream: &mut impl Seek) -> Result<usize, Error> {
+    stream
+        .stream_position()
+        .map(|position| position as usize)
+        .map_err(|e| Error::Message(format!("unable to get position: {}", e)))
+}
+
+/// Move a seekable stream to byte offset `position`.
+fn seek_stream(stream: &mut impl Seek, position: usize) -> Result<(), Error> {
+    stream
+        .seek(SeekFrom::Start(position as u64))
+        .map(|_| ())
+        .map_err(|e| Error::Message(format!("unable to set position: {}", e)))
+}
+
+/// Reads bytes from a shared binary input port, for textual ports created by `transcoded-port`.
+#[derive(Debug)]
+pub struct BinaryPortReader(RcRef<BinaryInputPort>);
+
+impl Read for BinaryPortReader {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if buf.is_empty() {
+            return Ok(0);
+        }
+        let mut port = borrow_port_mut(&self.0).map_err(|e| io::Error::other(e.to_string()))?;
+        match port.read_byte() {
+            Ok(Some(byte)) => {
+                buf[0] = byte;
+                Ok(1)
+            }
+            Ok(None) => Ok(0),
+            Err(e) => Err(io::Error::other(e.to_string())),
+        }
+    }
+}
+
+/// Writes bytes to a shared binary output port, for textual ports created by `transcoded-port`.
+#[derive(Debug)]
+pub struct BinaryPortWriter(RcRef<BinaryOutputPort>);
+
+impl Write for BinaryPortWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let mut port = borrow_port_mut(&self.0).map_err(|e| io::Error::other(e.to_string()))?;
+        for byte in buf {
+            port.write_byte(*byte)
+                .map_err(|e| io::Error::other(e.to_string()))?;
+        }
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        borrow_port_mut(&self.0)
+            .and_then(|mut port| port.flush())
+            .map_err(|e| io::Error::other(e.to_string()))
+    }
+}
+
+/// Number of items requested from a custom input port's `read!` procedure at a time.
+const CUSTOM_READ_SIZE: usize = 64;
+
+/// Element of a custom port: chars for textual ports and bytes for binary ports.
+pub trait CustomItem: Copy {
+    /// Create a Scheme string or bytevector with room for `count` items.
+    fn new_buffer(count: usize) -> Expr;
+
+    /// Return the first `count` items of a Scheme string or bytevector.
+    fn from_buffer(buffer: &Expr, count: usize) -> Option<Vec<Self>>;
+
+    /// Create a Scheme string or bytevector holding `items`.
+    fn to_buffer(items: &[Self]) -> Expr;
+}
+
+impl CustomItem for char {
+    fn new_buffer(count: usize) -> Expr {
+        Expr::String(SchemeString::from_chars(vec![' '; count]))
+    }
+
+    fn from_buffer(buffer: &Expr, count: usize) -> Option<Vec<char>> {
+        match buffer {
+            Expr::String(s) => s.chars().get(..count).map(|chars| chars.to_vec()),
+            _ => None,
+        }
+    }
+
+    fn to_buffer(items: &[char]) -> Expr {
+        Expr::String(SchemeString::from_chars(items.to_vec()))
+    }
+}
+
+impl CustomItem for u8 {
+    fn new_buffer(count: usize) -> Expr {
+        Expr::ByteVector(ByteVector::new(count))
+    }
+
+    fn from_buffer(buffer: &Expr, count: usize) -> Option<Vec<u8>> {
+        match buffer {
+            Expr::ByteVector(bv) => bv.get_bytes(0, count),
+            _ => None,
+        }
+    }
+
+    fn to_buffer(items: &[u8]) -> Expr {
+        Expr::ByteVector(ByteVector::from(items))
+    }
+}
+
+/// Port whose operations are Scheme procedures, created by `make-custom-*-port`.
+///
+/// Input ports call `read!` with a buffer, a start index and a count, and buffer
+/// what it returns so chars or bytes can be peeked. Output ports call `write!`
+/// with the same arguments.
+#[derive(Debug)]
+pub struct CustomPort<T> {
+    id: String,
+    /// The `read!` procedure of an input port or `write!` procedure of an output port.
+    transfer: Expr,
+    get_position: Option<Expr>,
+    set_position: Option<Expr>,
+    close: Option<Expr>,
+    env: EnvRef,
+    open: bool,
+    /// Items returned by `read!` that have not been consumed.
+    pending: VecDeque<T>,
+}
+
+impl<T: CustomItem> CustomPort<T> {
+    /// Create `CustomPort` from its procedures. Optional procedures are `#f` when absent.
+    pub fn new(
+        id: String,
+        transfer: Expr,
+        get_position: Option<Expr>,
+        set_position: Option<Expr>,
+        close: Option<Expr>,
+        env: EnvRef,
+    ) -> Self {
+        Self {
+            id,
+            transfer,
+            get_position,
+            set_position,
+            close,
+            env,
+            open: true,
+            pending: VecDeque::new(),
+        }
+    }
+
+    /// Return the ID given when the port was created.
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    /// Return if port is open.
+    pub fn is_open(&self) -> bool {
+        self.open
+    }
+
+    /// Close port, calling its `close` procedure the first time.
+    pub fn close(&mut self) -> Result<(), Error> {
+        if !self.open {
+            return Ok(());
+        }
+        self.open = false;
+        self.pending.clear();
+        match &self.close {
+            Some(close) => call_procedure(close, Vec::new(), self.env.clone()).map(|_| ()),
+            None => Ok(()),
+        }
+    }
+
+    /// Consume and return the next item. Returns `Ok(None)` at end of input.
+    pub fn read_item(&mut self) -> Result<Option<T>, Error> {
+        self.fill()?;
+        Ok(self.pending.pop_front())
+    }
+
+    /// Return the next item without consuming it. Returns `Ok(None)` at end of input.
+    pub fn peek_item(&mut self) -> Result<Option<T>, Error> {
+        self.fill()?;
+        Ok(self.pending.front().copied())
+    }
+
+    /// Pass every item to `write!`, calling it until all have been written.
+    pub fn write_items(&mut self, items: &[T]) -> Result<(), Error> {
+        self.check_open()?;
+        let mut start = 0;
+        while start < items.len() {
+            let count = items.len() - start;
+            let written = self.call_transfer(T::to_buffer(items), start, count)?;
+            if written == 0 || written > count {
+                return Err(Error::Message(format!(
+                    "custom port {}: write! returned invalid count {}",
+                    self.id, written
+                )));
+            }
+            start += written;
+        }
+        Ok(())
+    }
+
+    /// Return if the port has a `get-position` procedure.
+    pub fn has_position(&self) -> bool {
+        self.get_position.is_some()
+    }
+
+    /// Return if the port has a `set-position!` procedure.
+    pub fn has_set_position(&self) -> bool {
+        self.set_position.is_some()
+    }
+
+    /// Return the position from `get-position`, less the items read ahead but not consumed.
+    pub fn position(&self) -> Result<usize, Error> {
+        let Some(get_position) = &self.get_position else {
+            return Err(Error::new("port does not support port-position"));
+        };
+        self.check_open()?;
+        let position = call_procedure(get_position, Vec::new(), self.env.clone())?;
+        self.to_count(&position)?
+            .checked_sub(self.pending.len())
+            .ok_or_else(|| Error::Message(format!("custom port {}: invalid position", self.id)))
+    }
+
+    /// Move to `position` with `set-position!`, dropping items read ahead.
+    pub fn set_position(&mut self, position: usize) -> Result<(), Error> {
+        let Some(set_position) = &self.set_position else {
+            return Err(Error::new("port does not support set-port-position!"));
+        };
+        self.check_open()?;
+        self.pending.clear();
+        let args = vec![Expr::Number(Number::from_usize(position))];
+        call_procedure(set_position, args, self.env.clone()).map(|_| ())
+    }
+
+    /// Call `read!` if no items are pending.
+    fn fill(&mut self) -> Result<(), Error> {
+        self.check_open()?;
+        if !self.pending.is_empty() {
+            return Ok(());
+        }
+        let buffer = T::new_buffer(CUSTOM_READ_SIZE);
+        let count = self.call_transfer(buffer.clone(), 0, CUSTOM_READ_SIZE)?;
+        if count > CUSTOM_READ_SIZE {
+            return Err(Error::Message(format!(
+                "custom port {}: read! returned invalid count {}",
+                self.id, count
+            )));
+        }
+        let items = T::from_buffer(&buffer, count).ok_or_else(|| {
+            Error::Message(format!("custom port {}: read! changed its buffer", self.id))
+        })?;
+        self.pending.extend(items);
+        Ok(())
+    }
+
+    /// Call `read!` or `write!` with a buffer, start index and count, returning the count it returns.
+    fn call_transfer(&self, buffer: Expr, start: usize, count: usize) -> Result<usize, Error> {
+        let args = vec![
+            buffer,
+            Expr::Number(Number::from_usize(start)),
+            Expr::Number(Number::from_usize(count)),
+        ];
+        let count = call_procedure(&self.transfer, args, self.env.clone())?;
+        self.to_count(&count)
+    }
+
+    /// Return the value of an exact non-negative integer returned by a port procedure.
+    fn to_count(&self, expr: &Expr) -> Result<usize, Error> {
+        match expr {
+            Expr::Number(n) if n.is_exact() && !n.is_negative() => n
+                .to_usize()
+                .ok_or_else(|| Error::Message(format!("custom port {}: invalid count", self.id))),
+            _ => Err(Error::Message(format!(
+                "custom port {}: expected exact non-negative integer",
+                self.id
+            ))),
+        }
+    }
+
+    fn check_open(&self) -> Result<(), Error> {
+        match self.open {
+            true => Ok(()),
+            false => Err(Error::new("port is closed")),
+        }
+    }
 }
 
 #[derive(Debug)]
 pub enum TextOutputPort {
-    File(Option<FileOutputBuf>),
-    String(Option<String>),
+    File(Option<CharEncoder<FileOutputBuf>>),
+    String(Option<StringWriter>),
     Stdout,
+    Stderr,
+    Transcoded(Option<CharEncoder<BinaryPortWriter>>),
+    Custom(CustomPort<char>),
 }
 
 impl TextOutputPort {
-    /// Create `TextOutputPort::File` from file path.
-    pub fn from_file(path: &str) -> Result<Self, Error> {
+    /// Create `TextOutputPort::File` from file path, encoded with `transcoder`.
+    pub fn from_file(path: &str, transcoder: Transcoder) -> Result<Self, Error> {
         let file = File::create(path)
             .map_err(|e| Error::Message(format!("unable to create file: {}", e)))?;
-        Ok(Self::File(Some(BufWriter::new(file))))
+        let writer = BufWriter::new(file);
+        Ok(Self::File(Some(CharEncoder::with_transcoder(
+            writer, transcoder,
+        ))))
+    }
+
+    /// Create `TextOutputPort::Transcoded` that encodes to a binary output port with `transcoder`.
+    pub fn from_binary(port: RcRef<BinaryOutputPort>, transcoder: Transcoder) -> Self {
+        Self::Transcoded(Some(CharEncoder::with_transcoder(
+            BinaryPortWriter(port),
+            transcoder,
+        )))
     }
 
     /// Create `TextOutputPort::String` from string.
     pub fn from_string(s: String) -> Self {
-        Self::String(Some(s))
+        Self::String(Some(StringWriter::from(s)))
     }
 
     /// Create `TextOutputPort::String` from new string.
     pub fn from_new_string() -> Self {
-        Self::String(Some(String::new()))
+        Self::String(Some(StringWriter::from(String::new())))
     }
 
     /// Close port.
-    pub fn close(&mut self) {
+    pub fn close(&mut self) -> Result<(), Error> {
         match self {
             Self::File(stream) => {
                 stream.take();
@@ -422,8 +1196,13 @@ impl TextOutputPort {
             Self::String(stream) => {
                 stream.take();
             }
-            Self::Stdout => {}
+            Self::Stdout | Self::Stderr => {}
+            Self::Transcoded(stream) => {
+                stream.take();
+            }
+            Self::Custom(port) => return port.close(),
         }
+        Ok(())
     }
 
     /// Return if port is open.
@@ -431,20 +1210,63 @@ impl TextOutputPort {
         match self {
             Self::File(stream) => stream.is_some(),
             Self::String(stream) => stream.is_some(),
-            Self::Stdout => true,
+            Self::Stdout | Self::Stderr => true,
+            Self::Transcoded(stream) => stream.is_some(),
+            Self::Custom(port) => port.is_open(),
+        }
+    }
+
+    /// Return if `position` is supported.
+    pub fn has_position(&self) -> bool {
+        match self {
+            Self::File(_) | Self::String(_) => true,
+            Self::Stdout | Self::Stderr | Self::Transcoded(_) => false,
+            Self::Custom(port) => port.has_position(),
+        }
+    }
+
+    /// Return if `set_position` is supported.
+    pub fn has_set_position(&self) -> bool {
+        match self {
+            Self::File(_) | Self::String(_) => true,
+            Self::Stdout | Self::Stderr | Self::Transcoded(_) => false,
+            Self::Custom(port) => port.has_set_position(),
+        }
+    }
+
+    /// Return the current position, for ports that support `port-position`.
+    pub fn position(&mut self) -> Result<usize, Error> {
+        match self {
+            Self::File(Some(encoder)) => encoder.position(),
+            Self::String(Some(s)) => Ok(s.position()),
+            Self::File(None) | Self::String(None) => Err(Error::new("port is closed")),
+            Self::Custom(port) => port.position(),
+            _ => Err(Error::new("port does not support port-position")),
+        }
+    }
+
+    /// Move to `position`, for ports that support `set-port-position!`.
+    pub fn set_position(&mut self, position: usize) -> Result<(), Error> {
+        match self {
+            Self::File(Some(encoder)) => encoder.set_position(position),
+            Self::String(Some(s)) => s.set_position(position),
+            Self::File(None) | Self::String(None) => Err(Error::new("port is closed")),
+            Self::Custom(port) => port.set_position(position),
+            _ => Err(Error::new("port does not support set-port-position!")),
         }
     }
 
     /// Write `char` to `TextOutputPort`.
     pub fn write_char(&mut self, ch: char) -> Result<(), Error> {
         match self {
-            Self::File(Some(writer)) => writer
-                .write_all(&[ch as u8])
-                .map_err(|e| Error::Message(format!("write failed: {}", e))),
+            Self::File(Some(encoder)) => encoder.write_char(ch),
             Self::File(None) => Err(Error::new("port is closed")),
+            Self::Transcoded(Some(encoder)) => encoder.write_char(ch),
+            Self::Transcoded(None) => Err(Error::new("port is closed")),
+            Self::Custom(port) => port.write_items(&[ch]),
 
             Self::String(Some(s)) => {
-                s.push(ch);
+                s.write_char(ch);
                 Ok(())
             }
             Self::String(None) => Err(Error::new("port is closed")),
@@ -453,11 +1275,19 @@ impl TextOutputPort {
                 print!("{}", ch);
                 Ok(())
             }
+
+            Self::Stderr => {
+                eprint!("{}", ch);
+                Ok(())
+            }
         }
     }
 
     /// Write `String` to `TextOutputPort`.
     pub fn write_string(&mut self, s: &str) -> Result<(), Error> {
+        if let Self::Custom(port) = self {
+            return port.write_items(&s.chars().collect::<Vec<char>>());
+        }
         for c in s.chars() {
             self.write_char(c)?;
         }
@@ -465,13 +1295,17 @@ impl TextOutputPort {
     }
 
     /// Flush port buffer.
-    /// Note: only used for `TextOutputPort::File`.
+    /// Note: only used for `TextOutputPort::File` and `TextOutputPort::Transcoded`.
     pub fn flush(&mut self) -> Result<(), Error> {
         match self {
-            Self::File(Some(writer)) => writer.flush().map_err(|_| Error::new("unable to flush")),
+            Self::File(Some(encoder)) => encoder.flush(),
+            Self::Transcoded(Some(encoder)) => encoder.flush(),
             Self::Stdout => io::stdout()
                 .flush()
                 .map_err(|_| Error::new("unable to flush")),
+            Self::Stderr => io::stderr()
+                .flush()
+                .map_err(|_| Error::new("unable to flush")),
             _ => Ok(()),
         }
     }
@@ -479,12 +1313,112 @@ impl TextOutputPort {
     /// Create `String` from `TextOutputPort` buffer.
     pub fn get_output_string(&self) -> Option<&str> {
         match self {
-            Self::String(Some(s)) => Some(s),
+            Self::String(Some(s)) => Some(s.as_str()),
             _ => None,
         }
     }
 }
 
+#[derive(Debug, Clone)]
+pub struct StringReader {
+    chars: Vec<char>,
+    cursor: usize,
+}
+
+impl StringReader {
+    /// Create `StringReader` from string.
+    pub fn from(s: &str) -> StringReader {
+        Self {
+            chars: s.chars().collect(),
+            cursor: 0,
+        }
+    }
+
+    /// Return next char without consuming it.
+    /// Return `None` if there are no chars to read.
+    pub fn peek(&self) -> Option<char> {
+        self.chars.get(self.cursor).copied()
+    }
+
+    /// Consume and return the next char.
+    /// Return `None` if there are no chars to read.
+    pub fn read_char(&mut self) -> Option<char> {
+        let c = self.peek()?;
+        self.cursor += 1;
+        Some(c)
+    }
+
+    /// Return the index of the next char to be read.
+    pub fn position(&self) -> usize {
+        self.cursor
+    }
+
+    /// Move to char index `position`, which may be the end of the string.
+    pub fn set_position(&mut self, position: usize) -> Result<(), Error> {
+        if position > self.chars.len() {
+            return Err(Error::new("position out of range"));
+        }
+        self.cursor = position;
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct StringWriter {
+    text: String,
+    /// Number of chars in `text`.
+    len: usize,
+    cursor: usize,
+}
+
+impl StringWriter {
+    /// Create `StringWriter` that appends to string.
+    pub fn from(s: String) -> StringWriter {
+        let len = s.chars().count();
+        Self {
+            text: s,
+            len,
+            cursor: len,
+        }
+    }
+
+    /// Write char at the cursor, replacing the char there if the cursor is before the end.
+    pub fn write_char(&mut self, c: char) {
+        if self.cursor == self.len {
+            self.text.push(c);
+            self.len += 1;
+        } else {
+            let (start, old) = self
+                .text
+                .char_indices()
+                .nth(self.cursor)
+                .expect("cursor should be before the end");
+            self.text
+                .replace_range(start..start + old.len_utf8(), c.encode_utf8(&mut [0; 4]));
+        }
+        self.cursor += 1;
+    }
+
+    /// Return the string written so far.
+    pub fn as_str(&self) -> &str {
+        &self.text
+    }
+
+    /// Return the index of the next char to be written.
+    pub fn position(&self) -> usize {
+        self.cursor
+    }
+
+    /// Move to char index `position`, which may be the end of the string.
+    pub fn set_position(&mut self, position: usize) -> Result<(), Error> {
+        if position > self.len {
+            return Err(Error::new("position out of range"));
+        }
+        self.cursor = position;
+        Ok(())
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct ByteVecReader {
     byte_vec: Option<ByteVector>,
@@ -529,17 +1463,35 @@ impl ByteVecReader {
             let buf = bv.buffer.borrow();
             if self.cursor < buf.len() {
                 self.cursor += 1;
-                return Some(buf[self.cursor]);
+                return Some(buf[self.cursor - 1]);
             }
         }
         None
     }
+
+    /// Return the index of the next byte to be read.
+    pub fn position(&self) -> usize {
+        self.cursor
+    }
+
+    /// Move to byte index `position`, which may be the end of the `ByteVector`.
+    pub fn set_position(&mut self, position: usize) -> Result<(), Error> {
+        match &self.byte_vec {
+            Some(bv) if position <= bv.len() => {
+                self.cursor = position;
+                Ok(())
+            }
+            Some(_) => Err(Error::new("position out of range")),
+            None => Err(Error::new("bytevector port is closed")),
+        }
+    }
 }
 
 #[derive(Debug)]
 pub enum BinaryInputPort {
     File(Option<FileInputBuf>),
     ByteVector(ByteVecReader),
+    Custom(CustomPort<u8>),
 }
 
 impl BinaryInputPort {
@@ -556,7 +1508,7 @@ impl BinaryInputPort {
     }
 
     /// Close `BinaryInputPort`.
-    pub fn close(&mut self) {
+    pub fn close(&mut self) -> Result<(), Error> {
         match self {
             Self::File(stream) => {
                 stream.take();
@@ -564,7 +1516,9 @@ impl BinaryInputPort {
             Self::ByteVector(bv) => {
                 bv.byte_vec.take();
             }
+            Self::Custom(port) => return port.close(),
         }
+        Ok(())
     }
 
     /// Return if port is open.
@@ -572,6 +1526,44 @@ impl BinaryInputPort {
         match self {
             Self::File(stream) => stream.is_some(),
             Self::ByteVector(bv) => bv.byte_vec.is_some(),
+            Self::Custom(port) => port.is_open(),
+        }
+    }
+
+    /// Return if `position` is supported.
+    pub fn has_position(&self) -> bool {
+        match self {
+            Self::File(_) | Self::ByteVector(_) => true,
+            Self::Custom(port) => port.has_position(),
+        }
+    }
+
+    /// Return if `set_position` is supported.
+    pub fn has_set_position(&self) -> bool {
+        match self {
+            Self::File(_) | Self::ByteVector(_) => true,
+            Self::Custom(port) => port.has_set_position(),
+        }
+    }
+
+    /// Return the current position, for ports that support `port-position`.
+    pub fn position(&mut self) -> Result<usize, Error> {
+        match self {
+            Self::File(Some(reader)) => stream_position(reader),
+            Self::File(None) => Err(Error::new("port is closed")),
+            Self::ByteVector(bv) if bv.is_open() => Ok(bv.position()),
+            Self::ByteVector(_) => Err(Error::new("bytevector port is closed")),
+            Self::Custom(port) => port.position(),
+        }
+    }
+
+    /// Move to `position`, for ports that support `set-port-position!`.
+    pub fn set_position(&mut self, position: usize) -> Result<(), Error> {
+        match self {
+            Self::File(Some(reader)) => seek_stream(reader, position),
+            Self::File(None) => Err(Error::new("port is closed")),
+            Self::ByteVector(bv) => bv.set_position(position),
+            Self::Custom(port) => port.set_position(position),
         }
     }
 
@@ -590,6 +1582,7 @@ impl BinaryInputPort {
             Self::File(None) => Err(Error::new("file port is closed")),
             Self::ByteVector(bv) if bv.is_open() => Ok(bv.read_byte()),
             Self::ByteVector(_) => Err(Error::new("bytevector port is closed")),
+            Self::Custom(port) => port.read_item(),
         }
     }
 
@@ -608,6 +1601,7 @@ impl BinaryInputPort {
                 _ => Ok(None),
             },
             Self::ByteVector(_) => Err(Error::new("bytevector port is closed")),
+            Self::Custom(port) => port.peek_item(),
         }
     }
 
@@ -666,6 +1660,18 @@ impl BinaryInputPort {
                 }
                 Ok(Some(ByteVector::from(bytes.as_slice())))
             }
+            (Self::Custom(port), _) => {
+                let mut bytes = Vec::new();
+                while read_limit.is_none_or(|limit| bytes.len() < limit)
+                    && let Some(byte) = port.read_item()?
+                {
+                    bytes.push(byte);
+                }
+                if bytes.is_empty() {
+                    return Ok(None);
+                }
+                Ok(Some(ByteVector::from(bytes.as_slice())))
+            }
         }
     }
 }
@@ -674,6 +1680,8 @@ impl BinaryInputPort {
 pub struct ByteVecWriter {
     byte_vec: Option<ByteVector>,
     cursor: usize,
+    /// Number of bytes written, which may be past the cursor after `set_position`.
+    end: usize,
 }
 
 impl ByteVecWriter {
@@ -682,6 +1690,7 @@ impl ByteVecWriter {
         Self {
             byte_vec: Some(bv.clone()),
             cursor: 0,
+            end: 0,
         }
     }
 
@@ -700,10 +1709,13 @@ impl ByteVecWriter {
     pub fn write(&mut self, byte: u8) -> Result<(), Error> {
         if let Some(bv) = self.byte_vec.as_ref() {
             let mut buf = bv.buffer.borrow_mut();
-            if self.cursor == buf.len() - 1 {
+            if self.cursor == buf.len() {
                 return Err(Error::new("bytevector buffer is full"));
             }
             buf[self.cursor] = byte;
+            self.cursor += 1;
+            self.end = self.end.max(self.cursor);
+            return Ok(());
         }
         Err(Error::new("bytevector output port is closed"))
     }
@@ -712,18 +1724,34 @@ impl ByteVecWriter {
     pub fn get_bytes(&self) -> Option<ByteVector> {
         if let Some(bv) = self.byte_vec.as_ref() {
             let buf = bv.buffer.borrow();
-            assert!(self.cursor < buf.len());
-            let new_bv = ByteVector::from(&buf[..self.cursor + 1]); // range upper bound is exclusive.
-            return Some(new_bv);
+            return Some(ByteVector::from(&buf[..self.end]));
         }
         None
     }
+
+    /// Return the index of the next byte to be written.
+    pub fn position(&self) -> usize {
+        self.cursor
+    }
+
+    /// Move to byte index `position`, which may be the end of the bytes written.
+    pub fn set_position(&mut self, position: usize) -> Result<(), Error> {
+        match &self.byte_vec {
+            Some(_) if position <= self.end => {
+                self.cursor = position;
+                Ok(())
+            }
+            Some(_) => Err(Error::new("position out of range")),
+            None => Err(Error::new("bytevector output port is closed")),
+        }
+    }
 }
 
 #[derive(Debug)]
 pub enum BinaryOutputPort {
     File(Option<FileOutputBuf>),
     ByteVector(ByteVecWriter),
+    Custom(CustomPort<u8>),
 }
 
 impl BinaryOutputPort {
@@ -740,13 +1768,15 @@ impl BinaryOutputPort {
     }
 
     /// Close `BinaryOutputPort`.
-    pub fn close(&mut self) {
+    pub fn close(&mut self) -> Result<(), Error> {
         match self {
             Self::File(stream) => {
                 stream.take();
             }
             Self::ByteVector(bv) => bv.close(),
+            Self::Custom(port) => return port.close(),
         }
+        Ok(())
     }
 
     /// Return if port is open.
@@ -754,6 +1784,44 @@ impl BinaryOutputPort {
         match self {
             Self::File(stream) => stream.is_some(),
             Self::ByteVector(bv) => bv.is_open(),
+            Self::Custom(port) => port.is_open(),
+        }
+    }
+
+    /// Return if `position` is supported.
+    pub fn has_position(&self) -> bool {
+        match self {
+            Self::File(_) | Self::ByteVector(_) => true,
+            Self::Custom(port) => port.has_position(),
+        }
+    }
+
+    /// Return if `set_position` is supported.
+    pub fn has_set_position(&self) -> bool {
+        match self {
+            Self::File(_) | Self::ByteVector(_) => true,
+            Self::Custom(port) => port.has_set_position(),
+        }
+    }
+
+    /// Return the current position, for ports that support `port-position`.
+    pub fn position(&mut self) -> Result<usize, Error> {
+        match self {
+            Self::File(Some(writer)) => stream_position(writer),
+            Self::File(None) => Err(Error::new("port is closed")),
+            Self::ByteVector(bv) if bv.is_open() => Ok(bv.position()),
+            Self::ByteVector(_) => Err(Error::new("bytevector output port is closed")),
+            Self::Custom(port) => port.position(),
+        }
+    }
+
+    /// Move to `position`, for ports that support `set-port-position!`.
+    pub fn set_position(&mut self, position: usize) -> Result<(), Error> {
+        match self {
+            Self::File(Some(writer)) => seek_stream(writer, position),
+            Self::File(None) => Err(Error::new("port is closed")),
+            Self::ByteVector(bv) => bv.set_position(position),
+            Self::Custom(port) => port.set_position(position),
         }
     }
 
@@ -765,6 +1833,7 @@ impl BinaryOutputPort {
                 .map_err(|e| Error::Message(format!("write failed: {}", e))),
             Self::File(None) => Err(Error::new("port is closed")),
             Self::ByteVector(bv) => bv.write(byte),
+            Self::Custom(port) => port.write_items(&[byte]),
         }
     }
 
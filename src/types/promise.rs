@@ -0,0 +1,89 @@
+// Copyright (c) 2026 Sebastian Ibanez
+// Author: Sebastian Ibanez
+// Created: 2026-10-18
+
+//! Promises for the `(scheme lazy)` library.
+
+use crate::env::EnvRef;
+use crate::types::Expr;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+/// A promise created by `delay`, `delay-force` or `make-promise`.
+///
+/// Follows the R7RS reference implementation: a promise holds a shared box
+/// with its state, and forcing a `delay-force` promise makes the promise it
+/// produced share the same box. Clones refer to the same promise.
+#[derive(Debug, Clone)]
+pub struct Promise {
+    state: Rc<RefCell<Rc<RefCell<PromiseState>>>>,
+}
+
+/// State of a promise.
+#[derive(Debug, Clone)]
+pub enum PromiseState {
+    /// The promise has been forced and holds its value.
+    Done(Expr),
+    /// The promise holds an unevaluated expression and its environment.
+    /// `delay_force` is true if the expression evaluates to another promise.
+    Delayed {
+        expr: Expr,
+        env: EnvRef,
+        delay_force: bool,
+    },
+}
+
+impl Promise {
+    /// Create a promise that evaluates `expr` in `env` when forced.
+    pub fn delayed(expr: Expr, env: EnvRef, delay_force: bool) -> Self {
+        Promise::from_state(PromiseState::Delayed {
+            expr,
+            env,
+            delay_force,
+        })
+    }
+
+    /// Create a promise that is already forced to `value`.
+    pub fn done(value: Expr) -> Self {
+        Promise::from_state(PromiseState::Done(value))
+    }
+
+    fn from_state(state: PromiseState) -> Self {
+        Promise {
+            state: Rc::new(RefCell::new(Rc::new(RefCell::new(state)))),
+        }
+    }
+
+    /// Return the current state of the promise.
+    pub fn state(&self) -> PromiseState {
+        self.state.borrow().borrow().clone()
+    }
+
+    /// Return true if the promise has been forced.
+    pub fn is_done(&self) -> bool {
+        matches!(*self.state.borrow().borrow(), PromiseState::Done(_))
+    }
+
+    /// Store the forced value of the promise.
+    pub fn set_value(&self, value: Expr) {
+        *self.state.borrow().borrow_mut() = PromiseState::Done(value);
+    }
+
+    /// Take over the state of `other`, and make `other` share the state of `self`.
+    pub fn update_from(&self, other: &Promise) {
+        let state = other.state();
+        *self.state.borrow().borrow_mut() = state;
+        let shared = self.state.borrow().clone();
+        *other.state.borrow_mut() = shared;
+    }
+
+    /// Return true if `self` and `other` are the same promise.
+    pub fn ptr_eq(&self, other: &Promise) -> bool {
+        Rc::ptr_eq(&self.state, &other.state)
+    }
+
+    /// Returns raw pointer to the promise. Use responsibly.
+    pub fn raw_ptr(&self) -> *const () {
+        self.state.as_ptr() as *const ()
+    }
+}
@@ -0,0 +1,175 @@
+// Copyright (c) 2026 Sebastian Ibanez
+// Author: Sebastian Ibanez
+// Created: 2026-10-18
+
+//! Seedable pseudo-random number generation for SRFI 27 random sources.
+
+use crate::error::Error;
+use num_bigint::{BigInt, Sign};
+use num_traits::{Signed, ToPrimitive};
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Seed used for every new random source, so streams are reproducible between runs.
+const DEFAULT_SEED: u64 = 0x5eed_c0ff_ee00_0027;
+
+/// Increment of the SplitMix64 sequence.
+const GOLDEN_GAMMA: u64 = 0x9e37_79b9_7f4a_7c15;
+
+/// A random source backed by the xoshiro256** generator.
+///
+/// Clones share the same generator state.
+#[derive(Debug, Clone)]
+pub struct RandomSource {
+    state: Rc<RefCell<[u64; 4]>>,
+}
+
+impl RandomSource {
+    /// Create a new `RandomSource` in the default initial state.
+    pub fn new() -> Self {
+        RandomSource {
+            state: Rc::new(RefCell::new(seed_state(&[DEFAULT_SEED]))),
+        }
+    }
+
+    /// Reseed the source from the system clock.
+    pub fn randomize(&self) {
+        let nanos = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_nanos() as u64)
+            .unwrap_or(DEFAULT_SEED);
+        *self.state.borrow_mut() = seed_state(&[DEFAULT_SEED, nanos]);
+    }
+
+    /// Reseed the source deterministically from two non-negative integers.
+    pub fn pseudo_randomize(&self, i: &BigInt, j: &BigInt) -> Result<(), Error> {
+        if i.is_negative() || j.is_negative() {
+            return Err(Error::new("expected non-negative exact integers"));
+        }
+        let mut words = vec![DEFAULT_SEED];
+        for n in [i, j] {
+            let digits = n.to_u64_digits().1;
+            words.push(digits.len() as u64);
+            words.extend(digits);
+        }
+        *self.state.borrow_mut() = seed_state(&words);
+        Ok(())
+    }
+
+    /// Return a copy of the generator state.
+    pub fn state(&self) -> [u64; 4] {
+        *self.state.borrow()
+    }
+
+    /// Restore a generator state previously returned by `state`.
+    pub fn set_state(&self, state: [u64; 4]) -> Result<(), Error> {
+        if state == [0; 4] {
+            return Err(Error::new("invalid random source state"));
+        }
+        *self.state.borrow_mut() = state;
+        Ok(())
+    }
+
+    /// Return true if `self` and `other` share the same generator state.
+    pub fn ptr_eq(&self, other: &RandomSource) -> bool {
+        Rc::ptr_eq(&self.state, &other.state)
+    }
+
+    /// Returns raw pointer to the generator state. Use responsibly.
+    pub fn raw_ptr(&self) -> *const () {
+        self.state.as_ptr() as *const ()
+    }
+
+    /// Return a uniformly distributed integer in `[0, n)`.
+    pub fn integer(&self, n: &BigInt) -> Result<BigInt, Error> {
+        if !n.is_positive() {
+            return Err(Error::new("range must be a positive exact integer"));
+        }
+
+        if let Some(n) = n.to_u64() {
+            return Ok(BigInt::from(self.below(n)));
+        }
+
+        // Draw as many bits as the range needs and reject values outside of it.
+        let bits = n.bits();
+        let words = bits.div_ceil(64) as usize;
+        let top_mask = match bits % 64 {
+            0 => u64::MAX,
+            r => (1 << r) - 1,
+        };
+        loop {
+            let mut digits: Vec<u64> = (0..words).map(|_| self.next_u64()).collect();
+            if let Some(top) = digits.last_mut() {
+                *top &= top_mask;
+            }
+            let bytes: Vec<u8> = digits.iter().flat_map(|d| d.to_le_bytes()).collect();
+            let candidate = BigInt::from_bytes_le(Sign::Plus, &bytes);
+            if &candidate < n {
+                return Ok(candidate);
+            }
+        }
+    }
+
+    /// Return a uniformly distributed real in the open interval `(0, 1)`.
+    pub fn real(&self) -> f64 {
+        ((self.next_u64() >> 11) as f64 + 0.5) / (1u64 << 53) as f64
+    }
+
+    /// Return a uniformly distributed `u64` in `[0, n)`, where `n > 0`.
+    fn below(&self, n: u64) -> u64 {
+        // Reject the final partial copy of the range so every value is equally likely.
+        let limit = u64::MAX - u64::MAX % n;
+        loop {
+            let x = self.next_u64();
+            if x < limit {
+                return x % n;
+            }
+        }
+    }
+
+    /// Advance the generator and return the next 64 random bits.
+    fn next_u64(&self) -> u64 {
+        let mut s = self.state.borrow_mut();
+        let result = s[1].wrapping_mul(5).rotate_left(7).wrapping_mul(9);
+        let t = s[1] << 17;
+        s[2] ^= s[0];
+        s[3] ^= s[1];
+        s[1] ^= s[2];
+        s[0] ^= s[3];
+        s[2] ^= t;
+        s[3] = s[3].rotate_left(45);
+        result
+    }
+}
+
+impl Default for RandomSource {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Expand seed words into a xoshiro256** state using SplitMix64.
+fn seed_state(words: &[u64]) -> [u64; 4] {
+    let mut x = 0u64;
+    for word in words {
+        x = mix64((x ^ word).wrapping_add(GOLDEN_GAMMA));
+    }
+
+    let mut state = [0u64; 4];
+    for s in state.iter_mut() {
+        x = x.wrapping_add(GOLDEN_GAMMA);
+        *s = mix64(x);
+    }
+    if state == [0; 4] {
+        state[0] = 1;
+    }
+    state
+}
+
+/// The SplitMix64 output function.
+fn mix64(mut z: u64) -> u64 {
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    z ^ (z >> 31)
+}
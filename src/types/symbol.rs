@@ -0,0 +1,186 @@
+// Copyright (c) 2026 Sebastian Ibanez
+// Author: Sebastian Ibanez
+// Created: 2026-10-18
+
+//! Interned and uninterned symbols.
+
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
+use std::fmt;
+use std::rc::Rc;
+
+thread_local! {
+    /// Global symbol table shared by the reader and the runtime.
+    static SYMBOLS: RefCell<SymbolTable> = RefCell::new(SymbolTable::default());
+
+    /// Counter for naming symbols created by `gensym`.
+    static GENSYM_COUNTER: Cell<u64> = const { Cell::new(0) };
+}
+
+/// A symbol, identified by its index in the symbol table.
+///
+/// Interned symbols with the same name share an ID, so comparing symbols is an
+/// integer comparison. Uninterned symbols always get a fresh ID.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Symbol {
+    id: u32,
+}
+
+/// Names interned at fixed IDs when the symbol table is created, so the
+/// evaluator can match them against the `Symbol` constants without a lookup.
+const PREDEFINED: &[&str] = &[
+    "define",
+    "begin",
+    "lambda",
+    "quote",
+    "quasiquote",
+    "if",
+    "cond",
+    "parameterize",
+    "delay",
+    "delay-force",
+    "endianness",
+    "let",
+    "let*",
+    "letrec",
+    "letrec*",
+    ",",
+    "big",
+    "little",
+];
+
+/// Symbol names indexed by ID.
+///
+/// Entries are never removed, so every uninterned symbol keeps its name alive
+/// for the life of the thread.
+struct SymbolTable {
+    names: Vec<Rc<str>>,
+    interned: Vec<bool>,
+    ids: HashMap<Rc<str>, Symbol>,
+}
+
+impl Default for SymbolTable {
+    fn default() -> Self {
+        let mut table = SymbolTable {
+            names: Vec::new(),
+            interned: Vec::new(),
+            ids: HashMap::new(),
+        };
+        for name in PREDEFINED {
+            let name: Rc<str> = Rc::from(*name);
+            let symbol = table.push(name.clone(), true);
+            table.ids.insert(name, symbol);
+        }
+        table
+    }
+}
+
+impl SymbolTable {
+    /// Store `name` under a fresh ID.
+    fn push(&mut self, name: Rc<str>, interned: bool) -> Symbol {
+        let symbol = Symbol {
+            id: self.names.len() as u32,
+        };
+        self.names.push(name);
+        self.interned.push(interned);
+        symbol
+    }
+}
+
+impl Symbol {
+    pub const DEFINE: Symbol = Symbol::predefined("define");
+    pub const BEGIN: Symbol = Symbol::predefined("begin");
+    pub const LAMBDA: Symbol = Symbol::predefined("lambda");
+    pub const QUOTE: Symbol = Symbol::predefined("quote");
+    pub const QUASIQUOTE: Symbol = Symbol::predefined("quasiquote");
+    pub const IF: Symbol = Symbol::predefined("if");
+    pub const COND: Symbol = Symbol::predefined("cond");
+    pub const PARAMETERIZE: Symbol = Symbol::predefined("parameterize");
+    pub const DELAY: Symbol = Symbol::predefined("delay");
+    pub const DELAY_FORCE: Symbol = Symbol::predefined("delay-force");
+    pub const ENDIANNESS: Symbol = Symbol::predefined("endianness");
+    pub const LET: Symbol = Symbol::predefined("let");
+    pub const LET_STAR: Symbol = Symbol::predefined("let*");
+    pub const LETREC: Symbol = Symbol::predefined("letrec");
+    pub const LETREC_STAR: Symbol = Symbol::predefined("letrec*");
+    pub const UNQUOTE: Symbol = Symbol::predefined(",");
+    pub const BIG: Symbol = Symbol::predefined("big");
+    pub const LITTLE: Symbol = Symbol::predefined("little");
+
+    /// Return the symbol for `name` in `PREDEFINED`, failing to compile if it is missing.
+    const fn predefined(name: &str) -> Symbol {
+        let mut id = 0;
+        while id < PREDEFINED.len() {
+            if same_bytes(PREDEFINED[id].as_bytes(), name.as_bytes()) {
+                return Symbol { id: id as u32 };
+            }
+            id += 1;
+        }
+        panic!("symbol is not predefined");
+    }
+
+    /// Return the interned symbol named `name`, adding it to the symbol table if needed.
+    pub fn intern(name: &str) -> Symbol {
+        SYMBOLS.with_borrow_mut(|table| {
+            if let Some(symbol) = table.ids.get(name) {
+                return *symbol;
+            }
+            let name: Rc<str> = Rc::from(name);
+            let symbol = table.push(name.clone(), true);
+            table.ids.insert(name, symbol);
+            symbol
+        })
+    }
+
+    /// Create a symbol named `name` that is distinct from every other symbol.
+    ///
+    /// The name is never freed, so code that creates uninterned symbols in a
+    /// loop grows the symbol table without bound.
+    pub fn uninterned(name: &str) -> Symbol {
+        SYMBOLS.with_borrow_mut(|table| table.push(Rc::from(name), false))
+    }
+
+    /// Create an uninterned symbol with a unique name that starts with `prefix`.
+    pub fn gensym(prefix: &str) -> Symbol {
+        let count = GENSYM_COUNTER.get();
+        GENSYM_COUNTER.set(count + 1);
+        Symbol::uninterned(&format!("{prefix}{count}"))
+    }
+
+    /// Return the name of the symbol.
+    pub fn name(&self) -> Rc<str> {
+        SYMBOLS.with_borrow(|table| table.names[self.id as usize].clone())
+    }
+
+    /// Return true if the symbol is in the symbol table.
+    pub fn is_interned(&self) -> bool {
+        SYMBOLS.with_borrow(|table| table.interned[self.id as usize])
+    }
+}
+
+/// Return true if `a` and `b` hold the same bytes, usable in const context.
+const fn same_bytes(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+    let mut i = 0;
+    while i < a.len() {
+        if a[i] != b[i] {
+            return false;
+        }
+        i += 1;
+    }
+    true
+}
+
+impl From<&str> for Symbol {
+    fn from(name: &str) -> Self {
+        Symbol::intern(name)
+    }
+}
+
+impl fmt::Display for Symbol {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
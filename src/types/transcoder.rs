@@ -0,0 +1,124 @@
+// Copyright (c) 2026 Sebastian Ibanez
+// Author: Sebastian Ibanez
+// Created: 2026-10-18
+
+//! Character encodings for textual ports.
+
+use crate::error::Error;
+
+/// Character encoding of the bytes behind a textual port.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Codec {
+    /// UTF-8, skipping a byte order mark at the start of the stream.
+    Utf8,
+    /// UTF-16 with byte order detected from a byte order mark, defaulting to big-endian.
+    Utf16,
+    Utf16Le,
+    Utf16Be,
+    Latin1,
+    Ascii,
+}
+
+/// How a port handles bytes it cannot decode or chars it cannot encode.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ErrorMode {
+    Raise,
+    Replace,
+}
+
+/// Line ending used in the bytes behind a textual port.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum EolStyle {
+    Lf,
+    /// `\r\n` in the bytes, read as `\n` and written from `\n`.
+    Crlf,
+}
+
+/// Codec, line ending and error handling of a textual port.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Transcoder {
+    pub codec: Codec,
+    pub eol_style: EolStyle,
+    pub error_mode: ErrorMode,
+}
+
+impl Default for Transcoder {
+    fn default() -> Self {
+        Transcoder {
+            codec: Codec::Utf8,
+            eol_style: EolStyle::Lf,
+            error_mode: ErrorMode::Raise,
+        }
+    }
+}
+
+impl Codec {
+    /// Return the codec called `name`, such as `utf-16le` or `latin-1`.
+    pub fn from_name(name: &str) -> Result<Codec, Error> {
+        match name {
+            "utf-8" | "utf8" => Ok(Codec::Utf8),
+            "utf-16" | "utf16" => Ok(Codec::Utf16),
+            "utf-16le" | "utf16le" => Ok(Codec::Utf16Le),
+            "utf-16be" | "utf16be" => Ok(Codec::Utf16Be),
+            "latin-1" | "latin1" | "iso-8859-1" => Ok(Codec::Latin1),
+            "ascii" | "us-ascii" => Ok(Codec::Ascii),
+            _ => Err(Error::Message(format!("unknown encoding: {}", name))),
+        }
+    }
+
+    /// Return the name of the codec.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Codec::Utf8 => "utf-8",
+            Codec::Utf16 => "utf-16",
+            Codec::Utf16Le => "utf-16le",
+            Codec::Utf16Be => "utf-16be",
+            Codec::Latin1 => "latin-1",
+            Codec::Ascii => "ascii",
+        }
+    }
+
+    /// Return the bytes of `c`, or `None` if the codec cannot represent it.
+    /// `Codec::Utf16` is encoded big-endian.
+    pub fn encode(&self, c: char) -> Option<Vec<u8>> {
+        match self {
+            Codec::Utf8 => Some(c.encode_utf8(&mut [0; 4]).as_bytes().to_vec()),
+            Codec::Utf16 | Codec::Utf16Be => Some(
+                c.encode_utf16(&mut [0; 2])
+                    .iter()
+                    .flat_map(|unit| unit.to_be_bytes())
+                    .collect(),
+            ),
+            Codec::Utf16Le => Some(
+                c.encode_utf16(&mut [0; 2])
+                    .iter()
+                    .flat_map(|unit| unit.to_le_bytes())
+                    .collect(),
+            ),
+            Codec::Latin1 => u8::try_from(c).ok().map(|byte| vec![byte]),
+            Codec::Ascii => c.is_ascii().then(|| vec![c as u8]),
+        }
+    }
+}
+
+impl EolStyle {
+    /// Return the line ending called `name`: `lf` or `crlf`.
+    pub fn from_name(name: &str) -> Result<EolStyle, Error> {
+        match name {
+            "lf" | "none" => Ok(EolStyle::Lf),
+            "crlf" => Ok(EolStyle::Crlf),
+            _ => Err(Error::Message(format!("unknown line ending: {}", name))),
+        }
+    }
+}
+
+impl ErrorMode {
+    /// Return the error mode called `name`: `raise` or `replace`.
+    pub fn from_name(name: &str) -> Result<ErrorMode, Error> {
+        match name {
+            "raise" => Ok(ErrorMode::Raise),
+            "replace" => Ok(ErrorMode::Replace),
+            _ => Err(Error::Message(format!("unknown error mode: {}", name))),
+        }
+    }
+}